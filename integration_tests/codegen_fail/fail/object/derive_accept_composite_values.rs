@@ -0,0 +1,7 @@
+#[derive(juniper::GraphQLObject)]
+struct Object {
+    #[graphql(accept_composite_values)]
+    test: String,
+}
+
+fn main() {}
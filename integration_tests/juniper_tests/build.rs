@@ -0,0 +1,6 @@
+fn main() {
+    juniper_sdl_codegen::Generator::new()
+        .with_context_type("crate::sdl_codegen::Database")
+        .compile("src/sdl_codegen/schema.graphql")
+        .unwrap();
+}
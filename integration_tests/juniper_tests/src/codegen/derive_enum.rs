@@ -84,7 +84,7 @@ fn test_derived_enum() {
     );
     assert_eq!(
         FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("REGULAR")),
-        Some(SomeEnum::Regular)
+        Ok(SomeEnum::Regular)
     );
 
     // Test FULL variant.
@@ -94,7 +94,7 @@ fn test_derived_enum() {
     );
     assert_eq!(
         FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::scalar("FULL")),
-        Some(SomeEnum::Full)
+        Ok(SomeEnum::Full)
     );
 }
 
@@ -0,0 +1,178 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet, VecDeque},
+    rc::Rc,
+};
+
+use juniper::{
+    graphql_object, graphql_value, EmptyMutation, EmptySubscription, GraphQLInputObject, RootNode,
+    Variables,
+};
+
+mod as_output_field {
+    use super::*;
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn tags() -> BTreeSet<String> {
+            vec!["b".to_owned(), "a".to_owned(), "b".to_owned()]
+                .into_iter()
+                .collect()
+        }
+
+        fn ids() -> HashSet<i32> {
+            Some(7).into_iter().collect()
+        }
+
+        fn queue() -> VecDeque<i32> {
+            let mut queue = VecDeque::new();
+            queue.push_back(2);
+            queue.push_front(1);
+            queue
+        }
+
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("borrowed")
+        }
+    }
+
+    #[tokio::test]
+    async fn works() {
+        let query = "{ tags ids queue name }";
+
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let (res, errors) = juniper::execute(query, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(errors.len(), 0);
+        assert_eq!(
+            res,
+            graphql_value!({
+                "tags": ["a", "b"],
+                "ids": [7],
+                "queue": [1, 2],
+                "name": "borrowed",
+            }),
+        );
+    }
+
+    #[test]
+    fn has_list_types() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let sdl = schema.as_schema_language();
+
+        assert!(sdl.contains("tags: [String!]!"), "{}", sdl);
+        assert!(sdl.contains("ids: [Int!]!"), "{}", sdl);
+        assert!(sdl.contains("queue: [Int!]!"), "{}", sdl);
+    }
+}
+
+mod as_input_field {
+    use super::*;
+
+    #[derive(GraphQLInputObject)]
+    struct Input {
+        tags: BTreeSet<String>,
+        queue: VecDeque<i32>,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn summary(input: Input) -> String {
+            format!("{:?} {:?}", input.tags, input.queue)
+        }
+
+        fn unique(ids: HashSet<i32>) -> i32 {
+            ids.len() as i32
+        }
+
+        fn shout(text: Cow<'static, str>) -> String {
+            text.to_uppercase()
+        }
+    }
+
+    #[tokio::test]
+    async fn works() {
+        let query = r#"{
+            summary(input: { tags: ["b", "a", "b"], queue: [3, 1] })
+            unique(ids: [1, 2, 1])
+            shout(text: "hi")
+        }"#;
+
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let (res, errors) = juniper::execute(query, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(errors.len(), 0);
+        assert_eq!(
+            res,
+            graphql_value!({
+                "summary": r#"{"a", "b"} [3, 1]"#,
+                "unique": 2,
+                "shout": "HI",
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn coerces_single_value() {
+        let query = r#"{ unique(ids: 5) }"#;
+
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let (res, errors) = juniper::execute(query, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap();
+
+        assert_eq!(errors.len(), 0);
+        assert_eq!(res, graphql_value!({"unique": 1}));
+    }
+}
+
+mod rc {
+    use super::*;
+
+    struct User {
+        name: String,
+    }
+
+    #[graphql_object]
+    impl User {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    struct Query;
+
+    // `Rc` isn't `Sync`, so this schema is only executable synchronously.
+    #[graphql_object(noasync)]
+    impl Query {
+        fn user() -> Rc<User> {
+            Rc::new(User {
+                name: "Alice".to_owned(),
+            })
+        }
+    }
+
+    #[test]
+    fn works() {
+        let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let (res, errors) =
+            juniper::execute_sync("{ user { name } }", None, &schema, &Variables::new(), &())
+                .unwrap();
+
+        assert_eq!(errors.len(), 0);
+        assert_eq!(res, graphql_value!({"user": {"name": "Alice"}}));
+    }
+}
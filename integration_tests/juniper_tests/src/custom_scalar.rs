@@ -146,10 +146,10 @@ impl GraphQLScalar for i64 {
         Value::scalar(*self)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<i64> {
+    fn from_input_value(v: &InputValue) -> Result<i64, String> {
         match *v {
-            InputValue::Scalar(MyScalarValue::Long(i)) => Some(i),
-            _ => None,
+            InputValue::Scalar(MyScalarValue::Long(i)) => Ok(i),
+            _ => Err(format!("Expected `MyScalarValue::Long`, found: {}", v)),
         }
     }
 
@@ -5,6 +5,8 @@ mod array;
 #[cfg(test)]
 mod codegen;
 #[cfg(test)]
+mod collections;
+#[cfg(test)]
 mod custom_scalar;
 #[cfg(test)]
 mod explicit_null;
@@ -32,3 +34,5 @@ mod issue_925;
 mod issue_945;
 #[cfg(test)]
 mod pre_parse;
+#[cfg(test)]
+mod sdl_codegen;
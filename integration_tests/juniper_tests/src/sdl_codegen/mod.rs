@@ -0,0 +1,190 @@
+//! Resolvers of the types generated by `juniper_sdl_codegen` from `schema.graphql`, in `build.rs`.
+
+use juniper::{execute, graphql_value, EmptySubscription, FieldResult, RootNode, Variables, ID};
+
+include!(concat!(env!("OUT_DIR"), "/schema.rs"));
+
+pub struct Database;
+
+impl juniper::Context for Database {}
+
+struct Luke;
+
+impl HumanResolvers for Luke {
+    fn id(&self, _: &Database) -> FieldResult<ID> {
+        Ok("1000".to_owned().into())
+    }
+
+    fn name(&self, _: &Database) -> FieldResult<Option<String>> {
+        Ok(Some("Luke Skywalker".into()))
+    }
+
+    fn friends(&self, _: &Database, first: i32) -> FieldResult<Vec<CharacterValue>> {
+        Ok(vec![Droid(Box::new(Artoo)).into()]
+            .into_iter()
+            .take(first as usize)
+            .collect())
+    }
+
+    fn home_planet(&self, _: &Database) -> FieldResult<Option<String>> {
+        Ok(Some("Tatooine".into()))
+    }
+
+    fn planet(&self, _: &Database) -> FieldResult<Option<Planet>> {
+        Ok(Some(tatooine()))
+    }
+}
+
+struct Artoo;
+
+impl DroidResolvers for Artoo {
+    fn id(&self, _: &Database) -> FieldResult<ID> {
+        Ok("2001".to_owned().into())
+    }
+
+    fn name(&self, _: &Database) -> FieldResult<Option<String>> {
+        Ok(Some("R2-D2".into()))
+    }
+
+    fn friends(&self, _: &Database, first: i32) -> FieldResult<Vec<CharacterValue>> {
+        Ok(vec![Human(Box::new(Luke)).into()]
+            .into_iter()
+            .take(first as usize)
+            .collect())
+    }
+
+    fn primary_function(&self, _: &Database) -> FieldResult<Option<String>> {
+        Ok(Some("Astromech".into()))
+    }
+}
+
+fn tatooine() -> Planet {
+    Planet {
+        name: "Tatooine".into(),
+        climates: vec!["arid".into()],
+        diameter: Some(10465.0),
+        closest_neighbour: None,
+    }
+}
+
+struct Root;
+
+impl QueryResolvers for Root {
+    fn hero(&self, _: &Database, episode: Option<Episode>) -> FieldResult<Option<CharacterValue>> {
+        Ok(Some(match episode {
+            Some(Episode::Empire) => Human(Box::new(Luke)).into(),
+            _ => Droid(Box::new(Artoo)).into(),
+        }))
+    }
+
+    fn search(
+        &self,
+        _: &Database,
+        text: String,
+        gravity: f64,
+        exact: bool,
+    ) -> FieldResult<Vec<SearchResult>> {
+        if exact || gravity != 1.5 {
+            return Ok(vec![]);
+        }
+        let results = vec![
+            SearchResult::Human(Human(Box::new(Luke))),
+            SearchResult::Droid(Droid(Box::new(Artoo))),
+            SearchResult::Planet(tatooine()),
+        ];
+        Ok(results
+            .into_iter()
+            .filter(|r| match r {
+                SearchResult::Planet(p) => p.name.contains(&text),
+                _ => false,
+            })
+            .collect())
+    }
+
+    fn oldest_character(&self, _: &Database) -> FieldResult<Option<CharacterValue>> {
+        Ok(None)
+    }
+}
+
+impl MutationResolvers for Root {
+    fn create_human(&self, _: &Database, human: HumanInput) -> FieldResult<Human> {
+        assert_eq!(human.name, "Luke Skywalker");
+        assert_eq!(human.friend_ids, vec![ID::from("1000".to_owned())]);
+        assert_eq!(human.appears_in, vec![Episode::NewHope]);
+        Ok(Human(Box::new(Luke)))
+    }
+}
+
+type GeneratedSchema = RootNode<'static, Query, Mutation, EmptySubscription<Database>>;
+
+fn schema() -> GeneratedSchema {
+    GeneratedSchema::new(
+        Query(Box::new(Root)),
+        Mutation(Box::new(Root)),
+        EmptySubscription::new(),
+    )
+}
+
+#[tokio::test]
+async fn resolves_generated_objects_and_interfaces() {
+    let doc = r#"{
+        hero(episode: EMPIRE) {
+            name
+            friends {
+                __typename
+                name
+                ... on Droid { primaryFunction }
+            }
+            ... on Human { planet { name climates } }
+        }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &Database).await,
+        Ok((
+            graphql_value!({
+                "hero": {
+                    "name": "Luke Skywalker",
+                    "friends": [{
+                        "__typename": "Droid",
+                        "name": "R2-D2",
+                        "primaryFunction": "Astromech",
+                    }],
+                    "planet": {"name": "Tatooine", "climates": ["arid"]},
+                },
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_generated_unions_and_default_values() {
+    let doc = r#"{
+        search(text: "Tatoo") {
+            ... on Planet { name diameter }
+        }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &Database).await,
+        Ok((
+            graphql_value!({"search": [{"name": "Tatooine", "diameter": 10465.0}]}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_generated_input_objects() {
+    let doc = r#"mutation {
+        createHuman(human: {name: "Luke Skywalker", appearsIn: [NEW_HOPE]}) {
+            id
+        }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &Database).await,
+        Ok((graphql_value!({"createHuman": {"id": "1000"}}), vec![])),
+    );
+}
@@ -0,0 +1,14 @@
+#!/bin/sh
+# Vendors the pinned GraphiQL and GraphQL Playground assets listed in `assets.txt` into this
+# directory, or the given one, for the `bundled-assets` feature to embed them. Their integrity is
+# checked by the build.
+set -eu
+
+cd "$(dirname "$0")"
+dir="${1:-.}"
+
+grep -v '^#' assets.txt | while read -r _ name url _; do
+    [ -n "$name" ] || continue
+    mkdir -p "$dir/$(dirname "$name")"
+    curl --fail --silent --show-error --location --output "$dir/$name" "https:$url"
+done
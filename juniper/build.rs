@@ -0,0 +1,117 @@
+//! Generates the [`juniper::http::assets`] constants from the pinned list of `assets/assets.txt`.
+//!
+//! With the `bundled-assets` feature, the assets vendored in the `assets` directory by its
+//! `fetch.sh` script are embedded, once their contents are checked against the SRI hashes of the
+//! list. The build fails if any of them is missing, unpinned or doesn't match its hash.
+
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// An asset of the `assets/assets.txt` list.
+struct Asset<'a> {
+    ident: &'a str,
+    name: &'a str,
+    cdn_url: &'a str,
+    #[cfg(feature = "bundled-assets")]
+    integrity: Option<&'a str>,
+}
+
+impl<'a> Asset<'a> {
+    fn parse(line: &'a str) -> Self {
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            [ident, name, cdn_url, _integrity] => Self {
+                ident,
+                name,
+                cdn_url,
+                #[cfg(feature = "bundled-assets")]
+                integrity: Some(_integrity).filter(|i| *i != "-"),
+            },
+            _ => panic!("Malformed line in `assets/assets.txt`: {}", line),
+        }
+    }
+
+    /// Returns the expression of the `Content-Type` of this asset in `juniper::http::assets`.
+    fn content_type(&self) -> &'static str {
+        match Path::new(self.name).extension().and_then(|e| e.to_str()) {
+            Some("js") => "JS",
+            Some("css") => "CSS",
+            Some("png") => "\"image/png\"",
+            _ => panic!("Unknown `Content-Type` of the `{}` asset", self.name),
+        }
+    }
+}
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let assets_dir = manifest_dir.join("assets");
+    println!("cargo:rerun-if-changed={}", assets_dir.display());
+
+    let list = fs::read_to_string(assets_dir.join("assets.txt"))
+        .expect("Failed to read `assets/assets.txt`");
+    let assets = list
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(Asset::parse)
+        .collect::<Vec<_>>();
+
+    let mut code = String::from("assets! {\n");
+    for asset in &assets {
+        let path = bundle(asset, &assets_dir);
+        writeln!(
+            code,
+            "    {} = ({:?}, {}, {:?}, {:?}),",
+            asset.ident,
+            asset.name,
+            asset.content_type(),
+            asset.cdn_url,
+            path.display().to_string(),
+        )
+        .unwrap();
+    }
+    code.push_str("}\n");
+    fs::write(out_dir.join("assets.rs"), code).expect("Failed to write the generated assets");
+}
+
+/// Returns the path of the contents of the `asset`, only read with the `bundled-assets` feature.
+#[cfg(not(feature = "bundled-assets"))]
+fn bundle(asset: &Asset<'_>, assets_dir: &Path) -> PathBuf {
+    assets_dir.join(asset.name)
+}
+
+/// Returns the path of the vendored contents of the `asset`, once checked against its pinned
+/// integrity.
+#[cfg(feature = "bundled-assets")]
+fn bundle(asset: &Asset<'_>, assets_dir: &Path) -> PathBuf {
+    use sha2::{Digest as _, Sha384};
+
+    let path = assets_dir.join(asset.name);
+    let contents = fs::read(&path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read the `{}` asset, vendor the assets with `assets/fetch.sh`: `{}`: {}",
+            asset.name,
+            path.display(),
+            e,
+        )
+    });
+    let integrity = format!("sha384-{}", base64::encode(Sha384::digest(&contents)));
+    match asset.integrity {
+        Some(pinned) if pinned == integrity => path,
+        Some(pinned) => panic!(
+            "The `{}` asset doesn't match its pinned integrity `{}`: `{}` has `{}`",
+            asset.name,
+            pinned,
+            path.display(),
+            integrity,
+        ),
+        None => panic!(
+            "The `{}` asset isn't pinned in `assets/assets.txt`, its integrity is `{}`",
+            asset.name, integrity,
+        ),
+    }
+}
@@ -3,7 +3,7 @@ use std::{borrow::Cow, fmt, hash::Hash, slice, vec};
 use indexmap::IndexMap;
 
 use crate::{
-    executor::Variables,
+    executor::{IntoFieldError, Variables},
     parser::Spanning,
     value::{DefaultScalarValue, ScalarValue},
 };
@@ -152,20 +152,27 @@ pub type OwnedDocument<'a, S> = Vec<Definition<'a, S>>;
 
 /// Parse an unstructured input value into a Rust data type.
 ///
-/// The conversion _can_ fail, and must in that case return None. Implemented
+/// The conversion _can_ fail, and must in that case return an `Err` describing why the input was
+/// rejected. The error is reported to clients, either by validation or as a field error, so it
+/// should be descriptive (e.g. "Expected RFC 3339 timestamp, found `2021-13-01`"). Implemented
 /// automatically by the convenience proc macro `graphql_scalar` or by deriving GraphQLEnum.
 ///
 /// Must be implemented manually when manually exposing new enums or scalars.
 pub trait FromInputValue<S = DefaultScalarValue>: Sized {
+    /// Type of the error returned when the conversion fails.
+    ///
+    /// Thanks to [`IntoFieldError`], it can be represented in the GraphQL response.
+    type Error: IntoFieldError<S>;
+
     /// Performs the conversion.
-    fn from_input_value(v: &InputValue<S>) -> Option<Self>;
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error>;
 
     /// Performs the conversion from an absent value (e.g. to distinguish between implicit and
     /// explicit null). The default implementation just uses `from_input_value` as if an explicit
     /// null were provided. This conversion must not fail.
     fn from_implicit_null() -> Self {
         Self::from_input_value(&InputValue::<S>::Null)
-            .expect("input value conversion from null must not fail")
+            .unwrap_or_else(|_| panic!("input value conversion from null must not fail"))
     }
 }
 
@@ -200,6 +207,11 @@ impl<'a> Type<'a> {
     pub fn is_non_null(&self) -> bool {
         matches!(*self, Type::NonNullNamed(_) | Type::NonNullList(..))
     }
+
+    /// Determines if a type is a list, whether nullable or not.
+    pub fn is_list(&self) -> bool {
+        matches!(*self, Type::List(..) | Type::NonNullList(..))
+    }
 }
 
 impl<'a> fmt::Display for Type<'a> {
@@ -322,7 +334,7 @@ where
     }
 
     /// Shorthand form of invoking `FromInputValue::from()`.
-    pub fn convert<T>(&self) -> Option<T>
+    pub fn convert<T>(&self) -> Result<T, T::Error>
     where
         T: FromInputValue<S>,
     {
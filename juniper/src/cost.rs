@@ -0,0 +1,671 @@
+//! Cost estimation of operations, and rate limiting of clients by the cost of their operations.
+//!
+//! The cost of an operation is estimated statically, before executing it, from its selections and
+//! the schema: every selected object costs [`CostConfig::object_cost`], every selected leaf costs
+//! [`CostConfig::scalar_cost`], and the cost of a list field is multiplied by the number of items
+//! it's asked for through a size argument like `first` or `limit`.
+//!
+//! A [`RateLimiter`] charges these costs to a token bucket per client, and
+//! [`execute_rate_limited`](crate::execute_rate_limited) rejects operations whose client has run
+//! out of tokens before executing them:
+//!
+//! ```rust
+//! use juniper::{
+//!     cost::{CostConfig, RateLimitClient, RateLimiter},
+//!     graphql_object, EmptyMutation, EmptySubscription, RootNode, Variables,
+//! };
+//!
+//! struct Context {
+//!     api_key: String,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! impl RateLimitClient for Context {
+//!     fn client_id(&self) -> &str {
+//!         &self.api_key
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[graphql_object(context = Context)]
+//! impl Query {
+//!     fn numbers(first: i32) -> Vec<i32> {
+//!         (0..first).collect()
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+//! // Every client may spend up to 100 tokens at once, and regains 10 of them per second.
+//! let limiter = RateLimiter::new(100, 10.0)
+//!     .with_cost_config(CostConfig::new().scalar_cost(1));
+//! let context = Context { api_key: "abc".into() };
+//!
+//! let (res, report) = juniper::execute_rate_limited(
+//!     "{ numbers(first: 3) }", None, &schema, &Variables::new(), &context, &limiter,
+//! ).await;
+//!
+//! assert!(res.is_ok());
+//! let report = report.unwrap();
+//! assert_eq!(report.cost, 3);
+//! assert_eq!(report.remaining, 97);
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ast::{Definition, Document, Field, Fragment, InputValue, Operation, OperationType, Selection},
+    executor::{FieldError, IntoFieldError, Variables},
+    parser::Spanning,
+    schema::{
+        meta::{Field as MetaField, MetaType},
+        model::SchemaType,
+    },
+    types::base::is_excluded,
+    value::{Object, ScalarValue, Value},
+};
+
+/// Weights used to estimate the cost of an operation with [`operation_cost`].
+#[derive(Clone, Debug)]
+pub struct CostConfig {
+    object_cost: u64,
+    scalar_cost: u64,
+    list_size_arguments: Vec<String>,
+    default_list_size: u64,
+    field_costs: HashMap<(String, String), u64>,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            object_cost: 1,
+            scalar_cost: 0,
+            list_size_arguments: vec!["first".into(), "last".into(), "limit".into()],
+            default_list_size: 10,
+            field_costs: HashMap::new(),
+        }
+    }
+}
+
+impl CostConfig {
+    /// Creates a [`CostConfig`] where objects cost 1, leaves cost nothing, and lists are sized by
+    /// their `first`, `last` or `limit` argument, or assumed to hold 10 items without one.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cost of selecting a field of an object, interface or union type.
+    pub fn object_cost(mut self, cost: u64) -> Self {
+        self.object_cost = cost;
+        self
+    }
+
+    /// Sets the cost of selecting a field of a scalar or enum type.
+    pub fn scalar_cost(mut self, cost: u64) -> Self {
+        self.scalar_cost = cost;
+        self
+    }
+
+    /// Sets the names of the arguments holding the number of items a list field is asked for.
+    ///
+    /// The first of them provided to a field, or defaulted in the schema, is used.
+    pub fn list_size_arguments<I, N>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        self.list_size_arguments = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the number of items assumed for list fields without a size argument.
+    pub fn default_list_size(mut self, size: u64) -> Self {
+        self.default_list_size = size;
+        self
+    }
+
+    /// Overrides the cost of selecting the `field_name` field of the `type_name` type, e.g. for
+    /// fields calling an expensive service.
+    ///
+    /// The cost of the field's own selections is still added to it.
+    pub fn field_cost(mut self, type_name: &str, field_name: &str, cost: u64) -> Self {
+        self.field_costs
+            .insert((type_name.into(), field_name.into()), cost);
+        self
+    }
+}
+
+/// Estimates the cost of executing the provided `operation` of a validated `document`.
+///
+/// Fields excluded by `@skip` or `@include` cost nothing, and neither do introspection fields.
+/// Costs saturate at [`u64::MAX`] instead of overflowing.
+pub fn operation_cost<'a, S>(
+    document: &Document<'a, S>,
+    operation: &Spanning<Operation<'a, S>>,
+    schema: &SchemaType<S>,
+    variables: &Variables<S>,
+    config: &CostConfig,
+) -> u64
+where
+    S: ScalarValue,
+{
+    let root_type_name = match operation.item.operation_type {
+        OperationType::Query => Some(schema.query_type_name.as_str()),
+        OperationType::Mutation => schema.mutation_type_name.as_deref(),
+        OperationType::Subscription => schema.subscription_type_name.as_deref(),
+    };
+    let root_type = match root_type_name.and_then(|name| schema.concrete_type_by_name(name)) {
+        Some(root_type) => root_type,
+        None => return 0,
+    };
+
+    let mut estimator = CostEstimator {
+        document,
+        schema,
+        variables,
+        config,
+        fragment_stack: Vec::new(),
+    };
+    estimator.selection_set_cost(&operation.item.selection_set, root_type)
+}
+
+struct CostEstimator<'d, 'a, S> {
+    document: &'d Document<'a, S>,
+    schema: &'d SchemaType<'d, S>,
+    variables: &'d Variables<S>,
+    config: &'d CostConfig,
+    fragment_stack: Vec<&'d str>,
+}
+
+impl<'d, 'a, S> CostEstimator<'d, 'a, S>
+where
+    S: ScalarValue,
+{
+    fn selection_set_cost(
+        &mut self,
+        selection_set: &'d [Selection<'a, S>],
+        parent_type: &'d MetaType<'d, S>,
+    ) -> u64 {
+        selection_set.iter().fold(0, |total, selection| {
+            total.saturating_add(self.selection_cost(selection, parent_type))
+        })
+    }
+
+    fn selection_cost(
+        &mut self,
+        selection: &'d Selection<'a, S>,
+        parent_type: &'d MetaType<'d, S>,
+    ) -> u64 {
+        match selection {
+            Selection::Field(field) => {
+                if is_excluded(&field.item.directives, self.variables) {
+                    return 0;
+                }
+                self.field_cost(&field.item, parent_type)
+            }
+            Selection::InlineFragment(fragment) => {
+                if is_excluded(&fragment.item.directives, self.variables) {
+                    return 0;
+                }
+                let fragment_type = fragment
+                    .item
+                    .type_condition
+                    .as_ref()
+                    .and_then(|cond| self.schema.concrete_type_by_name(cond.item))
+                    .unwrap_or(parent_type);
+                self.selection_set_cost(&fragment.item.selection_set, fragment_type)
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.item.name.item;
+                // Validation rejects fragment cycles already, this only guards against them in
+                // documents estimated without validation.
+                if is_excluded(&spread.item.directives, self.variables)
+                    || self.fragment_stack.contains(&name)
+                {
+                    return 0;
+                }
+                let fragment = match self.fragment(name) {
+                    Some(fragment) => fragment,
+                    None => return 0,
+                };
+                let fragment_type = self
+                    .schema
+                    .concrete_type_by_name(fragment.type_condition.item)
+                    .unwrap_or(parent_type);
+
+                self.fragment_stack.push(name);
+                let cost = self.selection_set_cost(&fragment.selection_set, fragment_type);
+                self.fragment_stack.pop();
+                cost
+            }
+        }
+    }
+
+    fn field_cost(&mut self, field: &'d Field<'a, S>, parent_type: &'d MetaType<'d, S>) -> u64 {
+        // Introspection fields, except the `__typename` leaves, aren't part of any type.
+        let meta_field = match parent_type.field_by_name(field.name.item) {
+            Some(meta_field) => meta_field,
+            None => return 0,
+        };
+        let field_type = self
+            .schema
+            .concrete_type_by_name(meta_field.field_type.innermost_name());
+
+        let own_cost = parent_type
+            .name()
+            .and_then(|type_name| {
+                self.config
+                    .field_costs
+                    .get(&(type_name.into(), field.name.item.into()))
+            })
+            .copied()
+            .unwrap_or_else(|| {
+                if field_type.map_or(false, MetaType::is_composite) {
+                    self.config.object_cost
+                } else {
+                    self.config.scalar_cost
+                }
+            });
+        let children_cost = match (&field.selection_set, field_type) {
+            (Some(selection_set), Some(field_type)) => {
+                self.selection_set_cost(selection_set, field_type)
+            }
+            _ => 0,
+        };
+        let count = if meta_field.field_type.is_list() {
+            self.list_size(field, meta_field)
+        } else {
+            1
+        };
+
+        count.saturating_mul(own_cost.saturating_add(children_cost))
+    }
+
+    fn list_size(&self, field: &Field<'a, S>, meta_field: &MetaField<S>) -> u64 {
+        for name in &self.config.list_size_arguments {
+            let provided = field
+                .arguments
+                .as_ref()
+                .and_then(|args| args.item.get(name))
+                .map(|arg| arg.item.clone().into_const(self.variables));
+            let size = provided
+                .or_else(|| {
+                    meta_field
+                        .arguments
+                        .iter()
+                        .flatten()
+                        .find(|arg| &arg.name == name)
+                        .and_then(|arg| arg.default_value.clone())
+                })
+                .as_ref()
+                .and_then(InputValue::as_int_value);
+            if let Some(size) = size {
+                return size.max(0) as u64;
+            }
+        }
+        self.config.default_list_size
+    }
+
+    fn fragment(&self, name: &str) -> Option<&'d Fragment<'a, S>> {
+        self.document.iter().find_map(|def| match def {
+            Definition::Fragment(fragment) if fragment.item.name.item == name => {
+                Some(&fragment.item)
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Context of a rate limited operation, identifying the client the cost of the operation is
+/// charged to.
+pub trait RateLimitClient {
+    /// Identifier of the client, like an API key or a remote address.
+    fn client_id(&self) -> &str;
+}
+
+/// Token bucket rate limiter, keeping a bucket of tokens per client.
+///
+/// Each bucket starts full with `capacity` tokens, and regains `refill_per_second` of them per
+/// second until full again. Executing an operation takes as many tokens as the operation costs
+/// from the bucket of its client, and operations costing more than the tokens left are rejected.
+pub struct RateLimiter {
+    capacity: u64,
+    refill_per_second: f64,
+    cost_config: CostConfig,
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    by_client: HashMap<String, Bucket>,
+    pruned_at: Option<Instant>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Number of buckets after which full ones are dropped, as they're identical to fresh ones.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Minimum time between two prunings of the buckets, so that clients keeping them from being
+/// dropped don't make every acquisition scan all of them.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+impl RateLimiter {
+    /// Creates a [`RateLimiter`] with buckets of `capacity` tokens, regaining `refill_per_second`
+    /// tokens per second, which estimates costs with the default [`CostConfig`].
+    ///
+    /// # Panics
+    ///
+    /// If `refill_per_second` isn't a finite positive number.
+    pub fn new(capacity: u64, refill_per_second: f64) -> Self {
+        assert!(
+            refill_per_second.is_finite() && refill_per_second > 0.0,
+            "`RateLimiter` refill rate must be a finite positive number, found: {}",
+            refill_per_second,
+        );
+        Self {
+            capacity,
+            refill_per_second,
+            cost_config: CostConfig::default(),
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// Sets the [`CostConfig`] used to estimate the cost of operations.
+    pub fn with_cost_config(mut self, config: CostConfig) -> Self {
+        self.cost_config = config;
+        self
+    }
+
+    /// The [`CostConfig`] used to estimate the cost of operations.
+    pub fn cost_config(&self) -> &CostConfig {
+        &self.cost_config
+    }
+
+    /// The number of tokens of a full bucket.
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Takes `cost` tokens from the bucket of the provided client, returning the number of tokens
+    /// left in it.
+    ///
+    /// # Errors
+    ///
+    /// If the bucket holds less than `cost` tokens, in which case none are taken.
+    pub fn try_acquire(&self, client_id: &str, cost: u64) -> Result<u64, RateLimitExceeded> {
+        self.try_acquire_at(client_id, cost, Instant::now())
+    }
+
+    /// Same as [`RateLimiter::try_acquire`], but refilling the bucket up to the provided `now`
+    /// instead of the current one.
+    pub fn try_acquire_at(
+        &self,
+        client_id: &str,
+        cost: u64,
+        now: Instant,
+    ) -> Result<u64, RateLimitExceeded> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        let prune_due = buckets.pruned_at.map_or(true, |at| {
+            now.saturating_duration_since(at) >= PRUNE_INTERVAL
+        });
+        if buckets.by_client.len() >= PRUNE_THRESHOLD && prune_due {
+            buckets
+                .by_client
+                .retain(|_, bucket| self.refilled(*bucket, now) < self.capacity as f64);
+            buckets.pruned_at = Some(now);
+        }
+
+        let fresh = Bucket {
+            tokens: self.capacity as f64,
+            updated_at: now,
+        };
+        let bucket = buckets.by_client.entry(client_id.into()).or_insert(fresh);
+        bucket.tokens = self.refilled(*bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= cost as f64 {
+            bucket.tokens -= cost as f64;
+            Ok(bucket.tokens as u64)
+        } else {
+            let retry_after = if cost > self.capacity {
+                None
+            } else {
+                let secs = (cost as f64 - bucket.tokens) / self.refill_per_second;
+                Some(Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX))
+            };
+            Err(RateLimitExceeded {
+                cost,
+                remaining: bucket.tokens as u64,
+                retry_after,
+            })
+        }
+    }
+
+    fn refilled(&self, bucket: Bucket, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        let tokens = bucket.tokens + elapsed.as_secs_f64() * self.refill_per_second;
+        tokens.min(self.capacity as f64)
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("capacity", &self.capacity)
+            .field("refill_per_second", &self.refill_per_second)
+            .field("cost_config", &self.cost_config)
+            .finish()
+    }
+}
+
+/// Error of an operation costing more tokens than its client has left.
+///
+/// Resolves to a field error with a `RATE_LIMITED` code, the `cost` of the operation, the
+/// `remaining` tokens and, unless the operation can never be afforded, the milliseconds to wait
+/// before retrying as `retryAfterMs`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimitExceeded {
+    /// Estimated cost of the rejected operation.
+    pub cost: u64,
+
+    /// Tokens left for the client.
+    pub remaining: u64,
+
+    /// Time after which the client will have enough tokens for the operation, or [`None`] if the
+    /// operation costs more than a full bucket.
+    pub retry_after: Option<Duration>,
+}
+
+impl fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Operation cost of {} exceeds the {} remaining for this client",
+            self.cost, self.remaining,
+        )
+    }
+}
+
+impl std::error::Error for RateLimitExceeded {}
+
+impl<S: ScalarValue> IntoFieldError<S> for RateLimitExceeded {
+    fn into_field_error(self) -> FieldError<S> {
+        let mut extensions = Object::with_capacity(4);
+        extensions.add_field("code", Value::scalar("RATE_LIMITED".to_owned()));
+        extensions.add_field("cost", number(self.cost));
+        extensions.add_field("remaining", number(self.remaining));
+        if let Some(retry_after) = self.retry_after {
+            let millis = retry_after.as_millis().min(u64::MAX as u128) as u64;
+            extensions.add_field("retryAfterMs", number(millis));
+        }
+        FieldError::new(self, Value::object(extensions))
+    }
+}
+
+/// Cost of an executed operation, reported under the [`CostReport::EXTENSION_NAME`] response
+/// extension.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostReport {
+    /// Estimated cost of the operation.
+    pub cost: u64,
+
+    /// Tokens left for the client after the operation.
+    pub remaining: u64,
+
+    /// Tokens of a full bucket.
+    pub capacity: u64,
+}
+
+impl CostReport {
+    /// Name of the response extension this report is put under.
+    pub const EXTENSION_NAME: &'static str = "cost";
+
+    /// Renders this [`CostReport`] as the value of its response extension.
+    pub fn to_extension<S>(&self) -> Value<S>
+    where
+        S: ScalarValue,
+    {
+        let mut obj = Object::with_capacity(3);
+        obj.add_field("cost", number(self.cost));
+        obj.add_field("remaining", number(self.remaining));
+        obj.add_field("capacity", number(self.capacity));
+        Value::object(obj)
+    }
+}
+
+fn number<S: ScalarValue>(n: u64) -> Value<S> {
+    if n <= i32::MAX as u64 {
+        Value::scalar(n as i32)
+    } else {
+        Value::scalar(n as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{RateLimitExceeded, RateLimiter, PRUNE_THRESHOLD};
+
+    #[test]
+    fn takes_tokens_until_empty() {
+        let limiter = RateLimiter::new(10, 1.0);
+        let now = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at("a", 4, now), Ok(6));
+        assert_eq!(limiter.try_acquire_at("a", 6, now), Ok(0));
+        assert_eq!(
+            limiter.try_acquire_at("a", 2, now),
+            Err(RateLimitExceeded {
+                cost: 2,
+                remaining: 0,
+                retry_after: Some(Duration::from_secs(2)),
+            }),
+        );
+    }
+
+    #[test]
+    fn keeps_a_bucket_per_client() {
+        let limiter = RateLimiter::new(5, 1.0);
+        let now = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at("a", 5, now), Ok(0));
+        assert_eq!(limiter.try_acquire_at("b", 1, now), Ok(4));
+    }
+
+    #[test]
+    fn refills_over_time_up_to_capacity() {
+        let limiter = RateLimiter::new(10, 2.0);
+        let now = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at("a", 10, now), Ok(0));
+        assert_eq!(
+            limiter.try_acquire_at("a", 3, now + Duration::from_secs(1)),
+            Err(RateLimitExceeded {
+                cost: 3,
+                remaining: 2,
+                retry_after: Some(Duration::from_millis(500)),
+            }),
+        );
+        assert_eq!(
+            limiter.try_acquire_at("a", 3, now + Duration::from_secs(2)),
+            Ok(1),
+        );
+        assert_eq!(
+            limiter.try_acquire_at("a", 0, now + Duration::from_secs(60)),
+            Ok(10),
+        );
+    }
+
+    #[test]
+    fn prunes_full_buckets_at_most_once_per_interval() {
+        let limiter = RateLimiter::new(1, 1.0);
+        let now = Instant::now();
+        let len = || limiter.buckets.lock().unwrap().by_client.len();
+
+        for i in 0..PRUNE_THRESHOLD {
+            assert_eq!(limiter.try_acquire_at(&i.to_string(), 1, now), Ok(0));
+        }
+        // None of the buckets is full, so none is dropped.
+        assert_eq!(limiter.try_acquire_at("a", 1, now), Ok(0));
+        assert_eq!(len(), PRUNE_THRESHOLD + 1);
+
+        // All of the buckets are full again, but they were pruned too recently.
+        let later = now + Duration::from_secs(2);
+        assert_eq!(limiter.try_acquire_at("b", 1, later), Ok(0));
+        assert_eq!(len(), PRUNE_THRESHOLD + 2);
+
+        let much_later = now + Duration::from_secs(120);
+        assert_eq!(limiter.try_acquire_at("c", 1, much_later), Ok(0));
+        assert_eq!(len(), 1);
+    }
+
+    #[test]
+    fn never_affords_more_than_capacity() {
+        let limiter = RateLimiter::new(10, 1.0);
+
+        let err = limiter.try_acquire("a", 11).unwrap_err();
+
+        assert_eq!(err.retry_after, None);
+        assert_eq!(limiter.try_acquire("a", 10), Ok(0));
+    }
+
+    #[test]
+    fn saturates_retry_after_of_slow_refills() {
+        let limiter = RateLimiter::new(10, f64::MIN_POSITIVE);
+        let now = Instant::now();
+
+        assert_eq!(limiter.try_acquire_at("a", 10, now), Ok(0));
+        assert_eq!(
+            limiter.try_acquire_at("a", 1, now).unwrap_err().retry_after,
+            Some(Duration::MAX),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate must be a finite positive number")]
+    fn rejects_non_positive_refill_rates() {
+        RateLimiter::new(10, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "refill rate must be a finite positive number")]
+    fn rejects_non_finite_refill_rates() {
+        RateLimiter::new(10, f64::NAN);
+    }
+}
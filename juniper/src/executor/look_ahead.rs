@@ -1,6 +1,12 @@
 use crate::{
-    ast::{Directive, Fragment, InputValue, Selection},
-    parser::Spanning,
+    ast::{self, Fragment, FromInputValue, InputValue, Selection},
+    executor::{FieldResult, IntoFieldError},
+    parser::{SourcePosition, Spanning},
+    schema::{
+        meta::{Field, MetaType},
+        model::SchemaType,
+    },
+    types::base::is_excluded,
     value::ScalarValue,
 };
 
@@ -62,13 +68,36 @@ where
             ),
         }
     }
+
+    /// Converts this value back into an unlocated constant `InputValue`
+    pub fn to_input_value(&self) -> InputValue<S> {
+        match *self {
+            LookAheadValue::Null => InputValue::Null,
+            LookAheadValue::Scalar(s) => InputValue::Scalar(s.clone()),
+            LookAheadValue::Enum(e) => InputValue::enum_value(e),
+            LookAheadValue::List(ref l) => {
+                InputValue::list(l.iter().map(LookAheadValue::to_input_value).collect())
+            }
+            LookAheadValue::Object(ref o) => InputValue::parsed_object(
+                o.iter()
+                    .map(|&(n, ref v)| {
+                        (
+                            Spanning::unlocated(n.to_owned()),
+                            Spanning::unlocated(v.to_input_value()),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
 }
 
 /// An argument passed into the query
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct LookAheadArgument<'a, S: 'a> {
     name: &'a str,
     value: LookAheadValue<'a, S>,
+    span: (SourcePosition, SourcePosition),
 }
 
 impl<'a, S> LookAheadArgument<'a, S>
@@ -82,6 +111,7 @@ where
         LookAheadArgument {
             name: name.item,
             value: LookAheadValue::from_input_value(&value.item, vars),
+            span: (name.start, value.end),
         }
     }
 
@@ -94,6 +124,29 @@ where
     pub fn value(&'a self) -> &LookAheadValue<'a, S> {
         &self.value
     }
+
+    /// Converts the value of the argument into `T`
+    pub fn value_as<T>(&self) -> Result<T, T::Error>
+    where
+        T: FromInputValue<S>,
+    {
+        T::from_input_value(&self.value.to_input_value())
+    }
+
+    /// The start and end positions of the argument in the query document
+    pub fn span(&self) -> (SourcePosition, SourcePosition) {
+        self.span
+    }
+}
+
+/// Arguments are compared by name and value only, regardless of their span.
+impl<'a, S> PartialEq for LookAheadArgument<'a, S>
+where
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
 }
 
 #[doc(hidden)]
@@ -104,12 +157,28 @@ pub struct ChildSelection<'a, S: 'a> {
 }
 
 /// A selection performed by a query
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct LookAheadSelection<'a, S: 'a> {
     pub(super) name: &'a str,
     pub(super) alias: Option<&'a str>,
     pub(super) arguments: Vec<LookAheadArgument<'a, S>>,
     pub(super) children: Vec<ChildSelection<'a, S>>,
+    pub(super) field: Option<&'a Field<'a, S>>,
+    pub(super) span: (SourcePosition, SourcePosition),
+}
+
+/// Selections are compared by their structure only, regardless of the schema
+/// metadata and the spans they carry.
+impl<'a, S> PartialEq for LookAheadSelection<'a, S>
+where
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.alias == other.alias
+            && self.arguments == other.arguments
+            && self.children == other.children
+    }
 }
 
 impl<'a, S> Default for LookAheadSelection<'a, S>
@@ -122,6 +191,8 @@ where
             alias: None,
             arguments: vec![],
             children: vec![],
+            field: None,
+            span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
         }
     }
 }
@@ -130,115 +201,103 @@ impl<'a, S> LookAheadSelection<'a, S>
 where
     S: ScalarValue,
 {
-    fn should_include<'b, 'c>(
-        directives: Option<&'b Vec<Spanning<Directive<S>>>>,
-        vars: &'c Variables<S>,
-    ) -> bool
-    where
-        'b: 'a,
-        'c: 'a,
-    {
-        directives
-            .map(|d| {
-                d.iter().all(|d| {
-                    let d = &d.item;
-                    let arguments = &d.arguments;
-                    match (d.name.item, arguments) {
-                        ("include", &Some(ref a)) => a
-                            .item
-                            .items
-                            .iter()
-                            .find(|item| item.0.item == "if")
-                            .map(|&(_, ref v)| {
-                                if let LookAheadValue::Scalar(s) =
-                                    LookAheadValue::from_input_value(&v.item, vars)
-                                {
-                                    s.as_boolean().unwrap_or(false)
-                                } else {
-                                    false
-                                }
-                            })
-                            .unwrap_or(false),
-                        ("skip", &Some(ref a)) => a
-                            .item
-                            .items
-                            .iter()
-                            .find(|item| item.0.item == "if")
-                            .map(|&(_, ref v)| {
-                                if let LookAheadValue::Scalar(b) =
-                                    LookAheadValue::from_input_value(&v.item, vars)
-                                {
-                                    b.as_boolean().map(::std::ops::Not::not).unwrap_or(false)
-                                } else {
-                                    false
-                                }
-                            })
-                            .unwrap_or(false),
-                        ("skip", &None) => false,
-                        ("include", &None) => true,
-                        (_, _) => unreachable!(),
-                    }
-                })
-            })
-            .unwrap_or(true)
-    }
-
     pub(super) fn build_from_selection(
         s: &'a Selection<'a, S>,
+        meta_field: Option<&'a Field<'a, S>>,
+        schema: Option<&'a SchemaType<'a, S>>,
         vars: &'a Variables<S>,
         fragments: &'a HashMap<&'a str, Fragment<'a, S>>,
     ) -> Option<LookAheadSelection<'a, S>> {
-        Self::build_from_selection_with_parent(s, None, vars, fragments)
+        match *s {
+            Selection::Field(ref field) => {
+                if is_excluded(&field.item.directives, vars) {
+                    return None;
+                }
+                Some(Self::build_from_field(
+                    field, meta_field, schema, vars, fragments,
+                ))
+            }
+            _ => Self::build_from_selection_with_parent(s, None, None, schema, vars, fragments),
+        }
+    }
+
+    fn build_from_field(
+        field: &'a Spanning<ast::Field<'a, S>>,
+        meta_field: Option<&'a Field<'a, S>>,
+        schema: Option<&'a SchemaType<'a, S>>,
+        vars: &'a Variables<S>,
+        fragments: &'a HashMap<&'a str, Fragment<'a, S>>,
+    ) -> LookAheadSelection<'a, S> {
+        let span = (field.start, field.end);
+        let field = &field.item;
+        let arguments = field
+            .arguments
+            .as_ref()
+            .map(|a| &a.item)
+            .map(|a| {
+                a.items
+                    .iter()
+                    .map(|p| LookAheadArgument::new(p, vars))
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new);
+        let mut ret = LookAheadSelection {
+            name: field.name.item,
+            alias: field.alias.as_ref().map(|a| a.item),
+            arguments,
+            children: Vec::new(),
+            field: meta_field,
+            span,
+        };
+        ret.add_children(
+            field.selection_set.as_deref().unwrap_or_default(),
+            schema,
+            vars,
+            fragments,
+        );
+        ret
+    }
+
+    /// Adds the given selections to the children of this selection, resolving
+    /// them against the return type of its field.
+    pub(super) fn add_children(
+        &mut self,
+        selection_set: &'a [Selection<'a, S>],
+        schema: Option<&'a SchemaType<'a, S>>,
+        vars: &'a Variables<S>,
+        fragments: &'a HashMap<&'a str, Fragment<'a, S>>,
+    ) {
+        let field_type = self.field.and_then(|f| {
+            schema.and_then(|s| s.concrete_type_by_name(f.field_type.innermost_name()))
+        });
+        for c in selection_set {
+            let s = LookAheadSelection::build_from_selection_with_parent(
+                c,
+                Some(self),
+                field_type,
+                schema,
+                vars,
+                fragments,
+            );
+            assert!(s.is_none());
+        }
     }
 
     pub(super) fn build_from_selection_with_parent(
         s: &'a Selection<'a, S>,
         parent: Option<&mut Self>,
+        parent_type: Option<&'a MetaType<'a, S>>,
+        schema: Option<&'a SchemaType<'a, S>>,
         vars: &'a Variables<S>,
         fragments: &'a HashMap<&'a str, Fragment<'a, S>>,
     ) -> Option<LookAheadSelection<'a, S>> {
-        let empty: &[Selection<S>] = &[];
         match *s {
             Selection::Field(ref field) => {
-                let field = &field.item;
-                let include = Self::should_include(field.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&field.item.directives, vars) {
                     return None;
                 }
-                let name = field.name.item;
-                let alias = field.alias.as_ref().map(|a| a.item);
-                let arguments = field
-                    .arguments
-                    .as_ref()
-                    .map(|a| &a.item)
-                    .map(|a| {
-                        a.items
-                            .iter()
-                            .map(|p| LookAheadArgument::new(p, vars))
-                            .collect()
-                    })
-                    .unwrap_or_else(Vec::new);
-                let mut ret = LookAheadSelection {
-                    name,
-                    alias,
-                    arguments,
-                    children: Vec::new(),
-                };
-                for c in field
-                    .selection_set
-                    .as_ref()
-                    .map(|s| s as &[_])
-                    .unwrap_or_else(|| empty)
-                    .iter()
-                {
-                    let s = LookAheadSelection::build_from_selection_with_parent(
-                        c,
-                        Some(&mut ret),
-                        vars,
-                        fragments,
-                    );
-                    assert!(s.is_none());
-                }
+                let meta_field = parent_type.and_then(|t| t.field_by_name(field.item.name.item));
+                let ret = Self::build_from_field(field, meta_field, schema, vars, fragments);
                 if let Some(p) = parent {
                     p.children.push(ChildSelection {
                         inner: ret,
@@ -250,25 +309,48 @@ where
                 }
             }
             Selection::FragmentSpread(ref fragment) => {
-                let include = Self::should_include(fragment.item.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&fragment.item.directives, vars) {
                     return None;
                 }
                 let f = fragments.get(&fragment.item.name.item).expect("a fragment");
+                let type_condition = f.type_condition.item;
+                let fragment_type = schema
+                    .and_then(|s| s.concrete_type_by_name(type_condition))
+                    .or(parent_type);
                 if let Some(parent) = parent {
+                    let first_child = parent.children.len();
                     for c in f.selection_set.iter() {
                         let s = LookAheadSelection::build_from_selection_with_parent(
                             c,
                             Some(parent),
+                            fragment_type,
+                            schema,
                             vars,
                             fragments,
                         );
                         assert!(s.is_none());
                     }
+                    // Without a schema we can't tell the fragment's type
+                    // condition apart from the parent type, so the fields are
+                    // assumed to apply to all types.
+                    if parent_type.is_some() {
+                        Self::restrict_children(
+                            parent,
+                            first_child,
+                            Some(type_condition),
+                            parent_type,
+                            schema,
+                        );
+                    }
                 } else {
                     for c in f.selection_set.iter() {
                         let s = LookAheadSelection::build_from_selection_with_parent(
-                            c, None, vars, fragments,
+                            c,
+                            None,
+                            fragment_type,
+                            schema,
+                            vars,
+                            fragments,
                         );
                         assert!(s.is_some());
                     }
@@ -276,31 +358,66 @@ where
                 None
             }
             Selection::InlineFragment(ref inline) if parent.is_some() => {
-                let include = Self::should_include(inline.item.directives.as_ref(), vars);
-                if !include {
+                if is_excluded(&inline.item.directives, vars) {
                     return None;
                 }
                 let parent = parent.unwrap();
+                let type_condition = inline.item.type_condition.as_ref().map(|t| t.item);
+                let fragment_type = type_condition
+                    .and_then(|c| schema.and_then(|s| s.concrete_type_by_name(c)))
+                    .or(parent_type);
+                let first_child = parent.children.len();
                 for c in inline.item.selection_set.iter() {
                     let s = LookAheadSelection::build_from_selection_with_parent(
                         c,
                         Some(parent),
+                        fragment_type,
+                        schema,
                         vars,
                         fragments,
                     );
                     assert!(s.is_none());
-                    if let Some(ref c) = inline.item.type_condition.as_ref().map(|t| t.item) {
-                        if let Some(p) = parent.children.last_mut() {
-                            p.applies_for = Applies::OnlyType(c);
-                        }
-                    }
                 }
+                Self::restrict_children(parent, first_child, type_condition, parent_type, schema);
                 None
             }
             _ => unimplemented!(),
         }
     }
 
+    /// Restricts the children of `parent` starting at `first_child` to the
+    /// type condition of the fragment they were selected in, unless the
+    /// condition applies to any value of the parent type anyway.
+    fn restrict_children(
+        parent: &mut Self,
+        first_child: usize,
+        type_condition: Option<&'a str>,
+        parent_type: Option<&'a MetaType<'a, S>>,
+        schema: Option<&'a SchemaType<'a, S>>,
+    ) {
+        let type_condition = match type_condition {
+            Some(c) => c,
+            None => return,
+        };
+        if let Some(parent_type) = parent_type {
+            if parent_type.name() == Some(type_condition) {
+                return;
+            }
+            let condition_type =
+                schema.and_then(|s| Some((s, s.concrete_type_by_name(type_condition)?)));
+            if let Some((schema, condition_type)) = condition_type {
+                if condition_type.is_abstract()
+                    && schema.is_possible_type(condition_type, parent_type)
+                {
+                    return;
+                }
+            }
+        }
+        for c in &mut parent.children[first_child..] {
+            c.applies_for = Applies::OnlyType(type_condition);
+        }
+    }
+
     /// Convert a eventually type independent selection into one for a concrete type
     pub fn for_explicit_type(&self, type_name: &str) -> ConcreteLookAheadSelection<'a, S> {
         ConcreteLookAheadSelection {
@@ -318,17 +435,35 @@ where
             name: self.name,
             alias: self.alias,
             arguments: self.arguments.clone(),
+            field: self.field,
+            span: self.span,
         }
     }
 }
 
 /// A selection performed by a query on a concrete type
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct ConcreteLookAheadSelection<'a, S: 'a> {
     name: &'a str,
     alias: Option<&'a str>,
     arguments: Vec<LookAheadArgument<'a, S>>,
     children: Vec<ConcreteLookAheadSelection<'a, S>>,
+    field: Option<&'a Field<'a, S>>,
+    span: (SourcePosition, SourcePosition),
+}
+
+/// Selections are compared by their structure only, regardless of the schema
+/// metadata and the spans they carry.
+impl<'a, S> PartialEq for ConcreteLookAheadSelection<'a, S>
+where
+    S: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.alias == other.alias
+            && self.arguments == other.arguments
+            && self.children == other.children
+    }
 }
 
 /// Set of common methods for `ConcreteLookAheadSelection` and `LookAheadSelection`.
@@ -339,6 +474,30 @@ pub trait LookAheadMethods<'sel, S> {
     /// Get the (potentially aliased) name of the field represented by the current selection
     fn field_name(&self) -> &'sel str;
 
+    /// Get the original (not aliased) name of the field represented by the current selection
+    fn field_original_name(&self) -> &'sel str;
+
+    /// Get the alias of the field represented by the current selection, if any
+    fn field_alias(&self) -> Option<&'sel str>;
+
+    /// Get the schema metadata of the field represented by the current selection
+    ///
+    /// This gives access to the field's return type (e.g. whether it is a
+    /// list) and its deprecation status. It is `None` for fields not known to
+    /// the schema.
+    fn field_meta(&self) -> Option<&'sel Field<'sel, S>>;
+
+    /// Is the current selection the `__typename` meta field?
+    ///
+    /// `__typename` is resolved by the executor itself instead of the type's
+    /// resolvers, so it usually needs to be skipped when projecting children.
+    fn is_typename(&self) -> bool {
+        self.field_original_name() == "__typename"
+    }
+
+    /// Get the start and end positions of the current selection in the query document
+    fn span(&self) -> (SourcePosition, SourcePosition);
+
     /// Get the the child selection for a given field
     /// If a child has an alias, it will only match if the alias matches `name`
     fn select_child(&self, name: &str) -> Option<&Self>;
@@ -363,6 +522,33 @@ pub trait LookAheadMethods<'sel, S> {
         self.arguments().iter().find(|a| a.name == name)
     }
 
+    /// Get the top level argument with a given name from the current
+    /// selection, converted into `T`
+    ///
+    /// Like `Arguments::get`, this falls back to the default value declared in
+    /// the schema if the argument isn't passed in the query, and returns
+    /// `None` if there is neither.
+    fn argument_as<T>(&self, name: &str) -> FieldResult<Option<T>, S>
+    where
+        T: FromInputValue<S>,
+        S: ScalarValue,
+    {
+        self.argument(name)
+            .map(|a| a.value.to_input_value())
+            .or_else(|| {
+                self.field_meta()?
+                    .arguments
+                    .as_ref()?
+                    .iter()
+                    .find(|a| a.name == name)?
+                    .default_value
+                    .clone()
+            })
+            .map(|v| T::from_input_value(&v))
+            .transpose()
+            .map_err(IntoFieldError::into_field_error)
+    }
+
     /// Get the (possibly aliased) names of the top level children for the current selection
     fn child_names(&self) -> Vec<&'sel str>;
 
@@ -375,6 +561,22 @@ impl<'a, S> LookAheadMethods<'a, S> for ConcreteLookAheadSelection<'a, S> {
         self.alias.unwrap_or(self.name)
     }
 
+    fn field_original_name(&self) -> &'a str {
+        self.name
+    }
+
+    fn field_alias(&self) -> Option<&'a str> {
+        self.alias
+    }
+
+    fn field_meta(&self) -> Option<&'a Field<'a, S>> {
+        self.field
+    }
+
+    fn span(&self) -> (SourcePosition, SourcePosition) {
+        self.span
+    }
+
     fn select_child(&self, name: &str) -> Option<&Self> {
         self.children.iter().find(|c| c.field_name() == name)
     }
@@ -405,6 +607,22 @@ impl<'a, S> LookAheadMethods<'a, S> for LookAheadSelection<'a, S> {
         self.alias.unwrap_or(self.name)
     }
 
+    fn field_original_name(&self) -> &'a str {
+        self.name
+    }
+
+    fn field_alias(&self) -> Option<&'a str> {
+        self.alias
+    }
+
+    fn field_meta(&self) -> Option<&'a Field<'a, S>> {
+        self.field
+    }
+
+    fn span(&self) -> (SourcePosition, SourcePosition) {
+        self.span
+    }
+
     fn select_child(&self, name: &str) -> Option<&Self> {
         self.children
             .iter()
@@ -491,6 +709,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -506,6 +726,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -515,10 +736,12 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -545,6 +768,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -560,6 +785,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -569,10 +795,12 @@ query Hero {
                             alias: Some("my_name"),
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -603,6 +831,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -618,6 +848,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -627,6 +858,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -642,6 +874,7 @@ query Hero {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::All,
                                 },
@@ -651,14 +884,17 @@ query Hero {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::All,
                                 },
                             ],
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -685,6 +921,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -695,6 +933,7 @@ query Hero {
                 arguments: vec![LookAheadArgument {
                     name: "episode",
                     value: LookAheadValue::Enum("EMPIRE"),
+                    span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                 }],
                 children: vec![
                     ChildSelection {
@@ -703,6 +942,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -713,12 +953,15 @@ query Hero {
                             arguments: vec![LookAheadArgument {
                                 name: "uppercase",
                                 value: LookAheadValue::Scalar(&DefaultScalarValue::Boolean(true)),
+                                span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                             }],
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -746,6 +989,8 @@ query Hero($episode: Episode) {
             vars.insert("episode".into(), InputValue::Enum("JEDI".into()));
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -756,6 +1001,7 @@ query Hero($episode: Episode) {
                 arguments: vec![LookAheadArgument {
                     name: "episode",
                     value: LookAheadValue::Enum("JEDI"),
+                    span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                 }],
                 children: vec![
                     ChildSelection {
@@ -764,6 +1010,7 @@ query Hero($episode: Episode) {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -773,10 +1020,12 @@ query Hero($episode: Episode) {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -802,6 +1051,8 @@ query Hero($episode: Episode) {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -812,6 +1063,7 @@ query Hero($episode: Episode) {
                 arguments: vec![LookAheadArgument {
                     name: "episode",
                     value: LookAheadValue::Null,
+                    span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                 }],
                 children: vec![ChildSelection {
                     inner: LookAheadSelection {
@@ -819,9 +1071,11 @@ query Hero($episode: Episode) {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 }],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -852,6 +1106,8 @@ fragment commonFields on Character {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -867,6 +1123,7 @@ fragment commonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -876,6 +1133,7 @@ fragment commonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -885,10 +1143,12 @@ fragment commonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -916,6 +1176,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -931,6 +1193,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -940,10 +1203,12 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -974,6 +1239,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -989,6 +1256,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -998,6 +1266,7 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::OnlyType("Droid"),
                     },
@@ -1007,10 +1276,12 @@ query Hero {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::OnlyType("Human"),
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -1039,6 +1310,8 @@ query HeroAndHuman {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -1053,14 +1326,18 @@ query HeroAndHuman {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 }],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
 
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[1],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -1075,9 +1352,11 @@ query HeroAndHuman {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 }],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -1121,6 +1400,8 @@ fragment comparisonFields on Character {
             );
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -1131,6 +1412,7 @@ fragment comparisonFields on Character {
                 arguments: vec![LookAheadArgument {
                     name: "id",
                     value: LookAheadValue::Scalar(&DefaultScalarValue::Int(42)),
+                    span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                 }],
                 children: vec![
                     ChildSelection {
@@ -1139,6 +1421,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -1148,6 +1431,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -1157,6 +1441,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -1166,6 +1451,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
@@ -1175,6 +1461,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::OnlyType("Droid"),
                     },
@@ -1184,6 +1471,7 @@ fragment comparisonFields on Character {
                             alias: None,
                             arguments: Vec::new(),
                             children: Vec::new(),
+                            ..Default::default()
                         },
                         applies_for: Applies::OnlyType("Human"),
                     },
@@ -1199,6 +1487,7 @@ fragment comparisonFields on Character {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::All,
                                 },
@@ -1208,6 +1497,7 @@ fragment comparisonFields on Character {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::All,
                                 },
@@ -1217,6 +1507,7 @@ fragment comparisonFields on Character {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::All,
                                 },
@@ -1226,6 +1517,7 @@ fragment comparisonFields on Character {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::OnlyType("Droid"),
                                 },
@@ -1235,14 +1527,17 @@ fragment comparisonFields on Character {
                                         alias: None,
                                         arguments: Vec::new(),
                                         children: Vec::new(),
+                                        ..Default::default()
                                     },
                                     applies_for: Applies::OnlyType("Human"),
                                 },
                             ],
+                            ..Default::default()
                         },
                         applies_for: Applies::All,
                     },
                 ],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -1273,6 +1568,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -1288,14 +1585,20 @@ query Hero {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        field: None,
+                        span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                     },
                     ConcreteLookAheadSelection {
                         name: "height",
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        field: None,
+                        span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
                     },
                 ],
+                field: None,
+                span: (SourcePosition::new_origin(), SourcePosition::new_origin()),
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -1316,6 +1619,7 @@ query Hero {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 },
@@ -1331,6 +1635,7 @@ query Hero {
                                     alias: None,
                                     arguments: Vec::new(),
                                     children: Vec::new(),
+                                    ..Default::default()
                                 },
                                 applies_for: Applies::All,
                             },
@@ -1340,14 +1645,17 @@ query Hero {
                                     alias: None,
                                     arguments: Vec::new(),
                                     children: Vec::new(),
+                                    ..Default::default()
                                 },
                                 applies_for: Applies::All,
                             },
                         ],
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 },
             ],
+            ..Default::default()
         };
         let concret_query = lookahead.for_explicit_type("does not matter");
 
@@ -1358,6 +1666,7 @@ query Hero {
             alias: None,
             arguments: Vec::new(),
             children: Vec::new(),
+            ..Default::default()
         };
         assert_eq!(id, Some(&expected));
         assert_eq!(
@@ -1378,6 +1687,7 @@ query Hero {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 },
@@ -1387,10 +1697,12 @@ query Hero {
                         alias: None,
                         arguments: Vec::new(),
                         children: Vec::new(),
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 },
             ],
+            ..Default::default()
         };
         assert_eq!(friends, Some(&expected));
         assert_eq!(
@@ -1422,6 +1734,8 @@ fragment heroFriendNames on Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
@@ -1441,12 +1755,15 @@ fragment heroFriendNames on Hero {
                                 alias: None,
                                 arguments: Vec::new(),
                                 children: Vec::new(),
+                                ..Default::default()
                             },
                             applies_for: Applies::All,
                         }],
+                        ..Default::default()
                     },
                     applies_for: Applies::All,
                 }],
+                ..Default::default()
             };
             assert_eq!(look_ahead, expected);
         } else {
@@ -1476,6 +1793,8 @@ query Hero {
             let vars = Variables::default();
             let look_ahead = LookAheadSelection::build_from_selection(
                 &op.item.selection_set[0],
+                None,
+                None,
                 &vars,
                 &fragments,
             )
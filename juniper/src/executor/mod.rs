@@ -6,10 +6,11 @@ use std::{
     collections::HashMap,
     fmt::{Debug, Display},
     sync::{Arc, RwLock},
+    time::{Duration, Instant},
 };
 
 use fnv::FnvHashMap;
-use futures::Stream;
+use futures::{Future, Stream};
 
 use crate::{
     ast::{
@@ -27,7 +28,7 @@ use crate::{
     },
     types::{
         async_await::{GraphQLTypeAsync, GraphQLValueAsync},
-        base::{GraphQLType, GraphQLValue},
+        base::{is_excluded, GraphQLType, GraphQLValue},
         name::Name,
         subscriptions::{GraphQLSubscriptionType, GraphQLSubscriptionValue},
     },
@@ -41,10 +42,15 @@ pub use self::{
         LookAheadSelection, LookAheadValue,
     },
     owned_executor::OwnedExecutor,
+    trace::{
+        ErrorTrace, ExecutionTrace, ExecutionTracer, PathSegment, PhaseTrace, ResolverTrace,
+        TraceFormat,
+    },
 };
 
 mod look_ahead;
 mod owned_executor;
+mod trace;
 
 /// A type registry used to build schemas
 ///
@@ -61,6 +67,7 @@ pub struct Registry<'r, S = DefaultScalarValue> {
 pub enum FieldPath<'a> {
     Root(SourcePosition),
     Field(&'a str, SourcePosition, Arc<FieldPath<'a>>),
+    Index(usize, Arc<FieldPath<'a>>),
 }
 
 /// Query execution engine
@@ -77,10 +84,14 @@ where
     current_selection_set: Option<&'r [Selection<'a, S>]>,
     parent_selection_set: Option<&'r [Selection<'a, S>]>,
     current_type: TypeType<'a, S>,
+    current_field: Option<&'a Field<'a, S>>,
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
     field_path: Arc<FieldPath<'a>>,
+    operation_name: Option<&'r str>,
+    deadline: Option<Instant>,
+    tracer: Option<&'r ExecutionTracer>,
 }
 
 /// Error type for errors that occur during query execution
@@ -233,6 +244,50 @@ impl<S> FieldError<S> {
     }
 }
 
+/// Resolves the provided field `resolver`, failing with a `TIMEOUT` [`FieldError`] if it doesn't
+/// finish within the provided `timeout`.
+///
+/// The `resolver` is dropped as soon as the `timeout` elapses, so no more work is done for it.
+pub(crate) async fn resolve_with_timeout<S, F>(timeout: Duration, resolver: F) -> ExecutionResult<S>
+where
+    S: ScalarValue,
+    F: Future<Output = ExecutionResult<S>> + Unpin,
+{
+    use futures::future::{self, Either};
+
+    let timed_out = || {
+        FieldError::new(
+            format!("Field resolution timed out after {:?}", timeout),
+            graphql_value!({ "code": "TIMEOUT" }),
+        )
+    };
+
+    if timeout == Duration::ZERO {
+        return Err(timed_out());
+    }
+
+    match future::select(resolver, futures_timer::Delay::new(timeout)).await {
+        Either::Left((res, _)) => res,
+        Either::Right(_) => Err(timed_out()),
+    }
+}
+
+/// Creates a [`tracing::Span`] for executing the provided `operation`.
+#[cfg(feature = "tracing")]
+fn operation_span<S>(operation: &Operation<S>) -> tracing::Span {
+    tracing::info_span!(
+        "execute_operation",
+        graphql.operation = operation.name.as_ref().map(|n| n.item).unwrap_or_default(),
+        graphql.operation_type = ?operation.operation_type,
+    )
+}
+
+/// Records the provided field `error` as an event of the current [`tracing::Span`].
+#[cfg(feature = "tracing")]
+pub(crate) fn trace_field_error<S>(error: &FieldError<S>) {
+    tracing::error!(error = error.message(), "field resolution failed");
+}
+
 /// The result of resolving the value of a field of type `T`
 pub type FieldResult<T, S = DefaultScalarValue> = Result<T, FieldError<S>>;
 
@@ -268,6 +323,27 @@ impl<S> IntoFieldError<S> for std::convert::Infallible {
     }
 }
 
+impl<'a, S> IntoFieldError<S> for &'a str {
+    #[inline]
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::Null)
+    }
+}
+
+impl<S> IntoFieldError<S> for String {
+    #[inline]
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::Null)
+    }
+}
+
+impl<'a, S> IntoFieldError<S> for Cow<'a, str> {
+    #[inline]
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::Null)
+    }
+}
+
 #[doc(hidden)]
 pub trait IntoResolvable<'a, S, T, C>
 where
@@ -534,10 +610,14 @@ where
             current_selection_set: self.current_selection_set,
             parent_selection_set: self.parent_selection_set,
             current_type: self.current_type.clone(),
+            current_field: self.current_field,
             schema: self.schema,
             context: ctx,
             errors: self.errors,
             field_path: self.field_path.clone(),
+            operation_name: self.operation_name,
+            deadline: self.deadline,
+            tracer: self.tracer,
         }
     }
 
@@ -549,19 +629,19 @@ where
         location: SourcePosition,
         selection_set: Option<&'s [Selection<'a, S>]>,
     ) -> Executor<'s, 'a, CtxT, S> {
+        let field = self
+            .current_type
+            .innermost_concrete()
+            .field_by_name(field_name)
+            .expect("Field not found on inner type");
+
         Executor {
             fragments: self.fragments,
             variables: self.variables,
             current_selection_set: selection_set,
             parent_selection_set: self.current_selection_set,
-            current_type: self.schema.make_type(
-                &self
-                    .current_type
-                    .innermost_concrete()
-                    .field_by_name(field_name)
-                    .expect("Field not found on inner type")
-                    .field_type,
-            ),
+            current_type: self.schema.make_type(&field.field_type),
+            current_field: Some(field),
             schema: self.schema,
             context: self.context,
             errors: self.errors,
@@ -570,6 +650,34 @@ where
                 location,
                 Arc::clone(&self.field_path),
             )),
+            operation_name: self.operation_name,
+            deadline: self.deadline,
+            tracer: self.tracer,
+        }
+    }
+
+    /// Creates an executor for the item at `index` of the list this [`Executor`] resolves.
+    ///
+    /// Only traced executions keep track of the list indices, which are left out of the paths of
+    /// errors.
+    pub(crate) fn list_item_sub_executor(&self, index: usize) -> Executor<'_, 'a, CtxT, S> {
+        Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set,
+            parent_selection_set: self.parent_selection_set,
+            current_type: self.current_type.clone(),
+            current_field: self.current_field,
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            field_path: match self.tracer {
+                Some(_) => Arc::new(FieldPath::Index(index, Arc::clone(&self.field_path))),
+                None => Arc::clone(&self.field_path),
+            },
+            operation_name: self.operation_name,
+            deadline: self.deadline,
+            tracer: self.tracer,
         }
     }
 
@@ -588,10 +696,14 @@ where
                 Some(type_name) => self.schema.type_by_name(type_name).expect("Type not found"),
                 None => self.current_type.clone(),
             },
+            current_field: self.current_field,
             schema: self.schema,
             context: self.context,
             errors: self.errors,
             field_path: self.field_path.clone(),
+            operation_name: self.operation_name,
+            deadline: self.deadline,
+            tracer: self.tracer,
         }
     }
 
@@ -633,6 +745,94 @@ where
         self.field_path.location()
     }
 
+    /// Name of the operation being executed, if it has one.
+    pub fn operation_name(&self) -> Option<&'r str> {
+        self.operation_name
+    }
+
+    /// The point in time by which the whole operation has to be resolved, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Time left until the [`Executor::deadline`] passes.
+    ///
+    /// Returns [`None`] if there is no deadline, and [`Duration::ZERO`] once it has passed.
+    /// Long-running resolvers may check it to stop work that won't make it into the response.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    /// Time the field with the provided `field_timeout` is allowed to take to resolve, taking
+    /// the [`Executor::deadline`] into account.
+    ///
+    /// All the fields are resolved within the root ones, so the [`Executor::deadline`] only has
+    /// to cut the root fields, and the other ones are only given a time if they have their own
+    /// `field_timeout`.
+    pub(crate) fn field_timeout(&self, field_timeout: Option<Duration>) -> Option<Duration> {
+        match (field_timeout, self.remaining_time()) {
+            (Some(f), Some(r)) => Some(f.min(r)),
+            (Some(f), None) => Some(f),
+            (None, r) => match *self.field_path {
+                FieldPath::Root(_) => r,
+                FieldPath::Field(..) | FieldPath::Index(..) => None,
+            },
+        }
+    }
+
+    /// Creates a [`tracing::Span`] for resolving the field this [`Executor`] was created for by
+    /// [`Executor::field_sub_executor`].
+    #[cfg(feature = "tracing")]
+    pub(crate) fn field_span(
+        &self,
+        meta_field: &Field<S>,
+        parent_type: Option<&str>,
+    ) -> tracing::Span {
+        if meta_field.skip_tracing {
+            return tracing::Span::none();
+        }
+
+        let mut path = Vec::new();
+        self.field_path.construct_path(&mut path);
+
+        tracing::info_span!(
+            "resolve_field",
+            graphql.field = %meta_field.name,
+            graphql.path = %path.join("."),
+            graphql.parent_type = parent_type.unwrap_or_default(),
+            graphql.operation = self.operation_name.unwrap_or_default(),
+        )
+    }
+
+    /// Records the timing of resolving the field this [`Executor`] was created for by
+    /// [`Executor::field_sub_executor`], if the execution is traced.
+    pub(crate) fn trace_resolver(
+        &self,
+        meta_field: &Field<S>,
+        parent_type: Option<&str>,
+        started: Option<Instant>,
+    ) {
+        if let (Some(tracer), Some(started)) = (self.tracer, started) {
+            let mut path = Vec::new();
+            self.field_path.construct_trace_path(&mut path);
+
+            tracer.record_resolver(ResolverTrace {
+                path,
+                parent_type: parent_type.unwrap_or_default().to_owned(),
+                field_name: meta_field.name.to_string(),
+                return_type: meta_field.field_type.to_string(),
+                start_offset: tracer.offset(started),
+                duration: started.elapsed(),
+            });
+        }
+    }
+
+    /// Instant the field resolution starts at, if the execution is traced.
+    pub(crate) fn trace_start(&self) -> Option<Instant> {
+        self.tracer.map(|_| Instant::now())
+    }
+
     /// Add an error to the execution engine at the current executor location
     pub fn push_error(&self, error: FieldError<S>) {
         self.push_error_at(error, *self.location());
@@ -643,6 +843,16 @@ where
         let mut path = Vec::new();
         self.field_path.construct_path(&mut path);
 
+        if let Some(tracer) = self.tracer {
+            let mut path = Vec::new();
+            self.field_path.construct_trace_path(&mut path);
+            tracer.record_error(ErrorTrace {
+                path,
+                message: error.message().to_owned(),
+                location,
+            });
+        }
+
         let mut errors = self.errors.write().unwrap();
 
         errors.push(ExecutionError {
@@ -669,27 +879,31 @@ where
     /// This allows seeing the whole selection and perform operations
     /// affecting the children.
     pub fn look_ahead(&'a self) -> LookAheadSelection<'a, S> {
-        let field_name = match *self.field_path {
-            FieldPath::Field(x, ..) => x,
-            FieldPath::Root(_) => unreachable!(),
-        };
+        let field_name = self
+            .field_path
+            .field_name()
+            .expect("look-ahead of the root of the operation");
         self.parent_selection_set
             .map(|p| {
                 // Search the parent's fields to find this field within the set
-                let found_field = p.iter().find(|&x| {
-                    match *x {
-                        Selection::Field(ref field) => {
-                            let field = &field.item;
-                            // TODO: support excludes.
-                            let name = field.name.item;
-                            let alias = field.alias.as_ref().map(|a| a.item);
-                            alias.unwrap_or(name) == field_name
-                        }
-                        _ => false,
+                let found_field = p.iter().find(|&x| match *x {
+                    Selection::Field(ref field) => {
+                        let field = &field.item;
+                        let name = field.name.item;
+                        let alias = field.alias.as_ref().map(|a| a.item);
+                        alias.unwrap_or(name) == field_name
+                            && !is_excluded(&field.directives, self.variables)
                     }
+                    _ => false,
                 });
                 if let Some(p) = found_field {
-                    LookAheadSelection::build_from_selection(&p, self.variables, self.fragments)
+                    LookAheadSelection::build_from_selection(
+                        &p,
+                        self.current_field,
+                        Some(self.schema),
+                        self.variables,
+                        self.fragments,
+                    )
                 } else {
                     None
                 }
@@ -698,23 +912,23 @@ where
             .unwrap_or_else(|| {
                 // We didn't find a field in the parent's selection matching
                 // this field, which means we're inside a FragmentSpread
+                let name = self.current_field.map_or(field_name, |f| &f.name);
                 let mut ret = LookAheadSelection {
-                    name: field_name,
-                    alias: None,
-                    arguments: Vec::new(),
-                    children: Vec::new(),
+                    name,
+                    alias: Some(field_name).filter(|a| *a != name),
+                    field: self.current_field,
+                    span: (*self.location(), *self.location()),
+                    ..LookAheadSelection::default()
                 };
 
                 // Add in all the children - this will mutate `ret`
                 if let Some(selection_set) = self.current_selection_set {
-                    for c in selection_set {
-                        LookAheadSelection::build_from_selection_with_parent(
-                            c,
-                            Some(&mut ret),
-                            self.variables,
-                            self.fragments,
-                        );
-                    }
+                    ret.add_children(
+                        selection_set,
+                        Some(self.schema),
+                        self.variables,
+                        self.fragments,
+                    );
                 }
                 ret
             })
@@ -734,10 +948,13 @@ where
             current_selection_set: self.current_selection_set.map(|x| x.to_vec()),
             parent_selection_set: self.parent_selection_set.map(|x| x.to_vec()),
             current_type: self.current_type.clone(),
+            current_field: self.current_field,
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
             field_path: Arc::clone(&self.field_path),
+            operation_name: self.operation_name.map(ToOwned::to_owned),
+            deadline: self.deadline,
         }
     }
 }
@@ -750,12 +967,36 @@ impl<'a> FieldPath<'a> {
                 parent.construct_path(acc);
                 acc.push((*name).to_owned());
             }
+            FieldPath::Index(_, parent) => parent.construct_path(acc),
+        }
+    }
+
+    fn construct_trace_path(&self, acc: &mut Vec<PathSegment>) {
+        match self {
+            FieldPath::Root(_) => (),
+            FieldPath::Field(name, _, parent) => {
+                parent.construct_trace_path(acc);
+                acc.push(PathSegment::Field((*name).to_owned()));
+            }
+            FieldPath::Index(index, parent) => {
+                parent.construct_trace_path(acc);
+                acc.push(PathSegment::Index(*index));
+            }
+        }
+    }
+
+    fn field_name(&self) -> Option<&'a str> {
+        match self {
+            FieldPath::Root(_) => None,
+            FieldPath::Field(name, ..) => Some(name),
+            FieldPath::Index(_, parent) => parent.field_name(),
         }
     }
 
     fn location(&self) -> &SourcePosition {
-        match *self {
-            FieldPath::Root(ref pos) | FieldPath::Field(_, ref pos, _) => pos,
+        match self {
+            FieldPath::Root(pos) | FieldPath::Field(_, pos, _) => pos,
+            FieldPath::Index(_, parent) => parent.location(),
         }
     }
 }
@@ -859,12 +1100,19 @@ where
             current_selection_set: Some(&operation.item.selection_set[..]),
             parent_selection_set: None,
             current_type: root_type,
+            current_field: None,
             schema: &root_node.schema,
             context,
             errors: &errors,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            operation_name: operation.item.name.as_ref().map(|n| n.item),
+            deadline: None,
+            tracer: None,
         };
 
+        #[cfg(feature = "tracing")]
+        let _span = operation_span(&operation.item).entered();
+
         value = match operation.item.operation_type {
             OperationType::Query => executor.resolve_into_value(&root_node.query_info, &root_node),
             OperationType::Mutation => {
@@ -889,6 +1137,77 @@ pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, Subscripti
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_query_async_with_deadline(
+        document, operation, root_node, variables, context, None,
+    )
+    .await
+}
+
+/// Create new `Executor` and start asynchronous query execution, which must finish before the
+/// provided `deadline`.
+///
+/// Root fields still resolving when the `deadline` passes, and fields whose own
+/// [`Field::timeout`] elapses, resolve to an error with a `TIMEOUT` code, and their pending work
+/// is dropped. Dropping the
+/// returned future cancels all the fields that are still in flight.
+///
+/// Returns `IsSubscription` error if subscription is passed.
+pub async fn execute_validated_query_async_with_deadline<
+    'a,
+    'b,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    deadline: Option<Instant>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_query_async_traced(
+        document, operation, root_node, variables, context, deadline, None,
+    )
+    .await
+}
+
+/// Create new `Executor` and start asynchronous query execution, recording the timing of each
+/// resolved field into the provided `tracer`.
+///
+/// See [`execute_validated_query_async_with_deadline`] for the meaning of the `deadline`.
+///
+/// Returns `IsSubscription` error if subscription is passed.
+pub async fn execute_validated_query_async_traced<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    deadline: Option<Instant>,
+    tracer: Option<&ExecutionTracer>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -957,25 +1276,38 @@ where
             current_selection_set: Some(&operation.item.selection_set[..]),
             parent_selection_set: None,
             current_type: root_type,
+            current_field: None,
             schema: &root_node.schema,
             context,
             errors: &errors,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            operation_name: operation.item.name.as_ref().map(|n| n.item),
+            deadline,
+            tracer,
         };
 
-        value = match operation.item.operation_type {
-            OperationType::Query => {
-                executor
-                    .resolve_into_value_async(&root_node.query_info, &root_node)
-                    .await
-            }
-            OperationType::Mutation => {
-                executor
-                    .resolve_into_value_async(&root_node.mutation_info, &root_node.mutation_type)
-                    .await
+        let resolved = async {
+            match operation.item.operation_type {
+                OperationType::Query => {
+                    executor
+                        .resolve_into_value_async(&root_node.query_info, &root_node)
+                        .await
+                }
+                OperationType::Mutation => {
+                    executor
+                        .resolve_into_value_async(
+                            &root_node.mutation_info,
+                            &root_node.mutation_type,
+                        )
+                        .await
+                }
+                OperationType::Subscription => unreachable!(),
             }
-            OperationType::Subscription => unreachable!(),
         };
+        #[cfg(feature = "tracing")]
+        let resolved = tracing::Instrument::instrument(resolved, operation_span(&operation.item));
+
+        value = resolved.await;
     }
 
     let mut errors = errors.into_inner().unwrap();
@@ -1103,20 +1435,33 @@ where
             current_selection_set: Some(&operation.item.selection_set[..]),
             parent_selection_set: None,
             current_type: root_type,
+            current_field: None,
             schema: &root_node.schema,
             context,
             errors: &errors,
             field_path: Arc::new(FieldPath::Root(operation.start)),
+            operation_name: operation.item.name.as_ref().map(|n| n.item),
+            deadline: None,
+            tracer: None,
         };
 
-        value = match operation.item.operation_type {
-            OperationType::Subscription => {
-                executor
-                    .resolve_into_stream(&root_node.subscription_info, &root_node.subscription_type)
-                    .await
+        let resolved = async {
+            match operation.item.operation_type {
+                OperationType::Subscription => {
+                    executor
+                        .resolve_into_stream(
+                            &root_node.subscription_info,
+                            &root_node.subscription_type,
+                        )
+                        .await
+                }
+                _ => unreachable!(),
             }
-            _ => unreachable!(),
         };
+        #[cfg(feature = "tracing")]
+        let resolved = tracing::Instrument::instrument(resolved, operation_span(&operation.item));
+
+        value = resolved.await;
     }
 
     let mut errors = errors.into_inner().unwrap();
@@ -1169,6 +1514,9 @@ where
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            timeout: None,
+            skip_tracing: false,
+            directives: vec![],
         }
     }
 
@@ -1187,6 +1535,9 @@ where
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            timeout: None,
+            skip_tracing: false,
+            directives: vec![],
         }
     }
 
@@ -1273,7 +1624,7 @@ where
     pub fn build_enum_type<T>(
         &mut self,
         info: &T::TypeInfo,
-        values: &[EnumValue],
+        values: &[EnumValue<S>],
     ) -> EnumMeta<'r, S>
     where
         T: FromInputValue<S> + GraphQLType<S> + ?Sized,
@@ -1301,7 +1652,7 @@ where
     }
 
     /// Create a union meta type
-    pub fn build_union_type<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta<'r>
+    pub fn build_union_type<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta<'r, S>
     where
         T: GraphQLType<S> + ?Sized,
     {
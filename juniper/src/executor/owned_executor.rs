@@ -1,13 +1,17 @@
 use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
+    time::Instant,
 };
 
 use crate::{
     ast::Fragment,
     executor::FieldPath,
     parser::SourcePosition,
-    schema::model::{SchemaType, TypeType},
+    schema::{
+        meta::Field,
+        model::{SchemaType, TypeType},
+    },
     ExecutionError, Executor, Selection, Variables,
 };
 
@@ -19,10 +23,13 @@ pub struct OwnedExecutor<'a, CtxT, S> {
     pub(super) current_selection_set: Option<Vec<Selection<'a, S>>>,
     pub(super) parent_selection_set: Option<Vec<Selection<'a, S>>>,
     pub(super) current_type: TypeType<'a, S>,
+    pub(super) current_field: Option<&'a Field<'a, S>>,
     pub(super) schema: &'a SchemaType<'a, S>,
     pub(super) context: &'a CtxT,
     pub(super) errors: RwLock<Vec<ExecutionError<S>>>,
     pub(super) field_path: Arc<FieldPath<'a>>,
+    pub(super) operation_name: Option<String>,
+    pub(super) deadline: Option<Instant>,
 }
 
 impl<'a, CtxT, S> Clone for OwnedExecutor<'a, CtxT, S>
@@ -36,10 +43,13 @@ where
             current_selection_set: self.current_selection_set.clone(),
             parent_selection_set: self.parent_selection_set.clone(),
             current_type: self.current_type.clone(),
+            current_field: self.current_field,
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
             field_path: self.field_path.clone(),
+            operation_name: self.operation_name.clone(),
+            deadline: self.deadline,
         }
     }
 }
@@ -63,10 +73,13 @@ where
                 Some(type_name) => self.schema.type_by_name(type_name).expect("Type not found"),
                 None => self.current_type.clone(),
             },
+            current_field: self.current_field,
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
             field_path: self.field_path.clone(),
+            operation_name: self.operation_name.clone(),
+            deadline: self.deadline,
         }
     }
 
@@ -83,19 +96,19 @@ where
         location: SourcePosition,
         selection_set: Option<Vec<Selection<'a, S>>>,
     ) -> OwnedExecutor<'a, CtxT, S> {
+        let field = self
+            .current_type
+            .innermost_concrete()
+            .field_by_name(field_name)
+            .expect("Field not found on inner type");
+
         OwnedExecutor {
             fragments: self.fragments.clone(),
             variables: self.variables.clone(),
             current_selection_set: selection_set,
             parent_selection_set: self.current_selection_set.clone(),
-            current_type: self.schema.make_type(
-                &self
-                    .current_type
-                    .innermost_concrete()
-                    .field_by_name(field_name)
-                    .expect("Field not found on inner type")
-                    .field_type,
-            ),
+            current_type: self.schema.make_type(&field.field_type),
+            current_field: Some(field),
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
@@ -104,6 +117,8 @@ where
                 location,
                 Arc::clone(&self.field_path),
             )),
+            operation_name: self.operation_name.clone(),
+            deadline: self.deadline,
         }
     }
 
@@ -115,10 +130,14 @@ where
             current_selection_set: self.current_selection_set.as_deref(),
             parent_selection_set: self.parent_selection_set.as_deref(),
             current_type: self.current_type.clone(),
+            current_field: self.current_field,
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
             field_path: Arc::clone(&self.field_path),
+            operation_name: self.operation_name.as_deref(),
+            deadline: self.deadline,
+            tracer: None,
         }
     }
 }
@@ -0,0 +1,556 @@
+//! Per-field timings of an execution, reported as [Apollo Tracing][1] or as a federated trace
+//! ([FTV1][2]) in the response extensions.
+//!
+//! [1]: https://github.com/apollographql/apollo-tracing
+//! [2]: https://www.apollographql.com/docs/federation/metrics
+
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    parser::SourcePosition,
+    value::{Object, ScalarValue, Value},
+};
+
+/// Format of an [`ExecutionTrace`] reported in the response extensions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraceFormat {
+    /// Legacy [Apollo Tracing][1] format, reported as the `tracing` extension.
+    ///
+    /// [1]: https://github.com/apollographql/apollo-tracing
+    ApolloTracing,
+
+    /// Base64-encoded `Trace` protobuf message, reported as the `ftv1` extension.
+    ///
+    /// This is the format federated gateways expect from subgraphs.
+    Ftv1,
+}
+
+impl TraceFormat {
+    /// Name of the response extension an [`ExecutionTrace`] is reported in.
+    pub fn extension_name(&self) -> &'static str {
+        match self {
+            Self::ApolloTracing => "tracing",
+            Self::Ftv1 => "ftv1",
+        }
+    }
+}
+
+/// Timing of an execution phase, like parsing or validation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaseTrace {
+    /// Time since the start of the execution when the phase started.
+    pub start_offset: Duration,
+
+    /// Time the phase took.
+    pub duration: Duration,
+}
+
+/// Segment of the path of a resolved field in the response.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PathSegment {
+    /// Response name of a field.
+    Field(String),
+
+    /// Index of an item of a list.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Field(name) => f.write_str(name),
+            Self::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+impl PathSegment {
+    fn to_value<S: ScalarValue>(&self) -> Value<S> {
+        match self {
+            Self::Field(name) => Value::scalar(name.clone()),
+            Self::Index(index) => Value::scalar(*index as i32),
+        }
+    }
+}
+
+/// Timing of a single field resolution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolverTrace {
+    /// Response names of the fields and indices of the list items leading to the resolved field,
+    /// including itself.
+    pub path: Vec<PathSegment>,
+
+    /// Name of the type the field is defined on.
+    pub parent_type: String,
+
+    /// Name of the field in the schema.
+    pub field_name: String,
+
+    /// Type the field returns, like `[Droid!]!`.
+    pub return_type: String,
+
+    /// Time since the start of the execution when the field started resolving.
+    pub start_offset: Duration,
+
+    /// Time the field took to resolve, including its sub-selection.
+    pub duration: Duration,
+}
+
+/// Error of a field resolution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorTrace {
+    /// Path of the field the error occurred at, as in [`ResolverTrace::path`].
+    pub path: Vec<PathSegment>,
+
+    /// Message of the error.
+    pub message: String,
+
+    /// Position of the field in the document.
+    pub location: SourcePosition,
+}
+
+/// Collector of the timings of a single execution.
+///
+/// Passed to [`execute_validated_query_async_traced`], which records each resolved field into it.
+///
+/// [`execute_validated_query_async_traced`]: crate::executor::execute_validated_query_async_traced
+#[derive(Debug)]
+pub struct ExecutionTracer {
+    start: Instant,
+    start_time: SystemTime,
+    parsing: Mutex<Option<PhaseTrace>>,
+    validation: Mutex<Option<PhaseTrace>>,
+    resolvers: Mutex<Vec<ResolverTrace>>,
+    errors: Mutex<Vec<ErrorTrace>>,
+}
+
+impl Default for ExecutionTracer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecutionTracer {
+    /// Starts tracing a new execution.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            start_time: SystemTime::now(),
+            parsing: Mutex::new(None),
+            validation: Mutex::new(None),
+            resolvers: Mutex::new(Vec::new()),
+            errors: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records the parsing phase, taking place between `start` and `end`.
+    pub fn record_parsing(&self, start: Instant, end: Instant) {
+        *self.parsing.lock().unwrap() = Some(self.phase(start, end));
+    }
+
+    /// Records the validation phase, taking place between `start` and `end`.
+    pub fn record_validation(&self, start: Instant, end: Instant) {
+        *self.validation.lock().unwrap() = Some(self.phase(start, end));
+    }
+
+    pub(crate) fn record_resolver(&self, resolver: ResolverTrace) {
+        self.resolvers.lock().unwrap().push(resolver);
+    }
+
+    pub(crate) fn record_error(&self, error: ErrorTrace) {
+        self.errors.lock().unwrap().push(error);
+    }
+
+    pub(crate) fn offset(&self, at: Instant) -> Duration {
+        at.saturating_duration_since(self.start)
+    }
+
+    fn phase(&self, start: Instant, end: Instant) -> PhaseTrace {
+        PhaseTrace {
+            start_offset: self.offset(start),
+            duration: end.saturating_duration_since(start),
+        }
+    }
+
+    /// Stops tracing, returning the collected [`ExecutionTrace`].
+    pub fn finish(self) -> ExecutionTrace {
+        let duration = self.start.elapsed();
+        let mut resolvers = self.resolvers.into_inner().unwrap();
+        resolvers.sort_by_key(|r| r.start_offset);
+
+        ExecutionTrace {
+            start_time: self.start_time,
+            end_time: self.start_time + duration,
+            duration,
+            parsing: self.parsing.into_inner().unwrap(),
+            validation: self.validation.into_inner().unwrap(),
+            resolvers,
+            errors: self.errors.into_inner().unwrap(),
+        }
+    }
+}
+
+/// Timings collected by an [`ExecutionTracer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionTrace {
+    /// Wall-clock time the execution started at.
+    pub start_time: SystemTime,
+
+    /// Wall-clock time the execution finished at.
+    pub end_time: SystemTime,
+
+    /// Time the whole execution took.
+    pub duration: Duration,
+
+    /// Timing of the parsing phase, if it was recorded.
+    pub parsing: Option<PhaseTrace>,
+
+    /// Timing of the validation phase, if it was recorded.
+    pub validation: Option<PhaseTrace>,
+
+    /// Timings of all the resolved fields, ordered by their start.
+    pub resolvers: Vec<ResolverTrace>,
+
+    /// Errors of the resolved fields.
+    pub errors: Vec<ErrorTrace>,
+}
+
+impl ExecutionTrace {
+    /// Renders this [`ExecutionTrace`] as the value of the response extension of the provided
+    /// `format`.
+    ///
+    /// The [`ExecutionTrace::errors`] are only included in the [`TraceFormat::Ftv1`] format.
+    pub fn to_extension<S>(&self, format: TraceFormat) -> Value<S>
+    where
+        S: ScalarValue,
+    {
+        match format {
+            TraceFormat::ApolloTracing => self.to_apollo_tracing(),
+            TraceFormat::Ftv1 => Value::scalar(self.to_ftv1()),
+        }
+    }
+
+    /// Renders this [`ExecutionTrace`] in the [Apollo Tracing][1] format.
+    ///
+    /// [1]: https://github.com/apollographql/apollo-tracing
+    pub fn to_apollo_tracing<S>(&self) -> Value<S>
+    where
+        S: ScalarValue,
+    {
+        fn phase<S: ScalarValue>(phase: Option<PhaseTrace>) -> Value<S> {
+            phase
+                .map(|p| {
+                    let mut obj = Object::with_capacity(2);
+                    obj.add_field("startOffset", nanos(p.start_offset));
+                    obj.add_field("duration", nanos(p.duration));
+                    Value::object(obj)
+                })
+                .unwrap_or_else(Value::null)
+        }
+
+        let resolvers = self
+            .resolvers
+            .iter()
+            .map(|r| {
+                let mut obj = Object::with_capacity(6);
+                obj.add_field(
+                    "path",
+                    Value::list(r.path.iter().map(PathSegment::to_value).collect()),
+                );
+                obj.add_field("parentType", Value::scalar(r.parent_type.clone()));
+                obj.add_field("fieldName", Value::scalar(r.field_name.clone()));
+                obj.add_field("returnType", Value::scalar(r.return_type.clone()));
+                obj.add_field("startOffset", nanos(r.start_offset));
+                obj.add_field("duration", nanos(r.duration));
+                Value::object(obj)
+            })
+            .collect();
+
+        let mut execution = Object::with_capacity(1);
+        execution.add_field("resolvers", Value::list(resolvers));
+
+        let mut obj = Object::with_capacity(7);
+        obj.add_field("version", Value::scalar(1));
+        obj.add_field("startTime", Value::scalar(rfc3339(self.start_time)));
+        obj.add_field("endTime", Value::scalar(rfc3339(self.end_time)));
+        obj.add_field("duration", nanos(self.duration));
+        obj.add_field("parsing", phase(self.parsing));
+        obj.add_field("validation", phase(self.validation));
+        obj.add_field("execution", Value::object(execution));
+        Value::object(obj)
+    }
+
+    /// Renders this [`ExecutionTrace`] as a base64-encoded `Trace` protobuf message, as expected
+    /// by federated gateways in the `ftv1` response extension.
+    ///
+    /// The [`ExecutionTrace::errors`] are attached to the nodes of the fields they occurred at,
+    /// and the items of lists are represented by index nodes.
+    pub fn to_ftv1(&self) -> String {
+        let mut root = TraceNode::default();
+        let mut resolvers = self.resolvers.iter().collect::<Vec<_>>();
+        resolvers.sort_by_key(|r| r.path.len());
+        for r in resolvers {
+            if let Some((segment, parents)) = r.path.split_last() {
+                let response_name = segment.to_string();
+                root.find_mut(parents).children.push(TraceNode {
+                    original_field_name: if r.field_name != response_name {
+                        r.field_name.clone()
+                    } else {
+                        String::new()
+                    },
+                    segment: Some(segment.clone()),
+                    return_type: r.return_type.clone(),
+                    parent_type: r.parent_type.clone(),
+                    start: r.start_offset,
+                    end: r.start_offset + r.duration,
+                    ..TraceNode::default()
+                });
+            }
+        }
+        for e in &self.errors {
+            root.find_mut(&e.path).errors.push((
+                e.message.clone(),
+                e.location.line() as u64 + 1,
+                e.location.column() as u64 + 1,
+            ));
+        }
+
+        let mut trace = ProtoWriter::default();
+        trace.message(4, |w| timestamp(w, self.start_time));
+        trace.message(3, |w| timestamp(w, self.end_time));
+        trace.uint64(11, self.duration.as_nanos() as u64);
+        trace.message(14, |w| root.encode(w));
+        base64(&trace.0)
+    }
+}
+
+/// Node of the field tree of a `Trace` protobuf message.
+///
+/// The root node has no `segment`, and index nodes only have children.
+#[derive(Default)]
+struct TraceNode {
+    segment: Option<PathSegment>,
+    original_field_name: String,
+    return_type: String,
+    parent_type: String,
+    start: Duration,
+    end: Duration,
+    errors: Vec<(String, u64, u64)>,
+    children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    /// Finds the node at the provided `path`, creating the missing index nodes, and falling back
+    /// to the deepest existing node when a field node is missing.
+    fn find_mut(&mut self, path: &[PathSegment]) -> &mut Self {
+        let (segment, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return self,
+        };
+        match self
+            .children
+            .iter()
+            .rposition(|c| c.segment.as_ref() == Some(segment))
+        {
+            Some(idx) => self.children[idx].find_mut(rest),
+            None if matches!(segment, PathSegment::Index(_)) => {
+                self.children.push(Self {
+                    segment: Some(segment.clone()),
+                    ..Self::default()
+                });
+                self.children.last_mut().unwrap().find_mut(rest)
+            }
+            None => self,
+        }
+    }
+
+    fn encode(&self, w: &mut ProtoWriter) {
+        match &self.segment {
+            Some(PathSegment::Field(name)) => w.string(1, name),
+            // Part of a `oneof`, so written even when zero.
+            Some(PathSegment::Index(index)) => {
+                w.key(2, 0);
+                w.varint(*index as u64);
+            }
+            None => {}
+        }
+        w.string(3, &self.return_type);
+        w.uint64(8, self.start.as_nanos() as u64);
+        w.uint64(9, self.end.as_nanos() as u64);
+        for (message, line, column) in &self.errors {
+            w.message(11, |w| {
+                w.string(1, message);
+                w.message(2, |w| {
+                    w.uint64(1, *line);
+                    w.uint64(2, *column);
+                });
+            });
+        }
+        for child in &self.children {
+            w.message(12, |w| child.encode(w));
+        }
+        w.string(13, &self.parent_type);
+        w.string(14, &self.original_field_name);
+    }
+}
+
+/// Minimal protobuf encoder, writing only the wire types used by a `Trace` message.
+#[derive(Default)]
+struct ProtoWriter(Vec<u8>);
+
+impl ProtoWriter {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        self.varint(u64::from(field) << 3 | u64::from(wire_type));
+    }
+
+    fn uint64(&mut self, field: u32, value: u64) {
+        if value != 0 {
+            self.key(field, 0);
+            self.varint(value);
+        }
+    }
+
+    fn string(&mut self, field: u32, value: &str) {
+        if !value.is_empty() {
+            self.key(field, 2);
+            self.varint(value.len() as u64);
+            self.0.extend_from_slice(value.as_bytes());
+        }
+    }
+
+    fn message(&mut self, field: u32, encode: impl FnOnce(&mut Self)) {
+        let mut inner = Self::default();
+        encode(&mut inner);
+        self.key(field, 2);
+        self.varint(inner.0.len() as u64);
+        self.0.extend(inner.0);
+    }
+}
+
+/// Encodes the provided `time` as a `google.protobuf.Timestamp` message.
+fn timestamp(w: &mut ProtoWriter, time: SystemTime) {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    w.uint64(1, since_epoch.as_secs());
+    w.uint64(2, u64::from(since_epoch.subsec_nanos()));
+}
+
+/// Represents the provided `duration` as an amount of nanoseconds.
+///
+/// Falls back to a float for durations not fitting into an `Int`.
+fn nanos<S: ScalarValue>(duration: Duration) -> Value<S> {
+    let nanos = duration.as_nanos();
+    if nanos <= i32::MAX as u128 {
+        Value::scalar(nanos as i32)
+    } else {
+        Value::scalar(nanos as f64)
+    }
+}
+
+/// Formats the provided `time` as an RFC 3339 timestamp in UTC with millisecond precision.
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // Civil date from days since epoch: http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis(),
+    )
+}
+
+/// Encodes the provided `bytes` as standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{base64, rfc3339, ProtoWriter};
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn formats_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_millis(1_633_046_400_123)),
+            "2021-10-01T00:00:00.123Z",
+        );
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_secs(951_825_599)),
+            "2000-02-29T11:59:59.000Z",
+        );
+    }
+
+    #[test]
+    fn encodes_protobuf_wire_format() {
+        let mut w = ProtoWriter::default();
+        w.uint64(1, 150);
+        w.string(2, "testing");
+        w.uint64(3, 0);
+        w.message(4, |w| w.uint64(1, 1));
+        assert_eq!(
+            w.0,
+            vec![
+                0x08, 0x96, 0x01, 0x12, 0x07, b't', b'e', b's', b't', b'i', b'n', b'g', 0x22, 0x02,
+                0x08, 0x01,
+            ],
+        );
+    }
+}
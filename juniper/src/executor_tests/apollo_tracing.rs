@@ -0,0 +1,276 @@
+use crate::{
+    graphql_object,
+    http::{GraphQLRequest, GraphQLResponse},
+    EmptyMutation, EmptySubscription, FieldResult, RootNode, TraceFormat, Value,
+};
+
+struct User;
+
+#[graphql_object]
+impl User {
+    fn name() -> &'static str {
+        "Alice"
+    }
+
+    fn email() -> FieldResult<Option<String>> {
+        Err("Private".into())
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    async fn user() -> User {
+        User
+    }
+
+    fn users() -> Vec<User> {
+        vec![User, User]
+    }
+
+    fn broken() -> FieldResult<Option<i32>> {
+        Err("Broken".into())
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[tokio::test]
+async fn records_phases_and_resolvers() {
+    let schema = schema();
+    let doc = "{ user { name } users { name } }";
+
+    let vars = Default::default();
+    let (res, trace) = crate::execute_traced(doc, None, &schema, &vars, &(), None).await;
+
+    assert_eq!(
+        res.unwrap(),
+        (
+            graphql_value!({
+                "user": { "name": "Alice" },
+                "users": [{ "name": "Alice" }, { "name": "Alice" }],
+            }),
+            vec![],
+        ),
+    );
+    assert!(trace.parsing.is_some());
+    assert!(trace.validation.is_some());
+    assert!(trace.end_time >= trace.start_time);
+
+    let resolvers = trace
+        .resolvers
+        .iter()
+        .map(|r| {
+            (
+                r.path
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("."),
+                r.parent_type.as_str(),
+                r.field_name.as_str(),
+                r.return_type.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        resolvers,
+        vec![
+            ("user".into(), "Query", "user", "User!"),
+            ("user.name".into(), "User", "name", "String!"),
+            ("users".into(), "Query", "users", "[User!]!"),
+            ("users.0.name".into(), "User", "name", "String!"),
+            ("users.1.name".into(), "User", "name", "String!"),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn renders_apollo_tracing_extension() {
+    let schema = schema();
+    let req = GraphQLRequest::new("{ alias: user { name } users { name } }".into(), None, None);
+
+    let resp = req
+        .execute_traced(&schema, &(), TraceFormat::ApolloTracing)
+        .await;
+    assert!(resp.is_ok());
+
+    let tracing = resp
+        .extensions()
+        .unwrap()
+        .get_field_value("tracing")
+        .unwrap();
+    let tracing = tracing.as_object_value().unwrap();
+    assert_eq!(tracing.get_field_value("version"), Some(&graphql_value!(1)));
+    assert!(tracing
+        .get_field_value("parsing")
+        .unwrap()
+        .as_object_value()
+        .is_some());
+
+    let resolvers = tracing
+        .get_field_value("execution")
+        .and_then(Value::as_object_value)
+        .and_then(|e| e.get_field_value("resolvers"))
+        .and_then(Value::as_list_value)
+        .unwrap();
+    let first = resolvers[0].as_object_value().unwrap();
+    assert_eq!(
+        first.get_field_value("path"),
+        Some(&graphql_value!(["alias"])),
+    );
+    assert_eq!(
+        first.get_field_value("fieldName"),
+        Some(&graphql_value!("user")),
+    );
+    assert_eq!(
+        first.get_field_value("parentType"),
+        Some(&graphql_value!("Query")),
+    );
+    let paths = resolvers
+        .iter()
+        .filter_map(|r| r.as_object_value()?.get_field_value("path"))
+        .collect::<Vec<_>>();
+    assert!(paths.contains(&&graphql_value!(["users", 1, "name"])));
+
+    let json = serde_json::to_value(&resp).unwrap();
+    assert!(json["extensions"]["tracing"]["startTime"].is_string());
+}
+
+#[tokio::test]
+async fn renders_ftv1_extension() {
+    let schema = schema();
+    let req = GraphQLRequest::new("{ broken users { name mail: email } }".into(), None, None);
+
+    let resp: GraphQLResponse = req.execute_traced(&schema, &(), TraceFormat::Ftv1).await;
+
+    let ftv1 = resp.extensions().unwrap().get_field_value("ftv1").unwrap();
+    let ftv1 = ftv1.as_string_value().unwrap();
+    let bytes = proto::base64(ftv1);
+    let trace = proto::decode(&bytes);
+    let root = proto::decode(proto::bytes(&trace, 14).unwrap());
+    assert!(proto::varint(&trace, 11).is_some());
+    assert_eq!(
+        proto::render(&root),
+        "[broken:Int<Broken>, \
+          users:[User!]![\
+            0[name:String!, mail(email):String<Private>], \
+            1[name:String!, mail(email):String<Private>]\
+          ]]",
+    );
+
+    let json = serde_json::to_value(&resp).unwrap();
+    assert_eq!(json["errors"][0]["message"], "Broken");
+    assert_eq!(json["extensions"]["ftv1"], ftv1);
+}
+
+/// Minimal decoding of the `Trace` protobuf messages of the `ftv1` extension.
+mod proto {
+    /// Field of a protobuf message, as its number and its varint or length-delimited value.
+    pub(super) type Field<'a> = (u64, Result<u64, &'a [u8]>);
+
+    pub(super) fn base64(encoded: &str) -> Vec<u8> {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let sextets = encoded
+            .bytes()
+            .take_while(|b| *b != b'=')
+            .map(|b| ALPHABET.iter().position(|a| *a == b).unwrap() as u32)
+            .collect::<Vec<_>>();
+        sextets
+            .chunks(4)
+            .flat_map(|chunk| {
+                let n = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |n, (i, s)| n | s << (18 - 6 * i));
+                (0..chunk.len() - 1).map(move |i| (n >> (16 - 8 * i)) as u8)
+            })
+            .collect()
+    }
+
+    fn read_varint(bytes: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        for shift in (0..).step_by(7) {
+            let b = bytes[0];
+            *bytes = &bytes[1..];
+            value |= u64::from(b & 0x7f) << shift;
+            if b < 0x80 {
+                break;
+            }
+        }
+        value
+    }
+
+    pub(super) fn decode(mut bytes: &[u8]) -> Vec<Field<'_>> {
+        let mut fields = Vec::new();
+        while !bytes.is_empty() {
+            let key = read_varint(&mut bytes);
+            let value = match key & 0x7 {
+                0 => Ok(read_varint(&mut bytes)),
+                2 => {
+                    let len = read_varint(&mut bytes) as usize;
+                    let (value, rest) = bytes.split_at(len);
+                    bytes = rest;
+                    Err(value)
+                }
+                t => panic!("unexpected wire type {}", t),
+            };
+            fields.push((key >> 3, value));
+        }
+        fields
+    }
+
+    pub(super) fn varint(fields: &[Field<'_>], number: u64) -> Option<u64> {
+        fields
+            .iter()
+            .find_map(|(n, v)| v.ok().filter(|_| *n == number))
+    }
+
+    pub(super) fn bytes<'a>(fields: &[Field<'a>], number: u64) -> Option<&'a [u8]> {
+        fields
+            .iter()
+            .find_map(|(n, v)| v.err().filter(|_| *n == number))
+    }
+
+    fn string(fields: &[Field<'_>], number: u64) -> String {
+        bytes(fields, number)
+            .map(|b| String::from_utf8(b.to_vec()).unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Renders the children of a `Trace.Node` as `name(original):Type<error>[children]`, with
+    /// index nodes rendered as their index.
+    pub(super) fn render(node: &[Field<'_>]) -> String {
+        let children = node
+            .iter()
+            .filter(|(n, _)| *n == 12)
+            .map(|(_, child)| {
+                let child = decode(child.unwrap_err());
+                let mut out = match varint(&child, 2) {
+                    Some(index) => index.to_string(),
+                    None => string(&child, 1),
+                };
+                let original = string(&child, 14);
+                if !original.is_empty() {
+                    out.push_str(&format!("({})", original));
+                }
+                let ty = string(&child, 3);
+                if !ty.is_empty() {
+                    out.push_str(&format!(":{}", ty));
+                }
+                for (_, error) in child.iter().filter(|(n, _)| *n == 11) {
+                    out.push_str(&format!("<{}>", string(&decode(error.unwrap_err()), 1)));
+                }
+                if child.iter().any(|(n, _)| *n == 12) {
+                    out.push_str(&render(&child));
+                }
+                out
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", children.join(", "))
+    }
+}
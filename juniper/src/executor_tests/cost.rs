@@ -0,0 +1,282 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{
+    cost::{operation_cost, CostConfig, CostReport, RateLimitClient, RateLimiter},
+    executor::get_operation,
+    graphql_object,
+    http::GraphQLRequest,
+    parser::parse_document_source,
+    DefaultScalarValue, EmptyMutation, EmptySubscription, InputValue, RootNode, Value, Variables,
+};
+
+struct Context {
+    client: &'static str,
+    resolved: AtomicUsize,
+}
+
+impl Context {
+    fn new(client: &'static str) -> Self {
+        Self {
+            client,
+            resolved: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl crate::Context for Context {}
+
+impl RateLimitClient for Context {
+    fn client_id(&self) -> &str {
+        self.client
+    }
+}
+
+struct Comment;
+
+#[graphql_object(context = Context)]
+impl Comment {
+    fn text() -> &'static str {
+        "Nice"
+    }
+}
+
+struct Post;
+
+#[graphql_object(context = Context)]
+impl Post {
+    fn title() -> &'static str {
+        "Hello"
+    }
+
+    fn comments(first: i32) -> Vec<Comment> {
+        (0..first).map(|_| Comment).collect()
+    }
+}
+
+struct User;
+
+#[graphql_object(context = Context)]
+impl User {
+    fn name() -> &'static str {
+        "Alice"
+    }
+
+    #[graphql(arguments(limit(default = 5)))]
+    fn posts(limit: i32) -> Vec<Post> {
+        (0..limit).map(|_| Post).collect()
+    }
+
+    fn friends() -> Vec<User> {
+        vec![]
+    }
+}
+
+struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    fn viewer(context: &Context) -> User {
+        context.resolved.fetch_add(1, Ordering::SeqCst);
+        User
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+fn cost_with(doc: &str, vars: &Variables, config: &CostConfig) -> u64 {
+    let schema = schema();
+    let document = parse_document_source::<DefaultScalarValue>(doc, &schema.schema).unwrap();
+    let operation = get_operation(&document, None).unwrap();
+    operation_cost(&document, operation, &schema.schema, vars, config)
+}
+
+fn cost(doc: &str) -> u64 {
+    cost_with(doc, &Variables::new(), &CostConfig::default())
+}
+
+#[test]
+fn objects_cost_one_and_leaves_nothing() {
+    assert_eq!(cost("{ viewer { name } }"), 1);
+    assert_eq!(cost("{ viewer { name __typename } __typename }"), 1);
+}
+
+#[test]
+fn multiplies_lists_by_size_arguments() {
+    // viewer + 2 * (post + 3 * comment)
+    assert_eq!(
+        cost("{ viewer { posts(limit: 2) { title comments(first: 3) { text } } } }"),
+        1 + 2 * (1 + 3),
+    );
+}
+
+#[test]
+fn sizes_lists_by_variables_and_defaults() {
+    let doc = "query($n: Int!) { viewer { posts { comments(first: $n) { text } } } }";
+    let mut vars = Variables::new();
+    vars.insert("n".into(), InputValue::scalar(4));
+
+    // `limit` defaults to 5 in the schema.
+    assert_eq!(
+        cost_with(doc, &vars, &CostConfig::default()),
+        1 + 5 * (1 + 4),
+    );
+}
+
+#[test]
+fn assumes_default_size_of_unsized_lists() {
+    assert_eq!(cost("{ viewer { friends { name } } }"), 1 + 10);
+
+    let config = CostConfig::new().default_list_size(3);
+    assert_eq!(
+        cost_with(
+            "{ viewer { friends { name } } }",
+            &Variables::new(),
+            &config
+        ),
+        1 + 3,
+    );
+}
+
+#[test]
+fn follows_fragments_and_skips_excluded_fields() {
+    let doc = r#"
+        query {
+            viewer {
+                ...Posts
+                ... on User { friends @skip(if: true) { name } }
+            }
+        }
+
+        fragment Posts on User {
+            posts(limit: 1) { title }
+        }
+    "#;
+
+    assert_eq!(cost(doc), 1 + 1);
+}
+
+#[test]
+fn applies_configured_costs() {
+    let config = CostConfig::new()
+        .scalar_cost(1)
+        .field_cost("Post", "comments", 10)
+        .list_size_arguments(vec!["limit"]);
+    let doc = "{ viewer { posts(limit: 2) { title comments(first: 1) { text } } } }";
+
+    // `first` isn't a size argument anymore, so `comments` is assumed to hold 10 comments.
+    assert_eq!(
+        cost_with(doc, &Variables::new(), &config),
+        1 + 2 * (1 + 1 + 10 * (10 + 1)),
+    );
+}
+
+#[tokio::test]
+async fn executes_operations_within_limit() {
+    let schema = schema();
+    let limiter = RateLimiter::new(10, 0.001);
+    let context = Context::new("alice");
+    let doc = "{ viewer { posts(limit: 2) { title } } }";
+
+    let vars = Variables::new();
+    let (res, report) =
+        crate::execute_rate_limited(doc, None, &schema, &vars, &context, &limiter).await;
+
+    assert_eq!(
+        res,
+        Ok((
+            graphql_value!({"viewer": {"posts": [{"title": "Hello"}, {"title": "Hello"}]}}),
+            vec![],
+        )),
+    );
+    assert_eq!(
+        report,
+        Some(CostReport {
+            cost: 3,
+            remaining: 7,
+            capacity: 10,
+        }),
+    );
+}
+
+#[tokio::test]
+async fn rejects_operations_over_limit_without_executing_them() {
+    let schema = schema();
+    let limiter = RateLimiter::new(4, 0.001);
+    let context = Context::new("alice");
+    let doc = "{ viewer { posts(limit: 2) { title } } }";
+
+    let vars = Variables::new();
+    let (res, _) = crate::execute_rate_limited(doc, None, &schema, &vars, &context, &limiter).await;
+    assert!(res.unwrap().1.is_empty());
+
+    let (res, report) =
+        crate::execute_rate_limited(doc, None, &schema, &vars, &context, &limiter).await;
+    let (value, errors) = res.unwrap();
+
+    assert_eq!(value, Value::null());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].error().message(),
+        "Operation cost of 3 exceeds the 1 remaining for this client",
+    );
+    let extensions = errors[0].error().extensions().as_object_value().unwrap();
+    assert_eq!(
+        extensions.get_field_value("code"),
+        Some(&graphql_value!("RATE_LIMITED")),
+    );
+    assert_eq!(extensions.get_field_value("cost"), Some(&graphql_value!(3)));
+    assert_eq!(
+        extensions.get_field_value("remaining"),
+        Some(&graphql_value!(1)),
+    );
+    assert!(extensions.get_field_value("retryAfterMs").is_some());
+    assert_eq!(
+        report,
+        Some(CostReport {
+            cost: 3,
+            remaining: 1,
+            capacity: 4,
+        }),
+    );
+    assert_eq!(context.resolved.load(Ordering::SeqCst), 1);
+
+    // Other clients have their own budget.
+    let other = Context::new("bob");
+    let (res, _) = crate::execute_rate_limited(doc, None, &schema, &vars, &other, &limiter).await;
+    assert!(res.unwrap().1.is_empty());
+}
+
+#[tokio::test]
+async fn does_not_charge_invalid_operations() {
+    let schema = schema();
+    let limiter = RateLimiter::new(1, 0.001);
+    let context = Context::new("alice");
+
+    let vars = Variables::new();
+    let (res, report) =
+        crate::execute_rate_limited("{ unknown }", None, &schema, &vars, &context, &limiter).await;
+
+    assert!(res.is_err());
+    assert_eq!(report, None);
+    assert_eq!(limiter.try_acquire("alice", 1), Ok(0));
+}
+
+#[tokio::test]
+async fn reports_cost_in_extensions() {
+    let schema = schema();
+    let limiter = RateLimiter::new(100, 1.0);
+    let context = Context::new("alice");
+
+    let req = GraphQLRequest::new("{ viewer { friends { name } } }".into(), None, None);
+    let resp = req.execute_rate_limited(&schema, &context, &limiter).await;
+
+    assert!(resp.is_ok());
+    assert_eq!(
+        resp.extensions()
+            .unwrap()
+            .get_field_value(CostReport::EXTENSION_NAME),
+        Some(&graphql_value!({"cost": 11, "remaining": 89, "capacity": 100})),
+    );
+}
@@ -0,0 +1,265 @@
+use crate::{
+    federation::{Any, Entity, FederatedQuery},
+    graphql_object, EmptyMutation, EmptySubscription, FieldResult, GraphQLObject, InputValue,
+    RootNode, Value, Variables,
+};
+
+struct Context {
+    users: Vec<(i32, &'static str)>,
+}
+
+impl crate::Context for Context {}
+
+struct User {
+    id: i32,
+    name: &'static str,
+}
+
+#[graphql_object(context = Context, key = "id")]
+impl User {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[graphql(shareable)]
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    #[graphql(requires = "name")]
+    fn greeting(&self) -> String {
+        format!("Hello, {}!", self.name)
+    }
+}
+
+#[crate::async_trait]
+impl Entity for User {
+    async fn resolve_reference(
+        representation: &Any,
+        context: &Context,
+    ) -> FieldResult<Option<Self>> {
+        let id = representation.parse::<i32>("id").ok_or("Missing `id`")?;
+        Ok(context
+            .users
+            .iter()
+            .find(|(user_id, _)| *user_id == id)
+            .map(|&(id, name)| User { id, name }))
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context, key = "upc", key = "sku")]
+struct Product {
+    upc: String,
+    sku: String,
+    #[graphql(external)]
+    weight: i32,
+}
+
+#[crate::async_trait]
+impl Entity for Product {
+    async fn resolve_reference(representation: &Any, _: &Context) -> FieldResult<Option<Self>> {
+        Ok(Some(Product {
+            upc: representation.parse("upc").unwrap_or_default(),
+            sku: "sku".into(),
+            weight: 1,
+        }))
+    }
+}
+
+struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    fn me() -> User {
+        User {
+            id: 1,
+            name: "Alice",
+        }
+    }
+}
+
+type Schema<'a> = RootNode<
+    'a,
+    FederatedQuery<Query, (User, Product)>,
+    EmptyMutation<Context>,
+    EmptySubscription<Context>,
+>;
+
+fn schema<'a>() -> Schema<'a> {
+    RootNode::new_federated(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+fn context() -> Context {
+    Context {
+        users: vec![(1, "Alice"), (2, "Bob")],
+    }
+}
+
+#[tokio::test]
+async fn resolves_service_sdl() {
+    let schema = schema();
+    let doc = "{ _service { sdl } }";
+
+    let vars = Variables::new();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &context())
+        .await
+        .unwrap();
+    assert!(errs.is_empty(), "{:?}", errs);
+
+    let sdl = res
+        .as_object_value()
+        .and_then(|o| o.get_field_value("_service"))
+        .and_then(Value::as_object_value)
+        .and_then(|o| o.get_field_value("sdl"))
+        .and_then(Value::as_string_value)
+        .unwrap();
+
+    assert!(
+        sdl.starts_with("extend schema @link(url: \"https://specs.apollo.dev/federation/v2.0\"")
+    );
+    assert!(sdl.contains("type User @key(fields: \"id\") {"));
+    assert!(sdl.contains("type Product @key(fields: \"upc\") @key(fields: \"sku\") {"));
+    assert!(sdl.contains("name: String! @shareable"));
+    assert!(sdl.contains("greeting: String! @requires(fields: \"name\")"));
+    assert!(sdl.contains("weight: Int! @external"));
+    assert!(sdl.contains("me: User!"));
+    for federated in &[
+        "_service",
+        "_entities",
+        "_Any",
+        "_Entity",
+        "_FieldSet",
+        "_Service",
+    ] {
+        assert!(
+            !sdl.contains(federated),
+            "SDL contains {}:\n{}",
+            federated,
+            sdl
+        );
+    }
+}
+
+#[tokio::test]
+async fn resolves_entities_from_variables() {
+    let schema = schema();
+    let doc = r#"
+        query($representations: [_Any!]!) {
+            _entities(representations: $representations) {
+                __typename
+                ... on User { name greeting }
+                ... on Product { upc }
+            }
+        }
+    "#;
+
+    let vars = vec![(
+        "representations".to_owned(),
+        InputValue::list(vec![
+            InputValue::object(
+                vec![
+                    ("__typename", InputValue::scalar("User")),
+                    ("id", InputValue::scalar(2)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            InputValue::object(
+                vec![
+                    ("__typename", InputValue::scalar("Product")),
+                    ("upc", InputValue::scalar("123")),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            InputValue::object(
+                vec![
+                    ("__typename", InputValue::scalar("User")),
+                    ("id", InputValue::scalar(3)),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        ]),
+    )]
+    .into_iter()
+    .collect();
+
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &context())
+        .await
+        .unwrap();
+
+    assert!(errs.is_empty(), "{:?}", errs);
+    assert_eq!(
+        res,
+        graphql_value!({
+            "_entities": [
+                {"__typename": "User", "name": "Bob", "greeting": "Hello, Bob!"},
+                {"__typename": "Product", "upc": "123"},
+                None,
+            ],
+        }),
+    );
+}
+
+#[tokio::test]
+async fn resolves_entities_from_literal() {
+    let schema = schema();
+    let doc = r#"{
+        _entities(representations: [{__typename: "User", id: 1}]) {
+            ... on User { id name }
+        }
+    }"#;
+
+    let vars = Variables::new();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &context())
+        .await
+        .unwrap();
+
+    assert!(errs.is_empty(), "{:?}", errs);
+    assert_eq!(
+        res,
+        graphql_value!({ "_entities": [{"id": 1, "name": "Alice"}] }),
+    );
+}
+
+#[tokio::test]
+async fn errors_on_unknown_entity_type() {
+    let schema = schema();
+    let doc = r#"{
+        _entities(representations: [{__typename: "Review", id: 1}]) {
+            ... on User { id }
+        }
+    }"#;
+
+    let vars = Variables::new();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &context())
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!({ "_entities": [None] }));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].error().message(), "Unknown entity type \"Review\"");
+}
+
+#[tokio::test]
+async fn rejects_non_object_representations() {
+    let schema = schema();
+    let doc = r#"{ _entities(representations: ["User"]) { ... on User { id } } }"#;
+
+    let vars = Variables::new();
+    let res = crate::execute(doc, None, &schema, &vars, &context()).await;
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn prints_federation_directives() {
+    let sdl = schema().as_schema_language();
+
+    assert!(sdl.contains("type User @key(fields: \"id\") {"));
+    assert!(sdl.contains("_entities(representations: [_Any!]!): [_Entity]!"));
+    assert!(sdl.contains("_service: _Service!"));
+    assert!(sdl.contains("union _Entity = "));
+}
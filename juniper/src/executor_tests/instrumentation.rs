@@ -0,0 +1,196 @@
+use std::sync::{Arc, Mutex};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use crate::{graphql_object, EmptyMutation, EmptySubscription, FieldResult, RootNode};
+
+#[derive(Debug, Default)]
+struct Recorded {
+    spans: Vec<(&'static str, Vec<(&'static str, String)>)>,
+    stack: Vec<usize>,
+    events: Vec<(Option<&'static str>, String)>,
+}
+
+/// [`Subscriber`] recording all the spans and events into memory.
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Recorded>>);
+
+struct FieldsVisitor<'a>(&'a mut Vec<(&'static str, String)>);
+
+impl<'a> Visit for FieldsVisitor<'a> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = vec![];
+        span.record(&mut FieldsVisitor(&mut fields));
+        let mut rec = self.0.lock().unwrap();
+        rec.spans.push((span.metadata().name(), fields));
+        Id::from_u64(rec.spans.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = vec![];
+        event.record(&mut FieldsVisitor(&mut fields));
+        let mut rec = self.0.lock().unwrap();
+        let span = rec.stack.last().map(|&idx| rec.spans[idx].0);
+        let message = fields
+            .into_iter()
+            .find(|(name, _)| *name == "message")
+            .map(|(_, msg)| msg)
+            .unwrap_or_default();
+        rec.events.push((span, message));
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut rec = self.0.lock().unwrap();
+        rec.stack.push(span.into_u64() as usize - 1);
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+}
+
+impl Recorder {
+    fn spans_named(&self, name: &str) -> Vec<Vec<(&'static str, String)>> {
+        self.0
+            .lock()
+            .unwrap()
+            .spans
+            .iter()
+            .filter(|(n, _)| *n == name)
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+
+    fn events(&self) -> Vec<(Option<&'static str>, String)> {
+        self.0.lock().unwrap().events.clone()
+    }
+}
+
+struct Hero;
+
+#[graphql_object]
+impl Hero {
+    fn name() -> &'static str {
+        "R2-D2"
+    }
+
+    #[graphql(tracing(skip))]
+    fn id() -> &'static str {
+        "2001"
+    }
+
+    async fn friends() -> FieldResult<Option<Vec<Hero>>> {
+        Err("no friends".into())
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn hero() -> Hero {
+        Hero
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn records_phases_and_fields() {
+    let recorder = Recorder::default();
+    let schema = schema();
+    let doc = "query HeroName { hero { name id } }";
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        crate::execute_sync(doc, None, &schema, &Default::default(), &()).unwrap();
+    });
+
+    assert_eq!(recorder.spans_named("parse_document_source").len(), 1);
+    assert_eq!(recorder.spans_named("validate_document").len(), 1);
+    assert_eq!(recorder.spans_named("validate_input_values").len(), 1);
+    assert_eq!(
+        recorder.spans_named("execute_operation"),
+        vec![vec![
+            ("graphql.operation", "HeroName".to_owned()),
+            ("graphql.operation_type", "Query".to_owned()),
+        ]],
+    );
+    assert_eq!(
+        recorder.spans_named("resolve_field"),
+        vec![
+            vec![
+                ("graphql.field", "hero".to_owned()),
+                ("graphql.path", "hero".to_owned()),
+                ("graphql.parent_type", "Query".to_owned()),
+                ("graphql.operation", "HeroName".to_owned()),
+            ],
+            vec![
+                ("graphql.field", "name".to_owned()),
+                ("graphql.path", "hero.name".to_owned()),
+                ("graphql.parent_type", "Hero".to_owned()),
+                ("graphql.operation", "HeroName".to_owned()),
+            ],
+        ],
+    );
+}
+
+#[tokio::test]
+async fn records_errors_as_events() {
+    let recorder = Recorder::default();
+    let schema = schema();
+    let doc = "{ hero { friends { name } } }";
+
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let (_, errs) = crate::execute(doc, None, &schema, &Default::default(), &())
+        .await
+        .unwrap();
+
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        recorder.events(),
+        vec![(Some("resolve_field"), "field resolution failed".to_owned(),)],
+    );
+}
+
+#[test]
+fn records_validation_errors_as_events() {
+    let recorder = Recorder::default();
+    let schema = schema();
+    let doc = "{ villain }";
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        crate::execute_sync(doc, None, &schema, &Default::default(), &()).unwrap_err();
+    });
+
+    assert_eq!(
+        recorder.events(),
+        vec![(
+            Some("validate_document"),
+            "document validation failed".to_owned(),
+        )],
+    );
+}
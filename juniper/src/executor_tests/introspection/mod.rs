@@ -29,8 +29,11 @@ impl<S: ScalarValue> GraphQLScalar for Scalar {
         Value::scalar(self.0)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<Scalar> {
-        v.as_scalar().and_then(ScalarValue::as_int).map(Scalar)
+    fn from_input_value(v: &InputValue) -> Result<Scalar, String> {
+        v.as_scalar()
+            .and_then(ScalarValue::as_int)
+            .map(Scalar)
+            .ok_or_else(|| format!("Expected `Int`, found: {}", v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
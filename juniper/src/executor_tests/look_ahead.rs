@@ -0,0 +1,159 @@
+use std::sync::Mutex;
+
+use crate::{
+    graphql_object, EmptyMutation, EmptySubscription, Executor, GraphQLInputObject, InputValue,
+    LookAheadMethods, RootNode, Variables,
+};
+
+#[derive(Default)]
+struct Context {
+    seen: Mutex<Vec<String>>,
+}
+
+impl crate::Context for Context {}
+
+#[derive(GraphQLInputObject, Debug)]
+struct UserFilter {
+    name: String,
+}
+
+struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    #[graphql(arguments(first(default = 10)))]
+    fn users(
+        executor: &Executor<'_, '_, Context>,
+        first: i32,
+        filter: Option<UserFilter>,
+    ) -> Vec<User> {
+        let look_ahead = executor.look_ahead();
+        let mut seen = executor.context().seen.lock().unwrap();
+
+        seen.push(format!(
+            "first: {:?}",
+            look_ahead.argument_as::<i32>("first").unwrap(),
+        ));
+        seen.push(format!(
+            "filter: {:?}",
+            look_ahead.argument_as::<UserFilter>("filter").unwrap(),
+        ));
+        for child in look_ahead.children() {
+            let (start, _) = child.span();
+            let field = child.field_meta().unwrap();
+            seen.push(format!(
+                "{} ({}) at {}: list {}, deprecated {}, typename {}",
+                child.field_name(),
+                field.field_type,
+                start.column(),
+                field.field_type.is_list(),
+                field.deprecation_status.is_deprecated(),
+                child.is_typename(),
+            ));
+        }
+
+        vec![User]
+    }
+}
+
+struct User;
+
+#[graphql_object(context = Context)]
+impl User {
+    fn name() -> &'static str {
+        "Alice"
+    }
+
+    fn friends() -> Vec<User> {
+        vec![]
+    }
+
+    #[graphql(deprecated = "Use `name`")]
+    fn login() -> &'static str {
+        "alice"
+    }
+}
+
+async fn run_query(query: &str, vars: &Variables) -> Vec<String> {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    );
+    let ctx = Context::default();
+
+    let (_, errs) = crate::execute(query, None, &schema, vars, &ctx)
+        .await
+        .expect("Execution failed");
+    assert_eq!(errs, []);
+
+    ctx.seen.into_inner().unwrap()
+}
+
+#[tokio::test]
+async fn arguments_are_converted_with_schema_defaults() {
+    let seen = run_query("{ users { name } }", &Variables::new()).await;
+    assert_eq!(seen[..2], ["first: Some(10)", "filter: None"]);
+
+    let mut vars = Variables::new();
+    vars.insert("first".to_owned(), InputValue::scalar(3));
+    let seen = run_query(
+        r#"query($first: Int!) { users(first: $first, filter: { name: "Al" }) { name } }"#,
+        &vars,
+    )
+    .await;
+    assert_eq!(
+        seen[..2],
+        [
+            "first: Some(3)",
+            r#"filter: Some(UserFilter { name: "Al" })"#,
+        ],
+    );
+}
+
+#[tokio::test]
+async fn children_carry_field_metadata_and_spans() {
+    let seen = run_query(
+        "{ users { __typename name friends { name } login } }",
+        &Variables::new(),
+    )
+    .await;
+
+    assert_eq!(
+        seen[2..],
+        [
+            "__typename (String!) at 10: list false, deprecated false, typename true",
+            "name (String!) at 21: list false, deprecated false, typename false",
+            "friends ([User!]!) at 26: list true, deprecated false, typename false",
+            "login (String!) at 43: list false, deprecated true, typename false",
+        ],
+    );
+}
+
+#[tokio::test]
+async fn children_match_executed_selections() {
+    let mut vars = Variables::new();
+    vars.insert("withLogin".to_owned(), InputValue::scalar(false));
+    let seen = run_query(
+        r#"
+            query($withLogin: Boolean!) {
+                users {
+                    name @skip(if: false)
+                    login @include(if: $withLogin)
+                    ...userFields
+                    ... on User { n: name }
+                }
+            }
+
+            fragment userFields on User {
+                friends @skip(if: true) { name }
+            }
+        "#,
+        &vars,
+    )
+    .await;
+
+    assert_eq!(seen.len(), 4);
+    assert!(seen[2].starts_with("name (String!)"));
+    assert!(seen[3].starts_with("n (String!)"));
+}
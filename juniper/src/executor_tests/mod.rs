@@ -2,8 +2,16 @@ mod directives;
 mod enums;
 mod executor;
 mod introspection;
+mod look_ahead;
 mod variables;
 
 mod interfaces_unions;
 
 mod async_await;
+mod apollo_tracing;
+mod timeouts;
+mod cost;
+#[cfg(feature = "tracing")]
+mod instrumentation;
+#[cfg(feature = "federation")]
+mod federation;
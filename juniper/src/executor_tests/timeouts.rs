@@ -0,0 +1,207 @@
+use std::time::{Duration, Instant};
+
+use futures::future;
+
+use crate::{
+    executor::ExecutionError, graphql_interface, graphql_object, parser::SourcePosition,
+    EmptyMutation, EmptySubscription, FieldError, RootNode, Value,
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn fast() -> i32 {
+        1
+    }
+
+    #[graphql(timeout = "50ms")]
+    async fn slow() -> Option<i32> {
+        future::pending().await
+    }
+
+    #[graphql(timeout = 5000)]
+    async fn delayed() -> Option<i32> {
+        tokio::task::yield_now().await;
+        Some(3)
+    }
+
+    #[graphql(timeout = 5000)]
+    async fn stalled() -> Option<i32> {
+        future::pending().await
+    }
+
+    fn inner() -> Option<Inner> {
+        Some(Inner)
+    }
+
+    fn character() -> CharacterValue {
+        Droid.into()
+    }
+}
+
+struct Inner;
+
+#[graphql_object]
+impl Inner {
+    fn fast() -> i32 {
+        1
+    }
+
+    async fn stalled() -> Option<i32> {
+        future::pending().await
+    }
+}
+
+#[graphql_interface(for = Droid)]
+trait Character {
+    fn id(&self) -> i32;
+}
+
+struct Droid;
+
+#[graphql_interface]
+impl Character for Droid {
+    fn id(&self) -> i32 {
+        1
+    }
+}
+
+#[graphql_object(impl = CharacterValue)]
+impl Droid {
+    fn id() -> i32 {
+        1
+    }
+
+    #[graphql(timeout = "50ms")]
+    async fn status() -> Option<String> {
+        future::pending().await
+    }
+}
+
+fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+fn timeout_error(
+    timeout: Duration,
+    column: usize,
+    path: &[&str],
+) -> ExecutionError<crate::DefaultScalarValue> {
+    ExecutionError::new(
+        SourcePosition::new(column, 0, column),
+        path,
+        FieldError::new(
+            format!("Field resolution timed out after {:?}", timeout),
+            graphql_value!({ "code": "TIMEOUT" }),
+        ),
+    )
+}
+
+#[tokio::test]
+async fn field_timeout_resolves_to_error() {
+    let schema = schema();
+    let doc = "{ fast slow }";
+
+    let vars = Default::default();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &())
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!({ "fast": 1, "slow": None }));
+    assert_eq!(
+        errs,
+        vec![timeout_error(Duration::from_millis(50), 7, &["slow"])],
+    );
+}
+
+#[tokio::test]
+async fn field_within_timeout_resolves() {
+    let schema = schema();
+    let doc = "{ delayed }";
+
+    let vars = Default::default();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &())
+        .await
+        .unwrap();
+
+    assert!(errs.is_empty());
+    assert_eq!(res, graphql_value!({ "delayed": 3 }));
+}
+
+#[tokio::test]
+async fn request_deadline_cuts_field_timeout() {
+    let schema = schema();
+    let doc = "{ fast stalled }";
+
+    let vars = Default::default();
+    let deadline = Instant::now() + Duration::from_millis(50);
+    let (res, errs) = crate::execute_with_deadline(doc, None, &schema, &vars, &(), Some(deadline))
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!({ "fast": 1, "stalled": None }));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].path(), &["stalled"]);
+    assert_eq!(
+        errs[0].error().extensions(),
+        &graphql_value!({ "code": "TIMEOUT" }),
+    );
+}
+
+#[tokio::test]
+async fn request_deadline_cuts_root_fields() {
+    let schema = schema();
+    let doc = "{ fast inner { fast stalled } }";
+
+    let vars = Default::default();
+    let deadline = Instant::now() + Duration::from_millis(50);
+    let (res, errs) = crate::execute_with_deadline(doc, None, &schema, &vars, &(), Some(deadline))
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!({ "fast": 1, "inner": None }));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].path(), &["inner"]);
+    assert_eq!(
+        errs[0].error().extensions(),
+        &graphql_value!({ "code": "TIMEOUT" }),
+    );
+}
+
+#[tokio::test]
+async fn passed_deadline_skips_resolvers() {
+    let schema = schema();
+    let doc = "{ fast }";
+
+    let vars = Default::default();
+    let (res, errs) =
+        crate::execute_with_deadline(doc, None, &schema, &vars, &(), Some(Instant::now()))
+            .await
+            .unwrap();
+
+    assert_eq!(res, Value::null());
+    assert_eq!(errs, vec![timeout_error(Duration::ZERO, 2, &["fast"])]);
+}
+
+#[tokio::test]
+async fn interface_fields_use_timeout_of_implementer() {
+    let schema = schema();
+    let doc = "{ character { id ... on Droid { status } } }";
+
+    let vars = Default::default();
+    let (res, errs) = crate::execute(doc, None, &schema, &vars, &())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        res,
+        graphql_value!({ "character": { "id": 1, "status": None } }),
+    );
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].path(), &["character", "status"]);
+    assert_eq!(
+        errs[0].error().extensions(),
+        &graphql_value!({ "code": "TIMEOUT" }),
+    );
+}
@@ -20,14 +20,14 @@ impl<S: ScalarValue> GraphQLScalar for TestComplexScalar {
         Value::scalar(String::from("SerializedValue"))
     }
 
-    fn from_input_value(v: &InputValue) -> Option<TestComplexScalar> {
+    fn from_input_value(v: &InputValue) -> Result<TestComplexScalar, String> {
         if let Some(s) = v.as_scalar().and_then(ScalarValue::as_str) {
             if s == "SerializedValue" {
-                return Some(TestComplexScalar);
+                return Ok(TestComplexScalar);
             }
         }
 
-        None
+        Err(format!(r#"Expected "SerializedValue" string, found: {}"#, v))
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -349,6 +349,66 @@ async fn variable_error_on_incorrect_type() {
     );
 }
 
+#[tokio::test]
+async fn variable_error_on_rejected_complex_scalar() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query = r#"query q($input: TestInputObject) { fieldWithObjectInput(input: $input) }"#;
+    let vars = vec![(
+        "input".to_owned(),
+        InputValue::object(
+            vec![
+                ("c", InputValue::scalar("baz")),
+                ("d", InputValue::scalar("DeserializedValue")),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+    )]
+    .into_iter()
+    .collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Variable "$input" got invalid value. In field "d": Expected "TestComplexScalar": Expected "SerializedValue" string, found: "DeserializedValue"."#,
+            &[SourcePosition::new(8, 0, 8)],
+        )])
+    );
+}
+
+#[tokio::test]
+async fn inline_error_on_rejected_complex_scalar() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query = r#"{ fieldWithObjectInput(input: {c: "baz", d: "DeserializedValue"}) }"#;
+    let vars = vec![].into_iter().collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Invalid value for argument "input", expected type "TestInputObject": Expected "SerializedValue" string, found: "DeserializedValue""#,
+            &[SourcePosition::new(30, 0, 30)],
+        )])
+    );
+}
+
 #[tokio::test]
 async fn variable_error_on_omit_non_null() {
     let schema = RootNode::new(
@@ -837,6 +897,35 @@ async fn does_not_allow_lists_of_non_null_to_contain_null() {
     );
 }
 
+#[tokio::test]
+async fn does_not_allow_list_for_scalar_in_variable() {
+    let schema = RootNode::new(
+        TestType,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let query = r#"query q($input: String) { fieldWithNullableStringInput(input: $input) }"#;
+    let vars = vec![(
+        "input".to_owned(),
+        InputValue::list(vec![InputValue::scalar("A")]),
+    )]
+    .into_iter()
+    .collect();
+
+    let error = crate::execute(query, None, &schema, &vars, &())
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error,
+        ValidationError(vec![RuleError::new(
+            r#"Variable "$input" got invalid value. Expected "String", found list."#,
+            &[SourcePosition::new(8, 0, 8)],
+        ),])
+    );
+}
+
 #[tokio::test]
 async fn does_not_allow_non_null_lists_of_non_null_to_contain_null() {
     let schema = RootNode::new(
@@ -1223,7 +1312,7 @@ mod integers {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Int"."#,
+                r#"Variable "$var" got invalid value. Expected "Int": Expected `Int`, found: 10."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
@@ -1249,7 +1338,7 @@ mod integers {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Int"."#,
+                r#"Variable "$var" got invalid value. Expected "Int": Expected `Int`, found: "10"."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
@@ -1313,7 +1402,7 @@ mod floats {
         assert_eq!(
             error,
             ValidationError(vec![RuleError::new(
-                r#"Variable "$var" got invalid value. Expected "Float"."#,
+                r#"Variable "$var" got invalid value. Expected "Float": Expected `Float`, found: "10"."#,
                 &[SourcePosition::new(8, 0, 8)],
             )])
         );
@@ -0,0 +1,634 @@
+//! Support for running a schema as an [Apollo Federation][1] v2 subgraph.
+//!
+//! A subgraph extends its query type with the `_service` and `_entities` fields, which a
+//! federation gateway uses to compose the supergraph and to resolve entities across subgraphs.
+//! Wrap the query type into a [`FederatedQuery`] to expose them:
+//!
+//! ```rust
+//! use juniper::{
+//!     federation::{Any, Entity, FederatedQuery},
+//!     graphql_object, EmptyMutation, EmptySubscription, FieldResult, RootNode,
+//! };
+//!
+//! struct User {
+//!     id: String,
+//! }
+//!
+//! #[graphql_object(key = "id")]
+//! impl User {
+//!     fn id(&self) -> &str {
+//!         &self.id
+//!     }
+//! }
+//!
+//! #[juniper::async_trait]
+//! impl Entity for User {
+//!     async fn resolve_reference(representation: &Any, _: &()) -> FieldResult<Option<Self>> {
+//!         Ok(representation.parse("id").map(|id| User { id }))
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn me() -> User {
+//!         User { id: "1".into() }
+//!     }
+//! }
+//!
+//! type Schema =
+//!     RootNode<'static, FederatedQuery<Query, (User,)>, EmptyMutation, EmptySubscription>;
+//!
+//! let schema: Schema =
+//!     RootNode::new_federated(Query, EmptyMutation::new(), EmptySubscription::new());
+//! ```
+//!
+//! The federation directives are declared with attributes: `#[graphql(key = "...")]` and
+//! `#[graphql(shareable)]` on object types, and `#[graphql(external)]`, `#[graphql(shareable)]`,
+//! `#[graphql(requires = "...")]` and `#[graphql(provides = "...")]` on their fields.
+//!
+//! [1]: https://www.apollographql.com/docs/federation/
+
+use std::marker::PhantomData;
+
+use graphql_parser::schema::{Definition, Document, TypeDefinition};
+
+use crate::{
+    ast::{FromInputValue, InputValue, Selection, ToInputValue, Type},
+    executor::{ExecutionResult, Executor, FieldError, FieldResult, Registry},
+    parser::{ParseError, ScalarToken, Token},
+    schema::{
+        meta::MetaType,
+        model::{RootNode, SchemaType},
+        translate::{graphql_parser::GraphQLParserTranslator, SchemaTranslator},
+    },
+    types::{
+        async_await::{GraphQLTypeAsync, GraphQLValueAsync},
+        base::{Arguments, GraphQLType, GraphQLValue},
+        marker::{IsInputType, IsOutputType},
+    },
+    value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
+    BoxFuture, GraphQLObject,
+};
+
+/// Schema extension importing the federation directives, prepended to the `_service` SDL.
+const LINK_FEDERATION: &str = r#"extend schema @link(url: "https://specs.apollo.dev/federation/v2.0", import: ["@key", "@shareable", "@external", "@requires", "@provides"])"#;
+
+/// Types added to the schema by federation, which are omitted from the `_service` SDL.
+const FEDERATION_TYPES: &[&str] = &["_Any", "_Entity", "_FieldSet", "_Service"];
+
+/// Fields added to the query type by federation, which are omitted from the `_service` SDL.
+const FEDERATION_FIELDS: &[&str] = &["_entities", "_service"];
+
+/// Representation of an entity, as passed by a federation gateway to the `_entities` field
+///
+/// This is the `_Any` scalar: an object holding the `__typename` of the entity along with the
+/// fields of one of its keys.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Any<S = DefaultScalarValue>(InputValue<S>);
+
+impl<S> Any<S>
+where
+    S: ScalarValue,
+{
+    /// Name of the type of the represented entity.
+    pub fn typename(&self) -> Option<&str> {
+        self.get("__typename").and_then(InputValue::as_string_value)
+    }
+
+    /// Value of the `field` of the represented entity, if provided.
+    pub fn get(&self, field: &str) -> Option<&InputValue<S>> {
+        match self.0 {
+            InputValue::Object(ref fields) => fields
+                .iter()
+                .find(|(name, _)| name.item == field)
+                .map(|(_, value)| &value.item),
+            _ => None,
+        }
+    }
+
+    /// Value of the `field` of the represented entity, converted into `T`.
+    ///
+    /// Returns `None` if the field isn't provided, or fails to convert.
+    pub fn parse<T>(&self, field: &str) -> Option<T>
+    where
+        T: FromInputValue<S>,
+    {
+        self.get(field).and_then(|v| v.convert().ok())
+    }
+
+    /// The whole representation, as an input object.
+    pub fn as_input_value(&self) -> &InputValue<S> {
+        &self.0
+    }
+}
+
+impl<S> GraphQLType<S> for Any<S>
+where
+    S: ScalarValue,
+{
+    fn name(_: &()) -> Option<&str> {
+        Some("_Any")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry
+            .build_scalar_type::<Self>(info)
+            .description("Representation of a federated entity")
+            .accept_composite_values()
+            .into_meta()
+    }
+}
+
+impl<S> GraphQLValue<S> for Any<S>
+where
+    S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        Ok(to_output(&self.0))
+    }
+}
+
+impl<S> GraphQLValueAsync<S> for Any<S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a (),
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        Box::pin(futures::future::ready(self.resolve(
+            info,
+            selection_set,
+            executor,
+        )))
+    }
+}
+
+impl<S> FromInputValue<S> for Any<S>
+where
+    S: ScalarValue,
+{
+    type Error = String;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        match v {
+            InputValue::Object(_) => Ok(Self(v.clone())),
+            _ => Err(format!("Expected entity representation object, found: {}", v)),
+        }
+    }
+}
+
+impl<S> ToInputValue<S> for Any<S>
+where
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        self.0.clone()
+    }
+}
+
+impl<S> ParseScalarValue<S> for Any<S>
+where
+    S: ScalarValue,
+{
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+        // Representations are objects, which are never scalar tokens.
+        Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+    }
+}
+
+impl<S> IsInputType<S> for Any<S> where S: ScalarValue {}
+
+impl<S> IsOutputType<S> for Any<S> where S: ScalarValue {}
+
+/// Converts the provided representation into a value, so `_Any` can be output too.
+fn to_output<S>(input: &InputValue<S>) -> Value<S>
+where
+    S: ScalarValue,
+{
+    match input {
+        InputValue::Null | InputValue::Variable(_) => Value::null(),
+        InputValue::Scalar(s) => Value::Scalar(s.clone()),
+        InputValue::Enum(e) => Value::scalar(e.clone()),
+        InputValue::List(l) => Value::list(l.iter().map(|v| to_output(&v.item)).collect()),
+        InputValue::Object(o) => Value::object(
+            o.iter()
+                .map(|(k, v)| (k.item.as_str(), to_output(&v.item)))
+                .collect::<Object<S>>(),
+        ),
+    }
+}
+
+/// Selection of fields referenced by federation directives, like `"id"` in `@key(fields: "id")`
+///
+/// This is the `_FieldSet` scalar.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldSet(pub String);
+
+#[crate::graphql_scalar(name = "_FieldSet", description = "Selection of fields of an entity")]
+impl<S> GraphQLScalar for FieldSet
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.0.clone())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<FieldSet, String> {
+        v.as_string_value()
+            .map(|s| FieldSet(s.to_owned()))
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <String as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+/// Description of a subgraph, returned by the `_service` field.
+#[derive(GraphQLObject)]
+#[graphql(name = "_Service")]
+struct Service {
+    /// Schema of the subgraph, including the federation directives.
+    sdl: String,
+}
+
+impl Service {
+    fn new<S>(schema: &SchemaType<S>) -> Self
+    where
+        S: ScalarValue,
+    {
+        Self {
+            sdl: subgraph_sdl(schema),
+        }
+    }
+}
+
+/// Prints the provided `schema` in the schema language, without the types and fields added by
+/// federation.
+fn subgraph_sdl<S>(schema: &SchemaType<S>) -> String
+where
+    S: ScalarValue,
+{
+    let mut doc: Document<&str> = GraphQLParserTranslator::translate_schema(schema);
+    doc.definitions.retain(|def| match def {
+        Definition::TypeDefinition(def) => !FEDERATION_TYPES.contains(&type_definition_name(def)),
+        _ => true,
+    });
+    for def in &mut doc.definitions {
+        if let Definition::TypeDefinition(TypeDefinition::Object(obj)) = def {
+            if obj.name == schema.query_type_name {
+                obj.fields.retain(|f| !FEDERATION_FIELDS.contains(&f.name));
+            }
+        }
+    }
+    format!("{}\n\n{}", LINK_FEDERATION, doc)
+}
+
+fn type_definition_name<'a>(def: &TypeDefinition<'a, &'a str>) -> &'a str {
+    match def {
+        TypeDefinition::Scalar(t) => t.name,
+        TypeDefinition::Object(t) => t.name,
+        TypeDefinition::Interface(t) => t.name,
+        TypeDefinition::Union(t) => t.name,
+        TypeDefinition::Enum(t) => t.name,
+        TypeDefinition::InputObject(t) => t.name,
+    }
+}
+
+/// Object type resolvable by a federation gateway from its [`Any`] representation
+///
+/// Types implementing it should declare their keys with `#[graphql(key = "...")]`.
+#[crate::async_trait]
+pub trait Entity<S = DefaultScalarValue>:
+    GraphQLTypeAsync<S, TypeInfo = ()> + Send + Sized
+where
+    Self::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    /// Resolves the entity from its `representation`, holding the fields of one of its keys.
+    ///
+    /// Returns `None` if no such entity exists.
+    async fn resolve_reference(
+        representation: &Any<S>,
+        context: &Self::Context,
+    ) -> FieldResult<Option<Self>, S>;
+}
+
+/// Set of [`Entity`] types resolved by the `_entities` field, keyed by their `__typename`
+///
+/// It's implemented for tuples of [`Entity`] types sharing the same context, like
+/// `(User, Product)`, and for `()` when a subgraph defines no entities.
+pub trait EntityResolver<CtxT, S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    /// Registers the entity types, returning them.
+    fn entity_types<'r>(registry: &mut Registry<'r, S>) -> Vec<Type<'r>>
+    where
+        S: 'r;
+
+    /// Resolves the entity of the `typename` type from its `representation`.
+    ///
+    /// Returns `None` if no entity type is named `typename`.
+    fn resolve_entity<'b>(
+        typename: &str,
+        representation: &'b Any<S>,
+        executor: &'b Executor<'_, '_, CtxT, S>,
+    ) -> Option<BoxFuture<'b, ExecutionResult<S>>>;
+}
+
+impl<CtxT, S> EntityResolver<CtxT, S> for ()
+where
+    S: ScalarValue,
+{
+    fn entity_types<'r>(_: &mut Registry<'r, S>) -> Vec<Type<'r>>
+    where
+        S: 'r,
+    {
+        vec![]
+    }
+
+    fn resolve_entity<'b>(
+        _: &str,
+        _: &'b Any<S>,
+        _: &'b Executor<'_, '_, CtxT, S>,
+    ) -> Option<BoxFuture<'b, ExecutionResult<S>>> {
+        None
+    }
+}
+
+macro_rules! impl_entity_resolver_for_tuple {
+    ($($entity:ident),+) => {
+        impl<CtxT, S, $($entity,)+> EntityResolver<CtxT, S> for ($($entity,)+)
+        where
+            CtxT: Sync,
+            S: ScalarValue + Send + Sync,
+            $($entity: Entity<S, Context = CtxT>,)+
+        {
+            fn entity_types<'r>(registry: &mut Registry<'r, S>) -> Vec<Type<'r>>
+            where
+                S: 'r,
+            {
+                vec![$(registry.get_type::<$entity>(&()),)+]
+            }
+
+            fn resolve_entity<'b>(
+                typename: &str,
+                representation: &'b Any<S>,
+                executor: &'b Executor<'_, '_, CtxT, S>,
+            ) -> Option<BoxFuture<'b, ExecutionResult<S>>> {
+                $(
+                    if <$entity as GraphQLType<S>>::name(&()) == Some(typename) {
+                        return Some(Box::pin(async move {
+                            let context = executor.context();
+                            match $entity::resolve_reference(representation, context).await? {
+                                Some(entity) => executor.resolve_async(&(), &entity).await,
+                                None => Ok(Value::null()),
+                            }
+                        }));
+                    }
+                )+
+                None
+            }
+        }
+    };
+}
+
+impl_entity_resolver_for_tuple!(A);
+impl_entity_resolver_for_tuple!(A, B);
+impl_entity_resolver_for_tuple!(A, B, C);
+impl_entity_resolver_for_tuple!(A, B, C, D);
+impl_entity_resolver_for_tuple!(A, B, C, D, E);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G, H);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_entity_resolver_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// The `_Entity` union of all the [`Entity`] types of `EntitiesT`.
+struct EntityUnion<CtxT, EntitiesT>(PhantomData<fn() -> (CtxT, EntitiesT)>);
+
+impl<S, CtxT, EntitiesT> GraphQLType<S> for EntityUnion<CtxT, EntitiesT>
+where
+    S: ScalarValue,
+    EntitiesT: EntityResolver<CtxT, S>,
+{
+    fn name(_: &()) -> Option<&str> {
+        Some("_Entity")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let types = EntitiesT::entity_types(registry);
+        registry.build_union_type::<Self>(info, &types).into_meta()
+    }
+}
+
+impl<S, CtxT, EntitiesT> GraphQLValue<S> for EntityUnion<CtxT, EntitiesT>
+where
+    S: ScalarValue,
+    EntitiesT: EntityResolver<CtxT, S>,
+{
+    type Context = CtxT;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+}
+
+/// Query type of a federated subgraph
+///
+/// Extends the `QueryT` with the `_service` field, and with the `_entities` field resolving the
+/// [`Entity`] types of `EntitiesT` unless it's `()`.
+///
+/// # Panics
+///
+/// Building the schema panics if `QueryT` isn't a GraphQL object type.
+pub struct FederatedQuery<QueryT, EntitiesT = ()> {
+    query: QueryT,
+    _entities: PhantomData<fn() -> EntitiesT>,
+}
+
+impl<QueryT, EntitiesT> FederatedQuery<QueryT, EntitiesT> {
+    /// Wraps the provided `query` type.
+    pub fn new(query: QueryT) -> Self {
+        Self {
+            query,
+            _entities: PhantomData,
+        }
+    }
+
+    /// The wrapped query type.
+    pub fn inner(&self) -> &QueryT {
+        &self.query
+    }
+}
+
+impl<S, QueryT, EntitiesT> GraphQLType<S> for FederatedQuery<QueryT, EntitiesT>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    EntitiesT: EntityResolver<QueryT::Context, S>,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        QueryT::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.get_type::<FieldSet>(&());
+
+        let mut fields = vec![registry.field::<Service>("_service", &())];
+        if !EntitiesT::entity_types(registry).is_empty() {
+            fields.push(
+                registry
+                    .field::<Vec<Option<EntityUnion<QueryT::Context, EntitiesT>>>>("_entities", &())
+                    .argument(registry.arg::<Vec<Any<S>>>("representations", &())),
+            );
+        }
+
+        let mut meta = QueryT::meta(info, registry);
+        match meta {
+            MetaType::Object(ref mut obj) => obj.fields.append(&mut fields),
+            _ => panic!(
+                "`FederatedQuery` requires an object query type, but `{}` is of kind {:?}",
+                meta.name().unwrap_or("<unnamed>"),
+                meta.type_kind(),
+            ),
+        }
+        meta
+    }
+}
+
+impl<S, QueryT, EntitiesT> GraphQLValue<S> for FederatedQuery<QueryT, EntitiesT>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    EntitiesT: EntityResolver<QueryT::Context, S>,
+{
+    type Context = QueryT::Context;
+    type TypeInfo = QueryT::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        QueryT::name(info)
+    }
+
+    fn concrete_type_name(&self, context: &Self::Context, info: &Self::TypeInfo) -> String {
+        self.query.concrete_type_name(context, info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field {
+            "_service" => executor.resolve_with_ctx(&(), &Service::new(executor.schema())),
+            "_entities" => Err(FieldError::new(
+                "Entities can only be resolved by asynchronous execution",
+                Value::null(),
+            )),
+            _ => self.query.resolve_field(info, field, args, executor),
+        }
+    }
+}
+
+impl<S, QueryT, EntitiesT> GraphQLValueAsync<S> for FederatedQuery<QueryT, EntitiesT>
+where
+    S: ScalarValue + Send + Sync,
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    EntitiesT: EntityResolver<QueryT::Context, S>,
+{
+    fn resolve_field_async<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> BoxFuture<'b, ExecutionResult<S>> {
+        match field_name {
+            "_service" => {
+                let v = self.resolve_field(info, field_name, arguments, executor);
+                Box::pin(futures::future::ready(v))
+            }
+            "_entities" => {
+                let representations = match arguments.get::<Vec<Any<S>>>("representations") {
+                    Ok(representations) => representations.unwrap_or_default(),
+                    Err(e) => return Box::pin(futures::future::ready(Err(e))),
+                };
+                Box::pin(async move {
+                    let entities = representations.iter().map(|repr| async move {
+                        let typename = repr.typename().unwrap_or_default();
+                        match EntitiesT::resolve_entity(typename, repr, executor) {
+                            Some(entity) => entity.await,
+                            None => Err(FieldError::new(
+                                format!("Unknown entity type \"{}\"", typename),
+                                Value::null(),
+                            )),
+                        }
+                    });
+                    let values = futures::future::join_all(entities)
+                        .await
+                        .into_iter()
+                        .map(|res| {
+                            res.unwrap_or_else(|e| {
+                                executor.push_error(e);
+                                Value::null()
+                            })
+                        })
+                        .collect();
+                    Ok(Value::list(values))
+                })
+            }
+            _ => self
+                .query
+                .resolve_field_async(info, field_name, arguments, executor),
+        }
+    }
+}
+
+impl<'a, QueryT, EntitiesT, MutationT, SubscriptionT>
+    RootNode<'a, FederatedQuery<QueryT, EntitiesT>, MutationT, SubscriptionT, DefaultScalarValue>
+where
+    QueryT: GraphQLType<DefaultScalarValue, TypeInfo = ()>,
+    EntitiesT: EntityResolver<QueryT::Context, DefaultScalarValue>,
+    MutationT: GraphQLType<DefaultScalarValue, TypeInfo = ()>,
+    SubscriptionT: GraphQLType<DefaultScalarValue, TypeInfo = ()>,
+{
+    /// Constructs a new [`RootNode`] of a federated subgraph, wrapping the `query` node into a
+    /// [`FederatedQuery`] resolving the `EntitiesT`.
+    pub fn new_federated(query: QueryT, mutation: MutationT, subscription: SubscriptionT) -> Self {
+        Self::new(FederatedQuery::new(query), mutation, subscription)
+    }
+}
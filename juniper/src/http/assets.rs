@@ -0,0 +1,123 @@
+//! Pinned versions of the JavaScript and CSS assets the GraphiQL and GraphQL Playground pages are
+//! made of.
+//!
+//! By default, the pages load them from public CDNs. Environments without access to those can
+//! serve the assets themselves instead, and point the pages to them with an asset base URL: the
+//! page then loads each asset from `{base_url}/{name}`, where `name` is its [`Asset::name`].
+//!
+//! With the `bundled-assets` feature, the assets are embedded in the crate, and their
+//! [`Asset::contents`] can be served by the web framework integrations. They're vendored in the
+//! `assets` directory of the crate by its `fetch.sh` script, and their contents are checked against
+//! the SRI hashes pinned in `assets/assets.txt`, the list of assets this module is generated from,
+//! while building.
+
+/// An asset of the GraphiQL or GraphQL Playground pages.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Asset {
+    /// Name of this asset, made of its package and version, and its path in the package, like
+    /// `graphiql@0.17.5/graphiql.min.js`.
+    pub name: &'static str,
+
+    /// `Content-Type` to serve this asset with.
+    pub content_type: &'static str,
+
+    /// URL of this asset on a public CDN.
+    pub cdn_url: &'static str,
+
+    #[cfg(feature = "bundled-assets")]
+    contents: &'static [u8],
+}
+
+impl Asset {
+    /// Returns the URL to load this asset from, either from the given base URL the assets are
+    /// served from, or from a public CDN otherwise.
+    pub fn url(&self, assets_base_url: Option<&str>) -> String {
+        match assets_base_url {
+            Some(base_url) => format!("{}/{}", base_url.trim_end_matches('/'), self.name),
+            None => self.cdn_url.to_owned(),
+        }
+    }
+
+    /// Returns the contents of this asset, embedded in the crate.
+    #[cfg(feature = "bundled-assets")]
+    pub fn contents(&self) -> &'static [u8] {
+        self.contents
+    }
+}
+
+macro_rules! assets {
+    ($($ident:ident = ($name:literal, $content_type:expr, $cdn_url:literal, $path:literal),)*) => {
+        $(
+            #[doc = concat!("The `", $name, "` asset.")]
+            pub const $ident: Asset = Asset {
+                name: $name,
+                content_type: $content_type,
+                cdn_url: $cdn_url,
+                #[cfg(feature = "bundled-assets")]
+                contents: include_bytes!($path),
+            };
+        )*
+
+        /// All the assets, pinned to the versions the pages are known to work with.
+        pub const ALL: &[Asset] = &[$($ident),*];
+    };
+}
+
+/// `Cache-Control` to serve the assets with: as their names carry their versions, their contents
+/// never change.
+pub const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+const JS: &str = "application/javascript; charset=utf-8";
+const CSS: &str = "text/css; charset=utf-8";
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// Finds the asset with the given [`Asset::name`].
+pub fn find(name: &str) -> Option<&'static Asset> {
+    ALL.iter().find(|asset| asset.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, ALL, GRAPHIQL_2_CSS, GRAPHIQL_2_JS, PLAYGROUND_FAVICON};
+
+    #[test]
+    fn builds_urls() {
+        assert_eq!(
+            GRAPHIQL_2_JS.url(None),
+            "//unpkg.com/graphiql@2.4.7/graphiql.min.js",
+        );
+        assert_eq!(
+            GRAPHIQL_2_JS.url(Some("/assets/")),
+            "/assets/graphiql@2.4.7/graphiql.min.js",
+        );
+        assert_eq!(
+            GRAPHIQL_2_JS.url(Some("https://static.example.com")),
+            "https://static.example.com/graphiql@2.4.7/graphiql.min.js",
+        );
+    }
+
+    #[test]
+    fn finds_assets_by_name() {
+        assert_eq!(find("graphiql@2.4.7/graphiql.min.js"), Some(&GRAPHIQL_2_JS));
+        assert_eq!(find("graphiql.min.js"), None);
+        assert_eq!(find("../Cargo.toml"), None);
+    }
+
+    #[test]
+    fn lists_every_asset() {
+        assert_eq!(ALL.len(), 15);
+        assert_eq!(ALL[10], GRAPHIQL_2_JS);
+        assert_eq!(GRAPHIQL_2_JS.content_type, super::JS);
+        assert_eq!(GRAPHIQL_2_CSS.content_type, super::CSS);
+        assert_eq!(PLAYGROUND_FAVICON.content_type, "image/png");
+    }
+
+    #[cfg(feature = "bundled-assets")]
+    #[test]
+    fn embeds_every_asset() {
+        for asset in ALL {
+            assert!(!asset.contents().is_empty(), "`{}` is empty", asset.name);
+        }
+    }
+}
@@ -1,5 +1,219 @@
 //! Utility module to generate a GraphiQL interface
 
+use std::fmt::Write as _;
+
+use super::assets::{self, Asset};
+
+/// Version of GraphiQL to generate the interface with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraphiQLVersion {
+    /// GraphiQL 0.17, with subscriptions over the `graphql-ws` protocol of
+    /// `subscriptions-transport-ws`, as served by `juniper_graphql_ws`.
+    V0,
+
+    /// GraphiQL 2, with subscriptions over the `graphql-transport-ws` protocol of `graphql-ws`.
+    V2,
+}
+
+impl Default for GraphiQLVersion {
+    fn default() -> Self {
+        Self::V0
+    }
+}
+
+/// [Credentials mode][1] of the requests sent by the GraphQL interfaces.
+///
+/// [1]: https://developer.mozilla.org/en-US/docs/Web/API/Request/credentials
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Credentials {
+    /// Never send cookies.
+    Omit,
+
+    /// Only send cookies to the same origin as the interface.
+    SameOrigin,
+
+    /// Always send cookies, even for cross-origin requests.
+    Include,
+}
+
+impl Credentials {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Omit => "omit",
+            Self::SameOrigin => "same-origin",
+            Self::Include => "include",
+        }
+    }
+}
+
+impl Default for Credentials {
+    fn default() -> Self {
+        Self::Include
+    }
+}
+
+/// Configuration of a GraphiQL interface, rendered by [`graphiql_source_with_config`].
+///
+/// ```
+/// # use juniper::http::graphiql::{graphiql_source_with_config, Credentials, GraphiQLConfig};
+/// let config = GraphiQLConfig::new("/graphql")
+///     .with_subscriptions_endpoint_url("/subscriptions")
+///     .with_header("Authorization", "Bearer token")
+///     .with_query("query Hero($episode: Episode) { hero(episode: $episode) { name } }")
+///     .with_variables(r#"{ "episode": "JEDI" }"#)
+///     .with_credentials(Credentials::SameOrigin)
+///     .with_title("Star Wars API");
+///
+/// let graphiql = graphiql_source_with_config(&config);
+/// assert!(graphiql.contains("<title>Star Wars API</title>"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GraphiQLConfig {
+    graphql_endpoint_url: String,
+    subscriptions_endpoint_url: Option<String>,
+    version: GraphiQLVersion,
+    assets_base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    query: Option<String>,
+    variables: Option<String>,
+    tabs: Vec<(String, Option<String>)>,
+    credentials: Credentials,
+    title: Option<String>,
+    logo: Option<String>,
+}
+
+impl GraphiQLConfig {
+    /// Creates the configuration of a GraphiQL interface sending its requests to the given URL.
+    pub fn new(graphql_endpoint_url: &str) -> Self {
+        Self {
+            graphql_endpoint_url: graphql_endpoint_url.to_owned(),
+            subscriptions_endpoint_url: None,
+            version: GraphiQLVersion::default(),
+            assets_base_url: None,
+            headers: Vec::new(),
+            query: None,
+            variables: None,
+            tabs: Vec::new(),
+            credentials: Credentials::default(),
+            title: None,
+            logo: None,
+        }
+    }
+
+    /// Specifies the URL to send subscriptions to, over WebSocket connections.
+    pub fn with_subscriptions_endpoint_url(mut self, url: &str) -> Self {
+        self.subscriptions_endpoint_url = Some(url.to_owned());
+        self
+    }
+
+    /// Specifies the version of GraphiQL to use. Defaults to [`GraphiQLVersion::V0`].
+    pub fn with_version(mut self, version: GraphiQLVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Specifies the base URL to load the [`assets`] from, instead of public CDNs.
+    pub fn with_assets_base_url(mut self, url: &str) -> Self {
+        self.assets_base_url = Some(url.to_owned());
+        self
+    }
+
+    /// Adds a header to send with every request. With GraphiQL 2, headers pre-fill its headers
+    /// editor, so they can be edited before being sent.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Specifies the query the editor initially shows.
+    pub fn with_query(mut self, query: &str) -> Self {
+        self.query = Some(query.to_owned());
+        self
+    }
+
+    /// Specifies the JSON variables the variables editor initially shows.
+    pub fn with_variables(mut self, variables: &str) -> Self {
+        self.variables = Some(variables.to_owned());
+        self
+    }
+
+    /// Adds a tab initially opened with the given query and JSON variables. Only GraphiQL 2 has
+    /// tabs, so this is ignored by [`GraphiQLVersion::V0`].
+    pub fn with_tab(mut self, query: &str, variables: Option<&str>) -> Self {
+        self.tabs
+            .push((query.to_owned(), variables.map(ToOwned::to_owned)));
+        self
+    }
+
+    /// Specifies whether to send cookies with requests. Defaults to [`Credentials::Include`].
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Specifies the title of the page.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+
+    /// Specifies the text replacing the GraphiQL logo in the top bar.
+    pub fn with_logo(mut self, logo: &str) -> Self {
+        self.logo = Some(logo.to_owned());
+        self
+    }
+
+    fn assets_base_url(&self) -> Option<&str> {
+        self.assets_base_url.as_deref()
+    }
+
+    /// Script declaring the configured options as variables used by the fetcher sources.
+    fn options_source(&self) -> String {
+        let mut headers = String::from("{");
+        for (i, (name, value)) in self.headers.iter().enumerate() {
+            if i > 0 {
+                headers.push_str(", ");
+            }
+            write!(headers, "{}: {}", js_string(name), js_string(value)).unwrap();
+        }
+        headers.push('}');
+
+        let mut tabs = String::from("[");
+        for (i, (query, variables)) in self.tabs.iter().enumerate() {
+            if i > 0 {
+                tabs.push_str(", ");
+            }
+            write!(
+                tabs,
+                "{{ query: {}, variables: {} }}",
+                js_string(query),
+                js_optional_string(variables.as_deref()),
+            )
+            .unwrap();
+        }
+        tabs.push(']');
+
+        format!(
+            r#"
+    <script>
+        var DEFAULT_HEADERS = {headers};
+        var CREDENTIALS = '{credentials}';
+        var INITIAL_QUERY = {query};
+        var INITIAL_VARIABLES = {variables};
+        var DEFAULT_TABS = {tabs};
+        var LOGO = {logo};
+    </script>
+    "#,
+            headers = headers,
+            credentials = self.credentials.as_str(),
+            query = js_optional_string(self.query.as_deref()),
+            variables = js_optional_string(self.variables.as_deref()),
+            tabs = tabs,
+            logo = js_optional_string(self.logo.as_deref()),
+        )
+    }
+}
+
 /// Generate the HTML source to show a GraphiQL interface
 ///
 /// The subscriptions endpoint URL can optionally be provided. For example:
@@ -12,13 +226,33 @@ pub fn graphiql_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
 ) -> String {
-    let subscriptions_endpoint = if let Some(sub_url) = subscriptions_endpoint_url {
-        sub_url
-    } else {
-        ""
-    };
+    let mut config = GraphiQLConfig::new(graphql_endpoint_url);
+    if let Some(url) = subscriptions_endpoint_url {
+        config = config.with_subscriptions_endpoint_url(url);
+    }
+    graphiql_source_with_config(&config)
+}
+
+/// Generate the HTML source to show a GraphiQL interface with the given configuration.
+///
+/// ```
+/// # use juniper::http::graphiql::{graphiql_source_with_config, GraphiQLConfig, GraphiQLVersion};
+/// let graphiql = graphiql_source_with_config(
+///     &GraphiQLConfig::new("/graphql")
+///         .with_subscriptions_endpoint_url("/subscriptions")
+///         .with_version(GraphiQLVersion::V2)
+///         .with_assets_base_url("/graphiql/assets"),
+/// );
+/// assert!(graphiql.contains(r#"<script src="/graphiql/assets/graphiql@2.4.7/graphiql.min.js">"#));
+/// ```
+pub fn graphiql_source_with_config(config: &GraphiQLConfig) -> String {
+    match config.version {
+        GraphiQLVersion::V0 => graphiql_v0_source(config),
+        GraphiQLVersion::V2 => graphiql_v2_source(config),
+    }
+}
 
-    let stylesheet_source = r#"
+const STYLESHEET_SOURCE: &str = r#"
     <style>
         html, body, #app {
             height: 100%;
@@ -28,13 +262,8 @@ pub fn graphiql_source(
         }
     </style>
     "#;
-    let fetcher_source = r#"
-    <script>
-        if (usingSubscriptions) {
-            var subscriptionEndpoint = normalizeSubscriptionEndpoint(GRAPHQL_URL, GRAPHQL_SUBSCRIPTIONS_URL);
-            var subscriptionsClient = new window.SubscriptionsTransportWs.SubscriptionClient(subscriptionEndpoint, { reconnect: true });
-        }
 
+const NORMALIZE_SUBSCRIPTION_ENDPOINT_SOURCE: &str = r#"
         function normalizeSubscriptionEndpoint(endpoint, subscriptionEndpoint) {
             if (subscriptionEndpoint) {
                 if (subscriptionEndpoint.startsWith('/')) {
@@ -49,15 +278,24 @@ pub fn graphiql_source(
             }
             return null
         }
+"#;
+
+fn graphiql_v0_source(config: &GraphiQLConfig) -> String {
+    let fetcher_source = r#"
+    <script>
+        if (usingSubscriptions) {
+            var subscriptionEndpoint = normalizeSubscriptionEndpoint(GRAPHQL_URL, GRAPHQL_SUBSCRIPTIONS_URL);
+            var subscriptionsClient = new window.SubscriptionsTransportWs.SubscriptionClient(subscriptionEndpoint, { reconnect: true });
+        }
 
         function graphQLFetcher(params) {
             return fetch(GRAPHQL_URL, {
                 method: 'post',
-                headers: {
+                headers: Object.assign({
                     'Accept': 'application/json',
                     'Content-Type': 'application/json',
-                },
-                credentials: 'include',
+                }, DEFAULT_HEADERS),
+                credentials: CREDENTIALS,
                 body: JSON.stringify(params)
             }).then(function (response) {
                 return response.text();
@@ -75,39 +313,310 @@ pub fn graphiql_source(
         ReactDOM.render(
             React.createElement(GraphiQL, {
                 fetcher,
-            }),
+                query: INITIAL_QUERY,
+                variables: INITIAL_VARIABLES,
+            }, LOGO === undefined ? undefined : React.createElement(GraphiQL.Logo, {}, LOGO)),
             document.querySelector('#app'));
     </script>
     "#;
 
+    let assets_base_url = config.assets_base_url();
+    format!(
+        r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <title>{title}</title>
+    {stylesheet_source}
+    <link rel="stylesheet" type="text/css" href="{graphiql_css}">
+</head>
+<body>
+    <div id="app"></div>
+    <script src="{fetch_js}"></script>
+    <script src="{subscriptions_transport_ws_js}"></script>
+    <script src="{graphiql_subscriptions_fetcher_js}"></script>
+    <script src="{react_js}"></script>
+    <script src="{react_dom_js}"></script>
+    <script src="{graphiql_js}"></script>
+    <script>var GRAPHQL_URL = {graphql_url};</script>
+    <script>var usingSubscriptions = {using_subscriptions};</script>
+    <script>var GRAPHQL_SUBSCRIPTIONS_URL = {graphql_subscriptions_url};</script>
+    <script>{normalize_subscription_endpoint_source}</script>
+    {options_source}
+    {fetcher_source}
+</body>
+</html>
+"#,
+        title = html_escape(config.title.as_deref().unwrap_or("GraphQL")),
+        graphql_url = js_string(&config.graphql_endpoint_url),
+        stylesheet_source = STYLESHEET_SOURCE,
+        graphiql_css = asset_url(&assets::GRAPHIQL_0_CSS, assets_base_url),
+        fetch_js = asset_url(&assets::FETCH, assets_base_url),
+        subscriptions_transport_ws_js =
+            asset_url(&assets::SUBSCRIPTIONS_TRANSPORT_WS, assets_base_url),
+        graphiql_subscriptions_fetcher_js =
+            asset_url(&assets::GRAPHIQL_SUBSCRIPTIONS_FETCHER, assets_base_url),
+        react_js = asset_url(&assets::REACT_16, assets_base_url),
+        react_dom_js = asset_url(&assets::REACT_DOM_16, assets_base_url),
+        graphiql_js = asset_url(&assets::GRAPHIQL_0_JS, assets_base_url),
+        normalize_subscription_endpoint_source = NORMALIZE_SUBSCRIPTION_ENDPOINT_SOURCE,
+        options_source = config.options_source(),
+        fetcher_source = fetcher_source,
+        graphql_subscriptions_url =
+            js_string(config.subscriptions_endpoint_url.as_deref().unwrap_or("")),
+        using_subscriptions = config.subscriptions_endpoint_url.is_some(),
+    )
+}
+
+fn graphiql_v2_source(config: &GraphiQLConfig) -> String {
+    let fetcher_source = r#"
+    <script>
+        var fetcher = GraphiQL.createFetcher({
+            url: GRAPHQL_URL,
+            wsClient: usingSubscriptions
+                ? graphqlWs.createClient({
+                    url: normalizeSubscriptionEndpoint(GRAPHQL_URL, GRAPHQL_SUBSCRIPTIONS_URL),
+                })
+                : undefined,
+            fetch: function (url, init) {
+                return fetch(url, Object.assign({}, init, { credentials: CREDENTIALS }));
+            },
+        });
+
+        ReactDOM.createRoot(document.querySelector('#app')).render(
+            React.createElement(GraphiQL, {
+                fetcher,
+                query: INITIAL_QUERY,
+                variables: INITIAL_VARIABLES,
+                headers: Object.keys(DEFAULT_HEADERS).length > 0
+                    ? JSON.stringify(DEFAULT_HEADERS, null, 2)
+                    : undefined,
+                defaultTabs: DEFAULT_TABS.length > 0 ? DEFAULT_TABS : undefined,
+            }, LOGO === undefined ? undefined : React.createElement(GraphiQL.Logo, {}, LOGO)));
+    </script>
+    "#;
+
+    let assets_base_url = config.assets_base_url();
     format!(
         r#"
 <!DOCTYPE html>
 <html>
 <head>
-    <title>GraphQL</title>
+    <title>{title}</title>
     {stylesheet_source}
-    <link rel="stylesheet" type="text/css" href="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.css">
+    <link rel="stylesheet" type="text/css" href="{graphiql_css}">
 </head>
 <body>
     <div id="app"></div>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js"></script>
-    <script src="//unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js"></script>
-    <script src="//unpkg.com/graphiql-subscriptions-fetcher@0.0.2/browser/client.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/16.10.2/umd/react.production.min.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react-dom/16.10.2/umd/react-dom.production.min.js"></script>
-    <script src="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.js"></script>
-    <script>var GRAPHQL_URL = '{graphql_url}';</script>
+    <script src="{react_js}"></script>
+    <script src="{react_dom_js}"></script>
+    <script src="{graphql_ws_js}"></script>
+    <script src="{graphiql_js}"></script>
+    <script>var GRAPHQL_URL = {graphql_url};</script>
     <script>var usingSubscriptions = {using_subscriptions};</script>
-    <script>var GRAPHQL_SUBSCRIPTIONS_URL = '{graphql_subscriptions_url}';</script>
+    <script>var GRAPHQL_SUBSCRIPTIONS_URL = {graphql_subscriptions_url};</script>
+    <script>{normalize_subscription_endpoint_source}</script>
+    {options_source}
     {fetcher_source}
 </body>
 </html>
 "#,
-        graphql_url = graphql_endpoint_url,
-        stylesheet_source = stylesheet_source,
+        title = html_escape(config.title.as_deref().unwrap_or("GraphQL")),
+        graphql_url = js_string(&config.graphql_endpoint_url),
+        stylesheet_source = STYLESHEET_SOURCE,
+        graphiql_css = asset_url(&assets::GRAPHIQL_2_CSS, assets_base_url),
+        react_js = asset_url(&assets::REACT_18, assets_base_url),
+        react_dom_js = asset_url(&assets::REACT_DOM_18, assets_base_url),
+        graphql_ws_js = asset_url(&assets::GRAPHQL_WS, assets_base_url),
+        graphiql_js = asset_url(&assets::GRAPHIQL_2_JS, assets_base_url),
+        normalize_subscription_endpoint_source = NORMALIZE_SUBSCRIPTION_ENDPOINT_SOURCE,
+        options_source = config.options_source(),
         fetcher_source = fetcher_source,
-        graphql_subscriptions_url = subscriptions_endpoint,
-        using_subscriptions = subscriptions_endpoint_url.is_some(),
+        graphql_subscriptions_url =
+            js_string(config.subscriptions_endpoint_url.as_deref().unwrap_or("")),
+        using_subscriptions = config.subscriptions_endpoint_url.is_some(),
     )
 }
+
+/// Returns the URL to load the `asset` from, escaped to be embedded in an HTML attribute.
+pub(super) fn asset_url(asset: &Asset, assets_base_url: Option<&str>) -> String {
+    html_escape(&asset.url(assets_base_url))
+}
+
+/// Escapes the given text to be embedded in HTML.
+pub(super) fn html_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes the given text as a JavaScript string literal, safe to embed in a `<script>` element.
+pub(super) fn js_string(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => {
+                write!(literal, "\\u{:04x}", c as u32).unwrap()
+            }
+            c if c.is_control() => write!(literal, "\\u{:04x}", c as u32).unwrap(),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+fn js_optional_string(text: Option<&str>) -> String {
+    text.map_or_else(|| "undefined".to_owned(), js_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        graphiql_source, graphiql_source_with_config, js_string, Credentials, GraphiQLConfig,
+        GraphiQLVersion,
+    };
+
+    #[test]
+    fn loads_assets_from_cdns_by_default() {
+        let source = graphiql_source("/graphql", None);
+
+        assert!(source.contains(
+            r#"<script src="//cdn.jsdelivr.net/npm/graphiql@0.17.5/graphiql.min.js"></script>"#
+        ));
+        assert!(source.contains("<script>var usingSubscriptions = false;</script>"));
+    }
+
+    #[test]
+    fn loads_assets_from_base_url() {
+        for &version in &[GraphiQLVersion::V0, GraphiQLVersion::V2] {
+            let source = graphiql_source_with_config(
+                &GraphiQLConfig::new("/graphql")
+                    .with_version(version)
+                    .with_assets_base_url("/assets"),
+            );
+
+            assert!(
+                !source.contains("//cdn"),
+                "{:?} loads assets from a CDN",
+                version
+            );
+            assert!(
+                !source.contains("//unpkg"),
+                "{:?} loads assets from a CDN",
+                version
+            );
+            assert!(source.contains(r#"href="/assets/graphiql@"#));
+        }
+    }
+
+    #[test]
+    fn uses_graphql_ws_client_with_graphiql_2() {
+        let source = graphiql_source_with_config(
+            &GraphiQLConfig::new("/graphql")
+                .with_subscriptions_endpoint_url("/subscriptions")
+                .with_version(GraphiQLVersion::V2),
+        );
+
+        assert!(source.contains(
+            r#"<script src="//unpkg.com/graphql-ws@5.11.2/umd/graphql-ws.min.js"></script>"#
+        ));
+        assert!(source.contains("graphqlWs.createClient"));
+        assert!(source
+            .contains(r#"<script>var GRAPHQL_SUBSCRIPTIONS_URL = "/subscriptions";</script>"#));
+        assert!(!source.contains("SubscriptionsTransportWs"));
+    }
+
+    #[test]
+    fn defaults_options() {
+        let source = graphiql_source("/graphql", None);
+
+        assert!(source.contains("<title>GraphQL</title>"));
+        assert!(source.contains("var DEFAULT_HEADERS = {};"));
+        assert!(source.contains("var CREDENTIALS = 'include';"));
+        assert!(source.contains("var INITIAL_QUERY = undefined;"));
+        assert!(source.contains("var INITIAL_VARIABLES = undefined;"));
+        assert!(source.contains("var DEFAULT_TABS = [];"));
+        assert!(source.contains("var LOGO = undefined;"));
+    }
+
+    #[test]
+    fn renders_options() {
+        for &version in &[GraphiQLVersion::V0, GraphiQLVersion::V2] {
+            let source = graphiql_source_with_config(
+                &GraphiQLConfig::new("/graphql")
+                    .with_version(version)
+                    .with_header("Authorization", "Bearer token")
+                    .with_header("X-Tenant", "rebels")
+                    .with_query("{ hero { name } }")
+                    .with_variables("{}")
+                    .with_tab("{ human(id: \"1000\") { name } }", None)
+                    .with_tab(
+                        "query($id: String!) { droid(id: $id) { name } }",
+                        Some("{}"),
+                    )
+                    .with_credentials(Credentials::SameOrigin)
+                    .with_title("Star Wars <API>")
+                    .with_logo("Star Wars"),
+            );
+
+            assert!(source.contains("<title>Star Wars &lt;API&gt;</title>"));
+            assert!(source.contains(
+                r#"var DEFAULT_HEADERS = {"Authorization": "Bearer token", "X-Tenant": "rebels"};"#
+            ));
+            assert!(source.contains("var CREDENTIALS = 'same-origin';"));
+            assert!(source.contains(r#"var INITIAL_QUERY = "{ hero { name } }";"#));
+            assert!(source.contains(r#"var INITIAL_VARIABLES = "{}";"#));
+            assert!(source.contains(
+                r#"var DEFAULT_TABS = [{ query: "{ human(id: \"1000\") { name } }", variables: undefined }, { query: "query($id: String!) { droid(id: $id) { name } }", variables: "{}" }];"#
+            ));
+            assert!(source.contains(r#"var LOGO = "Star Wars";"#));
+        }
+    }
+
+    #[test]
+    fn quotes_js_strings() {
+        assert_eq!(js_string("plain"), r#""plain""#);
+        assert_eq!(
+            js_string("\"quoted\"\\\n</script>"),
+            r#""\"quoted\"\\\n\u003c/script\u003e""#,
+        );
+        assert_eq!(js_string("\u{0}\u{2028}"), r#""\u0000\u2028""#);
+    }
+
+    #[test]
+    fn escapes_urls() {
+        for &version in &[GraphiQLVersion::V0, GraphiQLVersion::V2] {
+            let source = graphiql_source_with_config(
+                &GraphiQLConfig::new("/graphql';alert(1)//</script>")
+                    .with_version(version)
+                    .with_subscriptions_endpoint_url("/subscriptions\"")
+                    .with_assets_base_url("/assets\"><script>"),
+            );
+
+            assert!(source.contains(
+                r#"<script>var GRAPHQL_URL = "/graphql';alert(1)//\u003c/script\u003e";</script>"#
+            ));
+            assert!(source.contains(
+                r#"<script>var GRAPHQL_SUBSCRIPTIONS_URL = "/subscriptions\"";</script>"#
+            ));
+            assert!(source.contains(r#"href="/assets&quot;&gt;&lt;script&gt;/graphiql@"#));
+            assert!(!source.contains("/assets\"><script>"));
+        }
+    }
+}
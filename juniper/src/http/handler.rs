@@ -0,0 +1,1152 @@
+//! Transport-agnostic implementation of the [GraphQL over HTTP][spec] specification.
+//!
+//! Web framework integrations only have to translate their requests into an [`HttpRequest`],
+//! and the [`HttpResponse`] returned by [`handle`] (or [`handle_sync`]) back into their
+//! responses:
+//! - `GET` and `POST` requests are accepted, any other method is answered with
+//!   `405 Method Not Allowed`;
+//! - `GET` requests carry the `query`, `operationName` and `variables` in their query string, and
+//!   can't execute mutations, which are answered with `405 Method Not Allowed` too;
+//! - `POST` requests carry either an `application/json` (possibly batched) request, or an
+//!   `application/graphql` query, any other or missing `Content-Type` is answered with
+//!   `415 Unsupported Media Type`;
+//! - responses are either `application/graphql-response+json` or `application/json`, as
+//!   negotiated with the `Accept` header, which is answered with `406 Not Acceptable` when it
+//!   allows neither of them;
+//! - malformed requests, and operations that can't be executed because of parsing or validation
+//!   errors, are answered with `400 Bad Request`, while executed ones are answered with `200 OK`,
+//!   even if some of their fields failed.
+//!
+//! Request bodies larger than [`DEFAULT_MAX_BODY_SIZE`] are answered with
+//! `413 Payload Too Large`. With a [`HandlerConfig`], requests may also be checked for cross-site
+//! request forgery, and rejected when their body, batch or query exceeds another maximum size.
+//!
+//! [spec]: https://graphql.github.io/graphql-over-http/draft
+
+use serde::Serialize;
+
+use crate::{
+    ast::{Definition, Document, InputValue, OperationType},
+    executor::get_operation,
+    parser::parse_document_source,
+    schema::model::SchemaType,
+    value::ScalarValue,
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
+};
+
+use super::{GraphQLBatchRequest, GraphQLBatchResponse, GraphQLRequest, GraphQLResponse};
+
+/// Parts of an HTTP request needed to execute it as a GraphQL request.
+#[derive(Clone, Copy, Debug)]
+pub struct HttpRequest<'a> {
+    /// Method of the request, like `"GET"`.
+    pub method: &'a str,
+
+    /// Query string of the request URI, without the leading `?`.
+    pub query_string: Option<&'a str>,
+
+    /// Value of the `Content-Type` header, if any.
+    pub content_type: Option<&'a str>,
+
+    /// Value of the `Accept` header, if any.
+    pub accept: Option<&'a str>,
+
+    /// Body of the request.
+    pub body: &'a [u8],
+
+    /// Headers of the request, as name and value pairs, only used by the CSRF prevention of
+    /// [`HandlerConfig`].
+    pub headers: &'a [(&'a str, &'a str)],
+}
+
+/// Parts of the HTTP response to send back for an [`HttpRequest`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    /// Status code of the response, like `200`.
+    pub status: u16,
+
+    /// Headers of the response, like `("Content-Type", "application/json")`.
+    pub headers: Vec<(&'static str, &'static str)>,
+
+    /// Body of the response.
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Builds the response to send back for the given executed request.
+    pub fn from_graphql<S>(response: &GraphQLBatchResponse<'_, S>, media_type: MediaType) -> Self
+    where
+        S: ScalarValue,
+    {
+        let status = if response.is_ok() { 200 } else { 400 };
+        Self::json(status, media_type, response)
+    }
+
+    /// Builds an error response for a request that couldn't be executed, with a GraphQL
+    /// response body holding the `message`.
+    pub fn error(status: u16, media_type: MediaType, message: &str) -> Self {
+        #[derive(Serialize)]
+        struct Error<'m> {
+            message: &'m str,
+        }
+        #[derive(Serialize)]
+        struct Body<'m> {
+            errors: [Error<'m>; 1],
+        }
+
+        Self::json(
+            status,
+            media_type,
+            &Body {
+                errors: [Error { message }],
+            },
+        )
+    }
+
+    fn json<T: Serialize>(status: u16, media_type: MediaType, body: &T) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", media_type.as_str())],
+            body: serde_json::to_string(body).expect("GraphQL responses are serializable"),
+        }
+    }
+
+    fn with_header(mut self, name: &'static str, value: &'static str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// The value of the header with the given `name`, if any.
+    pub fn header(&self, name: &str) -> Option<&'static str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| *v)
+    }
+}
+
+/// Media type of the body of GraphQL responses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaType {
+    /// `application/graphql-response+json`, whose status code tells whether the request was
+    /// executed.
+    GraphQLResponseJson,
+
+    /// `application/json`, for clients predating `application/graphql-response+json`.
+    Json,
+}
+
+impl MediaType {
+    /// The media type itself.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::GraphQLResponseJson => "application/graphql-response+json",
+            Self::Json => "application/json",
+        }
+    }
+
+    /// Picks the media type preferred by the given `Accept` header, or `None` if it accepts
+    /// neither.
+    ///
+    /// A missing header means `application/json`, as do wildcards, since not all the clients
+    /// sending them understand `application/graphql-response+json`.
+    pub fn negotiate(accept: Option<&str>) -> Option<Self> {
+        let accept = match accept {
+            Some(a) if !a.trim().is_empty() => a,
+            _ => return Some(Self::Json),
+        };
+
+        let (mut graphql_response_json, mut json) = (None::<f32>, None::<f32>);
+        let mut explicit_graphql_response_json = false;
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media_range = params
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let quality = params
+                .filter_map(|p| {
+                    let (name, value) = p.split_once('=')?;
+                    (name.trim() == "q").then(|| value.trim().parse::<f32>().ok())?
+                })
+                .next()
+                .unwrap_or(1.0);
+            let max = |q: &mut Option<f32>| *q = Some(q.map_or(quality, |q| q.max(quality)));
+
+            match media_range.as_str() {
+                "application/graphql-response+json" => {
+                    explicit_graphql_response_json = true;
+                    max(&mut graphql_response_json);
+                }
+                "application/json" => max(&mut json),
+                "*/*" | "application/*" => {
+                    max(&mut graphql_response_json);
+                    max(&mut json);
+                }
+                _ => {}
+            }
+        }
+
+        match (
+            graphql_response_json.filter(|q| *q > 0.0),
+            json.filter(|q| *q > 0.0),
+        ) {
+            (None, None) => None,
+            (Some(_), None) => Some(Self::GraphQLResponseJson),
+            (None, Some(_)) => Some(Self::Json),
+            (Some(g), Some(j)) if g > j || (g == j && explicit_graphql_response_json) => {
+                Some(Self::GraphQLResponseJson)
+            }
+            (Some(_), Some(_)) => Some(Self::Json),
+        }
+    }
+}
+
+/// Headers [`HandlerConfig::with_csrf_prevention`] accepts as a proof of a CORS preflight, the
+/// same as Apollo Server does.
+pub const DEFAULT_CSRF_PREVENTION_HEADERS: &[&str] =
+    &["x-apollo-operation-name", "apollo-require-preflight"];
+
+/// Maximum size of request bodies in bytes [`HandlerConfig`] applies by default, 2 MiB.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Checks applied to HTTP requests before executing them.
+///
+/// By default, only request bodies larger than [`DEFAULT_MAX_BODY_SIZE`] are rejected, so the
+/// integrations don't read arbitrarily large bodies into memory.
+///
+/// ```
+/// # use juniper::http::handler::HandlerConfig;
+/// let config = HandlerConfig::new()
+///     .with_csrf_prevention()
+///     .with_max_body_size(1024 * 1024)
+///     .with_max_batch_len(10)
+///     .with_max_query_len(10_000);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandlerConfig {
+    csrf_prevention_headers: Option<Vec<String>>,
+    max_body_size: Option<usize>,
+    max_batch_len: Option<usize>,
+    max_query_len: Option<usize>,
+}
+
+impl Default for HandlerConfig {
+    fn default() -> Self {
+        Self {
+            csrf_prevention_headers: None,
+            max_body_size: Some(DEFAULT_MAX_BODY_SIZE),
+            max_batch_len: None,
+            max_query_len: None,
+        }
+    }
+}
+
+impl HandlerConfig {
+    /// Creates a configuration only limiting the size of request bodies to
+    /// [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects requests browsers may send from any website without a CORS preflight, with
+    /// `400 Bad Request`, as [Apollo Server][1] does.
+    ///
+    /// Requests are only executed if their `Content-Type` is neither `text/plain`,
+    /// `application/x-www-form-urlencoded` nor `multipart/form-data`, or if they have a non-empty
+    /// header among the [`DEFAULT_CSRF_PREVENTION_HEADERS`]. As `GET` requests have no
+    /// `Content-Type`, clients have to send one of those headers with them.
+    ///
+    /// [1]: https://www.apollographql.com/docs/apollo-server/security/cors/#preventing-cross-site-request-forgery-csrf
+    pub fn with_csrf_prevention(self) -> Self {
+        self.with_csrf_prevention_headers(DEFAULT_CSRF_PREVENTION_HEADERS)
+    }
+
+    /// Like [`HandlerConfig::with_csrf_prevention`], but accepts the given headers, instead of the
+    /// [`DEFAULT_CSRF_PREVENTION_HEADERS`], as a proof of a CORS preflight.
+    pub fn with_csrf_prevention_headers(mut self, headers: &[&str]) -> Self {
+        self.csrf_prevention_headers = Some(headers.iter().map(|h| (*h).to_owned()).collect());
+        self
+    }
+
+    /// Rejects requests whose body is larger than the given size in bytes, with
+    /// `413 Payload Too Large`, instead of [`DEFAULT_MAX_BODY_SIZE`].
+    pub fn with_max_body_size(mut self, size: usize) -> Self {
+        self.max_body_size = Some(size);
+        self
+    }
+
+    /// Accepts request bodies of any size, when they are already limited before reaching the
+    /// integration, like by a reverse proxy.
+    pub fn without_max_body_size(mut self) -> Self {
+        self.max_body_size = None;
+        self
+    }
+
+    /// Rejects batches of more than the given number of requests, with `400 Bad Request`.
+    pub fn with_max_batch_len(mut self, len: usize) -> Self {
+        self.max_batch_len = Some(len);
+        self
+    }
+
+    /// Rejects requests whose query is longer than the given length in bytes, with
+    /// `400 Bad Request`.
+    pub fn with_max_query_len(mut self, len: usize) -> Self {
+        self.max_query_len = Some(len);
+        self
+    }
+
+    /// The maximum size of request bodies in bytes, if any.
+    pub fn max_body_size(&self) -> Option<usize> {
+        self.max_body_size
+    }
+
+    /// Checks that a request body of the given size in bytes isn't larger than the maximum size,
+    /// so integrations can stop reading a body as soon as it is.
+    ///
+    /// # Errors
+    ///
+    /// The `413 Payload Too Large` response to send back if it is.
+    pub fn check_body_size(&self, size: usize) -> Result<(), HttpResponse> {
+        self.check_body_size_as(size, MediaType::Json)
+    }
+
+    fn check_body_size_as(&self, size: usize, media_type: MediaType) -> Result<(), HttpResponse> {
+        match self.max_body_size {
+            Some(max) if size > max => Err(HttpResponse::error(
+                413,
+                media_type,
+                &format!("The request body is larger than {} bytes", max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the request can't be sent by browsers without a CORS preflight, if the CSRF
+    /// prevention is enabled, for integrations which don't extract requests with
+    /// [`HttpRequest::parse_with_config`].
+    ///
+    /// # Errors
+    ///
+    /// The `400 Bad Request` response to send back if it can.
+    pub fn check_csrf(&self, request: &HttpRequest<'_>) -> Result<(), HttpResponse> {
+        self.csrf_error(request)
+            .map_err(|e| HttpResponse::error(400, MediaType::Json, &e))
+    }
+
+    /// Checks that the batch isn't longer than the maximum batch length, and that its queries
+    /// aren't longer than the maximum query length, for integrations which don't extract requests
+    /// with [`HttpRequest::parse_with_config`].
+    ///
+    /// # Errors
+    ///
+    /// The `400 Bad Request` response to send back if any is.
+    pub fn check_lengths<S>(&self, request: &GraphQLBatchRequest<S>) -> Result<(), HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        self.lengths_error(request)
+            .map_err(|e| HttpResponse::error(400, MediaType::Json, &e))
+    }
+
+    fn csrf_error(&self, request: &HttpRequest<'_>) -> Result<(), String> {
+        let headers = match &self.csrf_prevention_headers {
+            Some(headers) => headers,
+            None => return Ok(()),
+        };
+
+        let essence = request
+            .content_type
+            .and_then(|t| t.split(';').next())
+            .map(|t| t.trim().to_ascii_lowercase())
+            .filter(|t| !t.is_empty());
+        let has_non_simple_content_type = !matches!(
+            essence.as_deref(),
+            None | Some("text/plain")
+                | Some("application/x-www-form-urlencoded")
+                | Some("multipart/form-data")
+        );
+        let has_preflight_header = request.headers.iter().any(|(name, value)| {
+            !value.trim().is_empty() && headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+        });
+        if has_non_simple_content_type || has_preflight_header {
+            return Ok(());
+        }
+
+        Err(format!(
+            "This request has been blocked as a potential Cross-Site Request Forgery (CSRF): \
+             it must either have a `Content-Type` other than `text/plain`, \
+             `application/x-www-form-urlencoded` or `multipart/form-data`, or a non-empty value \
+             for one of the following headers: {}",
+            headers.join(", "),
+        ))
+    }
+
+    fn lengths_error<S>(&self, request: &GraphQLBatchRequest<S>) -> Result<(), String>
+    where
+        S: ScalarValue,
+    {
+        if let Some(max) = self.max_batch_len {
+            if request.len() > max {
+                return Err(format!("Batches can't hold more than {} requests", max));
+            }
+        }
+        if let Some(max) = self.max_query_len {
+            let too_long = match request {
+                GraphQLBatchRequest::Single(req) => req.query.len() > max,
+                GraphQLBatchRequest::Batch(reqs) => reqs.iter().any(|r| r.query.len() > max),
+            };
+            if too_long {
+                return Err(format!("Queries can't be longer than {} bytes", max));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> HttpRequest<'a> {
+    /// Extracts the GraphQL request from this HTTP request, along with the media type to
+    /// respond with.
+    ///
+    /// # Errors
+    ///
+    /// The response to send back if this HTTP request can't be executed.
+    pub fn parse<S>(
+        &self,
+        schema: &SchemaType<S>,
+    ) -> Result<(GraphQLBatchRequest<S>, MediaType), HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        self.parse_with_config(schema, &HandlerConfig::default())
+    }
+
+    /// Like [`HttpRequest::parse`], but also applies the checks of the given [`HandlerConfig`].
+    ///
+    /// # Errors
+    ///
+    /// The response to send back if this HTTP request can't be executed.
+    pub fn parse_with_config<S>(
+        &self,
+        schema: &SchemaType<S>,
+        config: &HandlerConfig,
+    ) -> Result<(GraphQLBatchRequest<S>, MediaType), HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        let (request, media_type) = self.parse_unchecked_with_config(config)?;
+        if self.method.eq_ignore_ascii_case("GET") && is_mutation_request(&request, schema) {
+            return Err(mutation_over_get(media_type));
+        }
+        Ok((request, media_type))
+    }
+
+    /// Like [`HttpRequest::parse`], but without rejecting mutations sent with `GET` requests, as
+    /// telling them apart requires the schema.
+    ///
+    /// Callers are responsible for not executing mutations extracted from `GET` requests.
+    ///
+    /// # Errors
+    ///
+    /// The response to send back if this HTTP request can't be executed.
+    pub fn parse_unchecked<S>(&self) -> Result<(GraphQLBatchRequest<S>, MediaType), HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        self.parse_unchecked_with_config(&HandlerConfig::default())
+    }
+
+    /// Like [`HttpRequest::parse_unchecked`], but also applies the checks of the given
+    /// [`HandlerConfig`].
+    ///
+    /// # Errors
+    ///
+    /// The response to send back if this HTTP request can't be executed.
+    pub fn parse_unchecked_with_config<S>(
+        &self,
+        config: &HandlerConfig,
+    ) -> Result<(GraphQLBatchRequest<S>, MediaType), HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        let is_get = self.method.eq_ignore_ascii_case("GET");
+        if !is_get && !self.method.eq_ignore_ascii_case("POST") {
+            return Err(HttpResponse::error(
+                405,
+                MediaType::Json,
+                "Only GET and POST requests are allowed",
+            )
+            .with_header("Allow", "GET, POST"));
+        }
+
+        let media_type = MediaType::negotiate(self.accept).ok_or_else(|| {
+            HttpResponse::error(
+                406,
+                MediaType::Json,
+                "Responses are only available as `application/graphql-response+json` or \
+                 `application/json`",
+            )
+        })?;
+        let bad_request = |message: &str| HttpResponse::error(400, media_type, message);
+
+        config.csrf_error(self).map_err(|e| bad_request(&e))?;
+        config.check_body_size_as(self.body.len(), media_type)?;
+
+        let request = if is_get {
+            parse_query_string(self.query_string.unwrap_or_default())
+                .map(GraphQLBatchRequest::Single)
+                .map_err(|e| bad_request(&e))?
+        } else {
+            self.parse_body(media_type)?
+        };
+        config
+            .lengths_error(&request)
+            .map_err(|e| bad_request(&e))?;
+        Ok((request, media_type))
+    }
+
+    fn parse_body<S>(&self, media_type: MediaType) -> Result<GraphQLBatchRequest<S>, HttpResponse>
+    where
+        S: ScalarValue,
+    {
+        let bad_request = |message: &str| HttpResponse::error(400, media_type, message);
+
+        let essence = self
+            .content_type
+            .and_then(|t| t.split(';').next())
+            .map(|t| t.trim().to_ascii_lowercase());
+        let request = match essence.as_deref() {
+            Some("application/json") => serde_json::from_slice(self.body)
+                .map_err(|e| bad_request(&format!("Invalid JSON request: {}", e)))?,
+            Some("application/graphql") => {
+                let query = String::from_utf8(self.body.to_vec())
+                    .map_err(|_| bad_request("The query isn't valid UTF-8"))?;
+                GraphQLBatchRequest::Single(GraphQLRequest::new(query, None, None))
+            }
+            Some(_) | None => {
+                return Err(HttpResponse::error(
+                    415,
+                    media_type,
+                    "Requests must be `application/json` or `application/graphql`",
+                ))
+            }
+        };
+        Ok(request)
+    }
+}
+
+/// Executes the GraphQL request in the given HTTP request, and returns the HTTP response to send
+/// back.
+pub async fn handle<'a, QueryT, MutationT, SubscriptionT, S>(
+    request: HttpRequest<'_>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    context: &'a QueryT::Context,
+) -> HttpResponse
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    handle_with_config(request, root_node, context, &HandlerConfig::default()).await
+}
+
+/// Like [`handle`], but also applies the checks of the given [`HandlerConfig`].
+pub async fn handle_with_config<'a, QueryT, MutationT, SubscriptionT, S>(
+    request: HttpRequest<'_>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    context: &'a QueryT::Context,
+    config: &HandlerConfig,
+) -> HttpResponse
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let is_get = request.method.eq_ignore_ascii_case("GET");
+    let (request, media_type) = match request.parse_unchecked_with_config(config) {
+        Ok(parsed) => parsed,
+        Err(response) => return response,
+    };
+    let response = match &request {
+        // The document of a `GET` request is parsed once, to both reject mutations and execute it.
+        GraphQLBatchRequest::Single(request) if is_get => {
+            let operation_name = request.operation_name.as_deref();
+            let result = match parse_document_source(&request.query, &root_node.schema) {
+                Ok(document) if is_mutation(&document, operation_name) => {
+                    return mutation_over_get(media_type)
+                }
+                Ok(document) => {
+                    let variables = request.variables();
+                    crate::execute_document(
+                        &document,
+                        operation_name,
+                        root_node,
+                        &variables,
+                        context,
+                    )
+                    .await
+                }
+                Err(e) => Err(e.into()),
+            };
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(result))
+        }
+        _ => request.execute(root_node, context).await,
+    };
+    HttpResponse::from_graphql(&response, media_type)
+}
+
+/// Executes synchronously the GraphQL request in the given HTTP request, and returns the HTTP
+/// response to send back.
+pub fn handle_sync<QueryT, MutationT, SubscriptionT, S>(
+    request: HttpRequest<'_>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    context: &QueryT::Context,
+) -> HttpResponse
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    handle_sync_with_config(request, root_node, context, &HandlerConfig::default())
+}
+
+/// Like [`handle_sync`], but also applies the checks of the given [`HandlerConfig`].
+pub fn handle_sync_with_config<QueryT, MutationT, SubscriptionT, S>(
+    request: HttpRequest<'_>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    context: &QueryT::Context,
+    config: &HandlerConfig,
+) -> HttpResponse
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    S: ScalarValue,
+{
+    let is_get = request.method.eq_ignore_ascii_case("GET");
+    let (request, media_type) = match request.parse_unchecked_with_config(config) {
+        Ok(parsed) => parsed,
+        Err(response) => return response,
+    };
+    let response = match &request {
+        // The document of a `GET` request is parsed once, to both reject mutations and execute it.
+        GraphQLBatchRequest::Single(request) if is_get => {
+            let operation_name = request.operation_name.as_deref();
+            let result = match parse_document_source(&request.query, &root_node.schema) {
+                Ok(document) if is_mutation(&document, operation_name) => {
+                    return mutation_over_get(media_type)
+                }
+                Ok(document) => crate::execute_document_sync(
+                    &document,
+                    operation_name,
+                    root_node,
+                    &request.variables(),
+                    context,
+                ),
+                Err(e) => Err(e.into()),
+            };
+            GraphQLBatchResponse::Single(GraphQLResponse::from_result(result))
+        }
+        _ => request.execute_sync(root_node, context),
+    };
+    HttpResponse::from_graphql(&response, media_type)
+}
+
+fn parse_query_string<S>(query_string: &str) -> Result<GraphQLRequest<S>, String>
+where
+    S: ScalarValue,
+{
+    let (mut query, mut operation_name, mut variables) = (None, None, None);
+    for pair in query_string.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (key, value) = (decode_component(key)?, decode_component(value)?);
+        let slot = match key.as_str() {
+            "query" => &mut query,
+            "operationName" => &mut operation_name,
+            "variables" => &mut variables,
+            _ => continue,
+        };
+        if slot.replace(value).is_some() {
+            return Err(format!("`{}` parameter is specified multiple times", key));
+        }
+    }
+
+    let query = query.ok_or("`query` parameter is missing")?;
+    let variables = variables
+        .map(|v| serde_json::from_str::<InputValue<S>>(&v))
+        .transpose()
+        .map_err(|e| format!("Invalid `variables` parameter: {}", e))?;
+    Ok(GraphQLRequest::new(query, operation_name, variables))
+}
+
+/// Decodes an `application/x-www-form-urlencoded` component.
+fn decode_component(component: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(component.len());
+    let mut rest = component.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest
+                    .get(..2)
+                    .and_then(|h| std::str::from_utf8(h).ok())
+                    .and_then(|h| u8::from_str_radix(h, 16).ok());
+                match hex {
+                    Some(decoded) => {
+                        bytes.push(decoded);
+                        rest = &rest[2..];
+                    }
+                    None => bytes.push(b'%'),
+                }
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| "The query string isn't valid UTF-8".to_owned())
+}
+
+/// Checks that the GraphQL request extracted from a `GET` request isn't a mutation, for
+/// integrations which don't extract requests with [`HttpRequest::parse`].
+///
+/// # Errors
+///
+/// The `405 Method Not Allowed` response to send back if it is.
+pub fn check_get_request<S>(
+    request: &GraphQLBatchRequest<S>,
+    schema: &SchemaType<S>,
+) -> Result<(), HttpResponse>
+where
+    S: ScalarValue,
+{
+    if is_mutation_request(request, schema) {
+        return Err(mutation_over_get(MediaType::Json));
+    }
+    Ok(())
+}
+
+/// Whether the `request` is a single mutation, as batches can't be sent with `GET` requests.
+fn is_mutation_request<S>(request: &GraphQLBatchRequest<S>, schema: &SchemaType<S>) -> bool
+where
+    S: ScalarValue,
+{
+    match request {
+        // Documents failing to parse aren't mutations, so they're reported like for any other
+        // request.
+        GraphQLBatchRequest::Single(request) => parse_document_source(&request.query, schema)
+            .map_or(false, |document| {
+                is_mutation(&document, request.operation_name.as_deref())
+            }),
+        GraphQLBatchRequest::Batch(_) => false,
+    }
+}
+
+/// The `405 Method Not Allowed` response to a mutation sent with a `GET` request.
+fn mutation_over_get(media_type: MediaType) -> HttpResponse {
+    HttpResponse::error(
+        405,
+        media_type,
+        "Mutations can only be executed with POST requests",
+    )
+    .with_header("Allow", "POST")
+}
+
+/// Whether the executed operation of the `document` is a mutation.
+fn is_mutation<S>(document: &Document<S>, operation_name: Option<&str>) -> bool
+where
+    S: ScalarValue,
+{
+    match get_operation(document, operation_name) {
+        Ok(operation) => operation.item.operation_type == OperationType::Mutation,
+        // Unknown or ambiguous operation names are reported on execution, but none of the
+        // operations may be a mutation either way.
+        Err(_) => document.iter().any(|d| {
+            matches!(d, Definition::Operation(op) if op.item.operation_type == OperationType::Mutation)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+
+    use super::{
+        check_get_request, handle_sync, handle_sync_with_config, GraphQLBatchRequest,
+        GraphQLRequest, HandlerConfig, HttpRequest, HttpResponse, MediaType, DEFAULT_MAX_BODY_SIZE,
+    };
+
+    struct Mutation;
+
+    #[crate::graphql_object(context = Database)]
+    impl Mutation {
+        fn forget() -> bool {
+            true
+        }
+    }
+
+    fn request(method: &'static str) -> HttpRequest<'static> {
+        HttpRequest {
+            method,
+            query_string: None,
+            content_type: None,
+            accept: None,
+            body: b"",
+            headers: &[],
+        }
+    }
+
+    fn send(request: HttpRequest<'_>) -> HttpResponse {
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::<Database>::new());
+        handle_sync(request, &schema, &Database::new())
+    }
+
+    fn send_with_config(request: HttpRequest<'_>, config: &HandlerConfig) -> HttpResponse {
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::<Database>::new());
+        handle_sync_with_config(request, &schema, &Database::new(), config)
+    }
+
+    fn get(query_string: &'static str) -> HttpResponse {
+        send(HttpRequest {
+            query_string: Some(query_string),
+            ..request("GET")
+        })
+    }
+
+    fn post(content_type: &'static str, body: &'static str) -> HttpResponse {
+        send(HttpRequest {
+            content_type: Some(content_type),
+            body: body.as_bytes(),
+            ..request("POST")
+        })
+    }
+
+    #[test]
+    fn negotiates_media_type() {
+        let negotiate = MediaType::negotiate;
+
+        assert_eq!(negotiate(None), Some(MediaType::Json));
+        assert_eq!(negotiate(Some("*/*")), Some(MediaType::Json));
+        assert_eq!(
+            negotiate(Some("application/graphql-response+json")),
+            Some(MediaType::GraphQLResponseJson),
+        );
+        assert_eq!(
+            negotiate(Some(
+                "application/json;q=0.9, application/graphql-response+json"
+            )),
+            Some(MediaType::GraphQLResponseJson),
+        );
+        assert_eq!(
+            negotiate(Some(
+                "application/graphql-response+json;q=0.5, application/json"
+            )),
+            Some(MediaType::Json),
+        );
+        assert_eq!(
+            negotiate(Some("application/graphql-response+json, */*;q=0.1")),
+            Some(MediaType::GraphQLResponseJson),
+        );
+        assert_eq!(negotiate(Some("text/html")), None);
+        assert_eq!(negotiate(Some("application/json;q=0")), None);
+    }
+
+    #[test]
+    fn executes_get_requests() {
+        let resp = get("query=%7Bhero%7Bname%7D%7D");
+
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.header("content-type"), Some("application/json"));
+        assert_eq!(resp.body, r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+    }
+
+    #[test]
+    fn reads_operation_name_and_variables_from_query_string() {
+        let resp = get(
+            "query=query+A%7Bhero%7Bid%7D%7D+query+B(%24id%3AString!)%7Bhuman(id%3A%24id)%7Bname%7D%7D\
+             &operationName=B&variables=%7B%22id%22%3A%221000%22%7D",
+        );
+
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.body, r#"{"data":{"human":{"name":"Luke Skywalker"}}}"#);
+    }
+
+    #[test]
+    fn rejects_mutations_over_get() {
+        let resp = get("query=mutation%7Bforget%7D");
+
+        assert_eq!(resp.status, 405);
+        assert_eq!(resp.header("Allow"), Some("POST"));
+
+        let resp = post("application/json", r#"{"query": "mutation { forget }"}"#);
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.body, r#"{"data":{"forget":true}}"#);
+    }
+
+    #[test]
+    fn checks_extracted_get_requests() {
+        let schema = RootNode::new(Query, Mutation, EmptySubscription::<Database>::new());
+        let request = |query: &str| {
+            GraphQLBatchRequest::Single(GraphQLRequest::new(query.into(), None, None))
+        };
+
+        assert!(check_get_request(&request("{ hero { name } }"), &schema.schema).is_ok());
+        assert!(check_get_request(&request("{ hero {"), &schema.schema).is_ok());
+
+        let resp = check_get_request(&request("mutation { forget }"), &schema.schema).unwrap_err();
+        assert_eq!(resp.status, 405);
+        assert_eq!(resp.header("Allow"), Some("POST"));
+    }
+
+    #[test]
+    fn rejects_other_methods() {
+        let resp = send(request("PUT"));
+
+        assert_eq!(resp.status, 405);
+        assert_eq!(resp.header("Allow"), Some("GET, POST"));
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        for resp in [
+            get(""),
+            get("query=%7Bhero%7Bname%7D%7D&query=%7Bhero%7Bname%7D%7D"),
+            get("query=%7Bhero%7Bname%7D%7D&variables=blah"),
+            post("application/json", "blah"),
+            post("application/json", "[]"),
+        ] {
+            assert_eq!(resp.status, 400, "{}", resp.body);
+            assert!(resp.body.starts_with(r#"{"errors":[{"message":"#));
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_content_types() {
+        assert_eq!(post("text/plain", "{ hero { name } }").status, 415);
+        assert_eq!(
+            post("application/graphql; charset=utf-8", "{ hero { name } }").status,
+            200,
+        );
+
+        let resp = send(HttpRequest {
+            body: br#"{"query": "{ hero { name } }"}"#,
+            ..request("POST")
+        });
+        assert_eq!(resp.status, 415);
+    }
+
+    #[test]
+    fn rejects_unacceptable_requests() {
+        let resp = send(HttpRequest {
+            query_string: Some("query=%7Bhero%7Bname%7D%7D"),
+            accept: Some("text/html"),
+            ..request("GET")
+        });
+
+        assert_eq!(resp.status, 406);
+    }
+
+    #[test]
+    fn responds_with_negotiated_media_type() {
+        let send_accepting = |accept, query_string| {
+            send(HttpRequest {
+                query_string: Some(query_string),
+                accept: Some(accept),
+                ..request("GET")
+            })
+        };
+
+        let resp = send_accepting(
+            "application/graphql-response+json",
+            "query=%7Bhero%7Bname%7D%7D",
+        );
+        assert_eq!(resp.status, 200);
+        assert_eq!(
+            resp.header("Content-Type"),
+            Some("application/graphql-response+json"),
+        );
+
+        let resp = send_accepting(
+            "application/graphql-response+json",
+            "query=%7Bhero%7Bblah%7D%7D",
+        );
+        assert_eq!(resp.status, 400);
+        assert_eq!(
+            resp.header("Content-Type"),
+            Some("application/graphql-response+json"),
+        );
+    }
+
+    #[test]
+    fn executes_batches() {
+        let resp = post(
+            "application/json",
+            r#"[{"query": "{ hero { name } }"}, {"query": "{ hero { blah } }"}]"#,
+        );
+
+        assert_eq!(resp.status, 400);
+        assert!(resp
+            .body
+            .starts_with(r#"[{"data":{"hero":{"name":"R2-D2"}}},"#));
+    }
+
+    #[test]
+    fn ignores_unknown_parameters() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let resp = handle_sync(
+            HttpRequest {
+                query_string: Some("query=%7Bhero%7Bname%7D%7D&extensions=%7B%7D"),
+                ..request("GET")
+            },
+            &schema,
+            &Database::new(),
+        );
+
+        assert_eq!(resp.status, 200);
+    }
+
+    #[test]
+    fn prevents_csrf() {
+        let config = HandlerConfig::new().with_csrf_prevention();
+        let get = |headers| HttpRequest {
+            query_string: Some("query=%7Bhero%7Bname%7D%7D"),
+            headers,
+            ..request("GET")
+        };
+        let post = |content_type| HttpRequest {
+            content_type: Some(content_type),
+            body: br#"{"query": "{ hero { name } }"}"#,
+            ..request("POST")
+        };
+
+        for req in [
+            get(&[]),
+            get(&[("X-Apollo-Operation-Name", "")]),
+            get(&[("X-Requested-With", "XMLHttpRequest")]),
+            post("text/plain"),
+            post("application/x-www-form-urlencoded"),
+            post("multipart/form-data; boundary=blah"),
+        ] {
+            let resp = send_with_config(req, &config);
+            assert_eq!(resp.status, 400, "{:?}", req);
+            assert!(resp.body.contains("Cross-Site Request Forgery"));
+        }
+
+        for req in [
+            get(&[("X-Apollo-Operation-Name", "Hero")]),
+            get(&[("apollo-require-preflight", "true")]),
+            post("application/json"),
+        ] {
+            assert_eq!(send_with_config(req, &config).status, 200, "{:?}", req);
+        }
+
+        let config = HandlerConfig::new().with_csrf_prevention_headers(&["x-requested-with"]);
+        assert_eq!(
+            send_with_config(get(&[("X-Requested-With", "XMLHttpRequest")]), &config).status,
+            200,
+        );
+        assert_eq!(
+            send_with_config(get(&[("Apollo-Require-Preflight", "true")]), &config).status,
+            400,
+        );
+        assert_eq!(send(get(&[])).status, 200);
+    }
+
+    #[test]
+    fn limits_body_size() {
+        let config = HandlerConfig::new().with_max_body_size(30);
+        let post = |body: &'static str| HttpRequest {
+            content_type: Some("application/json"),
+            body: body.as_bytes(),
+            ..request("POST")
+        };
+
+        assert_eq!(
+            send_with_config(post(r#"{"query": "{ hero { name } }"}"#), &config).status,
+            200,
+        );
+        let resp = send_with_config(post(r#"{"query": "{ hero { id name } }"}"#), &config);
+        assert_eq!(resp.status, 413);
+        assert!(resp.body.contains("larger than 30 bytes"));
+        assert_eq!(config.check_body_size(30), Ok(()));
+        assert_eq!(config.check_body_size(31).unwrap_err().status, 413);
+    }
+
+    #[test]
+    fn limits_body_size_by_default() {
+        let config = HandlerConfig::default();
+
+        assert_eq!(config.max_body_size(), Some(DEFAULT_MAX_BODY_SIZE));
+        assert_eq!(config.check_body_size(DEFAULT_MAX_BODY_SIZE), Ok(()));
+        assert_eq!(
+            config
+                .check_body_size(DEFAULT_MAX_BODY_SIZE + 1)
+                .unwrap_err()
+                .status,
+            413,
+        );
+
+        let config = config.without_max_body_size();
+        assert_eq!(config.max_body_size(), None);
+        assert_eq!(config.check_body_size(usize::MAX), Ok(()));
+    }
+
+    #[test]
+    fn limits_batch_and_query_lengths() {
+        let config = HandlerConfig::new()
+            .with_max_batch_len(2)
+            .with_max_query_len(16);
+        let post = |body: &'static str| HttpRequest {
+            content_type: Some("application/json"),
+            body: body.as_bytes(),
+            ..request("POST")
+        };
+
+        let resp = send_with_config(
+            post(r#"[{"query": "{ hero { id } }"}, {"query": "{ hero { id } }"}]"#),
+            &config,
+        );
+        assert_eq!(resp.status, 200);
+
+        let resp = send_with_config(
+            post(
+                r#"[{"query": "{ hero { id } }"}, {"query": "{ hero { id } }"},
+                    {"query": "{ hero { id } }"}]"#,
+            ),
+            &config,
+        );
+        assert_eq!(resp.status, 400);
+        assert!(resp.body.contains("more than 2 requests"));
+
+        let resp = send_with_config(post(r#"{"query": "{ hero { name } }"}"#), &config);
+        assert_eq!(resp.status, 400);
+        assert!(resp.body.contains("longer than 16 bytes"));
+
+        let resp = send_with_config(
+            HttpRequest {
+                query_string: Some("query=%7B+hero+%7B+name+%7D+%7D"),
+                ..request("GET")
+            },
+            &config,
+        );
+        assert_eq!(resp.status, 400);
+    }
+}
@@ -1,8 +1,13 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+pub mod assets;
 pub mod graphiql;
+#[cfg(feature = "serde_json")]
+pub mod handler;
 pub mod playground;
 
+use std::time::Instant;
+
 use serde::{
     de,
     ser::{self, SerializeMap},
@@ -11,8 +16,9 @@ use serde::{
 
 use crate::{
     ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
-    value::{DefaultScalarValue, ScalarValue},
+    cost::{CostReport, RateLimitClient, RateLimiter},
+    executor::{ExecutionError, TraceFormat, ValuesStream},
+    value::{DefaultScalarValue, Object, ScalarValue},
     FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     Value, Variables,
 };
@@ -94,7 +100,7 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
+        GraphQLResponse::from_result(crate::execute_sync(
             &self.query,
             self.operation_name.as_deref(),
             root_node,
@@ -112,6 +118,30 @@ where
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        self.execute_with_deadline(root_node, context, None).await
+    }
+
+    /// Execute a GraphQL request using the specified schema and context, which has to be
+    /// resolved before the provided `deadline`
+    ///
+    /// This is a simple wrapper around the `execute_with_deadline` function exposed at the
+    /// top level of this crate.
+    pub async fn execute_with_deadline<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        deadline: Option<Instant>,
+    ) -> GraphQLResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -124,8 +154,71 @@ where
     {
         let op = self.operation_name.as_deref();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let res =
+            crate::execute_with_deadline(&self.query, op, root_node, vars, context, deadline).await;
+        GraphQLResponse::from_result(res)
+    }
+
+    /// Execute a GraphQL request using the specified schema and context, reporting the timings
+    /// of its execution in the `extensions` of the response
+    ///
+    /// The trace is put under the [`TraceFormat::extension_name`] key in the provided `format`.
+    /// See the `execute_traced` function exposed at the top level of this crate for details.
+    pub async fn execute_traced<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        format: TraceFormat,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        let op = self.operation_name.as_deref();
+        let vars = &self.variables();
+        let (res, trace) =
+            crate::execute_traced(&self.query, op, root_node, vars, context, None).await;
+        let extension = trace.to_extension(format);
+        GraphQLResponse::from_result(res).with_extension(format.extension_name(), extension)
+    }
+
+    /// Execute a GraphQL request using the specified schema and context, charging its cost to
+    /// the client of the `context` in the provided [`RateLimiter`]
+    ///
+    /// The cost is reported under the [`CostReport::EXTENSION_NAME`] key in the `extensions` of
+    /// the response. See the `execute_rate_limited` function exposed at the top level of this
+    /// crate for details.
+    pub async fn execute_rate_limited<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        limiter: &'a RateLimiter,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: RateLimitClient + Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        let op = self.operation_name.as_deref();
+        let vars = &self.variables();
+        let (res, report) =
+            crate::execute_rate_limited(&self.query, op, root_node, vars, context, limiter).await;
+        let resp = GraphQLResponse::from_result(res);
+        match report {
+            Some(report) => resp.with_extension(CostReport::EXTENSION_NAME, report.to_extension()),
+            None => resp,
+        }
     }
 }
 
@@ -165,6 +258,7 @@ where
 #[derive(Debug)]
 pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
     Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    Option<Object<S>>,
 );
 
 impl<'a, S> GraphQLResponse<'a, S>
@@ -173,12 +267,26 @@ where
 {
     /// Constructs new `GraphQLResponse` using the given result
     pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+        Self(r, None)
     }
 
     /// Constructs an error response outside of the normal execution flow
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        Self::from_result(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+    }
+
+    /// Adds an entry to the `extensions` of this response, replacing the one with the same
+    /// `name`, if any
+    pub fn with_extension(mut self, name: &str, value: Value<S>) -> Self {
+        self.1
+            .get_or_insert_with(|| Object::with_capacity(1))
+            .add_field(name, value);
+        self
+    }
+
+    /// The `extensions` of this response, if any were added
+    pub fn extensions(&self) -> Option<&Object<S>> {
+        self.1.as_ref()
     }
 
     /// Was the request successful or not?
@@ -213,12 +321,23 @@ where
                     map.serialize_value(err)?;
                 }
 
+                if let Some(ref ext) = self.1 {
+                    map.serialize_key("extensions")?;
+                    map.serialize_value(ext)?;
+                }
+
                 map.end()
             }
             Err(ref err) => {
-                let mut map = serializer.serialize_map(Some(1))?;
+                let mut map = serializer.serialize_map(None)?;
                 map.serialize_key("errors")?;
                 map.serialize_value(err)?;
+
+                if let Some(ref ext) = self.1 {
+                    map.serialize_key("extensions")?;
+                    map.serialize_value(ext)?;
+                }
+
                 map.end()
             }
         }
@@ -305,15 +424,116 @@ where
         SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
         SubscriptionT::TypeInfo: Sync,
         S: Send + Sync,
+    {
+        self.execute_with_deadline(root_node, context, None).await
+    }
+
+    /// Executes a GraphQL request using the specified schema and context, which has to be
+    /// resolved before the provided `deadline`
+    ///
+    /// This is a simple wrapper around the `execute_with_deadline` function exposed in
+    /// GraphQLRequest. The `deadline` is shared by all the operations of a batch.
+    pub async fn execute_with_deadline<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        deadline: Option<Instant>,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        match self {
+            Self::Single(req) => {
+                let resp = req
+                    .execute_with_deadline(root_node, context, deadline)
+                    .await;
+                GraphQLBatchResponse::Single(resp)
+            }
+            Self::Batch(reqs) => {
+                let resps = futures::future::join_all(
+                    reqs.iter()
+                        .map(|req| req.execute_with_deadline(root_node, context, deadline)),
+                )
+                .await;
+                GraphQLBatchResponse::Batch(resps)
+            }
+        }
+    }
+
+    /// Executes a GraphQL request using the specified schema and context, reporting the timings
+    /// of each operation in the `extensions` of its response
+    ///
+    /// This is a simple wrapper around the `execute_traced` function exposed in
+    /// GraphQLRequest.
+    pub async fn execute_traced<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        format: TraceFormat,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        match self {
+            Self::Single(req) => {
+                let resp = req.execute_traced(root_node, context, format).await;
+                GraphQLBatchResponse::Single(resp)
+            }
+            Self::Batch(reqs) => {
+                let resps = futures::future::join_all(
+                    reqs.iter()
+                        .map(|req| req.execute_traced(root_node, context, format)),
+                )
+                .await;
+                GraphQLBatchResponse::Batch(resps)
+            }
+        }
+    }
+
+    /// Executes a GraphQL request using the specified schema and context, charging the cost of
+    /// each operation to the client of the `context` in the provided [`RateLimiter`]
+    ///
+    /// This is a simple wrapper around the `execute_rate_limited` function exposed in
+    /// GraphQLRequest.
+    pub async fn execute_rate_limited<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        limiter: &'a RateLimiter,
+    ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: RateLimitClient + Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
     {
         match self {
             Self::Single(req) => {
-                let resp = req.execute(root_node, context).await;
+                let resp = req.execute_rate_limited(root_node, context, limiter).await;
                 GraphQLBatchResponse::Single(resp)
             }
             Self::Batch(reqs) => {
                 let resps = futures::future::join_all(
-                    reqs.iter().map(|req| req.execute(root_node, context)),
+                    reqs.iter()
+                        .map(|req| req.execute_rate_limited(root_node, context, limiter)),
                 )
                 .await;
                 GraphQLBatchResponse::Batch(resps)
@@ -328,6 +548,19 @@ where
             Self::Batch(reqs) => reqs.iter().map(|r| r.operation_name.as_deref()).collect(),
         }
     }
+
+    /// The number of requests in this batch, `1` for a single request.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Batch(reqs) => reqs.len(),
+        }
+    }
+
+    /// Whether this is an empty batch, holding no request at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// Simple wrapper around the result (GraphQLResponse) from executing a GraphQLBatchRequest
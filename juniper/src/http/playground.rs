@@ -1,16 +1,99 @@
 //! Utility module to generate a GraphQL Playground interface
 
+use super::{
+    assets,
+    graphiql::{asset_url, html_escape, js_string},
+};
+
+/// Configuration of a GraphQL Playground interface, rendered by [`playground_source_with_config`].
+///
+/// ```
+/// # use juniper::http::playground::{playground_source_with_config, PlaygroundConfig};
+/// let config = PlaygroundConfig::new("/graphql")
+///     .with_subscriptions_endpoint_url("/subscriptions")
+///     .with_settings(r#"{ "request.credentials": "same-origin", "editor.theme": "light" }"#)
+///     .with_title("Star Wars API");
+///
+/// let playground = playground_source_with_config(&config);
+/// assert!(playground.contains("<title>Star Wars API</title>"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlaygroundConfig {
+    graphql_endpoint_url: String,
+    subscriptions_endpoint_url: Option<String>,
+    assets_base_url: Option<String>,
+    settings: Option<String>,
+    title: Option<String>,
+}
+
+impl PlaygroundConfig {
+    /// Creates the configuration of a GraphQL Playground interface sending its requests to the
+    /// given URL.
+    pub fn new(graphql_endpoint_url: &str) -> Self {
+        Self {
+            graphql_endpoint_url: graphql_endpoint_url.to_owned(),
+            subscriptions_endpoint_url: None,
+            assets_base_url: None,
+            settings: None,
+            title: None,
+        }
+    }
+
+    /// Specifies the URL to send subscriptions to, over WebSocket connections. Defaults to the
+    /// GraphQL endpoint URL.
+    pub fn with_subscriptions_endpoint_url(mut self, url: &str) -> Self {
+        self.subscriptions_endpoint_url = Some(url.to_owned());
+        self
+    }
+
+    /// Specifies the base URL to load the [`assets`] from, instead of public CDNs.
+    pub fn with_assets_base_url(mut self, url: &str) -> Self {
+        self.assets_base_url = Some(url.to_owned());
+        self
+    }
+
+    /// Specifies the [settings][1] of the interface, as a JSON object like
+    /// `{ "request.credentials": "include" }`.
+    ///
+    /// [1]: https://github.com/graphql/graphql-playground#settings
+    pub fn with_settings(mut self, settings: &str) -> Self {
+        self.settings = Some(settings.to_owned());
+        self
+    }
+
+    /// Specifies the title of the page.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_owned());
+        self
+    }
+}
+
 /// Generate the HTML source to show a GraphQL Playground interface
-// source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
 pub fn playground_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
 ) -> String {
-    let subscriptions_endpoint = if let Some(sub_url) = subscriptions_endpoint_url {
-        sub_url
-    } else {
-        graphql_endpoint_url
-    };
+    let mut config = PlaygroundConfig::new(graphql_endpoint_url);
+    if let Some(url) = subscriptions_endpoint_url {
+        config = config.with_subscriptions_endpoint_url(url);
+    }
+    playground_source_with_config(&config)
+}
+
+/// Generate the HTML source to show a GraphQL Playground interface with the given configuration.
+// source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
+pub fn playground_source_with_config(config: &PlaygroundConfig) -> String {
+    let subscriptions_endpoint = config
+        .subscriptions_endpoint_url
+        .as_deref()
+        .unwrap_or(&config.graphql_endpoint_url);
+    let assets_base_url = config.assets_base_url.as_deref();
+    let settings = config
+        .settings
+        .as_ref()
+        .map(|settings| format!(", settings: {}", settings.replace("</", "<\\/")))
+        .unwrap_or_default();
+    let title = html_escape(config.title.as_deref().unwrap_or("GraphQL Playground"));
 
     r##"
 <!DOCTYPE html>
@@ -20,10 +103,10 @@ pub fn playground_source(
 <head>
   <meta charset=utf-8 />
   <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
-  <title>GraphQL Playground</title>
-  <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/css/index.css" />
-  <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/favicon.png" />
-  <script src="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js"></script>
+  <title>JUNIPER_PLAYGROUND_TITLE</title>
+  <link rel="stylesheet" href="JUNIPER_PLAYGROUND_CSS" />
+  <link rel="shortcut icon" href="JUNIPER_PLAYGROUND_FAVICON" />
+  <script src="JUNIPER_PLAYGROUND_JS"></script>
 
 </head>
 
@@ -546,11 +629,76 @@ pub fn playground_source(
       const root = document.getElementById('root');
       root.classList.add('playgroundIn');
 
-      GraphQLPlayground.init(root, { endpoint: 'JUNIPER_GRAPHQL_URL', subscriptionEndpoint: 'JUNIPER_SUBSCRIPTIONS_URL' })
+      GraphQLPlayground.init(root, { endpoint: JUNIPER_GRAPHQL_URL, subscriptionEndpoint: JUNIPER_SUBSCRIPTIONS_URLJUNIPER_PLAYGROUND_SETTINGS })
     })
   </script>
 </body>
 </html>
-  "##.replace("JUNIPER_GRAPHQL_URL", graphql_endpoint_url)
-     .replace("JUNIPER_SUBSCRIPTIONS_URL", subscriptions_endpoint)
+  "##.replace("JUNIPER_GRAPHQL_URL", &js_string(&config.graphql_endpoint_url))
+     .replace("JUNIPER_SUBSCRIPTIONS_URL", &js_string(subscriptions_endpoint))
+     .replace("JUNIPER_PLAYGROUND_CSS", &asset_url(&assets::PLAYGROUND_CSS, assets_base_url))
+     .replace("JUNIPER_PLAYGROUND_FAVICON", &asset_url(&assets::PLAYGROUND_FAVICON, assets_base_url))
+     .replace("JUNIPER_PLAYGROUND_JS", &asset_url(&assets::PLAYGROUND_JS, assets_base_url))
+     .replace("JUNIPER_PLAYGROUND_TITLE", &title)
+     .replace("JUNIPER_PLAYGROUND_SETTINGS", &settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{playground_source, playground_source_with_config, PlaygroundConfig};
+
+    #[test]
+    fn loads_assets_from_base_url() {
+        let source = playground_source_with_config(
+            &PlaygroundConfig::new("/graphql").with_assets_base_url("/assets/"),
+        );
+
+        assert!(source.contains(
+            r#"<script src="/assets/graphql-playground-react@1.7.26/middleware.js"></script>"#
+        ));
+        assert!(!source.contains("//cdn"));
+        assert!(playground_source("/graphql", None).contains(
+            "//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js"
+        ));
+    }
+
+    #[test]
+    fn renders_settings_and_title() {
+        let source = playground_source("/graphql", None);
+
+        assert!(source.contains("<title>GraphQL Playground</title>"));
+        assert!(source.contains(
+            "GraphQLPlayground.init(root, { endpoint: \"/graphql\", \
+             subscriptionEndpoint: \"/graphql\" })",
+        ));
+
+        let source = playground_source_with_config(
+            &PlaygroundConfig::new("/graphql")
+                .with_subscriptions_endpoint_url("/subscriptions")
+                .with_settings(r#"{"request.credentials": "omit", "x": "</script>"}"#)
+                .with_title("Star Wars & co"),
+        );
+
+        assert!(source.contains("<title>Star Wars &amp; co</title>"));
+        assert!(source.contains(
+            r#"GraphQLPlayground.init(root, { endpoint: "/graphql", subscriptionEndpoint: "/subscriptions", settings: {"request.credentials": "omit", "x": "<\/script>"} })"#,
+        ));
+    }
+
+    #[test]
+    fn escapes_urls() {
+        let source = playground_source_with_config(
+            &PlaygroundConfig::new("/graphql'</script>")
+                .with_subscriptions_endpoint_url("/subscriptions\"")
+                .with_assets_base_url("/assets\"><script>"),
+        );
+
+        assert!(source.contains(
+            r#"GraphQLPlayground.init(root, { endpoint: "/graphql'\u003c/script\u003e", subscriptionEndpoint: "/subscriptions\"" })"#,
+        ));
+        assert!(source.contains(
+            r#"<script src="/assets&quot;&gt;&lt;script&gt;/graphql-playground-react@1.7.26/middleware.js"></script>"#
+        ));
+        assert!(!source.contains("/assets\"><script>"));
+    }
 }
@@ -0,0 +1,88 @@
+/*!
+
+# Supported types
+
+| Rust Type    | JSON Serialization | Notes                                        |
+|--------------|--------------------|----------------------------------------------|
+| `BigDecimal` | string             | Accepts string, integer and float literals.  |
+
+Arbitrary precision decimals are serialized as strings (e.g. `"12.50"`),
+because JSON numbers (i.e. IEEE doubles) can't represent them precisely. On
+input, numeric literals are kept as written in the query, so `12.50` and
+`"12.50"` parse to the same value. Floats coming from variables are parsed
+from their shortest round-trip representation.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use bigdecimal::BigDecimal;
+
+use crate::{value::ParseScalarResult, Value};
+
+use super::decimal_common::{parse_decimal, parse_decimal_token};
+
+#[crate::graphql_scalar(description = "Arbitrary precision decimal number serialized as a string")]
+impl<S> GraphQLScalar for BigDecimal
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<BigDecimal, String> {
+        parse_decimal(v, "BigDecimal", |s| s.parse().ok())
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        parse_decimal_token(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bigdecimal::BigDecimal;
+
+    use crate::{parser::ScalarToken, FromInputValue, InputValue, ParseScalarValue, ToInputValue};
+
+    #[test]
+    fn big_decimal_from_input_value() {
+        for (input, expected) in vec![
+            (
+                InputValue::scalar("123456789012345678901234567890.5"),
+                "123456789012345678901234567890.5",
+            ),
+            (InputValue::scalar(-7), "-7"),
+            (InputValue::scalar(0.1), "0.1"),
+        ] {
+            let input: InputValue = input;
+            let parsed: BigDecimal = FromInputValue::from_input_value(&input).unwrap();
+            assert_eq!(parsed, expected.parse::<BigDecimal>().unwrap());
+        }
+
+        let input: InputValue = InputValue::scalar(true);
+        let err = <BigDecimal as FromInputValue>::from_input_value(&input).unwrap_err();
+        assert_eq!(err, "Expected `BigDecimal`, found: true");
+    }
+
+    #[test]
+    fn big_decimal_literal_keeps_precision() {
+        let parsed = <BigDecimal as ParseScalarValue>::from_str(ScalarToken::Float(
+            "3.14159265358979323846264338327950288",
+        ))
+        .unwrap();
+        let input: InputValue = InputValue::Scalar(parsed);
+        let parsed: BigDecimal = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(
+            parsed,
+            "3.14159265358979323846264338327950288"
+                .parse::<BigDecimal>()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn big_decimal_serializes_as_string() {
+        let value: InputValue = "12.50".parse::<BigDecimal>().unwrap().to_input_value();
+        assert_eq!(value, InputValue::scalar("12.50"));
+    }
+}
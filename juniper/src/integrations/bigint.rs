@@ -0,0 +1,228 @@
+/*!
+
+# Supported types
+
+| Rust Type      | GraphQL Scalar   | JSON Serialization | Notes                                |
+|----------------|------------------|--------------------|--------------------------------------|
+| `BigInt<i64>`  | `BigInt`         | string             | Accepts string and integer literals. |
+| `BigInt<u64>`  | `UnsignedBigInt` | string             | Accepts string and integer literals. |
+| `BigInt<i128>` | `BigInt128`      | string             | Accepts string and integer literals. |
+
+Values are serialized as decimal strings, because JSON numbers (i.e. IEEE
+doubles) can't represent integers beyond 2^53 precisely. On input, both strings
+(`"9007199254740993"`) and integer literals (`9007199254740993`) are accepted;
+integer literals are kept as written in the query, so no precision is lost.
+Floats coming from variables are only accepted if they hold an integer value
+exactly.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::ParseScalarResult,
+    InputValue, ScalarValue, Value,
+};
+
+/// The largest integer an IEEE double holds exactly.
+const MAX_SAFE_FLOAT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Integer serialized as a decimal string.
+///
+/// Implemented for `i64`, `u64` and `i128`, exposed as the `BigInt`, `UnsignedBigInt` and
+/// `BigInt128` scalars respectively.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BigInt<T>(pub T);
+
+impl<T> From<T> for BigInt<T> {
+    fn from(v: T) -> Self {
+        BigInt(v)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for BigInt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+fn parse_big_int<T, S>(name: &str, v: &InputValue<S>) -> Result<T, String>
+where
+    T: FromStr + TryFrom<i64>,
+    S: ScalarValue,
+{
+    let scalar = v
+        .as_scalar()
+        .ok_or_else(|| format!("Expected `{}`, found: {}", name, v))?;
+    if let Some(s) = scalar.as_str() {
+        s.parse()
+            .map_err(|_| format!("Expected `{}`, found out-of-range or invalid `{}`", name, s))
+    } else if let Some(i) = scalar.as_int() {
+        T::try_from(i.into()).map_err(|_| format!("Out-of-range `{}`: {}", name, i))
+    } else if let Some(f) = scalar.as_float() {
+        if f.fract() != 0.0 || f.abs() > MAX_SAFE_FLOAT_INTEGER {
+            return Err(format!("Expected `{}`, found imprecise float: {}", name, f));
+        }
+        T::try_from(f as i64).map_err(|_| format!("Out-of-range `{}`: {}", name, f))
+    } else {
+        Err(format!("Expected `{}`, found: {}", name, v))
+    }
+}
+
+/// Keeps `Int` literals fitting an `i32` as numbers, so they're printed back as written, and the
+/// bigger ones as strings, so no precision is lost.
+fn parse_big_int_token<'a, S>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S>
+where
+    S: ScalarValue,
+{
+    match value {
+        ScalarToken::Int(s) => Ok(s
+            .parse::<i32>()
+            .map_or_else(|_| S::from(s.to_owned()), S::from)),
+        ScalarToken::String(s) => Ok(S::from(s.to_owned())),
+        ScalarToken::Float(_) => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+    }
+}
+
+macro_rules! impl_big_int_scalar {
+    ($ty:ty, $name:literal) => {
+        #[crate::graphql_scalar(
+            name = $name,
+            description = "Integer serialized as a decimal string"
+        )]
+        impl<S> GraphQLScalar for BigInt<$ty>
+        where
+            S: ScalarValue,
+        {
+            fn resolve(&self) -> Value {
+                Value::scalar(self.0.to_string())
+            }
+
+            fn from_input_value(v: &InputValue) -> Result<BigInt<$ty>, String> {
+                parse_big_int($name, v).map(BigInt)
+            }
+
+            fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+                parse_big_int_token(value)
+            }
+        }
+    };
+}
+
+impl_big_int_scalar!(i64, "BigInt");
+impl_big_int_scalar!(u64, "UnsignedBigInt");
+impl_big_int_scalar!(i128, "BigInt128");
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graphql_object, graphql_value, parser::ScalarToken, EmptyMutation, EmptySubscription,
+        FromInputValue, InputValue, ParseScalarValue, RootNode, ToInputValue,
+    };
+
+    use super::BigInt;
+
+    #[test]
+    fn big_int_from_string() {
+        let input: InputValue = InputValue::scalar("-9223372036854775808");
+        let parsed: BigInt<i64> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(i64::MIN));
+
+        let input: InputValue = InputValue::scalar("18446744073709551615");
+        let parsed: BigInt<u64> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(u64::MAX));
+
+        let input: InputValue = InputValue::scalar("170141183460469231731687303715884105727");
+        let parsed: BigInt<i128> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(i128::MAX));
+    }
+
+    #[test]
+    fn big_int_from_numbers() {
+        let input: InputValue = InputValue::scalar(42);
+        let parsed: BigInt<u64> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(42));
+
+        let input: InputValue = InputValue::scalar(9_007_199_254_740_992.0);
+        let parsed: BigInt<i64> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(9_007_199_254_740_992));
+    }
+
+    #[test]
+    fn big_int_rejects_invalid_input() {
+        let input: InputValue = InputValue::scalar(-1);
+        let err = <BigInt<u64> as FromInputValue>::from_input_value(&input).unwrap_err();
+        assert_eq!(err, "Out-of-range `UnsignedBigInt`: -1");
+
+        let input: InputValue = InputValue::scalar(1.5);
+        let err = <BigInt<i64> as FromInputValue>::from_input_value(&input).unwrap_err();
+        assert_eq!(err, "Expected `BigInt`, found imprecise float: 1.5");
+
+        let input: InputValue = InputValue::scalar("9223372036854775808");
+        assert!(<BigInt<i64> as FromInputValue>::from_input_value(&input).is_err());
+    }
+
+    #[test]
+    fn big_int_literal_keeps_precision() {
+        let parsed =
+            <BigInt<i64> as ParseScalarValue>::from_str(ScalarToken::Int("9007199254740993"))
+                .unwrap();
+        let input: InputValue = InputValue::Scalar(parsed);
+        let parsed: BigInt<i64> = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed, BigInt(9_007_199_254_740_993));
+    }
+
+    #[test]
+    fn big_int_serializes_as_string() {
+        let value: InputValue = BigInt(u64::MAX).to_input_value();
+        assert_eq!(value, InputValue::scalar("18446744073709551615"));
+
+        let value: InputValue = BigInt(-5_i128).to_input_value();
+        assert_eq!(value, InputValue::scalar("-5"));
+    }
+
+    #[tokio::test]
+    async fn big_int_widths_are_distinct_scalars() {
+        struct Query;
+
+        #[graphql_object]
+        impl Query {
+            fn signed(v: BigInt<i64>) -> BigInt<i64> {
+                v
+            }
+
+            fn unsigned(v: BigInt<u64>) -> BigInt<u64> {
+                v
+            }
+        }
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        for name in &["BigInt", "UnsignedBigInt"] {
+            assert!(schema.schema.concrete_type_by_name(name).is_some());
+        }
+
+        let (res, errs) = crate::execute(
+            r#"{ signed(v: "-9223372036854775808") unsigned(v: "18446744073709551615") }"#,
+            None,
+            &schema,
+            &crate::Variables::new(),
+            &(),
+        )
+        .await
+        .unwrap();
+
+        assert!(errs.is_empty());
+        assert_eq!(
+            res,
+            graphql_value!({
+                "signed": "-9223372036854775808",
+                "unsigned": "18446744073709551615",
+            }),
+        );
+    }
+}
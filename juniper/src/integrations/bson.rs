@@ -16,9 +16,12 @@ where
         Value::scalar(self.to_hex())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<ObjectId> {
+    fn from_input_value(v: &InputValue) -> Result<ObjectId, String> {
         v.as_string_value()
-            .and_then(|s| ObjectId::with_string(s).ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                ObjectId::with_string(s).map_err(|e| format!("Failed to parse `ObjectId`: {}", e))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -39,9 +42,13 @@ where
         Value::scalar((*self).to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<UtcDateTime> {
+    fn from_input_value(v: &InputValue) -> Result<UtcDateTime, String> {
         v.as_string_value()
-            .and_then(|s| (s.parse::<DateTime<Utc>>().ok()))
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                s.parse::<DateTime<Utc>>()
+                    .map_err(|_| format!("Expected RFC 3339 timestamp, found `{}`", s))
+            })
             .map(UtcDateTime)
     }
 
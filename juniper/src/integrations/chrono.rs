@@ -13,10 +13,12 @@
 |                         |                        | resolution.                               |
 | `NaiveTime`             | H:M:S                  | Optional. Use the `scalar-naivetime`      |
 |                         |                        | feature.                                  |
+| `Duration`              | ISO 8601 duration      | E.g. `PT1H30M` or `-P1DT0.5S`. Years and  |
+|                         |                        | months aren't accepted.                   |
 
 */
 #![allow(clippy::needless_lifetimes)]
-use chrono::prelude::*;
+use chrono::{prelude::*, Duration};
 
 use crate::{
     parser::{ParseError, ScalarToken, Token},
@@ -24,6 +26,8 @@ use crate::{
     Value,
 };
 
+use super::iso8601_duration;
+
 #[doc(hidden)]
 pub static RFC3339_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
 
@@ -36,9 +40,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<FixedOffset>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<FixedOffset>, String> {
         v.as_string_value()
-            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                DateTime::parse_from_rfc3339(s)
+                    .map_err(|_| format!("Expected RFC 3339 timestamp, found `{}`", s))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -59,9 +67,13 @@ where
         Value::scalar(self.to_rfc3339())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<DateTime<Utc>> {
+    fn from_input_value(v: &InputValue) -> Result<DateTime<Utc>, String> {
         v.as_string_value()
-            .and_then(|s| (s.parse::<DateTime<Utc>>().ok()))
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                s.parse::<DateTime<Utc>>()
+                    .map_err(|_| format!("Expected RFC 3339 timestamp, found `{}`", s))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -87,9 +99,13 @@ where
         Value::scalar(self.format("%Y-%m-%d").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveDate> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveDate, String> {
         v.as_string_value()
-            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                    .map_err(|_| format!("Expected `YYYY-MM-DD` date, found `{}`", s))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -111,9 +127,13 @@ where
         Value::scalar(self.format("%H:%M:%S").to_string())
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveTime> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveTime, String> {
         v.as_string_value()
-            .and_then(|s| NaiveTime::parse_from_str(s, "%H:%M:%S").ok())
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                NaiveTime::parse_from_str(s, "%H:%M:%S")
+                    .map_err(|_| format!("Expected `HH:MM:SS` time, found `{}`", s))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -136,9 +156,13 @@ where
         Value::scalar(self.timestamp() as f64)
     }
 
-    fn from_input_value(v: &InputValue) -> Option<NaiveDateTime> {
+    fn from_input_value(v: &InputValue) -> Result<NaiveDateTime, String> {
         v.as_float_value()
-            .and_then(|f| NaiveDateTime::from_timestamp_opt(f as i64, 0))
+            .ok_or_else(|| format!("Expected `Float`, found: {}", v))
+            .and_then(|f| {
+                NaiveDateTime::from_timestamp_opt(f as i64, 0)
+                    .ok_or_else(|| format!("Out-of-range number of seconds: {}", f))
+            })
     }
 
     fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
@@ -146,10 +170,40 @@ where
     }
 }
 
+#[crate::graphql_scalar(description = "Duration")]
+impl<S> GraphQLScalar for Duration
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        let nanos =
+            i128::from(self.num_seconds()) * 1_000_000_000 + i128::from(self.subsec_nanos());
+        Value::scalar(iso8601_duration::format(nanos))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Duration, String> {
+        let s = v
+            .as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))?;
+        let (negative, duration) = iso8601_duration::parse(s)?;
+        let duration = Duration::from_std(duration)
+            .map_err(|_| format!("Out-of-range `Duration`: `{}`", s))?;
+        Ok(if negative { -duration } else { duration })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{value::DefaultScalarValue, InputValue};
-    use chrono::prelude::*;
+    use chrono::{prelude::*, Duration};
 
     fn datetime_fixedoffset_test(raw: &'static str) {
         let input: crate::InputValue<DefaultScalarValue> = InputValue::scalar(raw.to_string());
@@ -202,6 +256,15 @@ mod test {
         datetime_utc_test("2014-11-28T21:00:09.005+09:00");
     }
 
+    #[test]
+    fn datetime_utc_from_invalid_input_value() {
+        let input: crate::InputValue<DefaultScalarValue> = InputValue::scalar("2021-13-01");
+
+        let err = <DateTime<Utc> as crate::FromInputValue>::from_input_value(&input).unwrap_err();
+
+        assert_eq!(err, "Expected RFC 3339 timestamp, found `2021-13-01`");
+    }
+
     #[test]
     fn naivedate_from_input_value() {
         let input: crate::InputValue<DefaultScalarValue> =
@@ -245,11 +308,30 @@ mod test {
         assert_eq!(parsed, expected);
         assert_eq!(raw, expected.timestamp() as f64);
     }
+
+    #[test]
+    fn duration_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("-P1DT2H0.5S");
+
+        let parsed: Duration = crate::FromInputValue::from_input_value(&input).unwrap();
+        let expected = -(Duration::days(1) + Duration::hours(2) + Duration::milliseconds(500));
+
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn duration_from_invalid_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("P1M");
+
+        let err = <Duration as crate::FromInputValue>::from_input_value(&input).unwrap_err();
+
+        assert_eq!(err, "Years and months aren't exact durations, found `P1M`");
+    }
 }
 
 #[cfg(test)]
 mod integration_test {
-    use chrono::{prelude::*, Utc};
+    use chrono::{prelude::*, Duration, Utc};
 
     use crate::{
         executor::Variables,
@@ -280,6 +362,9 @@ mod integration_test {
             fn exampleDateTimeUtc() -> DateTime<Utc> {
                 Utc.timestamp(61, 0)
             }
+            fn exampleDuration() -> Duration {
+                Duration::minutes(90)
+            }
         }
 
         #[crate::graphql_object]
@@ -297,6 +382,9 @@ mod integration_test {
             fn exampleDateTimeUtc() -> DateTime<Utc> {
                 Utc.timestamp(61, 0)
             }
+            fn exampleDuration() -> Duration {
+                Duration::minutes(90)
+            }
         }
 
         #[cfg(feature = "scalar-naivetime")]
@@ -307,6 +395,7 @@ mod integration_test {
             exampleNaiveTime,
             exampleDateTimeFixedOffset,
             exampleDateTimeUtc,
+            exampleDuration,
         }
         "#;
 
@@ -317,6 +406,7 @@ mod integration_test {
             exampleNaiveDateTime,
             exampleDateTimeFixedOffset,
             exampleDateTimeUtc,
+            exampleDuration,
         }
         "#;
 
@@ -348,6 +438,7 @@ mod integration_test {
                         "exampleDateTimeUtc",
                         Value::scalar("1970-01-01T00:01:01+00:00"),
                     ),
+                    ("exampleDuration", Value::scalar("PT1H30M")),
                 ]
                 .into_iter()
                 .collect()
@@ -0,0 +1,51 @@
+//! Input handling shared by the decimal integrations.
+
+use crate::{parser::ScalarToken, value::ParseScalarResult, InputValue, ScalarValue};
+
+/// Parses a decimal from a string, integer or float input value with the
+/// given string parser.
+///
+/// Floats are parsed from their shortest round-trip representation, so
+/// `0.1` yields exactly `0.1`.
+pub(super) fn parse_decimal<D, S>(
+    v: &InputValue<S>,
+    type_name: &str,
+    parse: impl Fn(&str) -> Option<D>,
+) -> Result<D, String>
+where
+    S: ScalarValue,
+{
+    let scalar = v
+        .as_scalar()
+        .ok_or_else(|| format!("Expected `{}`, found: {}", type_name, v))?;
+    let s = if let Some(s) = scalar.as_str() {
+        s.to_owned()
+    } else if let Some(i) = scalar.as_int() {
+        i.to_string()
+    } else if let Some(f) = scalar.as_float() {
+        f.to_string()
+    } else {
+        return Err(format!("Expected `{}`, found: {}", type_name, v));
+    };
+    parse(&s).ok_or_else(|| format!("Failed to parse `{}`: {}", type_name, s))
+}
+
+/// Keeps numeric literals as numbers when `parse_decimal` reads them back
+/// exactly as written in the query, so they're printed back as numbers, and
+/// as strings otherwise, so no precision is lost.
+pub(super) fn parse_decimal_token<'a, S>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S>
+where
+    S: ScalarValue,
+{
+    Ok(match value {
+        ScalarToken::Int(s) => match s.parse::<i32>() {
+            Ok(i) if i.to_string() == s => S::from(i),
+            _ => S::from(s.to_owned()),
+        },
+        ScalarToken::Float(s) => match s.parse::<f64>() {
+            Ok(f) if f.to_string() == s => S::from(f),
+            _ => S::from(s.to_owned()),
+        },
+        ScalarToken::String(s) => S::from(s.to_owned()),
+    })
+}
@@ -0,0 +1,220 @@
+//! [ISO 8601 durations][1] shared by the `chrono` and `time` integrations.
+//!
+//! Only exact units are supported: weeks, days (of 24 hours), hours, minutes
+//! and seconds, the latter with an optional fraction of up to nanosecond
+//! precision. Years and months are rejected, as their length depends on the
+//! date they're applied to. Negative durations carry a leading `-`, e.g.
+//! `-PT1H30M`.
+//!
+//! [1]: https://en.wikipedia.org/wiki/ISO_8601#Durations
+
+use std::{convert::TryFrom, time::Duration};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const SECONDS_PER_MINUTE: i128 = 60;
+const SECONDS_PER_HOUR: i128 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: i128 = 24 * SECONDS_PER_HOUR;
+const SECONDS_PER_WEEK: i128 = 7 * SECONDS_PER_DAY;
+
+/// Formats a signed number of nanoseconds, e.g. `P1DT2H0.5S`.
+///
+/// Weeks are never emitted, so the output is stable for equal durations.
+pub(super) fn format(total_nanos: i128) -> String {
+    let mut out = String::new();
+    if total_nanos < 0 {
+        out.push('-');
+    }
+    out.push('P');
+
+    let total_nanos = total_nanos.unsigned_abs();
+    let nanos = total_nanos % NANOS_PER_SECOND as u128;
+    let mut secs = total_nanos / NANOS_PER_SECOND as u128;
+
+    let days = secs / SECONDS_PER_DAY as u128;
+    secs %= SECONDS_PER_DAY as u128;
+    let hours = secs / SECONDS_PER_HOUR as u128;
+    secs %= SECONDS_PER_HOUR as u128;
+    let minutes = secs / SECONDS_PER_MINUTE as u128;
+    secs %= SECONDS_PER_MINUTE as u128;
+
+    if days > 0 {
+        out.push_str(&format!("{}D", days));
+    }
+    if hours > 0 || minutes > 0 || secs > 0 || nanos > 0 || days == 0 {
+        out.push('T');
+    }
+    if hours > 0 {
+        out.push_str(&format!("{}H", hours));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{}M", minutes));
+    }
+    if nanos > 0 {
+        let fraction = format!("{:09}", nanos);
+        out.push_str(&format!("{}.{}S", secs, fraction.trim_end_matches('0')));
+    } else if secs > 0 || (days == 0 && hours == 0 && minutes == 0) {
+        out.push_str(&format!("{}S", secs));
+    }
+    out
+}
+
+/// Parses a duration into its sign and magnitude.
+pub(super) fn parse(s: &str) -> Result<(bool, Duration), String> {
+    let err = || format!("Expected ISO 8601 duration, found `{}`", s);
+
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(err)?;
+    let (date, time) = match rest.find('T') {
+        Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.map_or(true, str::is_empty) {
+        return Err(err());
+    }
+
+    let mut total_nanos: i128 = 0;
+    let mut add = |value: i128, unit_nanos: i128| -> Result<(), String> {
+        total_nanos = value
+            .checked_mul(unit_nanos)
+            .and_then(|n| total_nanos.checked_add(n))
+            .ok_or_else(|| format!("Out-of-range ISO 8601 duration: `{}`", s))?;
+        Ok(())
+    };
+
+    for (value, unit) in components(date).ok_or_else(err)? {
+        let unit_secs = match unit {
+            'W' => SECONDS_PER_WEEK,
+            'D' => SECONDS_PER_DAY,
+            'Y' | 'M' => {
+                return Err(format!(
+                    "Years and months aren't exact durations, found `{}`",
+                    s,
+                ))
+            }
+            _ => return Err(err()),
+        };
+        add(whole(value).ok_or_else(err)?, unit_secs * NANOS_PER_SECOND)?;
+    }
+    if let Some(time) = time {
+        let components = components(time).filter(|c| !c.is_empty());
+        for (value, unit) in components.ok_or_else(err)? {
+            match unit {
+                'H' => add(
+                    whole(value).ok_or_else(err)?,
+                    SECONDS_PER_HOUR * NANOS_PER_SECOND,
+                )?,
+                'M' => add(
+                    whole(value).ok_or_else(err)?,
+                    SECONDS_PER_MINUTE * NANOS_PER_SECOND,
+                )?,
+                'S' => add(fractional_nanos(value).ok_or_else(err)?, 1)?,
+                _ => return Err(err()),
+            }
+        }
+    }
+
+    let secs = u64::try_from(total_nanos / NANOS_PER_SECOND)
+        .map_err(|_| format!("Out-of-range ISO 8601 duration: `{}`", s))?;
+    let nanos = (total_nanos % NANOS_PER_SECOND) as u32;
+    Ok((negative, Duration::new(secs, nanos)))
+}
+
+/// Splits e.g. `1H30M` into `[("1", 'H'), ("30", 'M')]`.
+fn components(s: &str) -> Option<Vec<(&str, char)>> {
+    let mut components = vec![];
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c.is_ascii_alphabetic() {
+            if i == start {
+                return None;
+            }
+            components.push((&s[start..i], c));
+            start = i + 1;
+        }
+    }
+    if start != s.len() {
+        return None;
+    }
+    Some(components)
+}
+
+fn whole(value: &str) -> Option<i128> {
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Parses seconds with an optional fraction (`.` or `,` separated) into
+/// nanoseconds.
+fn fractional_nanos(value: &str) -> Option<i128> {
+    let (secs, fraction) = match value.find(&['.', ','][..]) {
+        Some(i) => (&value[..i], &value[i + 1..]),
+        None => (value, ""),
+    };
+    if fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let fraction = format!("{:0<9}", fraction);
+    Some(whole(secs)?.checked_mul(NANOS_PER_SECOND)? + fraction.parse::<i128>().ok()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format, parse};
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format(0), "PT0S");
+        assert_eq!(format(90 * 60 * 1_000_000_000), "PT1H30M");
+        assert_eq!(format(-500_000_000), "-PT0.5S");
+        assert_eq!(format(2 * 86_400 * 1_000_000_000), "P2D");
+        assert_eq!(format((86_400 + 3) * 1_000_000_000 + 1), "P1DT3.000000001S");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse("PT0S"), Ok((false, Duration::from_secs(0))));
+        assert_eq!(parse("PT1H30M"), Ok((false, Duration::from_secs(5400))));
+        assert_eq!(parse("-PT0.5S"), Ok((true, Duration::from_millis(500))));
+        assert_eq!(parse("PT0,25S"), Ok((false, Duration::from_millis(250))));
+        assert_eq!(
+            parse("P1W2DT3H4M5.000000006S"),
+            Ok((false, Duration::new(9 * 86_400 + 3 * 3600 + 4 * 60 + 5, 6))),
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        for input in &[
+            "", "P", "PT", "1H", "PT1.5H", "PTH", "P1DT", "PT1S2", "PT-1S",
+        ] {
+            assert!(parse(input).is_err(), "`{}` should be rejected", input);
+        }
+        assert_eq!(
+            parse("P1Y"),
+            Err("Years and months aren't exact durations, found `P1Y`".to_owned()),
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for nanos in &[
+            0_i128,
+            1,
+            -1,
+            59_999_999_999,
+            3_600_000_000_000,
+            -90_061_000_000_001,
+        ] {
+            let (negative, duration) = parse(&format(*nanos)).unwrap();
+            let parsed = duration.as_nanos() as i128;
+            assert_eq!(if negative { -parsed } else { parsed }, *nanos);
+        }
+    }
+}
@@ -11,6 +11,13 @@ pub mod chrono;
 /// GraphQL support for [chrono-tz](https://github.com/chronotope/chrono-tz) types.
 pub mod chrono_tz;
 
+#[cfg(feature = "time")]
+/// GraphQL support for [time](https://github.com/time-rs/time) types.
+pub mod time;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod iso8601_duration;
+
 #[cfg(feature = "url")]
 /// GraphQL support for [url](https://github.com/servo/rust-url) types.
 pub mod url;
@@ -22,3 +29,22 @@ pub mod uuid;
 #[cfg(feature = "bson")]
 /// GraphQL support for [bson](https://github.com/mongodb/bson-rust) types.
 pub mod bson;
+
+#[cfg(feature = "serde_json")]
+/// GraphQL support for [serde_json](https://github.com/serde-rs/json) values.
+pub mod serde_json;
+
+#[cfg(feature = "bigint")]
+/// GraphQL support for 64-bit and 128-bit integers serialized as strings.
+pub mod bigint;
+
+#[cfg(any(feature = "rust_decimal", feature = "bigdecimal"))]
+mod decimal_common;
+
+#[cfg(feature = "rust_decimal")]
+/// GraphQL support for [rust_decimal](https://github.com/paupino/rust-decimal) types.
+pub mod rust_decimal;
+
+#[cfg(feature = "bigdecimal")]
+/// GraphQL support for [bigdecimal](https://github.com/akubera/bigdecimal-rs) types.
+pub mod bigdecimal;
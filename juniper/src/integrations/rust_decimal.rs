@@ -0,0 +1,92 @@
+/*!
+
+# Supported types
+
+| Rust Type | JSON Serialization | Notes                                           |
+|-----------|--------------------|-------------------------------------------------|
+| `Decimal` | string             | Accepts string, integer and float literals.     |
+
+Decimals are serialized as strings (e.g. `"12.50"`), preserving their scale,
+because JSON numbers (i.e. IEEE doubles) can't represent them precisely. On
+input, numeric literals are kept as written in the query, so `12.50` and
+`"12.50"` parse to the same value. Floats coming from variables are parsed
+from their shortest round-trip representation.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use rust_decimal::Decimal;
+
+use crate::{value::ParseScalarResult, Value};
+
+use super::decimal_common::{parse_decimal, parse_decimal_token};
+
+#[crate::graphql_scalar(description = "Decimal number serialized as a string")]
+impl<S> GraphQLScalar for Decimal
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Decimal, String> {
+        parse_decimal(v, "Decimal", |s| s.parse().ok())
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        parse_decimal_token(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rust_decimal::Decimal;
+
+    use crate::{parser::ScalarToken, FromInputValue, InputValue, ParseScalarValue, ToInputValue};
+
+    #[test]
+    fn decimal_from_input_value() {
+        for (input, expected) in vec![
+            (InputValue::scalar("12.50"), "12.50"),
+            (InputValue::scalar(3), "3"),
+            (InputValue::scalar(0.1), "0.1"),
+        ] {
+            let input: InputValue = input;
+            let parsed: Decimal = FromInputValue::from_input_value(&input).unwrap();
+            assert_eq!(parsed, expected.parse::<Decimal>().unwrap());
+        }
+
+        let input: InputValue = InputValue::scalar("twelve");
+        let err = <Decimal as FromInputValue>::from_input_value(&input).unwrap_err();
+        assert_eq!(err, "Failed to parse `Decimal`: twelve");
+    }
+
+    #[test]
+    fn decimal_literal_keeps_precision() {
+        let parsed = <Decimal as ParseScalarValue>::from_str(ScalarToken::Float(
+            "79228162514264.337593543950335",
+        ))
+        .unwrap();
+        let input: InputValue = InputValue::Scalar(parsed);
+        let parsed: Decimal = FromInputValue::from_input_value(&input).unwrap();
+        assert_eq!(parsed.to_string(), "79228162514264.337593543950335");
+    }
+
+    #[test]
+    fn decimal_literal_keeps_number_form() {
+        for (token, expected) in vec![
+            (ScalarToken::Int("3"), InputValue::scalar(3)),
+            (ScalarToken::Float("2.5"), InputValue::scalar(2.5)),
+            (ScalarToken::Float("1.50"), InputValue::scalar("1.50")),
+        ] {
+            let parsed = <Decimal as ParseScalarValue>::from_str(token).unwrap();
+            assert_eq!(InputValue::Scalar(parsed), expected);
+        }
+    }
+
+    #[test]
+    fn decimal_serializes_as_string() {
+        let value: InputValue = "12.50".parse::<Decimal>().unwrap().to_input_value();
+        assert_eq!(value, InputValue::scalar("12.50"));
+    }
+}
@@ -0,0 +1,472 @@
+/*!
+
+# Supported types
+
+| Rust Type                  | GraphQL Scalar | Notes                                       |
+|----------------------------|----------------|---------------------------------------------|
+| `serde_json::Value`        | `JSON`         | Any JSON value, including `null`.           |
+| `serde_json::Map`          | `JSONObject`   | JSON objects only.                          |
+| `JsonMap<M>`               | `JSONObject`   | Any map `M` converted through `serde`.      |
+
+Both scalars accept list and object literals, e.g.
+`setFlags(flags: { beta: true, limits: [1, 2] })`, as well as variables of any
+shape. Numbers go through the [`ScalarValue`]'s own deserialization, so a
+`ScalarValue` holding 64-bit integers keeps them exact. With the
+[`DefaultScalarValue`](crate::DefaultScalarValue), integers outside the `Int`
+range become floats, like they do in variables.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use serde::{de::DeserializeOwned, Deserializer as _, Serialize};
+use serde_json::{Map, Number, Value as Json};
+
+use crate::{
+    ast::{FromInputValue, Selection, ToInputValue},
+    executor::{ExecutionResult, Executor, FieldError, Registry},
+    marker::{IsInputType, IsOutputType},
+    parser::{ParseError, ScalarToken, Token},
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{GraphQLType, GraphQLValue},
+    },
+    value::{Object, ParseScalarResult, ParseScalarValue},
+    BoxFuture, InputValue, ScalarValue, Value,
+};
+
+#[crate::graphql_scalar(
+    name = "JSON",
+    description = "Arbitrary JSON value",
+    accept_composite_values
+)]
+impl<S> GraphQLScalar for Json
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        to_value(self)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Json, String> {
+        from_input_value(v)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(_) => <String as ParseScalarValue<S>>::from_str(value),
+            ScalarToken::Int(n) | ScalarToken::Float(n) => n
+                .parse::<Number>()
+                .map(|n| scalar_from_number(&n))
+                .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+#[crate::graphql_scalar(
+    name = "JSONObject",
+    description = "Arbitrary JSON object",
+    accept_composite_values
+)]
+impl<S> GraphQLScalar for Map<String, Json>
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::Object(object_to_value(self))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Map<String, Json>, String> {
+        match (v, from_input_value(v)?) {
+            (InputValue::Object(_), Json::Object(map)) => Ok(map),
+            _ => Err(format!("Expected `JSONObject`, found: {}", v)),
+        }
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        // Parses the scalars nested in object literals, top-level ones are rejected later on.
+        <Json as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+/// Map exposed as a `JSONObject` scalar, like a `HashMap<String, V>` or a `BTreeMap<String, V>`.
+///
+/// The map is converted through `serde`, so its keys have to serialize as strings. Use
+/// [`MapEntries`](crate::MapEntries) to expose a map as a list of typed entries instead.
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use juniper::{graphql_object, integrations::serde_json::JsonMap};
+/// struct Feature {
+///     flags: HashMap<String, bool>,
+/// }
+///
+/// #[graphql_object]
+/// impl Feature {
+///     // Exposed as `flags: JSONObject!`.
+///     fn flags(&self) -> JsonMap<HashMap<String, bool>> {
+///         JsonMap(self.flags.clone())
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct JsonMap<M>(pub M);
+
+impl<S, M> GraphQLType<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: Serialize + DeserializeOwned + 'static,
+{
+    fn name(_: &()) -> Option<&str> {
+        Some("JSONObject")
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry
+            .build_scalar_type::<Self>(info)
+            .description("Arbitrary JSON object")
+            .accept_composite_values()
+            .into_meta()
+    }
+}
+
+impl<S, M> GraphQLValue<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: Serialize + DeserializeOwned + 'static,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i ()) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve(
+        &self,
+        _: &(),
+        _: Option<&[Selection<S>]>,
+        _: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match serde_json::to_value(&self.0) {
+            Ok(Json::Object(map)) => Ok(Value::Object(object_to_value(&map))),
+            Ok(other) => Err(FieldError::new(
+                format!("Expected map to serialize into an object, found: {}", other),
+                Value::null(),
+            )),
+            Err(e) => Err(FieldError::new(e, Value::null())),
+        }
+    }
+}
+
+impl<S, M> GraphQLValueAsync<S> for JsonMap<M>
+where
+    S: ScalarValue + Send + Sync,
+    M: Serialize + DeserializeOwned + Sync + 'static,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a (),
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        Box::pin(futures::future::ready(self.resolve(
+            info,
+            selection_set,
+            executor,
+        )))
+    }
+}
+
+impl<S, M> FromInputValue<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: DeserializeOwned,
+{
+    type Error = String;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        let map = <Map<String, Json> as FromInputValue<S>>::from_input_value(v)?;
+        serde_json::from_value(Json::Object(map))
+            .map(JsonMap)
+            .map_err(|e| format!("Expected `JSONObject`, found: {}: {}", v, e))
+    }
+}
+
+impl<S, M> ToInputValue<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: Serialize,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        serde_json::to_value(&self.0)
+            .map(|json| to_value(&json).to_input_value())
+            .unwrap_or_else(|_| InputValue::null())
+    }
+}
+
+impl<S, M> ParseScalarValue<S> for JsonMap<M>
+where
+    S: ScalarValue,
+{
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, S> {
+        // Parses the scalars nested in object literals, top-level ones are rejected later on.
+        <Json as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+impl<S, M> IsInputType<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: Serialize + DeserializeOwned + 'static,
+{
+}
+
+impl<S, M> IsOutputType<S> for JsonMap<M>
+where
+    S: ScalarValue,
+    M: Serialize + DeserializeOwned + 'static,
+{
+}
+
+fn to_value<S: ScalarValue>(json: &Json) -> Value<S> {
+    match json {
+        Json::Null => Value::null(),
+        Json::Bool(b) => Value::scalar(*b),
+        Json::Number(n) => Value::Scalar(scalar_from_number(n)),
+        Json::String(s) => Value::scalar(s.clone()),
+        Json::Array(items) => Value::list(items.iter().map(to_value).collect()),
+        Json::Object(map) => Value::Object(object_to_value(map)),
+    }
+}
+
+fn object_to_value<S: ScalarValue>(map: &Map<String, Json>) -> Object<S> {
+    map.iter().map(|(k, v)| (k.as_str(), to_value(v))).collect()
+}
+
+/// Converts the number through the [`ScalarValue`]'s deserialization, falling back to a float if
+/// it rejects the number.
+fn scalar_from_number<S: ScalarValue>(n: &Number) -> S {
+    n.deserialize_any(S::Visitor::default())
+        .unwrap_or_else(|_| n.as_f64().map_or_else(|| S::from(n.to_string()), S::from))
+}
+
+fn from_input_value<S: ScalarValue>(v: &InputValue<S>) -> Result<Json, String> {
+    match v {
+        InputValue::Null => Ok(Json::Null),
+        InputValue::Scalar(s) => {
+            serde_json::to_value(s).map_err(|e| format!("Expected `JSON`, found: {}: {}", v, e))
+        }
+        InputValue::Enum(e) => Ok(Json::String(e.clone())),
+        InputValue::List(items) => items
+            .iter()
+            .map(|item| from_input_value(&item.item))
+            .collect::<Result<_, _>>()
+            .map(Json::Array),
+        InputValue::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| Ok((k.item.clone(), from_input_value(&v.item)?)))
+            .collect::<Result<_, _>>()
+            .map(Json::Object),
+        InputValue::Variable(name) => Err(format!("Unresolved variable `${}` in `JSON`", name)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{json, Map, Value as Json};
+
+    use crate::{
+        parser::ScalarToken, DefaultScalarValue, FromInputValue, InputValue, ParseScalarValue,
+        ToInputValue, Value,
+    };
+
+    #[test]
+    fn json_from_input_value() {
+        let input: InputValue = InputValue::object(
+            vec![
+                ("beta", InputValue::scalar(true)),
+                (
+                    "limits",
+                    InputValue::list(vec![InputValue::scalar(1), InputValue::scalar(2.5)]),
+                ),
+                ("mode", InputValue::enum_value("FAST")),
+                ("owner", InputValue::null()),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let parsed: Json = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(
+            parsed,
+            json!({"beta": true, "limits": [1, 2.5], "mode": "FAST", "owner": null}),
+        );
+    }
+
+    #[test]
+    fn json_to_value() {
+        let json = json!({"name": "juniper", "tags": ["graphql", 42], "big": 4_294_967_296_i64});
+
+        let value: Value = super::to_value(&json);
+
+        assert_eq!(
+            value,
+            graphql_value!({
+                "name": "juniper",
+                "tags": ["graphql", 42],
+                "big": 4_294_967_296.0,
+            }),
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let json = json!([null, false, -7, 0.5, "s", {"nested": {"list": []}}]);
+
+        let input: InputValue = json.to_input_value();
+        let parsed: Json = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, json);
+    }
+
+    #[test]
+    fn json_literal_numbers() {
+        let parsed = <Json as ParseScalarValue>::from_str(ScalarToken::Int("2147483648")).unwrap();
+        assert_eq!(parsed, DefaultScalarValue::Float(2_147_483_648.0));
+
+        let parsed = <Json as ParseScalarValue>::from_str(ScalarToken::Float("1e3")).unwrap();
+        assert_eq!(parsed, DefaultScalarValue::Float(1000.0));
+    }
+
+    #[test]
+    fn json_object_rejects_non_objects() {
+        let input: InputValue = InputValue::list(vec![InputValue::scalar(1)]);
+
+        let err = <Map<String, Json> as FromInputValue>::from_input_value(&input).unwrap_err();
+
+        assert_eq!(err, "Expected `JSONObject`, found: [1]");
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use std::collections::BTreeMap;
+
+    use serde_json::{json, Map, Value as Json};
+
+    use crate::{
+        executor::Variables,
+        graphql_object,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        InputValue,
+    };
+
+    use super::JsonMap;
+
+    struct Root;
+
+    #[graphql_object]
+    impl Root {
+        fn echo(value: Json) -> Json {
+            value
+        }
+
+        fn settings(overrides: Option<Map<String, Json>>) -> Map<String, Json> {
+            let mut settings = json!({"theme": "dark", "flags": {"beta": false}})
+                .as_object()
+                .cloned()
+                .unwrap();
+            settings.extend(overrides.unwrap_or_default());
+            settings
+        }
+
+        fn limits(overrides: JsonMap<BTreeMap<String, i32>>) -> JsonMap<BTreeMap<String, i32>> {
+            let mut limits = BTreeMap::new();
+            limits.insert("requests".to_owned(), 100);
+            limits.extend(overrides.0);
+            JsonMap(limits)
+        }
+    }
+
+    fn schema() -> RootNode<'static, Root, EmptyMutation, EmptySubscription> {
+        RootNode::new(Root, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[tokio::test]
+    async fn accepts_literals() {
+        let doc = r#"{
+            echo(value: { a: [1, "two", null, { b: 3.5 }] })
+            settings(overrides: { theme: "light" })
+        }"#;
+
+        let (result, errs) = crate::execute(doc, None, &schema(), &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({
+                "echo": {"a": [1, "two", None, {"b": 3.5}]},
+                "settings": {"theme": "light", "flags": {"beta": false}},
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn accepts_variables() {
+        let doc = r#"query($v: JSON!) { echo(value: $v) }"#;
+        let mut vars = Variables::new();
+        vars.insert("v".to_owned(), InputValue::scalar("plain string"));
+
+        let (result, errs) = crate::execute(doc, None, &schema(), &vars, &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(result, graphql_value!({"echo": "plain string"}));
+    }
+
+    #[tokio::test]
+    async fn rejects_non_object_literal_for_json_object() {
+        let doc = r#"{ settings(overrides: ["dark"]) }"#;
+
+        let schema = schema();
+        let err = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Expected `JSONObject`"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn converts_maps_through_serde() {
+        let schema = schema();
+
+        let doc = r#"{ limits(overrides: { burst: 5 }) }"#;
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            graphql_value!({"limits": {"burst": 5, "requests": 100}}),
+        );
+
+        let doc = r#"{ limits(overrides: { burst: "many" }) }"#;
+        let (_, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+        assert_eq!(errs.len(), 1);
+        assert!(
+            errs[0].error().message().contains("expected i32"),
+            "{:?}",
+            errs,
+        );
+    }
+}
@@ -0,0 +1,327 @@
+/*!
+
+# Supported types
+
+| Rust Type           | GraphQL Scalar          | JSON Serialization     | Notes                                   |
+|---------------------|-------------------------|------------------------|-----------------------------------------|
+| `OffsetDateTime`    | `DateTimeFixedOffset`   | RFC3339 string         |                                         |
+| `Date`              | `NaiveDate`             | YYYY-MM-DD             |                                         |
+| `PrimitiveDateTime` | `NaiveDateTime`         | float (unix timestamp) | JSON numbers (i.e. IEEE doubles) are    |
+|                     |                         |                        | not precise enough for nanoseconds.     |
+|                     |                         |                        | Values will be truncated to second      |
+|                     |                         |                        | resolution.                             |
+| `Time`              | `NaiveTime`             | H:M:S                  | Optional. Use the `scalar-naivetime`    |
+|                     |                         |                        | feature.                                |
+| `Duration`          | `Duration`              | ISO 8601 duration      | E.g. `PT1H30M` or `-P1DT0.5S`. Years    |
+|                     |                         |                        | and months aren't accepted.             |
+
+The scalars share their names and formats with the `chrono` integration, so
+switching between both crates doesn't change the schema.
+
+*/
+#![allow(clippy::needless_lifetimes)]
+use std::convert::TryFrom;
+
+#[cfg(feature = "scalar-naivetime")]
+use time::Time;
+use time::{
+    format_description::{well_known::Rfc3339, FormatItem},
+    macros::format_description,
+    Date, Duration, OffsetDateTime, PrimitiveDateTime,
+};
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, ParseScalarValue},
+    Value,
+};
+
+use super::iso8601_duration;
+
+const DATE_FORMAT: &[FormatItem<'_>] = format_description!("[year]-[month]-[day]");
+
+#[cfg(feature = "scalar-naivetime")]
+const TIME_FORMAT: &[FormatItem<'_>] = format_description!("[hour]:[minute]:[second]");
+
+#[crate::graphql_scalar(name = "DateTimeFixedOffset", description = "DateTime")]
+impl<S> GraphQLScalar for OffsetDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(&Rfc3339)
+                .expect("`OffsetDateTime` out of RFC 3339 range"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<OffsetDateTime, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                OffsetDateTime::parse(s, &Rfc3339)
+                    .map_err(|_| format!("Expected RFC 3339 timestamp, found `{}`", s))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[crate::graphql_scalar(name = "NaiveDate", description = "NaiveDate")]
+impl<S> GraphQLScalar for Date
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(DATE_FORMAT)
+                .expect("`Date` out of `YYYY-MM-DD` range"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Date, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                Date::parse(s, DATE_FORMAT)
+                    .map_err(|_| format!("Expected `YYYY-MM-DD` date, found `{}`", s))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(feature = "scalar-naivetime")]
+#[crate::graphql_scalar(name = "NaiveTime", description = "NaiveTime")]
+impl<S> GraphQLScalar for Time
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(
+            self.format(TIME_FORMAT)
+                .expect("`Time` out of `HH:MM:SS` range"),
+        )
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Time, String> {
+        v.as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))
+            .and_then(|s| {
+                Time::parse(s, TIME_FORMAT)
+                    .map_err(|_| format!("Expected `HH:MM:SS` time, found `{}`", s))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+// JSON numbers (i.e. IEEE doubles) are not precise enough for nanosecond
+// datetimes. Values will be truncated to second resolution, like the `chrono`
+// `NaiveDateTime` ones.
+#[crate::graphql_scalar(name = "NaiveDateTime", description = "NaiveDateTime")]
+impl<S> GraphQLScalar for PrimitiveDateTime
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.assume_utc().unix_timestamp() as f64)
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<PrimitiveDateTime, String> {
+        v.as_float_value()
+            .ok_or_else(|| format!("Expected `Float`, found: {}", v))
+            .and_then(|f| {
+                OffsetDateTime::from_unix_timestamp(f as i64)
+                    .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
+                    .map_err(|_| format!("Out-of-range number of seconds: {}", f))
+            })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        <f64 as ParseScalarValue<S>>::from_str(value)
+    }
+}
+
+#[crate::graphql_scalar(description = "Duration")]
+impl<S> GraphQLScalar for Duration
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(iso8601_duration::format(self.whole_nanoseconds()))
+    }
+
+    fn from_input_value(v: &InputValue) -> Result<Duration, String> {
+        let s = v
+            .as_string_value()
+            .ok_or_else(|| format!("Expected `String`, found: {}", v))?;
+        let (negative, duration) = iso8601_duration::parse(s)?;
+        let duration = Duration::try_from(duration)
+            .map_err(|_| format!("Out-of-range `Duration`: `{}`", s))?;
+        Ok(if negative { -duration } else { duration })
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use time::{macros::datetime, Date, Duration, Month, OffsetDateTime, PrimitiveDateTime};
+
+    use crate::{value::DefaultScalarValue, FromInputValue, InputValue};
+
+    #[test]
+    fn offset_date_time_from_input_value() {
+        let input: InputValue<DefaultScalarValue> =
+            InputValue::scalar("2014-11-28T21:00:09.05+09:00");
+
+        let parsed: OffsetDateTime = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, datetime!(2014-11-28 21:00:09.05 +09:00));
+    }
+
+    #[test]
+    fn offset_date_time_from_invalid_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("2021-13-01");
+
+        let err = <OffsetDateTime as FromInputValue>::from_input_value(&input).unwrap_err();
+
+        assert_eq!(err, "Expected RFC 3339 timestamp, found `2021-13-01`");
+    }
+
+    #[test]
+    fn date_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("1996-12-19");
+
+        let parsed: Date = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(
+            parsed,
+            Date::from_calendar_date(1996, Month::December, 19).unwrap(),
+        );
+    }
+
+    #[cfg(feature = "scalar-naivetime")]
+    #[test]
+    fn time_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("21:12:19");
+
+        let parsed: time::Time = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, time::macros::time!(21:12:19));
+    }
+
+    #[test]
+    fn primitive_date_time_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar(1_000_000_000_f64);
+
+        let parsed: PrimitiveDateTime = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, datetime!(2001-09-09 01:46:40));
+    }
+
+    #[test]
+    fn duration_from_input_value() {
+        let input: InputValue<DefaultScalarValue> = InputValue::scalar("PT1H30M0.000000001S");
+
+        let parsed: Duration = FromInputValue::from_input_value(&input).unwrap();
+
+        assert_eq!(parsed, Duration::minutes(90) + Duration::nanoseconds(1));
+    }
+}
+
+#[cfg(test)]
+mod integration_test {
+    use time::{macros::datetime, Duration, OffsetDateTime, PrimitiveDateTime};
+
+    use crate::{
+        executor::Variables,
+        schema::model::RootNode,
+        types::scalars::{EmptyMutation, EmptySubscription},
+        value::Value,
+    };
+
+    #[tokio::test]
+    async fn test_serialization() {
+        struct Root;
+
+        #[crate::graphql_object]
+        impl Root {
+            fn exampleDate() -> time::Date {
+                datetime!(2015-03-14 0:00).date()
+            }
+            fn examplePrimitiveDateTime() -> PrimitiveDateTime {
+                datetime!(2016-07-08 09:10:11)
+            }
+            fn exampleOffsetDateTime() -> OffsetDateTime {
+                datetime!(1996-12-19 16:39:57 -08:00)
+            }
+            fn exampleDuration() -> Duration {
+                -Duration::seconds_f64(1.5)
+            }
+        }
+
+        let doc = r#"
+        {
+            exampleDate,
+            examplePrimitiveDateTime,
+            exampleOffsetDateTime,
+            exampleDuration,
+        }
+        "#;
+
+        let schema = RootNode::new(
+            Root,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let (result, errs) = crate::execute(doc, None, &schema, &Variables::new(), &())
+            .await
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(
+                vec![
+                    ("exampleDate", Value::scalar("2015-03-14")),
+                    ("examplePrimitiveDateTime", Value::scalar(1_467_969_011.0)),
+                    (
+                        "exampleOffsetDateTime",
+                        Value::scalar("1996-12-19T16:39:57-08:00"),
+                    ),
+                    ("exampleDuration", Value::scalar("-PT1.5S")),
+                ]
+                .into_iter()
+                .collect()
+            ),
+        );
+    }
+}
@@ -97,7 +97,7 @@ Juniper has not reached 1.0 yet, thus some API instability should be expected.
 // path correctly, without errors.
 extern crate self as juniper;
 
-use std::fmt;
+use std::{fmt, time::Instant};
 
 // These are required by the code generated via the `juniper_codegen` macros.
 #[doc(hidden)]
@@ -119,9 +119,13 @@ mod value;
 #[macro_use]
 mod macros;
 mod ast;
+pub mod cost;
 pub mod executor;
+#[cfg(feature = "federation")]
+pub mod federation;
 mod introspection;
 pub mod parser;
+pub mod printer;
 pub(crate) mod schema;
 mod types;
 mod util;
@@ -145,6 +149,7 @@ pub use crate::util::to_camel_case;
 use crate::introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS, IntrospectionFormat};
 
 use crate::{
+    cost::{CostReport, RateLimitClient, RateLimiter},
     executor::{execute_validated_query, get_operation},
     parser::parse_document_source,
     validation::{validate_input_values, visit_all_rules, ValidatorContext},
@@ -156,23 +161,26 @@ pub use crate::{
         ToInputValue, Type,
     },
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, Context, ExecutionError, ExecutionResult, ExecutionTrace, ExecutionTracer,
+        Executor, FieldError, FieldResult, FromContext, IntoFieldError, IntoResolvable,
+        LookAheadArgument, LookAheadMethods, LookAheadSelection, LookAheadValue, OwnedExecutor,
+        Registry, TraceFormat, ValuesStream, Variables,
     },
 
     macros::helper::{
         subscription::{ExtractTypeFromStream, IntoFieldResult},
-        AsDynGraphQLValue,
+        AsDynGraphQLValue, ExtractError,
     },
     parser::{ParseError, Spanning},
     schema::{
+        from_introspection::IntrospectionResultError,
         meta,
-        model::{RootNode, SchemaType},
+        model::{DirectiveLocation, DirectiveType, RootNode, SchemaType},
     },
     types::{
         async_await::{DynGraphQLValueAsync, GraphQLTypeAsync, GraphQLValueAsync},
         base::{Arguments, DynGraphQLValue, GraphQLType, GraphQLValue, TypeKind},
+        maps::{MapEntries, MapEntry},
         marker::{self, GraphQLInterface, GraphQLUnion},
         nullable::Nullable,
         scalars::{EmptyMutation, EmptySubscription, ID},
@@ -185,6 +193,9 @@ pub use crate::{
     value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
 };
 
+#[cfg(feature = "schema-language")]
+pub use crate::schema::{diff as schema_diff, snapshot as schema_snapshot};
+
 /// An error that prevented query execution
 #[derive(Debug, PartialEq)]
 #[allow(missing_docs)]
@@ -276,11 +287,284 @@ where
     SubscriptionT::TypeInfo: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let document = parse_document_source(document_source, &root_node.schema)?;
+    execute_with_deadline(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        None,
+    )
+    .await
+}
+
+/// Execute a query in a provided schema, which has to be resolved before the provided `deadline`
+///
+/// Root fields that are still resolving once the `deadline` passes resolve to an error with a
+/// `TIMEOUT` code. See [`executor::execute_validated_query_async_with_deadline`] for details.
+pub async fn execute_with_deadline<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    deadline: Option<Instant>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_with_tracer(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        deadline,
+        None,
+    )
+    .await
+}
 
+/// Execute a query in a provided schema, measuring the time of its parsing, validation and each
+/// resolved field
+///
+/// The returned [`ExecutionTrace`] can be reported to clients as [Apollo Tracing][1] or as a
+/// federated trace with [`ExecutionTrace::to_extension`].
+///
+/// [1]: https://github.com/apollographql/apollo-tracing
+pub async fn execute_traced<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    deadline: Option<Instant>,
+) -> (
+    Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    ExecutionTrace,
+)
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let tracer = ExecutionTracer::new();
+    let res = execute_with_tracer(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        deadline,
+        Some(&tracer),
+    )
+    .await;
+    (res, tracer.finish())
+}
+
+/// Execute a query in a provided schema, charging its estimated cost to the client of the
+/// `context` in the provided [`RateLimiter`]
+///
+/// The cost is estimated with [`cost::operation_cost`] once the query is validated, and the query
+/// is only executed if its client has enough tokens left for it. Otherwise, it resolves to a
+/// single error with a `RATE_LIMITED` code, as described in [`cost::RateLimitExceeded`].
+///
+/// The returned [`CostReport`] is [`None`] only if the query failed to parse or validate.
+pub async fn execute_rate_limited<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    limiter: &RateLimiter,
+) -> (
+    Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    Option<CostReport>,
+)
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: RateLimitClient + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let document = match parse_document_source(document_source, &root_node.schema) {
+        Ok(document) => document,
+        Err(e) => return (Err(e.into()), None),
+    };
+    let operation = match validate_operation(
+        &document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        None,
+    ) {
+        Ok(operation) => operation,
+        Err(e) => return (Err(e), None),
+    };
+
+    let cost = cost::operation_cost(
+        &document,
+        operation,
+        &root_node.schema,
+        variables,
+        limiter.cost_config(),
+    );
+    let remaining = match limiter.try_acquire(context.client_id(), cost) {
+        Ok(remaining) => remaining,
+        Err(e) => {
+            let report = CostReport {
+                cost,
+                remaining: e.remaining,
+                capacity: limiter.capacity(),
+            };
+            let errors = vec![ExecutionError::at_origin(e.into_field_error())];
+            return (Ok((Value::null(), errors)), Some(report));
+        }
+    };
+    let report = CostReport {
+        cost,
+        remaining,
+        capacity: limiter.capacity(),
+    };
+
+    let res = executor::execute_validated_query_async(
+        &document, operation, root_node, variables, context,
+    )
+    .await;
+    (res, Some(report))
+}
+
+async fn execute_with_tracer<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    deadline: Option<Instant>,
+    tracer: Option<&ExecutionTracer>,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let started = Instant::now();
+    let document = parse_document_source(document_source, &root_node.schema);
+    if let Some(tracer) = tracer {
+        tracer.record_parsing(started, Instant::now());
+    }
+    let document = document?;
+    let operation = validate_operation(
+        &document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        tracer,
+    )?;
+
+    executor::execute_validated_query_async_traced(
+        &document, operation, root_node, variables, context, deadline, tracer,
+    )
+    .await
+}
+
+/// Execute an already parsed query in a provided schema
+#[cfg(feature = "serde_json")]
+pub(crate) fn execute_document_sync<'a, S, QueryT, MutationT, SubscriptionT>(
+    document: &Document<'a, S>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    let operation = validate_operation(
+        document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        None,
+    )?;
+    execute_validated_query(document, operation, root_node, variables, context)
+}
+
+/// Execute an already parsed query in a provided schema
+#[cfg(feature = "serde_json")]
+pub(crate) async fn execute_document<'a, S, QueryT, MutationT, SubscriptionT>(
+    document: &Document<'a, S>,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let operation = validate_operation(
+        document,
+        operation_name,
+        &root_node.schema,
+        variables,
+        None,
+    )?;
+    executor::execute_validated_query_async(document, operation, root_node, variables, context)
+        .await
+}
+
+/// Validate the `document` against the `schema`, and the `variables` against its operation to
+/// execute, returning that operation
+///
+/// The validation of the `document` is recorded by the `tracer`, if any.
+fn validate_operation<'b, 'd, 'e, S>(
+    document: &'b Document<'d, S>,
+    operation_name: Option<&str>,
+    schema: &SchemaType<S>,
+    variables: &Variables<S>,
+    tracer: Option<&ExecutionTracer>,
+) -> Result<&'b Spanning<Operation<'d, S>>, GraphQLError<'e>>
+where
+    S: ScalarValue,
+{
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
-        visit_all_rules(&mut ctx, &document);
+        let started = Instant::now();
+        let mut ctx = ValidatorContext::new(schema, document);
+        visit_all_rules(&mut ctx, document);
+        if let Some(tracer) = tracer {
+            tracer.record_validation(started, Instant::now());
+        }
 
         let errors = ctx.into_errors();
         if !errors.is_empty() {
@@ -288,18 +572,17 @@ where
         }
     }
 
-    let operation = get_operation(&document, operation_name)?;
+    let operation = get_operation(document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let errors = validate_input_values(variables, operation, schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
-        .await
+    Ok(operation)
 }
 
 /// Resolve subscription into `ValuesStream`
@@ -371,6 +654,29 @@ where
     )
 }
 
+/// Parse and validate a document against the provided schema, without executing it
+///
+/// Useful with a schema built by [`SchemaType::from_introspection`], to check client documents
+/// against a remote schema.
+pub fn validate_document_source<'a, S>(
+    document_source: &'a str,
+    schema: &SchemaType<S>,
+) -> Result<(), GraphQLError<'a>>
+where
+    S: ScalarValue,
+{
+    let document = parse_document_source(document_source, schema)?;
+
+    let mut ctx = ValidatorContext::new(schema, &document);
+    visit_all_rules(&mut ctx, &document);
+
+    let errors = ctx.into_errors();
+    if !errors.is_empty() {
+        return Err(GraphQLError::ValidationError(errors));
+    }
+    Ok(())
+}
+
 impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
     fn from(f: Spanning<ParseError<'a>>) -> GraphQLError<'a> {
         GraphQLError::ParseError(f)
@@ -32,3 +32,18 @@ pub trait AsDynGraphQLValue<S: ScalarValue = DefaultScalarValue> {
 }
 
 crate::sa::assert_obj_safe!(AsDynGraphQLValue<Context = (), TypeInfo = ()>);
+
+/// Extraction of the error type of a [`Result`].
+///
+/// Used in `#[graphql_scalar]` macro to infer the [`FromInputValue::Error`] from the return type
+/// of the `from_input_value` method, even when it's written as a type alias.
+///
+/// [`FromInputValue::Error`]: crate::FromInputValue::Error
+pub trait ExtractError {
+    /// Type of the error of this [`Result`].
+    type Error;
+}
+
+impl<T, E> ExtractError for Result<T, E> {
+    type Error = E;
+}
@@ -26,9 +26,20 @@ pub fn parse_document_source<'a, 'b, S>(
 where
     S: ScalarValue,
 {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("parse_document_source").entered();
+
     let mut lexer = Lexer::new(s);
-    let mut parser = Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError))?;
-    parse_document(&mut parser, schema)
+    let res = Parser::new(&mut lexer)
+        .map_err(|s| s.map(ParseError::LexerError))
+        .and_then(|mut parser| parse_document(&mut parser, schema));
+
+    #[cfg(feature = "tracing")]
+    if let Err(e) = &res {
+        tracing::debug!(error = %e, "document parsing failed");
+    }
+
+    res
 }
 
 fn parse_document<'a, 'b, S>(
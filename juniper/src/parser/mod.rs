@@ -12,6 +12,8 @@ mod tests;
 
 pub use self::document::parse_document_source;
 
+pub(crate) use self::value::parse_value_literal;
+
 pub use self::{
     lexer::{Lexer, LexerError, ScalarToken, Token},
     parser::{OptionParseResult, ParseError, ParseResult, Parser, UnlocatedParseResult},
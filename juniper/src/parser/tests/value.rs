@@ -233,3 +233,26 @@ fn input_value_literals() {
         )
     );
 }
+
+#[test]
+fn object_literals_for_scalars() {
+    let mut lexer = Lexer::new("{key: 123}");
+    let mut parser = Parser::new(&mut lexer).expect("Lexer error");
+    let schema = SchemaType::new::<Query, EmptyMutation<()>, EmptySubscription<()>>(&(), &(), &());
+
+    let int_meta = scalar_meta::<i32>("Int");
+    assert!(parse_value_literal(&mut parser, false, &schema, Some(&int_meta)).is_err());
+
+    let json_meta =
+        MetaType::Scalar(ScalarMeta::new::<i32>("JSON".into()).accept_composite_values());
+    assert_eq!(
+        parse_value::<DefaultScalarValue>("{key: 123}", &json_meta)
+            .item
+            .to_object_value(),
+        Some(
+            vec![("key", &InputValue::scalar(123))]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
+        ),
+    );
+}
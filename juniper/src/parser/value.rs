@@ -3,7 +3,7 @@ use crate::ast::InputValue;
 use crate::{
     parser::{ParseError, ParseResult, Parser, ScalarToken, SourcePosition, Spanning, Token},
     schema::{
-        meta::{InputObjectMeta, MetaType},
+        meta::{InputObjectMeta, MetaType, ScalarMeta},
         model::SchemaType,
     },
     value::ScalarValue,
@@ -33,13 +33,29 @@ where
             },
             None,
         ) => parse_object_literal(parser, is_const, schema, None),
+        // Scalars opting into composite values, like `_Any` or `JSON`, may accept objects,
+        // which are validated later on. Their fields are parsed as the same scalar.
         (
             &Spanning {
                 item: Token::CurlyOpen,
                 ..
             },
-            Some(&MetaType::InputObject(ref o)),
-        ) => parse_object_literal(parser, is_const, schema, Some(o)),
+            Some(
+                t
+                @
+                &MetaType::Scalar(ScalarMeta {
+                    accepts_composite_values: true,
+                    ..
+                }),
+            ),
+        )
+        | (
+            &Spanning {
+                item: Token::CurlyOpen,
+                ..
+            },
+            Some(t @ &MetaType::InputObject(_)),
+        ) => parse_object_literal(parser, is_const, schema, Some(t)),
         (
             &Spanning {
                 item: Token::Dollar,
@@ -141,7 +157,7 @@ fn parse_object_literal<'a, 'b, S>(
     parser: &mut Parser<'a>,
     is_const: bool,
     schema: &'b SchemaType<'b, S>,
-    object_tpe: Option<&InputObjectMeta<'b, S>>,
+    object_tpe: Option<&MetaType<'b, S>>,
 ) -> ParseResult<'a, InputValue<S>>
 where
     S: ScalarValue,
@@ -159,16 +175,23 @@ fn parse_object_field<'a, 'b, S>(
     parser: &mut Parser<'a>,
     is_const: bool,
     schema: &'b SchemaType<'b, S>,
-    object_meta: Option<&InputObjectMeta<'b, S>>,
+    object_meta: Option<&MetaType<'b, S>>,
 ) -> ParseResult<'a, (Spanning<String>, Spanning<InputValue<S>>)>
 where
     S: ScalarValue,
 {
     let key = parser.expect_name()?;
 
-    let tpe = object_meta
-        .and_then(|o| o.input_fields.iter().find(|f| f.name == key.item))
-        .and_then(|f| schema.lookup_type(&f.arg_type));
+    let tpe = match object_meta {
+        Some(&MetaType::InputObject(InputObjectMeta {
+            ref input_fields, ..
+        })) => input_fields
+            .iter()
+            .find(|f| f.name == key.item)
+            .and_then(|f| schema.lookup_type(&f.arg_type)),
+        scalar @ Some(&MetaType::Scalar(_)) => scalar,
+        _ => None,
+    };
 
     parser.expect(&Token::Colon)?;
 
@@ -0,0 +1,620 @@
+//! Printing of executable documents, either minified or as canonical operation signatures.
+//!
+//! ```rust
+//! # use juniper::{
+//! #     graphql_object, parser::parse_document_source, printer::{minify, OperationSignature},
+//! #     DefaultScalarValue, EmptyMutation, EmptySubscription, RootNode,
+//! # };
+//! # struct Query;
+//! # #[graphql_object]
+//! # impl Query {
+//! #     fn user(id: i32) -> &'static str { "Alice" }
+//! # }
+//! # let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//! let document = parse_document_source::<DefaultScalarValue>(
+//!     r#"
+//!     query User {
+//!         user(id: 42)
+//!         __typename
+//!     }
+//!     "#,
+//!     &schema.schema,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(minify(&document), "query User{user(id:42)__typename}");
+//!
+//! let signature = OperationSignature::new(&document, Some("User")).unwrap();
+//! assert_eq!(signature.as_str(), "query User{__typename user(id:0)}");
+//! ```
+
+use std::{collections::HashSet, fmt, hash::Hasher};
+
+use fnv::FnvHasher;
+
+use crate::{
+    ast::{
+        Arguments, Definition, Directive, Document, Fragment, InputValue, Operation, OperationType,
+        Selection, VariableDefinitions,
+    },
+    executor::get_operation,
+    parser::Spanning,
+    value::ScalarValue,
+    GraphQLError,
+};
+
+/// Prints the `document` with all the insignificant whitespace and commas removed.
+pub fn minify<S>(document: &Document<S>) -> String
+where
+    S: ScalarValue,
+{
+    let mut printer = Printer::new(false);
+    for definition in document {
+        printer.definition(definition);
+    }
+    printer.out
+}
+
+/// Canonical signature of an operation, identifying it regardless of its formatting and literal
+/// values, for metrics or allow-lists.
+///
+/// The signature is the minified operation followed by the fragments it uses, sorted by name,
+/// with:
+/// - string, number, list and object literals replaced by `""`, `0`, `[]` and `{}`;
+/// - fields, fragment spreads, inline fragments, arguments and variable definitions sorted;
+/// - unused fragments and other operations dropped.
+///
+/// Aliases, directives, booleans, enums and `null`s are kept, as they change the shape or the
+/// meaning of the result.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OperationSignature {
+    signature: String,
+}
+
+impl OperationSignature {
+    /// Computes the signature of the `document` operation with the given name, or of its only
+    /// operation.
+    ///
+    /// # Errors
+    ///
+    /// If there's no such operation, or the operation name is ambiguous.
+    pub fn new<S>(
+        document: &Document<S>,
+        operation_name: Option<&str>,
+    ) -> Result<Self, GraphQLError<'static>>
+    where
+        S: ScalarValue,
+    {
+        let operation = get_operation(document, operation_name)?;
+
+        let mut fragments = vec![];
+        let mut visited = HashSet::new();
+        collect_fragments(
+            document,
+            &operation.item.selection_set,
+            &mut visited,
+            &mut fragments,
+        );
+        fragments.sort_by_key(|f| f.name.item);
+
+        let mut printer = Printer::new(true);
+        printer.operation(&operation.item);
+        for fragment in fragments {
+            printer.fragment(fragment);
+        }
+        Ok(Self {
+            signature: printer.out,
+        })
+    }
+
+    /// The signature itself.
+    pub fn as_str(&self) -> &str {
+        &self.signature
+    }
+
+    /// 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash of the signature.
+    ///
+    /// It's stable across processes, platforms and releases, so it can be persisted.
+    pub fn hash(&self) -> u64 {
+        let mut hasher = FnvHasher::default();
+        hasher.write(self.signature.as_bytes());
+        hasher.finish()
+    }
+
+    /// [`OperationSignature::hash`] as 16 lowercase hexadecimal digits.
+    pub fn hash_hex(&self) -> String {
+        format!("{:016x}", self.hash())
+    }
+}
+
+impl fmt::Display for OperationSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.signature)
+    }
+}
+
+impl<'a, S> fmt::Display for Definition<'a, S>
+where
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut printer = Printer::new(false);
+        printer.definition(self);
+        f.write_str(&printer.out)
+    }
+}
+
+impl<'a, S> fmt::Display for Operation<'a, S>
+where
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut printer = Printer::new(false);
+        printer.operation(self);
+        f.write_str(&printer.out)
+    }
+}
+
+impl<'a, S> fmt::Display for Fragment<'a, S>
+where
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut printer = Printer::new(false);
+        printer.fragment(self);
+        f.write_str(&printer.out)
+    }
+}
+
+fn collect_fragments<'d, 'a, S>(
+    document: &'d Document<'a, S>,
+    selection_set: &'d [Selection<'a, S>],
+    visited: &mut HashSet<&'a str>,
+    fragments: &mut Vec<&'d Fragment<'a, S>>,
+) {
+    for selection in selection_set {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(selection_set) = &field.item.selection_set {
+                    collect_fragments(document, selection_set, visited, fragments);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                collect_fragments(document, &fragment.item.selection_set, visited, fragments);
+            }
+            Selection::FragmentSpread(spread) => {
+                let name = spread.item.name.item;
+                if !visited.insert(name) {
+                    continue;
+                }
+                let fragment = document.iter().find_map(|d| match d {
+                    Definition::Fragment(f) if f.item.name.item == name => Some(&f.item),
+                    _ => None,
+                });
+                if let Some(fragment) = fragment {
+                    fragments.push(fragment);
+                    collect_fragments(document, &fragment.selection_set, visited, fragments);
+                }
+            }
+        }
+    }
+}
+
+/// Writes tokens separated only where the lexer needs it, hiding literals and sorting the
+/// unordered parts of the document when `canonical`.
+struct Printer {
+    out: String,
+    canonical: bool,
+}
+
+impl Printer {
+    fn new(canonical: bool) -> Self {
+        Self {
+            out: String::new(),
+            canonical,
+        }
+    }
+
+    fn token(&mut self, token: &str) {
+        let is_punctuator = |c: char| "!$&()...:=@[]{|}".contains(c);
+        if let (Some(last), Some(first)) = (self.out.chars().last(), token.chars().next()) {
+            if !is_punctuator(last) && !is_punctuator(first) {
+                self.out.push(' ');
+            }
+        }
+        self.out.push_str(token);
+    }
+
+    /// Prints each of the `items` on its own, then emits them in order, sorted when canonical.
+    fn list<T>(&mut self, items: &[T], print: impl Fn(&mut Self, &T)) {
+        let mut printed = items
+            .iter()
+            .map(|item| {
+                let mut printer = Self::new(self.canonical);
+                print(&mut printer, item);
+                printer.out
+            })
+            .collect::<Vec<_>>();
+        if self.canonical {
+            printed.sort();
+        }
+        for item in printed {
+            self.token(&item);
+        }
+    }
+
+    fn definition<S: ScalarValue>(&mut self, definition: &Definition<S>) {
+        match definition {
+            Definition::Operation(op) => self.operation(&op.item),
+            Definition::Fragment(f) => self.fragment(&f.item),
+        }
+    }
+
+    fn operation<S: ScalarValue>(&mut self, operation: &Operation<S>) {
+        let is_shorthand = operation.operation_type == OperationType::Query
+            && operation.name.is_none()
+            && operation.variable_definitions.is_none()
+            && operation.directives.is_none();
+        if !is_shorthand {
+            self.token(match operation.operation_type {
+                OperationType::Query => "query",
+                OperationType::Mutation => "mutation",
+                OperationType::Subscription => "subscription",
+            });
+            if let Some(name) = &operation.name {
+                self.token(name.item);
+            }
+            if let Some(defs) = &operation.variable_definitions {
+                self.variable_definitions(&defs.item);
+            }
+            self.directives(&operation.directives);
+        }
+        self.selection_set(&operation.selection_set);
+    }
+
+    fn fragment<S: ScalarValue>(&mut self, fragment: &Fragment<S>) {
+        self.token("fragment");
+        self.token(fragment.name.item);
+        self.token("on");
+        self.token(fragment.type_condition.item);
+        self.directives(&fragment.directives);
+        self.selection_set(&fragment.selection_set);
+    }
+
+    fn variable_definitions<S: ScalarValue>(&mut self, defs: &VariableDefinitions<S>) {
+        self.token("(");
+        self.list(&defs.items, |p, (name, def)| {
+            p.token("$");
+            p.token(name.item);
+            p.token(":");
+            p.token(&def.var_type.item.to_string());
+            if let Some(default) = &def.default_value {
+                p.token("=");
+                p.value(&default.item);
+            }
+        });
+        self.token(")");
+    }
+
+    fn selection_set<S: ScalarValue>(&mut self, selection_set: &[Selection<S>]) {
+        self.token("{");
+        self.list(selection_set, Self::selection);
+        self.token("}");
+    }
+
+    fn selection<S: ScalarValue>(&mut self, selection: &Selection<S>) {
+        match selection {
+            Selection::Field(field) => {
+                let field = &field.item;
+                if let Some(alias) = &field.alias {
+                    self.token(alias.item);
+                    self.token(":");
+                }
+                self.token(field.name.item);
+                self.arguments(&field.arguments);
+                self.directives(&field.directives);
+                if let Some(selection_set) = &field.selection_set {
+                    self.selection_set(selection_set);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                self.token("...");
+                self.token(spread.item.name.item);
+                self.directives(&spread.item.directives);
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment = &fragment.item;
+                self.token("...");
+                if let Some(type_condition) = &fragment.type_condition {
+                    self.token("on");
+                    self.token(type_condition.item);
+                }
+                self.directives(&fragment.directives);
+                self.selection_set(&fragment.selection_set);
+            }
+        }
+    }
+
+    fn directives<S: ScalarValue>(&mut self, directives: &Option<Vec<Spanning<Directive<S>>>>) {
+        for directive in directives.iter().flatten() {
+            self.token("@");
+            self.token(directive.item.name.item);
+            self.arguments(&directive.item.arguments);
+        }
+    }
+
+    fn arguments<S: ScalarValue>(&mut self, arguments: &Option<Spanning<Arguments<S>>>) {
+        if let Some(arguments) = arguments {
+            self.token("(");
+            self.list(&arguments.item.items, |p, (name, value)| {
+                p.token(name.item);
+                p.token(":");
+                p.value(&value.item);
+            });
+            self.token(")");
+        }
+    }
+
+    fn value<S: ScalarValue>(&mut self, value: &InputValue<S>) {
+        match value {
+            InputValue::Null => self.token("null"),
+            InputValue::Enum(name) => self.token(name),
+            InputValue::Variable(name) => {
+                self.token("$");
+                self.token(name);
+            }
+            InputValue::Scalar(s) => {
+                if let Some(b) = s.as_boolean() {
+                    self.token(if b { "true" } else { "false" });
+                } else if let Some(s) = s.as_str() {
+                    self.token(&if self.canonical {
+                        "\"\"".into()
+                    } else {
+                        quote(s)
+                    });
+                } else if self.canonical {
+                    self.token("0");
+                } else if let Some(i) = s.as_int() {
+                    self.token(&i.to_string());
+                } else if let Some(f) = s.as_float() {
+                    // `Debug` keeps the fractional part of round floats, unlike `Display`.
+                    self.token(&format!("{:?}", f));
+                } else {
+                    self.token(&s.to_string());
+                }
+            }
+            InputValue::List(items) => {
+                self.token("[");
+                if !self.canonical {
+                    for item in items {
+                        self.value(&item.item);
+                    }
+                }
+                self.token("]");
+            }
+            InputValue::Object(fields) => {
+                self.token("{");
+                if !self.canonical {
+                    for (name, value) in fields {
+                        self.token(&name.item);
+                        self.token(":");
+                        self.value(&value.item);
+                    }
+                }
+                self.token("}");
+            }
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::OwnedDocument, graphql_object, parser::parse_document_source, DefaultScalarValue,
+        EmptyMutation, EmptySubscription, GraphQLError, RootNode,
+    };
+
+    use super::{minify, OperationSignature};
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn user(id: i32) -> &'static str {
+            let _ = id;
+            "Alice"
+        }
+
+        fn search(text: String, tags: Vec<String>, ratio: f64) -> Vec<&'static str> {
+            let _ = (text, tags, ratio);
+            vec![]
+        }
+    }
+
+    fn parse(source: &str) -> OwnedDocument<'_, DefaultScalarValue> {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        parse_document_source(source, &schema.schema).unwrap()
+    }
+
+    fn signature(source: &str) -> String {
+        OperationSignature::new(&parse(source), None)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn minifies_documents() {
+        let document = parse(
+            r#"
+            query Search($text: String! = "a \"quoted\"\n text", $tags: [String!]) @cached {
+                results: search(text: $text, tags: $tags, ratio: 1.0) @include(if: true)
+                ... on Query { user(id: -1) }
+                ...Rest
+            }
+
+            fragment Rest on Query {
+                __typename
+                user(id: 2) @skip(if: false)
+            }
+            "#,
+        );
+
+        let minified = minify(&document);
+
+        assert_eq!(
+            minified,
+            "query Search($text:String!=\"a \\\"quoted\\\"\\n text\"$tags:[String!])@cached{\
+             results:search(text:$text tags:$tags ratio:1.0)@include(if:true)\
+             ...on Query{user(id:-1)}...Rest}\
+             fragment Rest on Query{__typename user(id:2)@skip(if:false)}",
+        );
+        assert_eq!(minify(&parse(&minified)), minified);
+    }
+
+    #[test]
+    fn minifies_shorthand_queries_and_values() {
+        assert_eq!(
+            minify(&parse(
+                r#"{ search(text: "", tags: ["a", "b"], ratio: 2.5) { x } }"#
+            )),
+            r#"{search(text:"" tags:["a" "b"]ratio:2.5){x}}"#,
+        );
+    }
+
+    #[test]
+    fn displays_definitions() {
+        let document = parse("query Q { user(id: 1) } fragment F on Query { __typename }");
+
+        assert_eq!(document[0].to_string(), "query Q{user(id:1)}");
+        assert_eq!(document[1].to_string(), "fragment F on Query{__typename}");
+    }
+
+    #[test]
+    fn hides_literals_and_sorts_selections() {
+        assert_eq!(
+            signature(
+                r#"
+                query Q($b: Int = 3, $a: String) {
+                    user(id: 42)
+                    search(tags: ["x"], text: "secret", ratio: 0.5) { name }
+                    ...F
+                    ... on Query { __typename }
+                    alias: user(id: $b)
+                }
+                fragment F on Query { user(id: 1) }
+                "#,
+            ),
+            "query Q($a:String$b:Int=0){\
+             ...F...on Query{__typename}alias:user(id:$b)\
+             search(ratio:0 tags:[]text:\"\"){name}user(id:0)}\
+             fragment F on Query{user(id:0)}",
+        );
+    }
+
+    #[test]
+    fn drops_unused_fragments_and_other_operations() {
+        let document = parse(
+            r#"
+            query A { ...Used }
+            query B { ...Unused }
+            fragment Unused on Query { user(id: 1) }
+            fragment Used on Query { ...Nested }
+            fragment Nested on Query { __typename ...Used }
+            "#,
+        );
+
+        assert_eq!(
+            OperationSignature::new(&document, Some("A"))
+                .unwrap()
+                .as_str(),
+            "query A{...Used}\
+             fragment Nested on Query{...Used __typename}\
+             fragment Used on Query{...Nested}",
+        );
+        assert_eq!(
+            OperationSignature::new(&document, None),
+            Err(GraphQLError::MultipleOperationsProvided),
+        );
+    }
+
+    #[test]
+    fn hashes_equivalent_operations_the_same() {
+        let a = OperationSignature::new(
+            &parse(r#"{ user(id: 1) search(text: "a", tags: [], ratio: 1) }"#),
+            None,
+        )
+        .unwrap();
+        let b = OperationSignature::new(
+            &parse("{\n  search(ratio: 2, tags: [\"t\"], text: \"b\")\n  user(id: 7)\n}"),
+            None,
+        )
+        .unwrap();
+        let c = OperationSignature::new(&parse("{ user(id: 1) }"), None).unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.hash(), b.hash());
+        assert_ne!(a.hash(), c.hash());
+        assert_eq!(c.as_str(), "{user(id:0)}");
+        assert_eq!(c.hash_hex(), format!("{:016x}", c.hash()));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn minifies_big_int_literals_losslessly() {
+        use crate::integrations::bigint::BigInt;
+
+        struct BigIntQuery;
+
+        #[graphql_object]
+        impl BigIntQuery {
+            fn user(id: BigInt<i64>) -> String {
+                id.to_string()
+            }
+        }
+
+        let schema = RootNode::new(
+            BigIntQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let document = parse_document_source::<DefaultScalarValue>(
+            r#"{ a: user(id: 42) b: user(id: 9007199254740993) c: user(id: "7") }"#,
+            &schema.schema,
+        )
+        .unwrap();
+
+        let minified = minify(&document);
+        assert_eq!(
+            minified,
+            r#"{a:user(id:42)b:user(id:"9007199254740993")c:user(id:"7")}"#,
+        );
+
+        let reparsed =
+            parse_document_source::<DefaultScalarValue>(&minified, &schema.schema).unwrap();
+        assert_eq!(minify(&reparsed), minified);
+    }
+}
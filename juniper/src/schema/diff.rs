@@ -0,0 +1,1057 @@
+//! Detection of breaking changes between two versions of a schema.
+//!
+//! Schemas are compared through their [`graphql_parser`] representation, the one printed by
+//! [`RootNode::as_schema_language`](crate::RootNode::as_schema_language), so a [`SchemaType`] can
+//! be compared with another one or with a schema language document, like a schema committed to a
+//! repository:
+//!
+//! ```rust
+//! use juniper::{graphql_object, schema_diff, EmptyMutation, EmptySubscription, RootNode};
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn name() -> String {
+//!         "Alice".into()
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//! let committed = "type Query { name: String! email: String }";
+//!
+//! let diff = schema_diff::diff_schema_language(committed, &schema.schema).unwrap();
+//!
+//! assert!(diff.has_breaking_changes());
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "BREAKING: Field `Query.email` was removed\n",
+//! );
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+use graphql_parser::schema::{
+    parse_schema, Definition, DirectiveDefinition, Document, EnumType, Field, InputValue,
+    ParseError, Type, TypeDefinition,
+};
+
+use crate::{
+    schema::{
+        model::SchemaType,
+        translate::{graphql_parser::GraphQLParserTranslator, SchemaTranslator},
+    },
+    value::ScalarValue,
+};
+
+/// How a [`SchemaChange`] affects existing clients of a schema.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Criticality {
+    /// The change can't break any client.
+    Safe,
+
+    /// The change doesn't break valid operations, but may change their results in ways clients
+    /// don't expect, like a new enum value or a new member of a union.
+    Dangerous,
+
+    /// The change breaks operations which were valid before, or the clients reading their
+    /// results.
+    Breaking,
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Safe => "SAFE",
+            Self::Dangerous => "DANGEROUS",
+            Self::Breaking => "BREAKING",
+        })
+    }
+}
+
+/// Single difference between two versions of a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SchemaChange {
+    /// How the change affects existing clients.
+    pub criticality: Criticality,
+
+    /// Dot-separated path to the changed schema element, like `User`, `User.name` for a field or
+    /// `Query.user.id` for an argument, and prefixed with `@` for directives.
+    pub path: String,
+
+    /// Human-readable description of the change.
+    pub message: String,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.criticality, self.message)
+    }
+}
+
+/// Differences between two versions of a schema, ordered by the path of the changed elements.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SchemaDiff {
+    changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// All the changes.
+    pub fn changes(&self) -> &[SchemaChange] {
+        &self.changes
+    }
+
+    /// Whether both versions of the schema are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Whether any change is [`Criticality::Breaking`].
+    pub fn has_breaking_changes(&self) -> bool {
+        self.breaking_changes().next().is_some()
+    }
+
+    /// The [`Criticality::Breaking`] changes.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.criticality == Criticality::Breaking)
+    }
+
+    /// The [`Criticality::Dangerous`] changes.
+    pub fn dangerous_changes(&self) -> impl Iterator<Item = &SchemaChange> {
+        self.changes
+            .iter()
+            .filter(|c| c.criticality == Criticality::Dangerous)
+    }
+}
+
+impl fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the `old` version of a schema with the `new` one.
+pub fn diff<'a, S1, S2>(old: &'a SchemaType<'a, S1>, new: &'a SchemaType<'a, S2>) -> SchemaDiff
+where
+    S1: ScalarValue + 'a,
+    S2: ScalarValue + 'a,
+{
+    diff_documents(
+        &GraphQLParserTranslator::translate_schema(old),
+        &GraphQLParserTranslator::translate_schema(new),
+    )
+}
+
+/// Compares the `old` version of a schema, written in the schema language, with the `new` one.
+///
+/// # Errors
+///
+/// If the `old` version isn't a valid schema language document.
+pub fn diff_schema_language<'a, S>(
+    old: &'a str,
+    new: &'a SchemaType<'a, S>,
+) -> Result<SchemaDiff, ParseError>
+where
+    S: ScalarValue + 'a,
+{
+    Ok(diff_documents(
+        &parse_schema(old)?,
+        &GraphQLParserTranslator::translate_schema(new),
+    ))
+}
+
+/// Compares the `old` version of a schema with the `new` one, both as [`graphql_parser`]
+/// documents.
+///
+/// Type extensions aren't taken into account, and neither are built-in scalars and directives.
+pub fn diff_documents<'a>(old: &Document<'a, String>, new: &Document<'a, String>) -> SchemaDiff {
+    let mut differ = Differ::default();
+
+    let (old_types, new_types) = (type_definitions(old), type_definitions(new));
+    let old_roots = root_type_names(old, &old_types);
+    let new_roots = root_type_names(new, &new_types);
+    for (i, kind) in ["Query", "Mutation", "Subscription"].iter().enumerate() {
+        differ.root_type(kind, old_roots[i].as_deref(), new_roots[i].as_deref());
+    }
+    differ.named(
+        &old_types,
+        &new_types,
+        |d, name, _| {
+            d.push(
+                Criticality::Breaking,
+                name,
+                format!("Type `{}` was removed", name),
+            )
+        },
+        |d, name, _| {
+            d.push(
+                Criticality::Safe,
+                name,
+                format!("Type `{}` was added", name),
+            )
+        },
+        |d, _, old, new| d.type_definition(old, new),
+    );
+
+    differ.named(
+        &directive_definitions(old),
+        &directive_definitions(new),
+        |d, name, _| {
+            let path = format!("@{}", name);
+            d.push(
+                Criticality::Breaking,
+                &path,
+                format!("Directive `{}` was removed", path),
+            );
+        },
+        |d, name, _| {
+            let path = format!("@{}", name);
+            d.push(
+                Criticality::Safe,
+                &path,
+                format!("Directive `{}` was added", path),
+            );
+        },
+        |d, _, old, new| d.directive_definition(old, new),
+    );
+
+    differ.changes.sort_by(|a, b| a.path.cmp(&b.path));
+    SchemaDiff {
+        changes: differ.changes,
+    }
+}
+
+const BUILTIN_SCALARS: &[&str] = &["Boolean", "Float", "ID", "Int", "String"];
+
+const BUILTIN_DIRECTIVES: &[&str] = &["deprecated", "include", "skip", "specifiedBy"];
+
+fn type_definitions<'d, 'a>(
+    doc: &'d Document<'a, String>,
+) -> BTreeMap<&'d str, &'d TypeDefinition<'a, String>> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(t) => Some((type_name(t), t)),
+            _ => None,
+        })
+        .filter(|(name, _)| !name.starts_with("__") && !BUILTIN_SCALARS.contains(name))
+        .collect()
+}
+
+fn directive_definitions<'d, 'a>(
+    doc: &'d Document<'a, String>,
+) -> BTreeMap<&'d str, &'d DirectiveDefinition<'a, String>> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::DirectiveDefinition(d) => Some((d.name.as_str(), d)),
+            _ => None,
+        })
+        .filter(|(name, _)| !BUILTIN_DIRECTIVES.contains(name))
+        .collect()
+}
+
+/// Names of the query, mutation and subscription types, defaulting to the types named after them
+/// without a `schema` definition.
+fn root_type_names<'a>(
+    doc: &Document<'a, String>,
+    types: &BTreeMap<&str, &TypeDefinition<'a, String>>,
+) -> [Option<String>; 3] {
+    let schema = doc.definitions.iter().find_map(|def| match def {
+        Definition::SchemaDefinition(s) => Some(s),
+        _ => None,
+    });
+    match schema {
+        Some(s) => [s.query.clone(), s.mutation.clone(), s.subscription.clone()],
+        None => {
+            let default = |name: &str| Some(name.to_owned()).filter(|n| types.contains_key(&**n));
+            [
+                default("Query"),
+                default("Mutation"),
+                default("Subscription"),
+            ]
+        }
+    }
+}
+
+fn type_name<'d, 'a>(t: &'d TypeDefinition<'a, String>) -> &'d str {
+    match t {
+        TypeDefinition::Scalar(t) => &t.name,
+        TypeDefinition::Object(t) => &t.name,
+        TypeDefinition::Interface(t) => &t.name,
+        TypeDefinition::Union(t) => &t.name,
+        TypeDefinition::Enum(t) => &t.name,
+        TypeDefinition::InputObject(t) => &t.name,
+    }
+}
+
+fn type_kind(t: &TypeDefinition<'_, String>) -> &'static str {
+    match t {
+        TypeDefinition::Scalar(_) => "a scalar",
+        TypeDefinition::Object(_) => "an object",
+        TypeDefinition::Interface(_) => "an interface",
+        TypeDefinition::Union(_) => "a union",
+        TypeDefinition::Enum(_) => "an enum",
+        TypeDefinition::InputObject(_) => "an input object",
+    }
+}
+
+fn type_description<'d>(t: &'d TypeDefinition<'_, String>) -> &'d Option<String> {
+    match t {
+        TypeDefinition::Scalar(t) => &t.description,
+        TypeDefinition::Object(t) => &t.description,
+        TypeDefinition::Interface(t) => &t.description,
+        TypeDefinition::Union(t) => &t.description,
+        TypeDefinition::Enum(t) => &t.description,
+        TypeDefinition::InputObject(t) => &t.description,
+    }
+}
+
+/// Indexes the provided `items` by their path under the `parent` one.
+fn by_path<'d, T>(
+    parent: &str,
+    items: &'d [T],
+    name: fn(&T) -> &String,
+) -> BTreeMap<String, &'d T> {
+    items
+        .iter()
+        .map(|item| (format!("{}.{}", parent, name(item)), item))
+        .collect()
+}
+
+fn is_deprecated(directives: &[graphql_parser::schema::Directive<'_, String>]) -> bool {
+    directives.iter().any(|d| d.name == "deprecated")
+}
+
+/// Whether every value of the `new` output type is a value of the `old` one, so clients reading
+/// it keep working.
+fn is_safe_output_change(old: &Type<'_, String>, new: &Type<'_, String>) -> bool {
+    match (old, new) {
+        (Type::NamedType(old), Type::NamedType(new)) => old == new,
+        (Type::ListType(old), Type::ListType(new))
+        | (Type::NonNullType(old), Type::NonNullType(new)) => is_safe_output_change(old, new),
+        (old, Type::NonNullType(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Whether every value of the `old` input type is a value of the `new` one, so clients providing
+/// it keep working.
+fn is_safe_input_change(old: &Type<'_, String>, new: &Type<'_, String>) -> bool {
+    match (old, new) {
+        (Type::NamedType(old), Type::NamedType(new)) => old == new,
+        (Type::ListType(old), Type::ListType(new))
+        | (Type::NonNullType(old), Type::NonNullType(new)) => is_safe_input_change(old, new),
+        (Type::NonNullType(old), new) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+fn is_required(input: &InputValue<'_, String>) -> bool {
+    matches!(input.value_type, Type::NonNullType(_)) && input.default_value.is_none()
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<SchemaChange>,
+}
+
+impl<'a> Differ {
+    fn push(&mut self, criticality: Criticality, path: &str, message: String) {
+        self.changes.push(SchemaChange {
+            criticality,
+            path: path.into(),
+            message,
+        });
+    }
+
+    /// Matches the `old` and `new` elements by name, calling the `removed`, `added` and `changed`
+    /// callbacks accordingly.
+    fn named<K, V>(
+        &mut self,
+        old: &BTreeMap<K, V>,
+        new: &BTreeMap<K, V>,
+        removed: impl Fn(&mut Self, &K, &V),
+        added: impl Fn(&mut Self, &K, &V),
+        changed: impl Fn(&mut Self, &K, &V, &V),
+    ) where
+        K: Ord,
+    {
+        for (name, old_value) in old {
+            match new.get(name) {
+                Some(new_value) => changed(self, name, old_value, new_value),
+                None => removed(self, name, old_value),
+            }
+        }
+        for (name, new_value) in new {
+            if !old.contains_key(name) {
+                added(self, name, new_value);
+            }
+        }
+    }
+
+    fn description(&mut self, path: &str, old: &Option<String>, new: &Option<String>) {
+        if old != new {
+            self.push(
+                Criticality::Safe,
+                path,
+                format!("Description of `{}` changed", path),
+            );
+        }
+    }
+
+    fn deprecation(&mut self, kind: &str, path: &str, old: bool, new: bool) {
+        match (old, new) {
+            (false, true) => self.push(
+                Criticality::Safe,
+                path,
+                format!("{} `{}` was deprecated", kind, path),
+            ),
+            (true, false) => self.push(
+                Criticality::Safe,
+                path,
+                format!("{} `{}` is no longer deprecated", kind, path),
+            ),
+            _ => {}
+        }
+    }
+
+    fn root_type(&mut self, kind: &str, old: Option<&str>, new: Option<&str>) {
+        let path = kind.to_owned();
+        match (old, new) {
+            (Some(old), Some(new)) if old != new => self.push(
+                Criticality::Breaking,
+                &path,
+                format!("{} root type changed from `{}` to `{}`", kind, old, new),
+            ),
+            (Some(old), None) => self.push(
+                Criticality::Breaking,
+                &path,
+                format!("{} root type `{}` was removed", kind, old),
+            ),
+            (None, Some(new)) => self.push(
+                Criticality::Safe,
+                &path,
+                format!("{} root type `{}` was added", kind, new),
+            ),
+            _ => {}
+        }
+    }
+
+    fn type_definition(
+        &mut self,
+        old: &TypeDefinition<'a, String>,
+        new: &TypeDefinition<'a, String>,
+    ) {
+        let name = type_name(new);
+        self.description(name, type_description(old), type_description(new));
+
+        match (old, new) {
+            (TypeDefinition::Scalar(_), TypeDefinition::Scalar(_)) => {}
+            (TypeDefinition::Object(old), TypeDefinition::Object(new)) => {
+                self.interfaces(name, &old.implements_interfaces, &new.implements_interfaces);
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinition::Interface(old), TypeDefinition::Interface(new)) => {
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinition::Union(old), TypeDefinition::Union(new)) => {
+                self.union_members(name, &old.types, &new.types);
+            }
+            (TypeDefinition::Enum(old), TypeDefinition::Enum(new)) => {
+                self.enum_values(name, old, new);
+            }
+            (TypeDefinition::InputObject(old), TypeDefinition::InputObject(new)) => {
+                self.input_fields(name, &old.fields, &new.fields);
+            }
+            _ => self.push(
+                Criticality::Breaking,
+                name,
+                format!(
+                    "Type `{}` changed from {} to {}",
+                    name,
+                    type_kind(old),
+                    type_kind(new),
+                ),
+            ),
+        }
+    }
+
+    fn interfaces(&mut self, type_name: &str, old: &[String], new: &[String]) {
+        for interface in old.iter().filter(|i| !new.contains(i)) {
+            self.push(
+                Criticality::Breaking,
+                type_name,
+                format!(
+                    "Type `{}` no longer implements interface `{}`",
+                    type_name, interface,
+                ),
+            );
+        }
+        for interface in new.iter().filter(|i| !old.contains(i)) {
+            self.push(
+                Criticality::Dangerous,
+                type_name,
+                format!(
+                    "Type `{}` now implements interface `{}`",
+                    type_name, interface,
+                ),
+            );
+        }
+    }
+
+    fn union_members(&mut self, union_name: &str, old: &[String], new: &[String]) {
+        for member in old.iter().filter(|m| !new.contains(m)) {
+            self.push(
+                Criticality::Breaking,
+                union_name,
+                format!(
+                    "Member `{}` was removed from union `{}`",
+                    member, union_name
+                ),
+            );
+        }
+        for member in new.iter().filter(|m| !old.contains(m)) {
+            self.push(
+                Criticality::Dangerous,
+                union_name,
+                format!("Member `{}` was added to union `{}`", member, union_name),
+            );
+        }
+    }
+
+    fn enum_values(
+        &mut self,
+        enum_name: &str,
+        old: &EnumType<'a, String>,
+        new: &EnumType<'a, String>,
+    ) {
+        self.named(
+            &by_path(enum_name, &old.values, |v| &v.name),
+            &by_path(enum_name, &new.values, |v| &v.name),
+            |d, path, _| {
+                d.push(
+                    Criticality::Breaking,
+                    path,
+                    format!("Enum value `{}` was removed", path),
+                )
+            },
+            |d, path, _| {
+                d.push(
+                    Criticality::Dangerous,
+                    path,
+                    format!("Enum value `{}` was added", path),
+                )
+            },
+            |d, path, old, new| {
+                d.description(path, &old.description, &new.description);
+                d.deprecation(
+                    "Enum value",
+                    path,
+                    is_deprecated(&old.directives),
+                    is_deprecated(&new.directives),
+                );
+            },
+        );
+    }
+
+    fn fields(&mut self, type_name: &str, old: &[Field<'a, String>], new: &[Field<'a, String>]) {
+        self.named(
+            &by_path(type_name, old, |f| &f.name),
+            &by_path(type_name, new, |f| &f.name),
+            |d, path, _| {
+                d.push(
+                    Criticality::Breaking,
+                    path,
+                    format!("Field `{}` was removed", path),
+                )
+            },
+            |d, path, _| {
+                d.push(
+                    Criticality::Safe,
+                    path,
+                    format!("Field `{}` was added", path),
+                )
+            },
+            |d, path, old, new| d.field(path, old, new),
+        );
+    }
+
+    fn field(&mut self, path: &str, old: &Field<'a, String>, new: &Field<'a, String>) {
+        self.description(path, &old.description, &new.description);
+        self.deprecation(
+            "Field",
+            path,
+            is_deprecated(&old.directives),
+            is_deprecated(&new.directives),
+        );
+
+        if old.field_type != new.field_type {
+            let criticality = if is_safe_output_change(&old.field_type, &new.field_type) {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            self.push(
+                criticality,
+                path,
+                format!(
+                    "Field `{}` changed type from `{}` to `{}`",
+                    path, old.field_type, new.field_type,
+                ),
+            );
+        }
+
+        self.arguments(path, &old.arguments, &new.arguments);
+    }
+
+    fn arguments(
+        &mut self,
+        parent_path: &str,
+        old: &[InputValue<'a, String>],
+        new: &[InputValue<'a, String>],
+    ) {
+        self.input_values("Argument", parent_path, old, new);
+    }
+
+    fn input_fields(
+        &mut self,
+        type_name: &str,
+        old: &[InputValue<'a, String>],
+        new: &[InputValue<'a, String>],
+    ) {
+        self.input_values("Input field", type_name, old, new);
+    }
+
+    fn input_values(
+        &mut self,
+        kind: &'static str,
+        parent_path: &str,
+        old: &[InputValue<'a, String>],
+        new: &[InputValue<'a, String>],
+    ) {
+        self.named(
+            &by_path(parent_path, old, |v| &v.name),
+            &by_path(parent_path, new, |v| &v.name),
+            |d, path, _| {
+                d.push(
+                    Criticality::Breaking,
+                    path,
+                    format!("{} `{}` was removed", kind, path),
+                )
+            },
+            |d, path, new| {
+                if is_required(new) {
+                    d.push(
+                        Criticality::Breaking,
+                        path,
+                        format!("Required {} `{}` was added", kind.to_lowercase(), path),
+                    )
+                } else {
+                    d.push(
+                        Criticality::Safe,
+                        path,
+                        format!("Optional {} `{}` was added", kind.to_lowercase(), path),
+                    )
+                }
+            },
+            |d, path, old, new| d.input_value(kind, path, old, new),
+        );
+    }
+
+    fn input_value(
+        &mut self,
+        kind: &str,
+        path: &str,
+        old: &InputValue<'a, String>,
+        new: &InputValue<'a, String>,
+    ) {
+        self.description(path, &old.description, &new.description);
+
+        if old.value_type != new.value_type {
+            let criticality = if is_safe_input_change(&old.value_type, &new.value_type) {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            self.push(
+                criticality,
+                path,
+                format!(
+                    "{} `{}` changed type from `{}` to `{}`",
+                    kind, path, old.value_type, new.value_type,
+                ),
+            );
+        }
+
+        if old.default_value != new.default_value {
+            let show = |v: &Option<graphql_parser::schema::Value<'a, String>>| {
+                v.as_ref()
+                    .map_or_else(|| "none".to_owned(), ToString::to_string)
+            };
+            self.push(
+                Criticality::Dangerous,
+                path,
+                format!(
+                    "Default value of {} `{}` changed from `{}` to `{}`",
+                    kind.to_lowercase(),
+                    path,
+                    show(&old.default_value),
+                    show(&new.default_value),
+                ),
+            );
+        }
+    }
+
+    fn directive_definition(
+        &mut self,
+        old: &DirectiveDefinition<'a, String>,
+        new: &DirectiveDefinition<'a, String>,
+    ) {
+        let path = format!("@{}", new.name);
+        self.description(&path, &old.description, &new.description);
+
+        for location in old.locations.iter().filter(|l| !new.locations.contains(l)) {
+            self.push(
+                Criticality::Breaking,
+                &path,
+                format!(
+                    "Location `{}` was removed from directive `{}`",
+                    location.as_str(),
+                    path,
+                ),
+            );
+        }
+        for location in new.locations.iter().filter(|l| !old.locations.contains(l)) {
+            self.push(
+                Criticality::Safe,
+                &path,
+                format!(
+                    "Location `{}` was added to directive `{}`",
+                    location.as_str(),
+                    path,
+                ),
+            );
+        }
+
+        self.arguments(&path, &old.arguments, &new.arguments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graphql_parser::schema::parse_schema;
+
+    use crate::{graphql_object, EmptyMutation, EmptySubscription, GraphQLEnum, RootNode};
+
+    use super::{diff, diff_documents, diff_schema_language, Criticality, SchemaDiff};
+
+    fn sdl_diff(old: &str, new: &str) -> SchemaDiff {
+        diff_documents(&parse_schema(old).unwrap(), &parse_schema(new).unwrap())
+    }
+
+    fn changes(diff: &SchemaDiff) -> Vec<(Criticality, &str)> {
+        diff.changes()
+            .iter()
+            .map(|c| (c.criticality, c.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_type_changes() {
+        let diff = sdl_diff(
+            "type Query { a: Int } type User { id: ID } enum Role { A } scalar Date",
+            "type Query { a: Int } interface User { id: ID } enum Role { A } scalar Url",
+        );
+
+        assert_eq!(
+            changes(&diff),
+            [
+                (Criticality::Breaking, "Type `Date` was removed"),
+                (Criticality::Safe, "Type `Url` was added"),
+                (
+                    Criticality::Breaking,
+                    "Type `User` changed from an object to an interface",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_field_changes() {
+        let diff = sdl_diff(
+            r#"type Query {
+                removed: Int
+                nullable: String
+                required: String!
+                list: [String]
+                renamed: Int
+            }"#,
+            r#"type Query {
+                added: Int
+                nullable: String!
+                required: String
+                list: [String!]!
+                renamed: String @deprecated
+            }"#,
+        );
+
+        assert_eq!(
+            changes(&diff),
+            [
+                (Criticality::Safe, "Field `Query.added` was added"),
+                (
+                    Criticality::Safe,
+                    "Field `Query.list` changed type from `[String]` to `[String!]!`",
+                ),
+                (
+                    Criticality::Safe,
+                    "Field `Query.nullable` changed type from `String` to `String!`",
+                ),
+                (Criticality::Breaking, "Field `Query.removed` was removed"),
+                (Criticality::Safe, "Field `Query.renamed` was deprecated"),
+                (
+                    Criticality::Breaking,
+                    "Field `Query.renamed` changed type from `Int` to `String`",
+                ),
+                (
+                    Criticality::Breaking,
+                    "Field `Query.required` changed type from `String!` to `String`",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_argument_changes() {
+        let diff = sdl_diff(
+            "type Query { user(id: ID, name: String!, first: Int = 10, gone: Int): Int }",
+            "type Query { user(id: ID!, name: String, first: Int = 20, page: Int!, size: Int): Int }",
+        );
+
+        assert_eq!(
+            changes(&diff),
+            [
+                (
+                    Criticality::Dangerous,
+                    "Default value of argument `Query.user.first` changed from `10` to `20`",
+                ),
+                (
+                    Criticality::Breaking,
+                    "Argument `Query.user.gone` was removed"
+                ),
+                (
+                    Criticality::Breaking,
+                    "Argument `Query.user.id` changed type from `ID` to `ID!`",
+                ),
+                (
+                    Criticality::Safe,
+                    "Argument `Query.user.name` changed type from `String!` to `String`",
+                ),
+                (
+                    Criticality::Breaking,
+                    "Required argument `Query.user.page` was added",
+                ),
+                (
+                    Criticality::Safe,
+                    "Optional argument `Query.user.size` was added",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_enum_union_and_input_changes() {
+        let diff = sdl_diff(
+            r#"
+            type Query { a: Int }
+            enum Role { ADMIN USER GUEST }
+            union Search = User | Post
+            type User { id: ID }
+            type Post { id: ID }
+            type Page { id: ID }
+            input NewUser { name: String! email: String }
+            "#,
+            r#"
+            type Query { a: Int }
+            enum Role { ADMIN USER OWNER }
+            union Search = User | Page
+            type User { id: ID }
+            type Post { id: ID }
+            type Page { id: ID }
+            input NewUser { name: String! age: Int! nick: String role: Role! = USER }
+            "#,
+        );
+
+        assert_eq!(
+            changes(&diff),
+            [
+                (
+                    Criticality::Breaking,
+                    "Required input field `NewUser.age` was added"
+                ),
+                (
+                    Criticality::Breaking,
+                    "Input field `NewUser.email` was removed"
+                ),
+                (
+                    Criticality::Safe,
+                    "Optional input field `NewUser.nick` was added"
+                ),
+                (
+                    Criticality::Safe,
+                    "Optional input field `NewUser.role` was added"
+                ),
+                (Criticality::Breaking, "Enum value `Role.GUEST` was removed"),
+                (Criticality::Dangerous, "Enum value `Role.OWNER` was added"),
+                (
+                    Criticality::Breaking,
+                    "Member `Post` was removed from union `Search`"
+                ),
+                (
+                    Criticality::Dangerous,
+                    "Member `Page` was added to union `Search`"
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn detects_interface_root_and_directive_changes() {
+        let diff = sdl_diff(
+            r#"
+            schema { query: Query mutation: Mutation }
+            type Query { a: Int }
+            type Mutation { a: Int }
+            interface Node { id: ID }
+            interface Named { name: String }
+            type User implements Node { id: ID name: String }
+            directive @cache(ttl: Int) on FIELD_DEFINITION | OBJECT
+            directive @old on FIELD
+            "#,
+            r#"
+            schema { query: Root }
+            type Root { a: Int }
+            interface Node { id: ID }
+            interface Named { name: String }
+            type User implements Named { id: ID name: String }
+            directive @cache(ttl: Int!) on FIELD_DEFINITION | INTERFACE
+            directive @new on FIELD
+            "#,
+        );
+
+        assert_eq!(
+            changes(&diff),
+            [
+                (
+                    Criticality::Breaking,
+                    "Location `OBJECT` was removed from directive `@cache`"
+                ),
+                (
+                    Criticality::Safe,
+                    "Location `INTERFACE` was added to directive `@cache`"
+                ),
+                (
+                    Criticality::Breaking,
+                    "Argument `@cache.ttl` changed type from `Int` to `Int!`",
+                ),
+                (Criticality::Safe, "Directive `@new` was added"),
+                (Criticality::Breaking, "Directive `@old` was removed"),
+                (
+                    Criticality::Breaking,
+                    "Mutation root type `Mutation` was removed"
+                ),
+                (Criticality::Breaking, "Type `Mutation` was removed"),
+                (
+                    Criticality::Breaking,
+                    "Query root type changed from `Query` to `Root`",
+                ),
+                (Criticality::Breaking, "Type `Query` was removed"),
+                (Criticality::Safe, "Type `Root` was added"),
+                (
+                    Criticality::Breaking,
+                    "Type `User` no longer implements interface `Node`",
+                ),
+                (
+                    Criticality::Dangerous,
+                    "Type `User` now implements interface `Named`",
+                ),
+            ],
+        );
+    }
+
+    #[derive(GraphQLEnum)]
+    enum Role {
+        Admin,
+        #[graphql(deprecated = "Use `Admin`")]
+        Owner,
+    }
+
+    struct Query;
+
+    /// The root query.
+    #[graphql_object]
+    impl Query {
+        fn role(default: Option<Role>) -> Option<Role> {
+            default
+        }
+
+        #[graphql(arguments(first(default = 10)))]
+        fn names(first: i32) -> Vec<String> {
+            (0..first).map(|i| i.to_string()).collect()
+        }
+    }
+
+    struct OtherQuery;
+
+    #[graphql_object(name = "Query")]
+    impl OtherQuery {
+        fn role() -> Role {
+            Role::Admin
+        }
+    }
+
+    #[test]
+    fn schema_has_no_changes_against_itself() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let diff = diff_schema_language(&schema.as_schema_language(), &schema.schema).unwrap();
+
+        assert!(diff.is_empty(), "{}", diff);
+        assert!(super::diff(&schema.schema, &schema.schema).is_empty());
+    }
+
+    #[test]
+    fn compares_schemas() {
+        let old = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let new = RootNode::new(
+            OtherQuery,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+
+        let diff = diff(&old.schema, &new.schema);
+
+        assert!(diff.has_breaking_changes());
+        assert_eq!(
+            diff.to_string(),
+            "SAFE: Description of `Query` changed\n\
+             BREAKING: Field `Query.names` was removed\n\
+             SAFE: Field `Query.role` changed type from `Role` to `Role!`\n\
+             BREAKING: Argument `Query.role.default` was removed\n",
+        );
+    }
+}
@@ -0,0 +1,938 @@
+//! Reconstruction of a [`SchemaType`] from the result of an introspection query.
+
+use std::{
+    borrow::{Borrow, Cow},
+    fmt,
+    str::FromStr,
+};
+
+use fnv::FnvHashMap;
+use indexmap::IndexMap;
+
+use crate::{
+    ast::{InputValue, Type},
+    executor::{FieldError, Registry},
+    parser::{parse_value_literal, Lexer, ParseError, Parser, ScalarToken, Token},
+    schema::{
+        meta::{
+            Argument, DeprecationStatus, EnumMeta, EnumValue, Field, InputObjectMeta,
+            InterfaceMeta, MetaType, ObjectMeta, ScalarMeta, UnionMeta,
+        },
+        model::{DirectiveLocation, DirectiveType, SchemaType},
+    },
+    types::{name::Name, scalars::ID},
+    value::ScalarValue,
+};
+
+/// Error of an introspection result not describing a valid schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntrospectionResultError {
+    message: String,
+}
+
+impl IntrospectionResultError {
+    fn new<M: Into<String>>(message: M) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    /// Describes what's wrong with the introspection result.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for IntrospectionResultError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid introspection result: {}", self.message)
+    }
+}
+
+impl std::error::Error for IntrospectionResultError {}
+
+type Result<T> = std::result::Result<T, IntrospectionResultError>;
+
+type JsonObject<'v, S> = IndexMap<&'v str, &'v InputValue<S>>;
+
+impl<S> SchemaType<'static, S>
+where
+    S: ScalarValue + 'static,
+{
+    /// Builds a [`SchemaType`] from the result of an introspection query, like the one returned
+    /// by [`introspect`](crate::introspect) or a `schema.json` file, so documents can be
+    /// validated against a remote schema without its Rust types.
+    ///
+    /// The `result` may be the whole response, its `data`, or the `__schema` object itself. It
+    /// must contain everything queried by the reference introspection query,
+    /// although descriptions are optional.
+    ///
+    /// Built-in scalars parse their values as usual, while custom scalars accept any value, as
+    /// their parsing isn't part of the introspection result. Such a schema can't execute
+    /// operations, only validate them:
+    ///
+    /// ```rust
+    /// # use juniper::{
+    /// #     graphql_object, validation::{visit_all_rules, ValidatorContext}, EmptyMutation,
+    /// #     EmptySubscription, RootNode, SchemaType, ToInputValue,
+    /// # };
+    /// # struct Query;
+    /// # #[graphql_object]
+    /// # impl Query {
+    /// #     fn hello() -> &'static str { "world" }
+    /// # }
+    /// # let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+    /// # let (result, _) = juniper::introspect(&schema, &(), Default::default()).unwrap();
+    /// let remote = SchemaType::from_introspection(&result.to_input_value()).unwrap();
+    ///
+    /// let errors = juniper::validate_document_source("{ hello goodbye }", &remote).unwrap_err();
+    /// assert_eq!(
+    ///     errors.to_string(),
+    ///     "Unknown field \"goodbye\" on type \"Query\". At 0:8\n",
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If the `result` is missing required entries, or references types it doesn't define.
+    pub fn from_introspection(result: &InputValue<S>) -> Result<Self> {
+        let schema = schema_object(result)?;
+
+        let mut registry = Registry::new(FnvHashMap::default());
+        // Built-in scalars keep parsing their literals the way they do in Rust.
+        registry.get_type::<bool>(&());
+        registry.get_type::<f64>(&());
+        registry.get_type::<ID>(&());
+        registry.get_type::<i32>(&());
+        registry.get_type::<String>(&());
+        #[cfg(not(feature = "disable_introspection"))]
+        registry.get_type::<SchemaType<S>>(&());
+        #[cfg(not(feature = "disable_introspection"))]
+        let mut meta_fields = vec![
+            registry.field::<SchemaType<S>>("__schema", &()),
+            registry
+                .field::<crate::schema::model::TypeType<S>>("__type", &())
+                .argument(registry.arg::<String>("name", &())),
+        ];
+
+        let mut directives = FnvHashMap::default();
+        directives.insert("skip".to_owned(), DirectiveType::new_skip(&mut registry));
+        directives.insert(
+            "include".to_owned(),
+            DirectiveType::new_include(&mut registry),
+        );
+
+        let mut types = registry.types;
+        let mut defaults = vec![];
+        for t in list(&schema, "types")? {
+            let t = object(t, "type")?;
+            let name = string(&t, "name")?;
+            if types.contains_key(name) || name.starts_with("__") {
+                continue;
+            }
+            let meta = meta_type(name, &t, &mut defaults)?;
+            let name =
+                Name::from_str(name).map_err(|e| IntrospectionResultError::new(e.to_string()))?;
+            types.insert(name, meta);
+        }
+
+        for d in list(&schema, "directives")? {
+            let d = object(d, "directive")?;
+            let name = string(&d, "name")?;
+            if directives.contains_key(name) {
+                continue;
+            }
+            let directive = directive_type(name, &d, &mut defaults)?;
+            directives.insert(name.to_owned(), directive);
+        }
+
+        let query_type_name = root_type_name(&schema, "queryType")?
+            .ok_or_else(|| IntrospectionResultError::new("Missing `queryType`"))?;
+        #[cfg(not(feature = "disable_introspection"))]
+        match types.get_mut(query_type_name.as_str()) {
+            Some(MetaType::Object(ObjectMeta { fields, .. })) => fields.append(&mut meta_fields),
+            _ => {
+                return Err(IntrospectionResultError::new(format!(
+                    "Query type `{}` isn't a defined object type",
+                    query_type_name,
+                )))
+            }
+        }
+
+        let mut schema_type = SchemaType {
+            types,
+            query_type_name,
+            mutation_type_name: root_type_name(&schema, "mutationType")?,
+            subscription_type_name: root_type_name(&schema, "subscriptionType")?,
+            directives,
+            schema_directives: vec![],
+        };
+        schema_type.check_type_references()?;
+        schema_type.set_default_values(defaults)?;
+        Ok(schema_type)
+    }
+
+    fn check_type_references(&self) -> Result<()> {
+        let check = |name: &str, referrer: &dyn fmt::Display| {
+            if self.types.contains_key(name) {
+                Ok(())
+            } else {
+                Err(IntrospectionResultError::new(format!(
+                    "Unknown type `{}` referenced by `{}`",
+                    name, referrer,
+                )))
+            }
+        };
+        let check_arguments = |args: &[Argument<S>], parent: &str| {
+            args.iter().try_for_each(|arg| {
+                check(
+                    arg.arg_type.innermost_name(),
+                    &format_args!("{}.{}", parent, arg.name),
+                )
+            })
+        };
+        let check_fields = |fields: &[Field<S>], parent: &str| {
+            fields.iter().try_for_each(|f| {
+                let path = format!("{}.{}", parent, f.name);
+                check(f.field_type.innermost_name(), &path)?;
+                check_arguments(f.arguments.as_deref().unwrap_or_default(), &path)
+            })
+        };
+
+        for name in [
+            Some(&self.query_type_name),
+            self.mutation_type_name.as_ref(),
+        ]
+        .iter()
+        .chain(&[self.subscription_type_name.as_ref()])
+        .flatten()
+        {
+            check(name, &"schema")?;
+        }
+        for (name, t) in &self.types {
+            let name: &str = name.borrow();
+            match t {
+                MetaType::Object(o) => {
+                    check_fields(&o.fields, name)?;
+                    o.interface_names.iter().try_for_each(|i| check(i, &name))?;
+                }
+                MetaType::Interface(i) => check_fields(&i.fields, name)?,
+                MetaType::Union(u) => u.of_type_names.iter().try_for_each(|m| check(m, &name))?,
+                MetaType::InputObject(i) => check_arguments(&i.input_fields, name)?,
+                _ => {}
+            }
+        }
+        for d in self.directives.values() {
+            check_arguments(&d.arguments, &format!("@{}", d.name))?;
+        }
+        Ok(())
+    }
+
+    /// Parses the `defaults` of arguments and input fields, once all the types they may refer to
+    /// are known.
+    fn set_default_values(&mut self, defaults: Vec<PendingDefault>) -> Result<()> {
+        let mut parsed = Vec::with_capacity(defaults.len());
+        for default in defaults {
+            let arg = self.argument_mut_at(&default.location).ok_or_else(|| {
+                IntrospectionResultError::new(format!("Unknown `{}`", default.location))
+            })?;
+            let arg_type = arg.arg_type.clone();
+            let value = parse_default_value(&default.source, &arg_type, self).map_err(|e| {
+                IntrospectionResultError::new(format!(
+                    "Invalid default value `{}` of `{}`: {}",
+                    default.source, default.location, e,
+                ))
+            })?;
+            parsed.push((default.location, value));
+        }
+        for (location, value) in parsed {
+            if let Some(arg) = self.argument_mut_at(&location) {
+                arg.default_value = Some(value);
+            }
+        }
+        Ok(())
+    }
+
+    fn argument_mut_at(&mut self, location: &DefaultLocation) -> Option<&mut Argument<'static, S>> {
+        match location {
+            DefaultLocation::Argument {
+                type_name,
+                field_name,
+                argument_name,
+            } => {
+                let fields = match self.types.get_mut(type_name.as_str())? {
+                    MetaType::Object(ObjectMeta { fields, .. })
+                    | MetaType::Interface(InterfaceMeta { fields, .. }) => fields,
+                    _ => return None,
+                };
+                let field = fields.iter_mut().find(|f| f.name == field_name.as_str())?;
+                by_name(field.arguments.as_mut()?, argument_name)
+            }
+            DefaultLocation::InputField {
+                type_name,
+                field_name,
+            } => match self.types.get_mut(type_name.as_str())? {
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                    by_name(input_fields, field_name)
+                }
+                _ => None,
+            },
+            DefaultLocation::DirectiveArgument {
+                directive_name,
+                argument_name,
+            } => by_name(
+                &mut self.directives.get_mut(directive_name)?.arguments,
+                argument_name,
+            ),
+        }
+    }
+}
+
+/// Default value of an argument or an input field, parsed once the whole schema is known.
+struct PendingDefault {
+    location: DefaultLocation,
+    source: String,
+}
+
+enum DefaultLocation {
+    Argument {
+        type_name: String,
+        field_name: String,
+        argument_name: String,
+    },
+    InputField {
+        type_name: String,
+        field_name: String,
+    },
+    DirectiveArgument {
+        directive_name: String,
+        argument_name: String,
+    },
+}
+
+impl fmt::Display for DefaultLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Argument {
+                type_name,
+                field_name,
+                argument_name,
+            } => write!(f, "{}.{}.{}", type_name, field_name, argument_name),
+            Self::InputField {
+                type_name,
+                field_name,
+            } => write!(f, "{}.{}", type_name, field_name),
+            Self::DirectiveArgument {
+                directive_name,
+                argument_name,
+            } => write!(f, "@{}.{}", directive_name, argument_name),
+        }
+    }
+}
+
+fn parse_default_value<S>(
+    source: &str,
+    arg_type: &Type,
+    schema: &SchemaType<S>,
+) -> std::result::Result<InputValue<S>, String>
+where
+    S: ScalarValue,
+{
+    let mut lexer = Lexer::new(source);
+    let mut parser = Parser::new(&mut lexer).map_err(|e| e.item.to_string())?;
+    let meta = schema.concrete_type_by_name(arg_type.innermost_name());
+    let value = parse_value_literal(&mut parser, true, schema, meta).map_err(|e| e.to_string())?;
+    if parser.peek().item != Token::EndOfFile {
+        return Err("Unexpected trailing tokens".into());
+    }
+    Ok(value.item)
+}
+
+fn by_name<'a, 's, S>(
+    args: &'a mut [Argument<'s, S>],
+    name: &str,
+) -> Option<&'a mut Argument<'s, S>> {
+    args.iter_mut().find(|a| a.name == name)
+}
+
+fn schema_object<S>(result: &InputValue<S>) -> Result<JsonObject<'_, S>>
+where
+    S: ScalarValue,
+{
+    let mut value = result;
+    for key in &["data", "__schema"] {
+        if let Some(inner) = value.to_object_value().and_then(|o| o.get(key).copied()) {
+            value = inner;
+        }
+    }
+    object(value, "__schema")
+}
+
+fn object<'v, S>(value: &'v InputValue<S>, what: &str) -> Result<JsonObject<'v, S>>
+where
+    S: ScalarValue,
+{
+    value
+        .to_object_value()
+        .ok_or_else(|| IntrospectionResultError::new(format!("Expected `{}` object", what)))
+}
+
+fn entry<'v, S>(obj: &JsonObject<'v, S>, key: &str) -> Option<&'v InputValue<S>>
+where
+    S: ScalarValue,
+{
+    obj.get(key).copied().filter(|v| !v.is_null())
+}
+
+fn list<'v, S>(obj: &JsonObject<'v, S>, key: &str) -> Result<Vec<&'v InputValue<S>>>
+where
+    S: ScalarValue,
+{
+    entry(obj, key)
+        .and_then(InputValue::to_list_value)
+        .ok_or_else(|| IntrospectionResultError::new(format!("Expected `{}` list", key)))
+}
+
+fn optional_list<'v, S>(obj: &JsonObject<'v, S>, key: &str) -> Result<Vec<&'v InputValue<S>>>
+where
+    S: ScalarValue,
+{
+    match entry(obj, key) {
+        Some(_) => list(obj, key),
+        None => Ok(vec![]),
+    }
+}
+
+fn string<'v, S>(obj: &JsonObject<'v, S>, key: &str) -> Result<&'v str>
+where
+    S: ScalarValue,
+{
+    optional_string(obj, key)
+        .ok_or_else(|| IntrospectionResultError::new(format!("Expected `{}` string", key)))
+}
+
+fn optional_string<'v, S>(obj: &JsonObject<'v, S>, key: &str) -> Option<&'v str>
+where
+    S: ScalarValue,
+{
+    entry(obj, key).and_then(InputValue::as_string_value)
+}
+
+fn description_of<S>(obj: &JsonObject<'_, S>) -> Option<String>
+where
+    S: ScalarValue,
+{
+    optional_string(obj, "description").map(Into::into)
+}
+
+fn deprecation_status<S>(obj: &JsonObject<'_, S>) -> DeprecationStatus
+where
+    S: ScalarValue,
+{
+    let is_deprecated = entry(obj, "isDeprecated")
+        .and_then(InputValue::as_scalar)
+        .and_then(ScalarValue::as_boolean)
+        .unwrap_or(false);
+    if is_deprecated {
+        DeprecationStatus::Deprecated(optional_string(obj, "deprecationReason").map(Into::into))
+    } else {
+        DeprecationStatus::Current
+    }
+}
+
+fn root_type_name<S>(schema: &JsonObject<'_, S>, key: &str) -> Result<Option<String>>
+where
+    S: ScalarValue,
+{
+    entry(schema, key)
+        .map(|t| Ok(string(&object(t, key)?, "name")?.to_owned()))
+        .transpose()
+}
+
+fn type_ref<S>(value: &InputValue<S>) -> Result<Type<'static>>
+where
+    S: ScalarValue,
+{
+    let obj = object(value, "type")?;
+    let of_type = || {
+        entry(&obj, "ofType")
+            .ok_or_else(|| IntrospectionResultError::new("Expected `ofType` of wrapping type"))
+            .and_then(type_ref)
+    };
+    Ok(match string(&obj, "kind")? {
+        "NON_NULL" => match of_type()? {
+            Type::Named(name) => Type::NonNullNamed(name),
+            Type::List(of_type, size) => Type::NonNullList(of_type, size),
+            _ => {
+                return Err(IntrospectionResultError::new(
+                    "Non-null type of non-null type",
+                ))
+            }
+        },
+        "LIST" => Type::List(Box::new(of_type()?), None),
+        _ => Type::Named(Cow::Owned(string(&obj, "name")?.to_owned())),
+    })
+}
+
+fn input_values<S>(
+    obj: &JsonObject<'_, S>,
+    key: &str,
+    location: impl Fn(&str) -> DefaultLocation,
+    defaults: &mut Vec<PendingDefault>,
+) -> Result<Vec<Argument<'static, S>>>
+where
+    S: ScalarValue,
+{
+    list(obj, key)?
+        .into_iter()
+        .map(|v| {
+            let v = object(v, key)?;
+            let name = string(&v, "name")?;
+            if let Some(source) = optional_string(&v, "defaultValue") {
+                defaults.push(PendingDefault {
+                    location: location(name),
+                    source: source.into(),
+                });
+            }
+            Ok(Argument {
+                name: name.into(),
+                description: description_of(&v),
+                arg_type: type_ref(entry(&v, "type").unwrap_or(&InputValue::Null))?,
+                default_value: None,
+                directives: vec![],
+            })
+        })
+        .collect()
+}
+
+fn fields<S>(
+    type_name: &str,
+    obj: &JsonObject<'_, S>,
+    defaults: &mut Vec<PendingDefault>,
+) -> Result<Vec<Field<'static, S>>>
+where
+    S: ScalarValue,
+{
+    list(obj, "fields")?
+        .into_iter()
+        .map(|f| {
+            let f = object(f, "field")?;
+            let name = string(&f, "name")?;
+            let arguments = input_values(
+                &f,
+                "args",
+                |arg| DefaultLocation::Argument {
+                    type_name: type_name.into(),
+                    field_name: name.into(),
+                    argument_name: arg.into(),
+                },
+                defaults,
+            )?;
+            Ok(Field {
+                name: name.into(),
+                description: description_of(&f),
+                arguments: Some(arguments).filter(|args| !args.is_empty()),
+                field_type: type_ref(entry(&f, "type").unwrap_or(&InputValue::Null))?,
+                deprecation_status: deprecation_status(&f),
+                timeout: None,
+                skip_tracing: false,
+                directives: vec![],
+            })
+        })
+        .collect()
+}
+
+fn type_names<S>(obj: &JsonObject<'_, S>, key: &str) -> Result<Vec<String>>
+where
+    S: ScalarValue,
+{
+    optional_list(obj, key)?
+        .into_iter()
+        .map(|t| Ok(string(&object(t, key)?, "name")?.to_owned()))
+        .collect()
+}
+
+fn meta_type<S>(
+    name: &str,
+    obj: &JsonObject<'_, S>,
+    defaults: &mut Vec<PendingDefault>,
+) -> Result<MetaType<'static, S>>
+where
+    S: ScalarValue,
+{
+    let name_cow = Cow::Owned(name.to_owned());
+    let description = description_of(obj);
+    Ok(match string(obj, "kind")? {
+        "SCALAR" => MetaType::Scalar(ScalarMeta {
+            name: name_cow,
+            description,
+            directives: vec![],
+            // The semantics of introspected scalars are unknown, so accept any literal.
+            accepts_composite_values: true,
+            try_parse_fn: accept_any,
+            parse_fn: parse_any_scalar,
+        }),
+        "OBJECT" => MetaType::Object(ObjectMeta {
+            name: name_cow,
+            description,
+            fields: fields(name, obj, defaults)?,
+            interface_names: type_names(obj, "interfaces")?,
+            directives: vec![],
+        }),
+        "INTERFACE" => MetaType::Interface(InterfaceMeta {
+            name: name_cow,
+            description,
+            fields: fields(name, obj, defaults)?,
+            directives: vec![],
+        }),
+        "UNION" => MetaType::Union(UnionMeta {
+            name: name_cow,
+            description,
+            of_type_names: type_names(obj, "possibleTypes")?,
+            directives: vec![],
+        }),
+        "ENUM" => MetaType::Enum(EnumMeta {
+            name: name_cow,
+            description,
+            values: list(obj, "enumValues")?
+                .into_iter()
+                .map(|v| {
+                    let v = object(v, "enumValues")?;
+                    Ok(EnumValue {
+                        name: string(&v, "name")?.into(),
+                        description: description_of(&v),
+                        deprecation_status: deprecation_status(&v),
+                        directives: vec![],
+                    })
+                })
+                .collect::<Result<_>>()?,
+            directives: vec![],
+            // Literals are checked against the `values` instead.
+            try_parse_fn: accept_any,
+        }),
+        "INPUT_OBJECT" => MetaType::InputObject(InputObjectMeta {
+            name: name_cow,
+            description,
+            input_fields: input_values(
+                obj,
+                "inputFields",
+                |field| DefaultLocation::InputField {
+                    type_name: name.into(),
+                    field_name: field.into(),
+                },
+                defaults,
+            )?,
+            directives: vec![],
+            try_parse_fn: accept_any,
+        }),
+        kind => {
+            return Err(IntrospectionResultError::new(format!(
+                "Unexpected kind `{}` of type `{}`",
+                kind, name,
+            )))
+        }
+    })
+}
+
+fn directive_type<S>(
+    name: &str,
+    obj: &JsonObject<'_, S>,
+    defaults: &mut Vec<PendingDefault>,
+) -> Result<DirectiveType<'static, S>>
+where
+    S: ScalarValue,
+{
+    let locations = list(obj, "locations")?
+        .into_iter()
+        .map(|l| {
+            l.as_string_value()
+                .or_else(|| l.as_enum_value())
+                .and_then(|l| {
+                    InputValue::<S>::enum_value(l)
+                        .convert::<DirectiveLocation>()
+                        .ok()
+                })
+                .ok_or_else(|| {
+                    IntrospectionResultError::new(format!(
+                        "Invalid location `{}` of directive `@{}`",
+                        l, name,
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let arguments = input_values(
+        obj,
+        "args",
+        |arg| DefaultLocation::DirectiveArgument {
+            directive_name: name.into(),
+            argument_name: arg.into(),
+        },
+        defaults,
+    )?;
+
+    let mut directive = DirectiveType::new(name, &locations, &arguments);
+    directive.description = description_of(obj);
+    Ok(directive)
+}
+
+fn accept_any<S>(_: &InputValue<S>) -> std::result::Result<(), FieldError<S>> {
+    Ok(())
+}
+
+fn parse_any_scalar<S>(token: ScalarToken<'_>) -> std::result::Result<S, ParseError<'_>>
+where
+    S: ScalarValue,
+{
+    match token {
+        ScalarToken::String(s) => Ok(S::from(s.to_owned())),
+        ScalarToken::Int(i) => i
+            .parse::<i32>()
+            .map(S::from)
+            .or_else(|_| i.parse::<f64>().map(S::from))
+            .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(token))),
+        ScalarToken::Float(f) => f
+            .parse::<f64>()
+            .map(S::from)
+            .map_err(|_| ParseError::UnexpectedToken(Token::Scalar(token))),
+    }
+}
+
+#[cfg(all(test, not(feature = "disable_introspection")))]
+mod tests {
+    use crate::{
+        ast::ToInputValue, graphql_object, introspect, schema::model::SchemaType, EmptyMutation,
+        EmptySubscription, GraphQLEnum, GraphQLInputObject, GraphQLUnion, IntrospectionFormat,
+        RootNode,
+    };
+
+    #[derive(GraphQLEnum)]
+    enum Episode {
+        NewHope,
+        Empire,
+    }
+
+    #[derive(GraphQLInputObject)]
+    struct Filter {
+        episode: Episode,
+        #[graphql(default = "10")]
+        limit: i32,
+    }
+
+    struct Human;
+
+    #[graphql_object]
+    impl Human {
+        fn name() -> &'static str {
+            "Luke"
+        }
+    }
+
+    struct Droid;
+
+    #[graphql_object]
+    impl Droid {
+        fn model() -> &'static str {
+            "R2"
+        }
+    }
+
+    #[derive(GraphQLUnion)]
+    enum Character {
+        Human(Human),
+        Droid(Droid),
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn hero(episode: Option<Episode>) -> Character {
+            match episode {
+                Some(Episode::Empire) => Character::Droid(Droid),
+                Some(Episode::NewHope) | None => Character::Human(Human),
+            }
+        }
+
+        fn count(filter: Filter) -> i32 {
+            filter.limit
+        }
+
+        #[graphql(
+            deprecated = "Use `hero`",
+            arguments(greeting(default = "Hi".to_owned())),
+        )]
+        fn greet(greeting: String) -> String {
+            greeting
+        }
+    }
+
+    fn remote_schema() -> SchemaType<'static, crate::DefaultScalarValue> {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let (result, errors) = introspect(&schema, &(), IntrospectionFormat::All).unwrap();
+        assert_eq!(errors, []);
+
+        SchemaType::from_introspection(&result.to_input_value()).unwrap()
+    }
+
+    fn validate(doc: &str) -> Result<(), String> {
+        let schema = remote_schema();
+        crate::validate_document_source(doc, &schema).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn reconstructs_types() {
+        let schema = remote_schema();
+
+        let query = schema.concrete_type_by_name("Query").unwrap();
+        let greet = query.field_by_name("greet").unwrap();
+        assert!(greet.deprecation_status.is_deprecated());
+        let greeting = &greet.arguments.as_ref().unwrap()[0];
+        assert_eq!(greeting.arg_type.to_string(), "String");
+        assert_eq!(
+            greeting.default_value,
+            Some(crate::InputValue::scalar("Hi")),
+        );
+
+        let character = schema.concrete_type_by_name("Character").unwrap();
+        assert!(character.is_abstract());
+        for name in &["Human", "Droid"] {
+            let implementer = schema.concrete_type_by_name(name).unwrap();
+            assert!(schema.is_possible_type(character, implementer));
+        }
+    }
+
+    #[cfg(feature = "schema-language")]
+    #[test]
+    fn does_not_differ_from_original() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let (result, _) = introspect(&schema, &(), IntrospectionFormat::All).unwrap();
+        let remote = SchemaType::from_introspection(&result.to_input_value()).unwrap();
+
+        assert_eq!(
+            crate::schema::diff::diff(&schema.schema, &remote).to_string(),
+            "",
+        );
+    }
+
+    #[test]
+    fn validates_documents() {
+        assert_eq!(
+            validate(
+                r#"
+                query($filter: Filter!) {
+                    hero(episode: EMPIRE) {
+                        __typename
+                        ... on Human { name }
+                        ... on Droid { model }
+                    }
+                    count(filter: $filter)
+                    literal: count(filter: { episode: NEW_HOPE })
+                    greet
+                }"#,
+            ),
+            Ok(()),
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        assert_eq!(
+            validate("{ hero(episode: RETURN) { name } }"),
+            Err(
+                "Invalid value for argument \"episode\", expected type \"Episode\". At 0:16\n\
+                 Unknown field \"name\" on type \"Character\". At 0:26\n"
+                    .to_owned(),
+            ),
+        );
+        assert_eq!(
+            validate("{ count(filter: { limit: 1 }) }"),
+            Err(
+                "Invalid value for argument \"filter\", expected type \"Filter!\". At 0:16\n"
+                    .to_owned(),
+            ),
+        );
+    }
+
+    #[test]
+    fn accepts_json_results() {
+        let result: crate::InputValue = serde_json::from_str(
+            r#"{"data": {"__schema": {
+                "queryType": {"name": "Root"},
+                "mutationType": null,
+                "subscriptionType": null,
+                "types": [{
+                    "kind": "OBJECT",
+                    "name": "Root",
+                    "fields": [{
+                        "name": "now",
+                        "args": [{
+                            "name": "zone",
+                            "type": {"kind": "SCALAR", "name": "TimeZone", "ofType": null},
+                            "defaultValue": "\"UTC\""
+                        }],
+                        "type": {
+                            "kind": "NON_NULL",
+                            "name": null,
+                            "ofType": {"kind": "SCALAR", "name": "DateTime", "ofType": null}
+                        },
+                        "isDeprecated": false,
+                        "deprecationReason": null
+                    }],
+                    "interfaces": []
+                }, {
+                    "kind": "SCALAR",
+                    "name": "DateTime"
+                }, {
+                    "kind": "SCALAR",
+                    "name": "TimeZone"
+                }],
+                "directives": []
+            }}}"#,
+        )
+        .unwrap();
+
+        let schema = SchemaType::from_introspection(&result).unwrap();
+
+        assert_eq!(
+            crate::validate_document_source(r#"{ now(zone: "CET") }"#, &schema),
+            Ok(()),
+        );
+        assert!(crate::validate_document_source("{ later }", &schema).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_type_references() {
+        let result: crate::InputValue = serde_json::from_str(
+            r#"{"__schema": {
+                "queryType": {"name": "Query"},
+                "types": [{
+                    "kind": "OBJECT",
+                    "name": "Query",
+                    "fields": [{
+                        "name": "me",
+                        "args": [],
+                        "type": {"kind": "OBJECT", "name": "User"}
+                    }],
+                    "interfaces": []
+                }],
+                "directives": []
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            SchemaType::from_introspection(&result)
+                .unwrap_err()
+                .to_string(),
+            "Invalid introspection result: Unknown type `User` referenced by `Query.me`",
+        );
+    }
+}
@@ -3,16 +3,22 @@
 use std::{
     borrow::{Cow, ToOwned},
     fmt,
+    time::Duration,
 };
 
 use crate::{
     ast::{FromInputValue, InputValue, Type},
+    executor::{FieldError, IntoFieldError},
     parser::{ParseError, ScalarToken},
     schema::model::SchemaType,
     types::base::TypeKind,
     value::{DefaultScalarValue, ParseScalarValue, ScalarValue},
 };
 
+/// Function checking whether an `InputValue` can be converted into a type, returning the
+/// conversion error otherwise.
+pub type InputValueParseFn<S> = for<'b> fn(&'b InputValue<S>) -> Result<(), FieldError<S>>;
+
 /// Whether an item is deprecated, with context.
 #[derive(Debug, PartialEq, Hash, Clone)]
 pub enum DeprecationStatus {
@@ -40,13 +46,43 @@ impl DeprecationStatus {
     }
 }
 
+/// Directive applied to a type system definition, like `@auth(role: ADMIN)` on an object type
+///
+/// Applied directives are not part of introspection, but are printed in the schema language.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppliedDirective<S> {
+    #[doc(hidden)]
+    pub name: String,
+    #[doc(hidden)]
+    pub arguments: Vec<(String, InputValue<S>)>,
+}
+
+impl<S> AppliedDirective<S> {
+    /// Build a new directive application without arguments
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments: vec![],
+        }
+    }
+
+    /// Add an argument to the directive application
+    pub fn argument(mut self, name: &str, value: InputValue<S>) -> Self {
+        self.arguments.push((name.to_owned(), value));
+        self
+    }
+}
+
 /// Scalar type metadata
 pub struct ScalarMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    pub(crate) accepts_composite_values: bool,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
     pub(crate) parse_fn: for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>,
 }
 
@@ -78,6 +114,8 @@ pub struct ObjectMeta<'a, S> {
     pub fields: Vec<Field<'a, S>>,
     #[doc(hidden)]
     pub interface_names: Vec<String>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 /// Enum type metadata
@@ -87,8 +125,10 @@ pub struct EnumMeta<'a, S> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub values: Vec<EnumValue>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub values: Vec<EnumValue<S>>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
 }
 
 /// Interface type metadata
@@ -100,17 +140,21 @@ pub struct InterfaceMeta<'a, S> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 /// Union type metadata
 #[derive(Debug)]
-pub struct UnionMeta<'a> {
+pub struct UnionMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
     pub of_type_names: Vec<String>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 /// Input object metadata
@@ -121,7 +165,9 @@ pub struct InputObjectMeta<'a, S> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub input_fields: Vec<Argument<'a, S>>,
-    pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
+    pub(crate) try_parse_fn: InputValueParseFn<S>,
 }
 
 /// A placeholder for not-yet-registered types
@@ -150,7 +196,7 @@ pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     Interface(InterfaceMeta<'a, S>),
     #[doc(hidden)]
-    Union(UnionMeta<'a>),
+    Union(UnionMeta<'a, S>),
     #[doc(hidden)]
     InputObject(InputObjectMeta<'a, S>),
     #[doc(hidden)]
@@ -170,6 +216,10 @@ pub struct Field<'a, S> {
     pub field_type: Type<'a>,
     #[doc(hidden)]
     pub deprecation_status: DeprecationStatus,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) skip_tracing: bool,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 impl<'a, S> Field<'a, S> {
@@ -178,6 +228,20 @@ impl<'a, S> Field<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// Returns the maximum time the field is allowed to take to resolve, if any.
+    ///
+    /// See [`Field::timeout`].
+    pub fn timeout_duration(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns true if the field is excluded from `tracing` instrumentation.
+    ///
+    /// See [`Field::skip_tracing`].
+    pub fn is_tracing_skipped(&self) -> bool {
+        self.skip_tracing
+    }
 }
 
 /// Metadata for an argument to a field
@@ -191,6 +255,8 @@ pub struct Argument<'a, S> {
     pub arg_type: Type<'a>,
     #[doc(hidden)]
     pub default_value: Option<InputValue<S>>,
+    #[doc(hidden)]
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -203,7 +269,7 @@ impl<'a, S> Argument<'a, S> {
 
 /// Metadata for a single value in an enum
 #[derive(Debug, Clone)]
-pub struct EnumValue {
+pub struct EnumValue<S = DefaultScalarValue> {
     /// The name of the enum value
     ///
     /// This is the string literal representation of the enum in responses.
@@ -215,6 +281,8 @@ pub struct EnumValue {
     pub description: Option<String>,
     /// Whether the field is deprecated or not, with an optional reason.
     pub deprecation_status: DeprecationStatus,
+    /// Directives applied to the enum value, printed in the schema language.
+    pub directives: Vec<AppliedDirective<S>>,
 }
 
 impl<'a, S> MetaType<'a, S> {
@@ -332,10 +400,10 @@ impl<'a, S> MetaType<'a, S> {
     /// Access the input value parse function, if applicable
     ///
     /// An input value parse function is a function that takes an `InputValue` instance and returns
-    /// `true` if it can be parsed as the provided type.
+    /// `Ok` if it can be parsed as the provided type, or the conversion error otherwise.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<for<'b> fn(&'b InputValue<S>) -> bool> {
+    pub fn input_value_parse_fn(&self) -> Option<InputValueParseFn<S>> {
         match *self {
             MetaType::Scalar(ScalarMeta {
                 ref try_parse_fn, ..
@@ -431,6 +499,8 @@ where
         ScalarMeta {
             name,
             description: None,
+            directives: vec![],
+            accepts_composite_values: false,
             try_parse_fn: try_parse_fn::<S, T>,
             parse_fn: <T as ParseScalarValue<S>>::from_str,
         }
@@ -444,6 +514,22 @@ where
         self
     }
 
+    /// Apply a directive to the scalar type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> ScalarMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
+    /// Accept list and object literals for the scalar type, like a `JSON` scalar does
+    ///
+    /// Scalars reject composite literals by default, before they get to parse them.
+    pub fn accept_composite_values(mut self) -> ScalarMeta<'a, S> {
+        self.accepts_composite_values = true;
+        self
+    }
+
     /// Wrap the scalar in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Scalar(self)
@@ -488,6 +574,7 @@ where
             description: None,
             fields: fields.to_vec(),
             interface_names: vec![],
+            directives: vec![],
         }
     }
 
@@ -511,6 +598,14 @@ where
         self
     }
 
+    /// Apply a directive to the object type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> ObjectMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this object type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Object(self)
@@ -522,7 +617,7 @@ where
     S: ScalarValue + 'a,
 {
     /// Build a new enum type with the specified name and possible values
-    pub fn new<T>(name: Cow<'a, str>, values: &[EnumValue]) -> Self
+    pub fn new<T>(name: Cow<'a, str>, values: &[EnumValue<S>]) -> Self
     where
         T: FromInputValue<S>,
     {
@@ -530,6 +625,7 @@ where
             name,
             description: None,
             values: values.to_vec(),
+            directives: vec![],
             try_parse_fn: try_parse_fn::<S, T>,
         }
     }
@@ -542,6 +638,14 @@ where
         self
     }
 
+    /// Apply a directive to the enum type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> EnumMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this enum type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
@@ -558,6 +662,7 @@ where
             name,
             description: None,
             fields: fields.to_vec(),
+            directives: vec![],
         }
     }
 
@@ -569,15 +674,23 @@ where
         self
     }
 
+    /// Apply a directive to the interface type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> InterfaceMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
     }
 }
 
-impl<'a> UnionMeta<'a> {
+impl<'a, S> UnionMeta<'a, S> {
     /// Build a new union type with the specified name and possible types
-    pub fn new(name: Cow<'a, str>, of_types: &[Type]) -> UnionMeta<'a> {
+    pub fn new(name: Cow<'a, str>, of_types: &[Type]) -> UnionMeta<'a, S> {
         UnionMeta {
             name,
             description: None,
@@ -585,19 +698,28 @@ impl<'a> UnionMeta<'a> {
                 .iter()
                 .map(|t| t.innermost_name().to_owned())
                 .collect(),
+            directives: vec![],
         }
     }
 
     /// Set the description of the type
     ///
     /// If a description was provided prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> UnionMeta<'a> {
+    pub fn description(mut self, description: &str) -> UnionMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
+    /// Apply a directive to the union type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> UnionMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this union type in a generic meta type
-    pub fn into_meta<S>(self) -> MetaType<'a, S> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Union(self)
     }
 }
@@ -615,6 +737,7 @@ where
             name,
             description: None,
             input_fields: input_fields.to_vec(),
+            directives: vec![],
             try_parse_fn: try_parse_fn::<S, T>,
         }
     }
@@ -627,6 +750,14 @@ where
         self
     }
 
+    /// Apply a directive to the input object type
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> InputObjectMeta<'a, S> {
+        self.directives.push(directive);
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::InputObject(self)
@@ -665,6 +796,37 @@ impl<'a, S> Field<'a, S> {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Set the maximum time the field is allowed to take to resolve.
+    ///
+    /// When the timeout elapses, the field resolves to an error with a `TIMEOUT` code in its
+    /// extensions. Only honored by asynchronous execution.
+    ///
+    /// Interface fields are resolved by the fields of their implementers, so only the timeouts
+    /// of object fields are honored.
+    ///
+    /// This overwrites the timeout if any was previously set.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Exclude the field from `tracing` instrumentation.
+    ///
+    /// Useful for trivial fields, whose spans would only add noise. Has no effect unless the
+    /// `tracing` feature is enabled.
+    pub fn skip_tracing(mut self) -> Self {
+        self.skip_tracing = true;
+        self
+    }
+
+    /// Apply a directive to the field.
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -675,6 +837,7 @@ impl<'a, S> Argument<'a, S> {
             description: None,
             arg_type,
             default_value: None,
+            directives: vec![],
         }
     }
 
@@ -693,22 +856,31 @@ impl<'a, S> Argument<'a, S> {
         self.default_value = Some(default_value);
         self
     }
+
+    /// Apply a directive to the argument
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
 }
 
-impl EnumValue {
+impl<S> EnumValue<S> {
     /// Construct a new enum value with the provided name
-    pub fn new(name: &str) -> EnumValue {
+    pub fn new(name: &str) -> EnumValue<S> {
         EnumValue {
             name: name.to_owned(),
             description: None,
             deprecation_status: DeprecationStatus::Current,
+            directives: vec![],
         }
     }
 
     /// Set the description of the enum value
     ///
     /// This overwrites the description if any was previously set.
-    pub fn description(mut self, description: &str) -> EnumValue {
+    pub fn description(mut self, description: &str) -> EnumValue<S> {
         self.description = Some(description.to_owned());
         self
     }
@@ -720,6 +892,14 @@ impl EnumValue {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+
+    /// Apply a directive to the enum value
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.directives.push(directive);
+        self
+    }
 }
 
 impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
@@ -727,6 +907,7 @@ impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
         fmt.debug_struct("ScalarMeta")
             .field("name", &self.name)
             .field("description", &self.description)
+            .field("directives", &self.directives)
             .finish()
     }
 }
@@ -737,6 +918,7 @@ impl<'a, S: fmt::Debug> fmt::Debug for EnumMeta<'a, S> {
             .field("name", &self.name)
             .field("description", &self.description)
             .field("values", &self.values)
+            .field("directives", &self.directives)
             .finish()
     }
 }
@@ -747,13 +929,16 @@ impl<'a, S: fmt::Debug> fmt::Debug for InputObjectMeta<'a, S> {
             .field("name", &self.name)
             .field("description", &self.description)
             .field("input_fields", &self.input_fields)
+            .field("directives", &self.directives)
             .finish()
     }
 }
 
-fn try_parse_fn<S, T>(v: &InputValue<S>) -> bool
+fn try_parse_fn<S, T>(v: &InputValue<S>) -> Result<(), FieldError<S>>
 where
     T: FromInputValue<S>,
 {
-    <T as FromInputValue<S>>::from_input_value(v).is_some()
+    <T as FromInputValue<S>>::from_input_value(v)
+        .map(drop)
+        .map_err(IntoFieldError::into_field_error)
 }
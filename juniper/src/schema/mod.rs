@@ -1,6 +1,11 @@
 #![allow(clippy::module_inception)]
 
+#[cfg(feature = "schema-language")]
+pub mod diff;
+pub mod from_introspection;
 pub mod meta;
 pub mod model;
 pub mod schema;
+#[cfg(feature = "schema-language")]
+pub mod snapshot;
 pub mod translate;
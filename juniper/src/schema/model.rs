@@ -7,7 +7,9 @@ use graphql_parser::schema::Document;
 use crate::{
     ast::Type,
     executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
+    schema::meta::{
+        AppliedDirective, Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta,
+    },
     types::{base::GraphQLType, name::Name},
     value::{DefaultScalarValue, ScalarValue},
     GraphQLEnum,
@@ -53,7 +55,8 @@ pub struct SchemaType<'a, S> {
     pub(crate) query_type_name: String,
     pub(crate) mutation_type_name: Option<String>,
     pub(crate) subscription_type_name: Option<String>,
-    directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    pub(crate) schema_directives: Vec<AppliedDirective<S>>,
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
@@ -65,27 +68,72 @@ pub enum TypeType<'a, S: 'a> {
     List(Box<TypeType<'a, S>>, Option<usize>),
 }
 
+/// Definition of a directive, like `@skip` or a custom `@auth`
 #[derive(Debug)]
 pub struct DirectiveType<'a, S> {
+    /// Name of the directive, without the leading `@`
     pub name: String,
+    /// Optional description of the directive
     pub description: Option<String>,
+    /// Locations the directive may be used at
     pub locations: Vec<DirectiveLocation>,
+    /// Arguments accepted by the directive
     pub arguments: Vec<Argument<'a, S>>,
 }
 
+/// A location in the GraphQL language a directive can be adjacent to.
 #[derive(Clone, PartialEq, Eq, Debug, GraphQLEnum)]
-#[graphql(name = "__DirectiveLocation", internal)]
+#[graphql(
+    name = "__DirectiveLocation",
+    description = "A Directive can be adjacent to many parts of the GraphQL language, \
+                   a __DirectiveLocation describes one such possible adjacencies.",
+    internal
+)]
 pub enum DirectiveLocation {
+    /// Location adjacent to a query operation.
     Query,
+    /// Location adjacent to a mutation operation.
     Mutation,
+    /// Location adjacent to a subscription operation.
     Subscription,
+    /// Location adjacent to a field.
     Field,
+    /// Location adjacent to a fragment definition.
     #[graphql(name = "FRAGMENT_DEFINITION")]
     FragmentDefinition,
+    /// Location adjacent to a fragment spread.
     #[graphql(name = "FRAGMENT_SPREAD")]
     FragmentSpread,
+    /// Location adjacent to an inline fragment.
     #[graphql(name = "INLINE_FRAGMENT")]
     InlineFragment,
+    /// Location adjacent to a schema definition.
+    Schema,
+    /// Location adjacent to a scalar definition.
+    Scalar,
+    /// Location adjacent to an object type definition.
+    Object,
+    /// Location adjacent to a field definition.
+    #[graphql(name = "FIELD_DEFINITION")]
+    FieldDefinition,
+    /// Location adjacent to an argument definition.
+    #[graphql(name = "ARGUMENT_DEFINITION")]
+    ArgumentDefinition,
+    /// Location adjacent to an interface definition.
+    Interface,
+    /// Location adjacent to a union definition.
+    Union,
+    /// Location adjacent to an enum definition.
+    Enum,
+    /// Location adjacent to an enum value definition.
+    #[graphql(name = "ENUM_VALUE")]
+    EnumValue,
+    /// Location adjacent to an input object type definition.
+    #[graphql(name = "INPUT_OBJECT")]
+    InputObject,
+    /// Location adjacent to an input object field definition.
+    #[graphql(name = "INPUT_FIELD_DEFINITION")]
+    InputFieldDefinition,
 }
 
 impl<'a, QueryT, MutationT, SubscriptionT>
@@ -153,6 +201,30 @@ where
         }
     }
 
+    /// Declare a custom type system directive, like `@auth(role: Role!) on OBJECT`.
+    ///
+    /// The directive is listed in introspection and its definition is printed in the schema
+    /// language. The registry passed to `directive_fn` allows building the directive arguments,
+    /// registering their types in the schema.
+    pub fn with_directive<F>(mut self, directive_fn: F) -> Self
+    where
+        F: FnOnce(&mut Registry<'a, S>) -> DirectiveType<'a, S>,
+    {
+        let mut registry = Registry::new(std::mem::take(&mut self.schema.types));
+        let directive = directive_fn(&mut registry);
+        self.schema.types = registry.types;
+        self.schema.add_directive(directive);
+        self
+    }
+
+    /// Apply a directive to the schema definition itself, like `schema @link(url: "...")`.
+    ///
+    /// The directive is appended to the ones applied previously.
+    pub fn schema_directive(mut self, directive: AppliedDirective<S>) -> Self {
+        self.schema.schema_directives.push(directive);
+        self
+    }
+
     #[cfg(feature = "schema-language")]
     /// The schema definition as a `String` in the
     /// [GraphQL Schema Language](https://graphql.org/learn/schema/#type-language)
@@ -251,6 +323,7 @@ impl<'a, S> SchemaType<'a, S> {
                 None
             },
             directives,
+            schema_directives: vec![],
         }
     }
 
@@ -462,7 +535,7 @@ impl<'a, S> TypeType<'a, S> {
     }
 
     #[inline]
-    pub fn innermost_concrete(&self) -> &'a MetaType<S> {
+    pub fn innermost_concrete(&self) -> &'a MetaType<'a, S> {
         match *self {
             TypeType::Concrete(t) => t,
             TypeType::NonNull(ref n) | TypeType::List(ref n, _) => n.innermost_concrete(),
@@ -488,6 +561,7 @@ impl<'a, S> DirectiveType<'a, S>
 where
     S: ScalarValue + 'a,
 {
+    /// Build a new directive definition with the specified name, locations and arguments
     pub fn new(
         name: &str,
         locations: &[DirectiveLocation],
@@ -501,7 +575,7 @@ where
         }
     }
 
-    fn new_skip(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
+    pub(crate) fn new_skip(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
     where
         S: ScalarValue,
     {
@@ -516,7 +590,7 @@ where
         )
     }
 
-    fn new_include(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
+    pub(crate) fn new_include(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S>
     where
         S: ScalarValue,
     {
@@ -531,6 +605,9 @@ where
         )
     }
 
+    /// Set the description of the directive
+    ///
+    /// If a description was provided prior to calling this method, it will be overwritten.
     pub fn description(mut self, description: &str) -> DirectiveType<'a, S> {
         self.description = Some(description.to_owned());
         self
@@ -547,6 +624,17 @@ impl fmt::Display for DirectiveLocation {
             DirectiveLocation::FragmentDefinition => "fragment definition",
             DirectiveLocation::FragmentSpread => "fragment spread",
             DirectiveLocation::InlineFragment => "inline fragment",
+            DirectiveLocation::Schema => "schema",
+            DirectiveLocation::Scalar => "scalar",
+            DirectiveLocation::Object => "object",
+            DirectiveLocation::FieldDefinition => "field definition",
+            DirectiveLocation::ArgumentDefinition => "argument definition",
+            DirectiveLocation::Interface => "interface",
+            DirectiveLocation::Union => "union",
+            DirectiveLocation::Enum => "enum",
+            DirectiveLocation::EnumValue => "enum value",
+            DirectiveLocation::InputObject => "input object",
+            DirectiveLocation::InputFieldDefinition => "input field definition",
         })
     }
 }
@@ -677,14 +765,18 @@ mod test {
             );
             let ast = graphql_parser::parse_schema::<&str>(
                 r#"
-                union GlutenFree = Cake | IceCream
+                type Cake {
+                    fresh: Boolean!
+                }
+                input Coordinate {
+                    latitude: Float!
+                    longitude: Float!
+                }
                 enum Fruit {
                     APPLE
                     ORANGE
                 }
-                type Cake {
-                    fresh: Boolean!
-                }
+                union GlutenFree = Cake | IceCream
                 type IceCream {
                     cold: Boolean!
                 }
@@ -698,10 +790,6 @@ mod test {
                   old: Int! @deprecated
                   reallyOld: Float! @deprecated(reason: "This field is deprecated, use another.")
                 }
-                input Coordinate {
-                    latitude: Float!
-                    longitude: Float!
-                }
                 schema {
                   query: Query
                 }
@@ -710,5 +798,167 @@ mod test {
             .unwrap();
             assert_eq!(format!("{}", ast), schema.as_schema_language());
         }
+
+        #[test]
+        fn schema_language_with_directives() {
+            use crate::{meta::AppliedDirective, DirectiveLocation, DirectiveType, InputValue, ID};
+
+            #[derive(GraphQLEnum)]
+            enum Role {
+                Admin,
+                User,
+            }
+            #[derive(GraphQLObject)]
+            #[graphql(directive(tag(name = "public")))]
+            struct Cake {
+                #[graphql(directive(tag(name = "public"), cost(weight = 2)))]
+                fresh: bool,
+            }
+            #[derive(GraphQLObject)]
+            struct IceCream {
+                cold: bool,
+            }
+            #[derive(GraphQLUnion)]
+            #[graphql(directive(tag(name = "public")))]
+            enum Dessert {
+                Cake(Cake),
+                IceCream(IceCream),
+            }
+            #[derive(GraphQLEnum)]
+            #[graphql(directive(tag(name = "public")))]
+            enum Fruit {
+                #[graphql(directive(tag(name = "public")))]
+                Apple,
+                Orange,
+            }
+            #[derive(GraphQLInputObject)]
+            #[graphql(directive(tag(name = "public")))]
+            struct Coordinate {
+                #[graphql(directive(range(min = -90, max = 90)))]
+                latitude: f64,
+                longitude: f64,
+            }
+            struct Query;
+            #[graphql_object(directive(auth(role = ADMIN, scopes = ["read", "write"])))]
+            impl Query {
+                #[graphql(
+                    directive(auth(role = USER)),
+                    arguments(id(directive(tag(name = "public")))),
+                )]
+                fn cake(id: ID) -> Cake {
+                    let _ = id;
+                    Cake { fresh: true }
+                }
+                fn dessert(at: Coordinate) -> Dessert {
+                    let _ = at;
+                    Dessert::IceCream(IceCream { cold: true })
+                }
+                fn fruit() -> Fruit {
+                    Fruit::Apple
+                }
+            }
+
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .with_directive(|registry| {
+                DirectiveType::new(
+                    "auth",
+                    &[
+                        DirectiveLocation::Object,
+                        DirectiveLocation::FieldDefinition,
+                    ],
+                    &[registry.arg::<Role>("role", &())],
+                )
+            })
+            .schema_directive(
+                AppliedDirective::new("contact").argument("name", InputValue::scalar("team")),
+            );
+            let sdl = schema.as_schema_language();
+
+            for expected in &[
+                "directive @auth(role: Role!) on OBJECT | FIELD_DEFINITION",
+                "schema @contact(name: \"team\") {",
+                "type Query @auth(role: ADMIN, scopes: [\"read\", \"write\"]) {",
+                "cake(id: ID! @tag(name: \"public\")): Cake! @auth(role: USER)",
+                "type Cake @tag(name: \"public\") {",
+                "fresh: Boolean! @tag(name: \"public\") @cost(weight: 2)",
+                "union Dessert @tag(name: \"public\") = Cake | IceCream",
+                "enum Fruit @tag(name: \"public\") {",
+                "APPLE @tag(name: \"public\")",
+                "input Coordinate @tag(name: \"public\") {",
+                "latitude: Float! @range(min: -90, max: 90)",
+                "enum Role {",
+            ] {
+                assert!(
+                    sdl.contains(expected),
+                    "missing `{}` in:\n{}",
+                    expected,
+                    sdl
+                );
+            }
+        }
+
+        #[test]
+        fn introspects_custom_directives() {
+            use crate::{graphql_value, DirectiveLocation, DirectiveType, Variables};
+
+            struct Query;
+            #[graphql_object]
+            impl Query {
+                fn blah() -> bool {
+                    true
+                }
+            }
+
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+            .with_directive(|registry| {
+                DirectiveType::new(
+                    "tag",
+                    &[
+                        DirectiveLocation::FieldDefinition,
+                        DirectiveLocation::ArgumentDefinition,
+                        DirectiveLocation::InputFieldDefinition,
+                        DirectiveLocation::EnumValue,
+                    ],
+                    &[registry.arg::<String>("name", &())],
+                )
+                .description("Tags a definition.")
+            });
+
+            let doc = r#"{
+                __schema {
+                    directives { name description locations args { name } }
+                }
+            }"#;
+            let (res, errs) =
+                crate::execute_sync(doc, None, &schema, &Variables::new(), &()).unwrap();
+            assert_eq!(errs, []);
+
+            let directives = res
+                .as_object_value()
+                .and_then(|o| o.get_field_value("__schema"))
+                .and_then(|s| s.as_object_value())
+                .and_then(|s| s.get_field_value("directives"))
+                .and_then(|d| d.as_list_value())
+                .unwrap();
+            assert!(directives.contains(&graphql_value!({
+                "name": "tag",
+                "description": "Tags a definition.",
+                "locations": [
+                    "FIELD_DEFINITION",
+                    "ARGUMENT_DEFINITION",
+                    "INPUT_FIELD_DEFINITION",
+                    "ENUM_VALUE",
+                ],
+                "args": [{"name": "name"}],
+            })));
+        }
     }
 }
@@ -69,7 +69,7 @@ where
                 .resolve(&(), &self.schema),
             #[cfg(not(feature= "disable_introspection"))]        
             "__type" => {
-                let type_name: String = args.get("name").unwrap();
+                let type_name: String = args.get("name")?.unwrap();
                 executor
                     .replaced_context(&self.schema)
                     .resolve(&(), &self.schema.type_by_name(&type_name))
@@ -296,7 +296,7 @@ where
     }
 
     #[graphql(arguments(include_deprecated(default = false)))]
-    fn enum_values(&self, include_deprecated: bool) -> Option<Vec<&EnumValue>> {
+    fn enum_values(&self, include_deprecated: bool) -> Option<Vec<&EnumValue<S>>> {
         match *self {
             TypeType::Concrete(&MetaType::Enum(EnumMeta { ref values, .. })) => Some(
                 values
@@ -389,7 +389,7 @@ where
     // FIXME: make this redundant.
     noasync,
 )]
-impl<'a, S> EnumValue
+impl<'a, S> EnumValue<S>
 where
     S: crate::ScalarValue + 'a,
 {
@@ -0,0 +1,293 @@
+//! Snapshots of a schema in the GraphQL Schema Language, committed to a repository.
+//!
+//! [`write`] renders a schema, as printed by
+//! [`RootNode::as_schema_language`](crate::RootNode::as_schema_language), to a file from a build
+//! script or a test, and [`assert_matches`] fails with a line diff when the schema drifts from
+//! the committed snapshot:
+//!
+//! ```rust,no_run
+//! use juniper::{graphql_object, schema_snapshot, EmptyMutation, EmptySubscription, RootNode};
+//!
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn name() -> String {
+//!         "Alice".into()
+//!     }
+//! }
+//!
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//!
+//! schema_snapshot::assert_matches(
+//!     &schema.schema,
+//!     concat!(env!("CARGO_MANIFEST_DIR"), "/schema.graphql"),
+//! );
+//! ```
+//!
+//! Running with the `JUNIPER_UPDATE_SNAPSHOTS` environment variable set makes [`assert_matches`]
+//! write the snapshot instead of comparing it, accepting the changes of the schema.
+
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use graphql_parser::schema::Document;
+
+use crate::{
+    schema::{
+        model::SchemaType,
+        translate::{graphql_parser::GraphQLParserTranslator, SchemaTranslator},
+    },
+    value::ScalarValue,
+};
+
+/// Environment variable making [`assert_matches`] update the snapshots instead of checking them.
+pub const UPDATE_ENV_VAR: &str = "JUNIPER_UPDATE_SNAPSHOTS";
+
+/// Lines of context printed around the changed lines of a [`SnapshotError::Drift`].
+const CONTEXT_LINES: usize = 2;
+
+/// Error of checking a schema against its snapshot.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// The snapshot couldn't be read.
+    Io {
+        /// Path of the snapshot.
+        path: PathBuf,
+        /// Error of reading it.
+        error: io::Error,
+    },
+
+    /// The schema doesn't match the snapshot.
+    Drift {
+        /// Path of the snapshot.
+        path: PathBuf,
+        /// Line diff from the snapshot to the schema, with `-` lines only in the snapshot and
+        /// `+` lines only in the schema.
+        diff: String,
+    },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(
+                f,
+                "failed to read schema snapshot `{}`: {}",
+                path.display(),
+                error,
+            ),
+            Self::Drift { path, diff } => write!(
+                f,
+                "schema doesn't match snapshot `{}`, run with `{}=1` to update it:\n{}",
+                path.display(),
+                UPDATE_ENV_VAR,
+                diff,
+            ),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            Self::Drift { .. } => None,
+        }
+    }
+}
+
+/// Writes the schema language of `schema` to the snapshot at `path`.
+///
+/// The file is left untouched if it's already up to date, so build scripts don't trigger
+/// rebuilds of what depends on it.
+pub fn write<S>(schema: &SchemaType<S>, path: impl AsRef<Path>) -> io::Result<()>
+where
+    S: ScalarValue,
+{
+    let path = path.as_ref();
+    let sdl = schema_language(schema);
+    match fs::read_to_string(path) {
+        Ok(snapshot) if normalize(&snapshot) == sdl => Ok(()),
+        _ => fs::write(path, sdl),
+    }
+}
+
+/// Checks the schema language of `schema` against the snapshot at `path`.
+pub fn check<S>(schema: &SchemaType<S>, path: impl AsRef<Path>) -> Result<(), SnapshotError>
+where
+    S: ScalarValue,
+{
+    let path = path.as_ref();
+    let snapshot = fs::read_to_string(path).map_err(|error| SnapshotError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    let snapshot = normalize(&snapshot);
+    let sdl = schema_language(schema);
+    if snapshot == sdl {
+        Ok(())
+    } else {
+        Err(SnapshotError::Drift {
+            path: path.to_owned(),
+            diff: line_diff(&snapshot, &sdl),
+        })
+    }
+}
+
+/// Asserts that the schema language of `schema` matches the snapshot at `path`.
+///
+/// If the `JUNIPER_UPDATE_SNAPSHOTS` environment variable is set, the snapshot is written
+/// instead.
+///
+/// # Panics
+///
+/// If the snapshot can't be read or written, or doesn't match the schema, with a line diff of
+/// the changes.
+#[track_caller]
+pub fn assert_matches<S>(schema: &SchemaType<S>, path: impl AsRef<Path>)
+where
+    S: ScalarValue,
+{
+    let path = path.as_ref();
+    if std::env::var_os(UPDATE_ENV_VAR).is_some() {
+        if let Err(e) = write(schema, path) {
+            panic!(
+                "failed to write schema snapshot `{}`: {}",
+                path.display(),
+                e,
+            );
+        }
+    } else if let Err(e) = check(schema, path) {
+        panic!("{}", e);
+    }
+}
+
+fn schema_language<S: ScalarValue>(schema: &SchemaType<S>) -> String {
+    let doc: Document<&str> = GraphQLParserTranslator::translate_schema(schema);
+    doc.to_string()
+}
+
+/// Drops the carriage returns `git` may add to the line endings of a checked out snapshot.
+fn normalize(snapshot: &str) -> String {
+    snapshot.replace("\r\n", "\n")
+}
+
+/// Diffs `old` and `new` line by line, printing the changed lines with some context around them.
+fn line_diff(old: &str, new: &str) -> String {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // Longest common subsequences of the suffixes of both sides.
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(('-', old[i]));
+            i += 1;
+        } else {
+            lines.push(('+', new[j]));
+            j += 1;
+        }
+    }
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != ' ')
+        .map(|(n, _)| n)
+        .collect::<Vec<_>>();
+    let mut diff = String::new();
+    let mut last = None;
+    for (n, (tag, line)) in lines.iter().enumerate() {
+        let near_change = changed
+            .iter()
+            .any(|c| (c.saturating_sub(CONTEXT_LINES)..=c + CONTEXT_LINES).contains(&n));
+        if !near_change {
+            continue;
+        }
+        if matches!(last, Some(l) if l + 1 != n) {
+            diff.push_str("...\n");
+        }
+        diff.push_str(&format!("{} {}\n", tag, line));
+        last = Some(n);
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+
+    use super::{check, line_diff, write, SnapshotError};
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn name() -> String {
+            "Alice".into()
+        }
+    }
+
+    #[test]
+    fn diffs_changed_lines_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh\ni\nj\nk\nl\n";
+
+        assert_eq!(
+            line_diff(old, new),
+            "  c\n  d\n- e\n+ E\n  f\n  g\n...\n  j\n  k\n+ l\n",
+        );
+    }
+
+    #[test]
+    fn checks_schema_against_written_snapshot() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        );
+        let path = env::temp_dir().join(format!("juniper-snapshot-{}.graphql", std::process::id()));
+
+        assert!(matches!(
+            check(&schema.schema, &path),
+            Err(SnapshotError::Io { .. }),
+        ));
+
+        write(&schema.schema, &path).unwrap();
+        assert!(check(&schema.schema, &path).is_ok());
+
+        fs::write(&path, "type Query {\r\n  email: String\r\n}\r\n").unwrap();
+        let err = check(&schema.schema, &path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        match err {
+            SnapshotError::Drift { diff, .. } => assert_eq!(
+                diff,
+                "  type Query {\n-   email: String\n+   name: String!\n  }\n+ \n+ schema {\n+   query: Query\n+ }\n",
+            ),
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+}
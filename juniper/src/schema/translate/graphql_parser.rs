@@ -3,10 +3,12 @@ use std::{boxed::Box, collections::BTreeMap};
 use graphql_parser::{
     query::{Directive as ExternalDirective, Number as ExternalNumber, Type as ExternalType},
     schema::{
-        Definition, Document, EnumType as ExternalEnum, EnumValue as ExternalEnumValue,
-        Field as ExternalField, InputObjectType as ExternalInputObjectType,
-        InputValue as ExternalInputValue, InterfaceType as ExternalInterfaceType,
-        ObjectType as ExternalObjectType, ScalarType as ExternalScalarType, SchemaDefinition, Text,
+        Definition, DirectiveDefinition as ExternalDirectiveDefinition,
+        DirectiveLocation as ExternalDirectiveLocation, Document, EnumType as ExternalEnum,
+        EnumValue as ExternalEnumValue, Field as ExternalField,
+        InputObjectType as ExternalInputObjectType, InputValue as ExternalInputValue,
+        InterfaceType as ExternalInterfaceType, ObjectType as ExternalObjectType,
+        ScalarType as ExternalScalarType, SchemaDefinition, Text,
         TypeDefinition as ExternalTypeDefinition, UnionType as ExternalUnionType,
         Value as ExternalValue,
     },
@@ -16,8 +18,8 @@ use graphql_parser::{
 use crate::{
     ast::{InputValue, Type},
     schema::{
-        meta::{Argument, DeprecationStatus, EnumValue, Field, MetaType},
-        model::SchemaType,
+        meta::{AppliedDirective, Argument, DeprecationStatus, EnumValue, Field, MetaType},
+        model::{DirectiveLocation, DirectiveType, SchemaType},
         translate::SchemaTranslator,
     },
     value::ScalarValue,
@@ -46,20 +48,40 @@ where
     {
         let mut doc = Document::default();
 
-        // Translate type defs.
+        // Translate type defs, sorted by name so the output doesn't depend on the order of the
+        // registry's hash map.
         let mut types = input
             .types
             .iter()
             .filter(|(_, meta)| !meta.is_builtin())
+            .collect::<Vec<_>>();
+        types.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut types = types
+            .into_iter()
             .map(|(_, meta)| GraphQLParserTranslator::translate_meta(meta))
             .map(Definition::TypeDefinition)
             .collect();
         doc.definitions.append(&mut types);
 
+        // Translate custom directive defs, `@skip` and `@include` are built-in.
+        let mut directives = input.directive_list();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut directives = directives
+            .into_iter()
+            .filter(|d| d.name != "skip" && d.name != "include")
+            .map(GraphQLParserTranslator::translate_directive_type)
+            .map(Definition::DirectiveDefinition)
+            .collect();
+        doc.definitions.append(&mut directives);
+
         doc.definitions
             .push(Definition::SchemaDefinition(SchemaDefinition {
                 position: Pos::default(),
-                directives: vec![],
+                directives: input
+                    .schema_directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
                 query: Some(From::from(input.query_type_name.as_str())),
                 mutation: input
                     .mutation_type_name
@@ -90,7 +112,11 @@ impl GraphQLParserTranslator {
                 .default_value
                 .as_ref()
                 .map(|x| GraphQLParserTranslator::translate_value(x)),
-            directives: vec![],
+            directives: input
+                .directives
+                .iter()
+                .map(GraphQLParserTranslator::translate_directive)
+                .collect(),
         }
     }
 
@@ -162,13 +188,21 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(From::from),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
             }),
             MetaType::Enum(x) => ExternalTypeDefinition::Enum(ExternalEnum {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
                 values: x
                     .values
                     .iter()
@@ -179,7 +213,11 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
                 types: x
                     .of_type_names
                     .iter()
@@ -190,7 +228,11 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
                 fields: x
                     .fields
                     .iter()
@@ -203,7 +245,11 @@ impl GraphQLParserTranslator {
                     position: Pos::default(),
                     description: x.description.as_ref().map(|s| From::from(s.as_str())),
                     name: From::from(x.name.as_ref()),
-                    directives: vec![],
+                    directives: x
+                        .directives
+                        .iter()
+                        .map(GraphQLParserTranslator::translate_directive)
+                        .collect(),
                     fields: x
                         .input_fields
                         .iter()
@@ -216,7 +262,11 @@ impl GraphQLParserTranslator {
                 position: Pos::default(),
                 description: x.description.as_ref().map(|s| From::from(s.as_str())),
                 name: From::from(x.name.as_ref()),
-                directives: vec![],
+                directives: x
+                    .directives
+                    .iter()
+                    .map(GraphQLParserTranslator::translate_directive)
+                    .collect(),
                 fields: x
                     .fields
                     .iter()
@@ -233,15 +283,24 @@ impl GraphQLParserTranslator {
         }
     }
 
-    fn translate_enum_value<'a, T>(input: &'a EnumValue) -> ExternalEnumValue<'a, T>
+    fn translate_enum_value<'a, S: 'a, T>(input: &'a EnumValue<S>) -> ExternalEnumValue<'a, T>
     where
+        S: ScalarValue,
         T: Text<'a>,
     {
+        let mut directives = generate_directives(&input.deprecation_status);
+        directives.extend(
+            input
+                .directives
+                .iter()
+                .map(GraphQLParserTranslator::translate_directive),
+        );
+
         ExternalEnumValue {
             position: Pos::default(),
             name: From::from(input.name.as_ref()),
             description: input.description.as_ref().map(|s| From::from(s.as_str())),
-            directives: generate_directives(&input.deprecation_status),
+            directives,
         }
     }
 
@@ -261,15 +320,93 @@ impl GraphQLParserTranslator {
             })
             .unwrap_or_else(Vec::new);
 
+        let mut directives = generate_directives(&input.deprecation_status);
+        directives.extend(
+            input
+                .directives
+                .iter()
+                .map(GraphQLParserTranslator::translate_directive),
+        );
+
         ExternalField {
             position: Pos::default(),
             name: From::from(input.name.as_str()),
             description: input.description.as_ref().map(|s| From::from(s.as_str())),
-            directives: generate_directives(&input.deprecation_status),
+            directives,
             field_type: GraphQLParserTranslator::translate_type(&input.field_type),
             arguments,
         }
     }
+
+    fn translate_directive_type<'a, S: 'a, T>(
+        input: &'a DirectiveType<S>,
+    ) -> ExternalDirectiveDefinition<'a, T>
+    where
+        S: ScalarValue,
+        T: Text<'a>,
+    {
+        ExternalDirectiveDefinition {
+            position: Pos::default(),
+            description: input.description.clone(),
+            name: From::from(input.name.as_str()),
+            arguments: input
+                .arguments
+                .iter()
+                .map(GraphQLParserTranslator::translate_argument)
+                .collect(),
+            locations: input
+                .locations
+                .iter()
+                .map(GraphQLParserTranslator::translate_directive_location)
+                .collect(),
+        }
+    }
+
+    fn translate_directive_location(input: &DirectiveLocation) -> ExternalDirectiveLocation {
+        match input {
+            DirectiveLocation::Query => ExternalDirectiveLocation::Query,
+            DirectiveLocation::Mutation => ExternalDirectiveLocation::Mutation,
+            DirectiveLocation::Subscription => ExternalDirectiveLocation::Subscription,
+            DirectiveLocation::Field => ExternalDirectiveLocation::Field,
+            DirectiveLocation::FragmentDefinition => ExternalDirectiveLocation::FragmentDefinition,
+            DirectiveLocation::FragmentSpread => ExternalDirectiveLocation::FragmentSpread,
+            DirectiveLocation::InlineFragment => ExternalDirectiveLocation::InlineFragment,
+            DirectiveLocation::Schema => ExternalDirectiveLocation::Schema,
+            DirectiveLocation::Scalar => ExternalDirectiveLocation::Scalar,
+            DirectiveLocation::Object => ExternalDirectiveLocation::Object,
+            DirectiveLocation::FieldDefinition => ExternalDirectiveLocation::FieldDefinition,
+            DirectiveLocation::ArgumentDefinition => ExternalDirectiveLocation::ArgumentDefinition,
+            DirectiveLocation::Interface => ExternalDirectiveLocation::Interface,
+            DirectiveLocation::Union => ExternalDirectiveLocation::Union,
+            DirectiveLocation::Enum => ExternalDirectiveLocation::Enum,
+            DirectiveLocation::EnumValue => ExternalDirectiveLocation::EnumValue,
+            DirectiveLocation::InputObject => ExternalDirectiveLocation::InputObject,
+            DirectiveLocation::InputFieldDefinition => {
+                ExternalDirectiveLocation::InputFieldDefinition
+            }
+        }
+    }
+
+    fn translate_directive<'a, S: 'a, T>(input: &'a AppliedDirective<S>) -> ExternalDirective<'a, T>
+    where
+        S: ScalarValue,
+        T: Text<'a>,
+    {
+        ExternalDirective {
+            position: Pos::default(),
+            name: From::from(input.name.as_str()),
+            arguments: input
+                .arguments
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        From::from(name.as_str()),
+                        GraphQLParserTranslator::translate_value(value),
+                    )
+                })
+                .collect(),
+        }
+    }
 }
 
 fn deprecation_to_directive<'a, T>(status: &DeprecationStatus) -> Option<ExternalDirective<'a, T>>
@@ -980,50 +980,116 @@ pub(crate) fn schema_introspection_result() -> value::Value {
             {
               "kind": "ENUM",
               "name": "__DirectiveLocation",
-              "description": Null,
+              "description": "A Directive can be adjacent to many parts of the GraphQL language, a __DirectiveLocation describes one such possible adjacencies.",
               "fields": Null,
               "inputFields": Null,
               "interfaces": Null,
               "enumValues": [
                 {
                   "name": "QUERY",
-                  "description": Null,
+                  "description": "Location adjacent to a query operation.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "MUTATION",
-                  "description": Null,
+                  "description": "Location adjacent to a mutation operation.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "SUBSCRIPTION",
-                  "description": Null,
+                  "description": "Location adjacent to a subscription operation.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "FIELD",
-                  "description": Null,
+                  "description": "Location adjacent to a field.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "FRAGMENT_DEFINITION",
-                  "description": Null,
+                  "description": "Location adjacent to a fragment definition.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "FRAGMENT_SPREAD",
-                  "description": Null,
+                  "description": "Location adjacent to a fragment spread.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 },
                 {
                   "name": "INLINE_FRAGMENT",
-                  "description": Null,
+                  "description": "Location adjacent to an inline fragment.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "SCHEMA",
+                  "description": "Location adjacent to a schema definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "SCALAR",
+                  "description": "Location adjacent to a scalar definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "OBJECT",
+                  "description": "Location adjacent to an object type definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "FIELD_DEFINITION",
+                  "description": "Location adjacent to a field definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "description": "Location adjacent to an argument definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INTERFACE",
+                  "description": "Location adjacent to an interface definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "UNION",
+                  "description": "Location adjacent to a union definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ENUM",
+                  "description": "Location adjacent to an enum definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ENUM_VALUE",
+                  "description": "Location adjacent to an enum value definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INPUT_OBJECT",
+                  "description": "Location adjacent to an input object type definition.",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "description": "Location adjacent to an input object field definition.",
                   "isDeprecated": false,
                   "deprecationReason": Null
                 }
@@ -2234,6 +2300,61 @@ pub(crate) fn schema_introspection_result_without_descriptions() -> value::Value
                   "name": "INLINE_FRAGMENT",
                   "isDeprecated": false,
                   "deprecationReason": Null
+                },
+                {
+                  "name": "SCHEMA",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "SCALAR",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "OBJECT",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "FIELD_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ARGUMENT_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INTERFACE",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "UNION",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ENUM",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "ENUM_VALUE",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INPUT_OBJECT",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
+                },
+                {
+                  "name": "INPUT_FIELD_DEFINITION",
+                  "isDeprecated": false,
+                  "deprecationReason": Null
                 }
               ],
               "possibleTypes": Null
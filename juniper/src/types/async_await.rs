@@ -1,6 +1,6 @@
 use crate::{
     ast::Selection,
-    executor::{ExecutionResult, Executor},
+    executor::{resolve_with_timeout, ExecutionResult, Executor},
     parser::Spanning,
     value::{DefaultScalarValue, Object, ScalarValue, Value},
 };
@@ -258,19 +258,32 @@ where
 
                 let pos = *start_pos;
                 let is_non_null = meta_field.field_type.is_non_null();
+                let timeout = executor.field_timeout(meta_field.timeout);
+
+                #[cfg(feature = "tracing")]
+                let span = sub_exec.field_span(meta_field, meta_type.name());
 
                 let response_name = response_name.to_string();
-                async_values.push(AsyncValueFuture::Field(async move {
+                let field_future = async move {
+                    let started = sub_exec.trace_start();
+
                     // TODO: implement custom future type instead of
                     //       two-level boxing.
-                    let res = instance
-                        .resolve_field_async(info, f.name.item, &args, &sub_exec)
-                        .await;
+                    let resolver =
+                        instance.resolve_field_async(info, f.name.item, &args, &sub_exec);
+                    let res = match timeout {
+                        Some(timeout) => resolve_with_timeout(timeout, resolver).await,
+                        None => resolver.await,
+                    };
+                    sub_exec.trace_resolver(meta_field, meta_type.name(), started);
 
                     let value = match res {
                         Ok(Value::Null) if is_non_null => None,
                         Ok(v) => Some(v),
                         Err(e) => {
+                            #[cfg(feature = "tracing")]
+                            crate::executor::trace_field_error(&e);
+
                             sub_exec.push_error_at(e, pos);
 
                             if is_non_null {
@@ -284,7 +297,10 @@ where
                         name: response_name,
                         value,
                     })
-                }));
+                };
+                #[cfg(feature = "tracing")]
+                let field_future = tracing::Instrument::instrument(field_future, span);
+                async_values.push(AsyncValueFuture::Field(field_future));
             }
 
             Selection::FragmentSpread(Spanning {
@@ -2,7 +2,7 @@ use indexmap::IndexMap;
 
 use crate::{
     ast::{Directive, FromInputValue, InputValue, Selection},
-    executor::{ExecutionResult, Executor, Registry, Variables},
+    executor::{ExecutionResult, Executor, FieldResult, IntoFieldError, Registry, Variables},
     parser::Spanning,
     schema::meta::{Argument, MetaType},
     value::{DefaultScalarValue, Object, ScalarValue, Value},
@@ -104,16 +104,20 @@ where
     /// If the argument is found, or a default argument has been provided,
     /// the `InputValue` will be converted into the type `T`.
     ///
-    /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
-    pub fn get<T>(&self, key: &str) -> Option<T>
+    /// Returns `Ok(None)` if the argument is absent, and the conversion
+    /// error as a [`FieldError`] if the type conversion fails.
+    ///
+    /// [`FieldError`]: crate::FieldError
+    pub fn get<T>(&self, key: &str) -> FieldResult<Option<T>, S>
     where
         T: FromInputValue<S>,
     {
         self.args
             .as_ref()
             .and_then(|args| args.get(key))
-            .and_then(InputValue::convert)
+            .map(InputValue::convert)
+            .transpose()
+            .map_err(IntoFieldError::into_field_error)
     }
 }
 
@@ -465,6 +469,9 @@ where
                     f.selection_set.as_ref().map(|v| &v[..]),
                 );
 
+                #[cfg(feature = "tracing")]
+                let _span = sub_exec.field_span(meta_field, meta_type.name()).entered();
+
                 let field_result = instance.resolve_field(
                     info,
                     f.name.item,
@@ -486,6 +493,9 @@ where
                     Ok(Value::Null) if meta_field.field_type.is_non_null() => return false,
                     Ok(v) => merge_key_into(result, response_name, v),
                     Err(e) => {
+                        #[cfg(feature = "tracing")]
+                        crate::executor::trace_field_error(&e);
+
                         sub_exec.push_error_at(e, *start_pos);
 
                         if meta_field.field_type.is_non_null() {
@@ -589,7 +599,7 @@ where
     true
 }
 
-pub(super) fn is_excluded<S>(
+pub(crate) fn is_excluded<S>(
     directives: &Option<Vec<Spanning<Directive<S>>>>,
     vars: &Variables<S>,
 ) -> bool
@@ -602,6 +612,10 @@ where
             ..
         } in directives
         {
+            if directive.name.item != "skip" && directive.name.item != "include" {
+                continue;
+            }
+
             let condition: bool = directive
                 .arguments
                 .iter()
@@ -1,11 +1,14 @@
 use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    hash::{BuildHasher, Hash},
+    iter,
     mem::{self, MaybeUninit},
     ptr,
 };
 
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
-    executor::{ExecutionResult, Executor, Registry},
+    executor::{ExecutionResult, Executor, FieldError, IntoFieldError, Registry},
     schema::meta::MetaType,
     types::{
         async_await::GraphQLValueAsync,
@@ -85,9 +88,11 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
         match v {
-            &InputValue::Null => Some(None),
+            &InputValue::Null => Ok(None),
             v => v.convert().map(Some),
         }
     }
@@ -168,12 +173,11 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
         match *v {
-            InputValue::List(ref ls) => {
-                let v: Vec<_> = ls.iter().filter_map(|i| i.item.convert()).collect();
-                (v.len() == ls.len()).then(|| v)
-            }
+            InputValue::List(ref ls) => ls.iter().map(|i| i.item.convert()).collect(),
             ref other => other.convert().map(|e| vec![e]),
         }
     }
@@ -189,6 +193,257 @@ where
     }
 }
 
+impl<S, T> GraphQLType<S> for VecDeque<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_list_type::<T>(info, None).into_meta()
+    }
+}
+
+impl<S, T> GraphQLValue<S> for VecDeque<T>
+where
+    T: GraphQLValue<S>,
+    S: ScalarValue,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        _: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        resolve_into_list(executor, info, self.iter())
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for VecDeque<T>
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_async(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
+impl<T, S> FromInputValue<S> for VecDeque<T>
+where
+    T: FromInputValue<S>,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        match *v {
+            InputValue::List(ref ls) => ls.iter().map(|i| i.item.convert()).collect(),
+            ref other => other.convert().map(|e| iter::once(e).collect()),
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for VecDeque<T>
+where
+    T: ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(T::to_input_value).collect())
+    }
+}
+
+impl<S, T, H> GraphQLType<S> for HashSet<T, H>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_list_type::<T>(info, None).into_meta()
+    }
+}
+
+// Items are resolved in iteration order, which is unspecified for a `HashSet`.
+impl<S, T, H> GraphQLValue<S> for HashSet<T, H>
+where
+    T: GraphQLValue<S>,
+    S: ScalarValue,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        _: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        resolve_into_list(executor, info, self.iter())
+    }
+}
+
+impl<S, T, H> GraphQLValueAsync<S> for HashSet<T, H>
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    H: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_async(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
+impl<T, S, H> FromInputValue<S> for HashSet<T, H>
+where
+    T: FromInputValue<S>,
+    T: Eq + Hash,
+    H: BuildHasher + Default,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        match *v {
+            InputValue::List(ref ls) => ls.iter().map(|i| i.item.convert()).collect(),
+            ref other => other.convert().map(|e| iter::once(e).collect()),
+        }
+    }
+}
+
+impl<T, S, H> ToInputValue<S> for HashSet<T, H>
+where
+    T: ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(T::to_input_value).collect())
+    }
+}
+
+impl<S, T> GraphQLType<S> for BTreeSet<T>
+where
+    T: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        registry.build_list_type::<T>(info, None).into_meta()
+    }
+}
+
+impl<S, T> GraphQLValue<S> for BTreeSet<T>
+where
+    T: GraphQLValue<S>,
+    S: ScalarValue,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name(&self, _: &Self::TypeInfo) -> Option<&'static str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        _: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        resolve_into_list(executor, info, self.iter())
+    }
+}
+
+impl<S, T> GraphQLValueAsync<S> for BTreeSet<T>
+where
+    T: GraphQLValueAsync<S>,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        _: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> crate::BoxFuture<'a, ExecutionResult<S>> {
+        let f = resolve_into_list_async(executor, info, self.iter());
+        Box::pin(f)
+    }
+}
+
+impl<T, S> FromInputValue<S> for BTreeSet<T>
+where
+    T: FromInputValue<S>,
+    T: Ord,
+    S: ScalarValue,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
+        match *v {
+            InputValue::List(ref ls) => ls.iter().map(|i| i.item.convert()).collect(),
+            ref other => other.convert().map(|e| iter::once(e).collect()),
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for BTreeSet<T>
+where
+    T: ToInputValue<S>,
+    S: ScalarValue,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(T::to_input_value).collect())
+    }
+}
+
 impl<S, T> GraphQLType<S> for [T]
 where
     S: ScalarValue,
@@ -318,7 +573,9 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Self> {
+    type Error = FieldError<S>;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Self, Self::Error> {
         struct PartiallyInitializedArray<T, const N: usize> {
             arr: [MaybeUninit<T>; N],
             init_len: usize,
@@ -363,19 +620,20 @@ where
                     no_drop: false,
                 };
 
-                let mut items = ls.iter().filter_map(|i| i.item.convert());
-                for elem in &mut out.arr[..] {
-                    if let Some(i) = items.next() {
-                        *elem = MaybeUninit::new(i);
-                        out.init_len += 1;
-                    } else {
-                        // There is not enough `items` to fill the array.
-                        return None;
-                    }
+                if ls.len() != N {
+                    return Err(array_len_error(N, ls.len()));
                 }
-                if items.next().is_some() {
-                    // There is too much `items` to fit into the array.
-                    return None;
+
+                let mut items = ls.iter().map(|i| i.item.convert());
+                for elem in &mut out.arr[..] {
+                    // The length is checked above, so there are exactly `N`
+                    // `items` to fill the array.
+                    let i = items
+                        .next()
+                        .unwrap()
+                        .map_err(IntoFieldError::into_field_error)?;
+                    *elem = MaybeUninit::new(i);
+                    out.init_len += 1;
                 }
 
                 // Do not drop collected `items`, because we're going to return
@@ -391,34 +649,41 @@ where
                 //         we won't have a double-free when `T: Drop` here,
                 //         because original array elements are `MaybeUninit`, so
                 //         do nothing on `Drop`.
-                Some(unsafe { mem::transmute_copy::<_, Self>(&out.arr) })
+                Ok(unsafe { mem::transmute_copy::<_, Self>(&out.arr) })
             }
             ref other => {
-                other.convert().and_then(|e: T| {
-                    // TODO: Use `mem::transmute` instead of
-                    //       `mem::transmute_copy` below, once it's allowed for
-                    //       const generics:
-                    //       https://github.com/rust-lang/rust/issues/61956
-                    if N == 1 {
-                        // SAFETY: `mem::transmute_copy` is safe here, because
-                        //         we check `N` to be `1`.
-                        //         Also, despite `mem::transmute_copy` copies
-                        //         the value, we won't have a double-free when
-                        //         `T: Drop` here, because original `e: T` value
-                        //         is wrapped into `mem::ManuallyDrop`, so does
-                        //         nothing on `Drop`.
-                        Some(unsafe {
-                            mem::transmute_copy::<_, Self>(&[mem::ManuallyDrop::new(e)])
-                        })
-                    } else {
-                        None
-                    }
-                })
+                let e: T = other.convert().map_err(IntoFieldError::into_field_error)?;
+                // TODO: Use `mem::transmute` instead of
+                //       `mem::transmute_copy` below, once it's allowed for
+                //       const generics:
+                //       https://github.com/rust-lang/rust/issues/61956
+                if N == 1 {
+                    // SAFETY: `mem::transmute_copy` is safe here, because
+                    //         we check `N` to be `1`.
+                    //         Also, despite `mem::transmute_copy` copies
+                    //         the value, we won't have a double-free when
+                    //         `T: Drop` here, because original `e: T` value
+                    //         is wrapped into `mem::ManuallyDrop`, so does
+                    //         nothing on `Drop`.
+                    Ok(unsafe { mem::transmute_copy::<_, Self>(&[mem::ManuallyDrop::new(e)]) })
+                } else {
+                    Err(array_len_error(N, 1))
+                }
             }
         }
     }
 }
 
+fn array_len_error<S>(expected: usize, found: usize) -> FieldError<S> {
+    FieldError::new(
+        format!(
+            "Expected list of {} elements, found {} elements",
+            expected, found,
+        ),
+        Value::Null,
+    )
+}
+
 impl<T, S, const N: usize> ToInputValue<S> for [T; N]
 where
     T: ToInputValue<S>,
@@ -446,8 +711,8 @@ where
         .is_non_null();
     let mut result = Vec::with_capacity(iter.len());
 
-    for o in iter {
-        let val = executor.resolve(info, o)?;
+    for (i, o) in iter.enumerate() {
+        let val = executor.list_item_sub_executor(i).resolve(info, o)?;
         if stop_on_null && val.is_null() {
             return Ok(val);
         } else {
@@ -479,7 +744,13 @@ where
         .is_non_null();
 
     let mut futures = items
-        .map(|it| async move { executor.resolve_into_value_async(info, it).await })
+        .enumerate()
+        .map(|(i, it)| async move {
+            executor
+                .list_item_sub_executor(i)
+                .resolve_into_value_async(info, it)
+                .await
+        })
         .collect::<FuturesOrdered<_>>();
 
     let mut values = Vec::with_capacity(futures.len());
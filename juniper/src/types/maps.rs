@@ -0,0 +1,422 @@
+//! Maps exposed as lists of key/value entry objects.
+
+use std::{
+    any,
+    collections::{BTreeMap, HashMap},
+    iter::FromIterator,
+    sync::Mutex,
+};
+
+use crate::{
+    ast::{Selection, Type},
+    executor::{ExecutionResult, Executor, FieldError, FromContext, Registry},
+    schema::meta::MetaType,
+    types::{
+        async_await::GraphQLValueAsync,
+        base::{Arguments, GraphQLType, GraphQLValue},
+        marker::{GraphQLObjectType, IsOutputType},
+    },
+    value::{ScalarValue, Value},
+    BoxFuture,
+};
+
+/// Map exposed as a list of `{ key, value }` entry objects.
+///
+/// GraphQL has no map type, so each entry becomes an object named after its key and value types,
+/// e.g. `StringToIntEntry` for a `MapEntries<String, i32>`, `StringToListOfIntEntry` for a
+/// `MapEntries<String, Vec<i32>>` and `StringToNullableIntEntry` for a
+/// `MapEntries<String, Option<i32>>`.
+///
+/// Entries keep the iteration order of the map they're built from, which is only meaningful for a
+/// [`BTreeMap`].
+///
+/// ```rust
+/// # use std::collections::BTreeMap;
+/// # use juniper::{graphql_object, MapEntries};
+/// struct Player {
+///     scores: BTreeMap<String, i32>,
+/// }
+///
+/// #[graphql_object]
+/// impl Player {
+///     // Exposed as `scores: [StringToIntEntry!]!`.
+///     fn scores(&self) -> MapEntries<String, i32> {
+///         self.scores.clone().into()
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapEntries<K, V>(pub Vec<MapEntry<K, V>>);
+
+/// Single entry of [`MapEntries`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapEntry<K, V> {
+    /// Key of the entry.
+    pub key: K,
+
+    /// Value of the entry.
+    pub value: V,
+}
+
+impl<K, V> FromIterator<(K, V)> for MapEntries<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| MapEntry { key, value })
+                .collect(),
+        )
+    }
+}
+
+impl<K, V, H> From<HashMap<K, V, H>> for MapEntries<K, V> {
+    fn from(map: HashMap<K, V, H>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for MapEntries<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// Names of the registered [`MapEntry`] object types, by the Rust type of the entries.
+///
+/// Names are built once per entry type and live as long as the program does, like the types they
+/// describe.
+static ENTRY_TYPE_NAMES: Mutex<Vec<(&'static str, &'static str)>> = Mutex::new(Vec::new());
+
+/// Name of the [`MapEntry`] object type with `K` keys and `V` values, if it's been registered.
+fn entry_type_name<K, V>() -> Option<&'static str> {
+    let entry = any::type_name::<MapEntry<K, V>>();
+    let names = ENTRY_TYPE_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    names
+        .iter()
+        .find(|(e, _)| *e == entry)
+        .map(|(_, name)| *name)
+}
+
+/// Registers the name of the [`MapEntry`] object type with `K` keys and `V` values, built from
+/// their GraphQL `key` and `value` types.
+fn register_entry_type_name<K, V>(key: &Type, value: &Type) {
+    let entry = any::type_name::<MapEntry<K, V>>();
+    let mut names = ENTRY_TYPE_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    if names.iter().any(|(e, _)| *e == entry) {
+        return;
+    }
+
+    let name = format!("{}To{}Entry", type_name_part(key), type_name_part(value));
+    let name = match names.iter().find(|(_, n)| **n == name) {
+        Some((_, existing)) => *existing,
+        None => Box::leak(name.into_boxed_str()),
+    };
+    names.push((entry, name));
+}
+
+/// Spells out a GraphQL type as a part of a type name, e.g. `NullableListOfInt` for `[Int!]`.
+fn type_name_part(ty: &Type) -> String {
+    match *ty {
+        Type::NonNullNamed(ref n) => n.to_string(),
+        Type::Named(ref n) => format!("Nullable{}", n),
+        Type::NonNullList(ref t, _) => format!("ListOf{}", type_name_part(t)),
+        Type::List(ref t, _) => format!("NullableListOf{}", type_name_part(t)),
+    }
+}
+
+impl<S, K, V> GraphQLType<S> for MapEntries<K, V>
+where
+    S: ScalarValue,
+    K: GraphQLType<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context>,
+    V: GraphQLType<S, TypeInfo = ()>,
+{
+    fn name(_: &()) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let key = registry.get_type::<K>(&());
+        let value = registry.get_type::<V>(&());
+        register_entry_type_name::<K, V>(&key, &value);
+
+        registry
+            .build_list_type::<MapEntry<K, V>>(info, None)
+            .into_meta()
+    }
+}
+
+impl<S, K, V> GraphQLValue<S> for MapEntries<K, V>
+where
+    S: ScalarValue,
+    K: GraphQLValue<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context>,
+    V: GraphQLValue<S, TypeInfo = ()>,
+{
+    type Context = V::Context;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, _: &'i ()) -> Option<&'i str> {
+        None
+    }
+
+    fn resolve(
+        &self,
+        info: &(),
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        self.0.resolve(info, selection_set, executor)
+    }
+}
+
+impl<S, K, V> GraphQLValueAsync<S> for MapEntries<K, V>
+where
+    S: ScalarValue + Send + Sync,
+    K: GraphQLValueAsync<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context> + Sync,
+    V: GraphQLValueAsync<S, TypeInfo = ()>,
+    V::Context: Sync,
+{
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a (),
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        self.0.resolve_async(info, selection_set, executor)
+    }
+}
+
+impl<S, K, V> IsOutputType<S> for MapEntries<K, V>
+where
+    S: ScalarValue,
+    K: IsOutputType<S>,
+    V: IsOutputType<S>,
+{
+}
+
+impl<S, K, V> GraphQLType<S> for MapEntry<K, V>
+where
+    S: ScalarValue,
+    K: GraphQLType<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context>,
+    V: GraphQLType<S, TypeInfo = ()>,
+{
+    fn name(_: &()) -> Option<&str> {
+        entry_type_name::<K, V>()
+    }
+
+    fn meta<'r>(info: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let fields = [
+            registry.field::<K>("key", &()),
+            registry.field::<V>("value", &()),
+        ];
+        register_entry_type_name::<K, V>(&fields[0].field_type, &fields[1].field_type);
+
+        registry
+            .build_object_type::<Self>(info, &fields)
+            .into_meta()
+    }
+}
+
+impl<S, K, V> GraphQLValue<S> for MapEntry<K, V>
+where
+    S: ScalarValue,
+    K: GraphQLValue<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context>,
+    V: GraphQLValue<S, TypeInfo = ()>,
+{
+    type Context = V::Context;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, _: &'i ()) -> Option<&'i str> {
+        entry_type_name::<K, V>()
+    }
+
+    fn concrete_type_name(&self, _: &Self::Context, info: &()) -> String {
+        self.type_name(info).unwrap().to_owned()
+    }
+
+    fn resolve_field(
+        &self,
+        _: &(),
+        field: &str,
+        _: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field {
+            "key" => executor.resolve_with_ctx(&(), &self.key),
+            "value" => executor.resolve(&(), &self.value),
+            _ => Err(unknown_field_error(field)),
+        }
+    }
+}
+
+impl<S, K, V> GraphQLValueAsync<S> for MapEntry<K, V>
+where
+    S: ScalarValue + Send + Sync,
+    K: GraphQLValueAsync<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context> + Sync,
+    V: GraphQLValueAsync<S, TypeInfo = ()>,
+    V::Context: Sync,
+{
+    fn resolve_field_async<'b>(
+        &'b self,
+        _: &'b (),
+        field_name: &'b str,
+        _: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> BoxFuture<'b, ExecutionResult<S>> {
+        match field_name {
+            "key" => Box::pin(executor.resolve_with_ctx_async(&(), &self.key)),
+            "value" => Box::pin(executor.resolve_async(&(), &self.value)),
+            _ => Box::pin(futures::future::ready(Err(unknown_field_error(field_name)))),
+        }
+    }
+}
+
+fn unknown_field_error<S>(field: &str) -> FieldError<S> {
+    FieldError::new(
+        format!("Field \"{}\" not found on map entry", field),
+        Value::Null,
+    )
+}
+
+impl<S, K, V> GraphQLObjectType<S> for MapEntry<K, V>
+where
+    S: ScalarValue,
+    K: GraphQLType<S, TypeInfo = ()>,
+    K::Context: FromContext<V::Context>,
+    V: GraphQLType<S, TypeInfo = ()>,
+{
+}
+
+impl<S, K, V> IsOutputType<S> for MapEntry<K, V>
+where
+    S: ScalarValue,
+    K: IsOutputType<S>,
+    V: IsOutputType<S>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use crate::{
+        graphql_object, EmptyMutation, EmptySubscription, GraphQLEnum, RootNode, Variables,
+    };
+
+    use super::MapEntries;
+
+    #[derive(Clone, Copy, Debug, Eq, GraphQLEnum, Hash, Ord, PartialEq, PartialOrd)]
+    enum Stat {
+        Wins,
+        Losses,
+    }
+
+    struct Context {
+        bonus: i32,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Score(i32);
+
+    #[graphql_object(context = Context)]
+    impl Score {
+        fn points(&self, context: &Context) -> i32 {
+            self.0 + context.bonus
+        }
+    }
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        fn stats() -> MapEntries<Stat, i32> {
+            let mut stats = BTreeMap::new();
+            stats.insert(Stat::Losses, 1);
+            stats.insert(Stat::Wins, 3);
+            stats.into()
+        }
+
+        fn scores() -> MapEntries<String, Score> {
+            let mut scores = HashMap::new();
+            scores.insert("alice".to_owned(), Score(10));
+            scores.into()
+        }
+
+        fn rounds() -> MapEntries<String, Vec<Option<i32>>> {
+            vec![("alice".to_owned(), vec![Some(3), None])]
+                .into_iter()
+                .collect()
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[tokio::test]
+    async fn resolves_entries() {
+        let doc = r#"{
+            stats { key value }
+            scores { key value { points } }
+            rounds { key value }
+        }"#;
+
+        let (res, errs) = crate::execute(
+            doc,
+            None,
+            &schema(),
+            &Variables::new(),
+            &Context { bonus: 1 },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            res,
+            graphql_value!({
+                "stats": [{"key": "WINS", "value": 3}, {"key": "LOSSES", "value": 1}],
+                "scores": [{"key": "alice", "value": {"points": 11}}],
+                "rounds": [{"key": "alice", "value": [3, None]}],
+            }),
+        );
+    }
+
+    #[test]
+    fn names_entry_types() {
+        let schema = schema();
+
+        let stats = schema
+            .schema
+            .concrete_type_by_name("StatToIntEntry")
+            .unwrap();
+        assert_eq!(stats.name(), Some("StatToIntEntry"));
+
+        let query = schema.schema.concrete_type_by_name("Query").unwrap();
+        let scores = query.field_by_name("scores").unwrap();
+        assert_eq!(scores.field_type.to_string(), "[StringToScoreEntry!]!");
+
+        let rounds = query.field_by_name("rounds").unwrap();
+        assert_eq!(
+            rounds.field_type.to_string(),
+            "[StringToListOfNullableIntEntry!]!",
+        );
+        let value = schema
+            .schema
+            .concrete_type_by_name("StringToListOfNullableIntEntry")
+            .and_then(|t| t.field_by_name("value"))
+            .unwrap();
+        assert_eq!(value.field_type.to_string(), "[Int]!");
+    }
+}
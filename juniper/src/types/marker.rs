@@ -5,7 +5,12 @@
 //! traits are used. Encountering an error where one of these traits
 //! is involved implies that the construct is not valid in GraphQL.
 
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
 
 use crate::{GraphQLType, ScalarValue};
 
@@ -58,6 +63,28 @@ where
     }
 }
 
+impl<S, T> GraphQLObjectType<S> for Rc<T>
+where
+    T: GraphQLObjectType<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<'a, S, T> GraphQLObjectType<S> for Cow<'a, T>
+where
+    T: GraphQLObjectType<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 /// Maker trait for [GraphQL interfaces][1].
 ///
 /// This trait extends the [`GraphQLType`] and is only used to mark an [interface][1]. During
@@ -115,6 +142,28 @@ where
     }
 }
 
+impl<S, T> GraphQLInterface<S> for Rc<T>
+where
+    T: GraphQLInterface<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<'a, S, T> GraphQLInterface<S> for Cow<'a, T>
+where
+    T: GraphQLInterface<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 /// Maker trait for [GraphQL unions][1].
 ///
 /// This trait extends the [`GraphQLType`] and is only used to mark an [union][1]. During compile
@@ -172,6 +221,28 @@ where
     }
 }
 
+impl<S, T> GraphQLUnion<S> for Rc<T>
+where
+    T: GraphQLUnion<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<'a, S, T> GraphQLUnion<S> for Cow<'a, T>
+where
+    T: GraphQLUnion<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 /// Marker trait for types which can be used as output types.
 ///
 /// The GraphQL specification differentiates between input and output
@@ -220,6 +291,28 @@ where
     }
 }
 
+impl<S, T> IsOutputType<S> for Rc<T>
+where
+    T: IsOutputType<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<'a, S, T> IsOutputType<S> for Cow<'a, T>
+where
+    T: IsOutputType<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 impl<S, T> IsOutputType<S> for Option<T>
 where
     T: IsOutputType<S>,
@@ -242,6 +335,39 @@ where
     }
 }
 
+impl<S, T> IsOutputType<S> for VecDeque<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T, H> IsOutputType<S> for HashSet<T, H>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T> IsOutputType<S> for BTreeSet<T>
+where
+    T: IsOutputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 impl<S, T> IsOutputType<S> for [T]
 where
     T: IsOutputType<S>,
@@ -313,6 +439,28 @@ where
     }
 }
 
+impl<S, T> IsInputType<S> for Rc<T>
+where
+    T: IsInputType<S> + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<'a, S, T> IsInputType<S> for Cow<'a, T>
+where
+    T: IsInputType<S> + ToOwned + ?Sized,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 impl<S, T> IsInputType<S> for Option<T>
 where
     T: IsInputType<S>,
@@ -335,6 +483,39 @@ where
     }
 }
 
+impl<S, T> IsInputType<S> for VecDeque<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T, H> IsInputType<S> for HashSet<T, H>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
+impl<S, T> IsInputType<S> for BTreeSet<T>
+where
+    T: IsInputType<S>,
+    S: ScalarValue,
+{
+    #[inline]
+    fn mark() {
+        T::mark()
+    }
+}
+
 impl<S, T> IsInputType<S> for [T]
 where
     T: IsInputType<S>,
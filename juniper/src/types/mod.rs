@@ -1,6 +1,7 @@
 pub mod async_await;
 pub mod base;
 pub mod containers;
+pub mod maps;
 pub mod marker;
 pub mod name;
 pub mod nullable;
@@ -283,9 +283,11 @@ where
     T: FromInputValue<S>,
     S: ScalarValue,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Nullable<T>> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Nullable<T>, Self::Error> {
         match v {
-            &InputValue::Null => Some(Self::ExplicitNull),
+            &InputValue::Null => Ok(Self::ExplicitNull),
             v => v.convert().map(Self::Some),
         }
     }
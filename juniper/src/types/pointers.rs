@@ -1,4 +1,4 @@
-use std::{fmt, sync::Arc};
+use std::{borrow::Cow, fmt, rc::Rc, sync::Arc};
 
 use crate::{
     ast::{FromInputValue, InputValue, Selection, ToInputValue},
@@ -93,7 +93,9 @@ where
     S: ScalarValue,
     T: FromInputValue<S>,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Box<T>> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Box<T>, Self::Error> {
         <T as FromInputValue<S>>::from_input_value(v).map(Box::new)
     }
 }
@@ -285,7 +287,9 @@ where
     S: ScalarValue,
     T: FromInputValue<S>,
 {
-    fn from_input_value(v: &InputValue<S>) -> Option<Arc<T>> {
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Arc<T>, Self::Error> {
         <T as FromInputValue<S>>::from_input_value(v).map(Arc::new)
     }
 }
@@ -299,3 +303,195 @@ where
         (**self).to_input_value()
     }
 }
+
+// `Rc` isn't `Sync`, so it only implements the synchronous resolution.
+impl<S, T> GraphQLType<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + ?Sized,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+}
+
+impl<S, T> GraphQLValue<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S> + ?Sized,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        (**self).type_name(info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<T, S> FromInputValue<S> for Rc<T>
+where
+    S: ScalarValue,
+    T: FromInputValue<S>,
+{
+    type Error = T::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Rc<T>, Self::Error> {
+        <T as FromInputValue<S>>::from_input_value(v).map(Rc::new)
+    }
+}
+
+impl<T, S> ToInputValue<S> for Rc<T>
+where
+    S: fmt::Debug,
+    T: ToInputValue<S>,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        (**self).to_input_value()
+    }
+}
+
+impl<'e, S, T> GraphQLType<S> for Cow<'e, T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + ToOwned + ?Sized,
+{
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+}
+
+impl<'e, S, T> GraphQLValue<S> for Cow<'e, T>
+where
+    S: ScalarValue,
+    T: GraphQLValue<S> + ToOwned + ?Sized,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        (**self).type_name(info)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &Self::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &Self::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<'e, S, T> GraphQLValueAsync<S> for Cow<'e, T>
+where
+    T: GraphQLValueAsync<S> + ToOwned + ?Sized,
+    T::Owned: Sync,
+    T::TypeInfo: Sync,
+    T::Context: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    fn resolve_field_async<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> BoxFuture<'b, ExecutionResult<S>> {
+        (**self).resolve_field_async(info, field_name, arguments, executor)
+    }
+
+    fn resolve_async<'a>(
+        &'a self,
+        info: &'a Self::TypeInfo,
+        selection_set: Option<&'a [Selection<S>]>,
+        executor: &'a Executor<Self::Context, S>,
+    ) -> BoxFuture<'a, ExecutionResult<S>> {
+        (**self).resolve_async(info, selection_set, executor)
+    }
+}
+
+impl<'e, T, S> FromInputValue<S> for Cow<'e, T>
+where
+    S: ScalarValue,
+    T: ToOwned + ?Sized,
+    T::Owned: FromInputValue<S>,
+{
+    type Error = <T::Owned as FromInputValue<S>>::Error;
+
+    fn from_input_value(v: &InputValue<S>) -> Result<Cow<'e, T>, Self::Error> {
+        <T::Owned as FromInputValue<S>>::from_input_value(v).map(Cow::Owned)
+    }
+}
+
+impl<'e, T, S> ToInputValue<S> for Cow<'e, T>
+where
+    S: fmt::Debug,
+    T: ToInputValue<S> + ToOwned + ?Sized,
+{
+    fn to_input_value(&self) -> InputValue<S> {
+        (**self).to_input_value()
+    }
+}
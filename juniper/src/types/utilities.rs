@@ -1,47 +1,51 @@
 use crate::{
     ast::InputValue,
     schema::{
-        meta::{EnumMeta, InputObjectMeta, MetaType},
+        meta::{EnumMeta, InputObjectMeta, MetaType, ScalarMeta},
         model::{SchemaType, TypeType},
     },
     value::ScalarValue,
 };
 use std::collections::HashSet;
 
-pub fn is_valid_literal_value<S>(
+/// Checks whether `arg_value` is a valid literal of `arg_type`.
+///
+/// On failure, returns the reason reported by the input conversion of the
+/// offending scalar or enum, if any.
+pub fn validate_literal_value<S>(
     schema: &SchemaType<S>,
     arg_type: &TypeType<S>,
     arg_value: &InputValue<S>,
-) -> bool
+) -> Result<(), Option<String>>
 where
     S: ScalarValue,
 {
     match *arg_type {
         TypeType::NonNull(ref inner) => {
             if arg_value.is_null() {
-                false
+                Err(None)
             } else {
-                is_valid_literal_value(schema, inner, arg_value)
+                validate_literal_value(schema, inner, arg_value)
             }
         }
         TypeType::List(ref inner, expected_size) => match *arg_value {
             InputValue::List(ref items) => {
                 if let Some(expected) = expected_size {
                     if items.len() != expected {
-                        return false;
+                        return Err(None);
                     }
                 }
                 items
                     .iter()
-                    .all(|i| is_valid_literal_value(schema, inner, &i.item))
+                    .try_for_each(|i| validate_literal_value(schema, inner, &i.item))
             }
             ref v => {
                 if let Some(expected) = expected_size {
                     if expected != 1 {
-                        return false;
+                        return Err(None);
                     }
                 }
-                is_valid_literal_value(schema, inner, v)
+                validate_literal_value(schema, inner, v)
             }
         },
         TypeType::Concrete(t) => {
@@ -50,19 +54,41 @@ where
             if let (&InputValue::Scalar(_), Some(&MetaType::Enum(EnumMeta { .. }))) =
                 (arg_value, arg_type.to_concrete())
             {
-                return false;
+                return Err(None);
             }
 
             match *arg_value {
-                InputValue::Null | InputValue::Variable(_) => true,
+                InputValue::Null | InputValue::Variable(_) => Ok(()),
                 ref v @ InputValue::Scalar(_) | ref v @ InputValue::Enum(_) => {
-                    if let Some(parse_fn) = t.input_value_parse_fn() {
-                        parse_fn(v)
-                    } else {
-                        false
+                    let parse_fn = t.input_value_parse_fn().ok_or(None)?;
+                    parse_fn(v).map_err(|e| Some(e.message().to_owned()))?;
+
+                    // Enum literals must name one of the enum's values, whichever way the enum
+                    // parses them.
+                    match (v, t) {
+                        (InputValue::Enum(name), MetaType::Enum(EnumMeta { values, .. }))
+                            if !values.iter().any(|ev| &ev.name == name) =>
+                        {
+                            Err(None)
+                        }
+                        _ => Ok(()),
                     }
                 }
-                InputValue::List(_) => false,
+                // Scalars opting into composite values, like `_Any`, decide on their own.
+                ref v @ InputValue::List(_) | ref v @ InputValue::Object(_)
+                    if matches!(
+                        *t,
+                        MetaType::Scalar(ScalarMeta {
+                            accepts_composite_values: true,
+                            ..
+                        })
+                    ) =>
+                {
+                    t.input_value_parse_fn()
+                        .ok_or(None)
+                        .and_then(|parse_fn| parse_fn(v).map_err(|e| Some(e.message().to_owned())))
+                }
+                InputValue::List(_) => Err(None),
                 InputValue::Object(ref obj) => {
                     if let MetaType::InputObject(InputObjectMeta {
                         ref input_fields, ..
@@ -79,7 +105,7 @@ where
                             })
                             .collect::<HashSet<_>>();
 
-                        let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
+                        obj.iter().try_for_each(|&(ref key, ref value)| {
                             remaining_required_fields.remove(&key.item);
                             if let Some(ref arg_type) = input_fields
                                 .iter()
@@ -87,15 +113,19 @@ where
                                 .map(|f| schema.make_type(&f.arg_type))
                                 .next()
                             {
-                                is_valid_literal_value(schema, arg_type, &value.item)
+                                validate_literal_value(schema, arg_type, &value.item)
                             } else {
-                                false
+                                Err(None)
                             }
-                        });
+                        })?;
 
-                        all_types_ok && remaining_required_fields.is_empty()
+                        if remaining_required_fields.is_empty() {
+                            Ok(())
+                        } else {
+                            Err(None)
+                        }
                     } else {
-                        false
+                        Err(None)
                     }
                 }
             }
@@ -97,6 +97,12 @@ impl<'a, S: Debug> ValidatorContext<'a, S> {
         self.errors.push(RuleError::new(message, locations))
     }
 
+    /// Errors reported so far.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn errors(&self) -> &[RuleError] {
+        &self.errors
+    }
+
     #[doc(hidden)]
     pub fn into_errors(mut self) -> Vec<RuleError> {
         self.errors.sort();
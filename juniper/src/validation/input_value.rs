@@ -28,6 +28,9 @@ pub fn validate_input_values<S>(
 where
     S: ScalarValue,
 {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("validate_input_values").entered();
+
     let mut errs = vec![];
 
     if let Some(ref vars) = operation.item.variable_definitions {
@@ -35,6 +38,12 @@ where
     }
 
     errs.sort();
+
+    #[cfg(feature = "tracing")]
+    for e in &errs {
+        tracing::debug!(error = %e, "input values validation failed");
+    }
+
     errs
 }
 
@@ -163,14 +172,16 @@ where
                     if e.is_empty() {
                         // All the fields didn't have errors, see if there is an
                         // overall error when parsing the input value.
-                        if !(iom.try_parse_fn)(value) {
+                        if let Err(e) = (iom.try_parse_fn)(value) {
                             errors.push(unification_error(
                                 var_name,
                                 var_pos,
                                 &path,
                                 &format!(
-                                    r#"Expected input of type "{}". Got: "{}""#,
-                                    iom.name, value
+                                    r#"Expected input of type "{}". Got: "{}": {}"#,
+                                    iom.name,
+                                    value,
+                                    e.message(),
                                 ),
                             ));
                         }
@@ -197,29 +208,29 @@ where
 {
     let mut errors: Vec<RuleError> = vec![];
 
-    if !(meta.try_parse_fn)(value) {
-        return vec![unification_error(
-            var_name,
-            var_pos,
-            path,
-            &format!(r#"Expected "{}""#, meta.name),
-        )];
-    }
-
     match *value {
-        InputValue::List(_) => errors.push(unification_error(
+        InputValue::List(_) if !meta.accepts_composite_values => errors.push(unification_error(
             var_name,
             var_pos,
             path,
             &format!(r#"Expected "{}", found list"#, meta.name),
         )),
-        InputValue::Object(_) => errors.push(unification_error(
+        InputValue::Object(_) if !meta.accepts_composite_values => errors.push(unification_error(
             var_name,
             var_pos,
             path,
             &format!(r#"Expected "{}", found object"#, meta.name),
         )),
-        _ => (),
+        _ => {
+            if let Err(e) = (meta.try_parse_fn)(value) {
+                errors.push(unification_error(
+                    var_name,
+                    var_pos,
+                    path,
+                    &format!(r#"Expected "{}": {}"#, meta.name, e.message()),
+                ));
+            }
+        }
     }
     errors
 }
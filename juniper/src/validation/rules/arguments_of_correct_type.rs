@@ -2,7 +2,7 @@ use crate::{
     ast::{Directive, Field, InputValue},
     parser::Spanning,
     schema::meta::Argument,
-    types::utilities::is_valid_literal_value,
+    types::utilities::validate_literal_value,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -57,11 +57,13 @@ where
         {
             let meta_type = ctx.schema.make_type(&argument_meta.arg_type);
 
-            if !is_valid_literal_value(ctx.schema, &meta_type, &arg_value.item) {
-                ctx.report_error(
-                    &error_message(arg_name.item, &format!("{}", argument_meta.arg_type)),
-                    &[arg_value.start],
-                );
+            if let Err(reason) = validate_literal_value(ctx.schema, &meta_type, &arg_value.item) {
+                let type_name = format!("{}", argument_meta.arg_type);
+                let message = match reason {
+                    Some(reason) => error_message_with_reason(arg_name.item, &type_name, &reason),
+                    None => error_message(arg_name.item, &type_name),
+                };
+                ctx.report_error(&message, &[arg_value.start]);
             }
         }
     }
@@ -74,9 +76,13 @@ fn error_message(arg_name: &str, type_name: &str) -> String {
     )
 }
 
+fn error_message_with_reason(arg_name: &str, type_name: &str, reason: &str) -> String {
+    format!("{}: {}", error_message(arg_name, type_name), reason)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{error_message, factory};
+    use super::{error_message, error_message_with_reason, factory};
 
     use crate::{
         parser::SourcePosition,
@@ -240,7 +246,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message_with_reason("stringArg", "String", "Expected `String`, found: 1"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -258,7 +264,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message_with_reason("stringArg", "String", "Expected `String`, found: 1"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -276,7 +282,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message_with_reason("stringArg", "String", "Expected `String`, found: true"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -294,7 +300,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringArg", "String"),
+                &error_message_with_reason("stringArg", "String", "Expected `String`, found: BAR"),
                 &[SourcePosition::new(89, 3, 42)],
             )],
         );
@@ -312,7 +318,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message_with_reason("intArg", "Int", r#"Expected `Int`, found: "3""#),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -330,7 +336,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message_with_reason("intArg", "Int", "Expected `Int`, found: FOO"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -348,7 +354,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message_with_reason("intArg", "Int", "Expected `Int`, found: 3"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -366,7 +372,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("intArg", "Int"),
+                &error_message_with_reason("intArg", "Int", "Expected `Int`, found: 3.333"),
                 &[SourcePosition::new(83, 3, 36)],
             )],
         );
@@ -384,7 +390,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message_with_reason(
+                    "floatArg",
+                    "Float",
+                    r#"Expected `Float`, found: "3.333""#,
+                ),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -402,7 +412,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message_with_reason("floatArg", "Float", "Expected `Float`, found: true"),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -420,7 +430,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("floatArg", "Float"),
+                &error_message_with_reason("floatArg", "Float", "Expected `Float`, found: FOO"),
                 &[SourcePosition::new(87, 3, 40)],
             )],
         );
@@ -438,12 +448,30 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message_with_reason("booleanArg", "Boolean", "Expected `Boolean`, found: 2"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
     }
 
+    #[test]
+    fn list_into_int() {
+        expect_fails_rule::<_, _, DefaultScalarValue>(
+            factory,
+            r#"
+            {
+              complicatedArgs {
+                intArgField(intArg: [2])
+              }
+            }
+        "#,
+            &[RuleError::new(
+                &error_message("intArg", "Int"),
+                &[SourcePosition::new(83, 3, 36)],
+            )],
+        );
+    }
+
     #[test]
     fn float_into_boolean() {
         expect_fails_rule::<_, _, DefaultScalarValue>(
@@ -456,7 +484,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message_with_reason("booleanArg", "Boolean", "Expected `Boolean`, found: 1"),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -474,7 +502,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message_with_reason(
+                    "booleanArg",
+                    "Boolean",
+                    r#"Expected `Boolean`, found: "true""#,
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -492,7 +524,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("booleanArg", "Boolean"),
+                &error_message_with_reason(
+                    "booleanArg",
+                    "Boolean",
+                    "Expected `Boolean`, found: TRUE",
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -510,7 +546,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message_with_reason("idArg", "ID", "Expected `String` or `Int`, found: 1"),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -528,7 +564,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message_with_reason(
+                    "idArg",
+                    "ID",
+                    "Expected `String` or `Int`, found: true",
+                ),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -546,7 +586,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("idArg", "ID"),
+                &error_message_with_reason(
+                    "idArg",
+                    "ID",
+                    "Expected `String` or `Int`, found: SOMETHING",
+                ),
                 &[SourcePosition::new(81, 3, 34)],
             )],
         );
@@ -636,7 +680,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message_with_reason(
+                    "dogCommand",
+                    "DogCommand",
+                    "Unknown `DogCommand`: JUGGLE",
+                ),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -654,7 +702,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("dogCommand", "DogCommand"),
+                &error_message_with_reason("dogCommand", "DogCommand", "Unknown `DogCommand`: sit"),
                 &[SourcePosition::new(79, 3, 44)],
             )],
         );
@@ -714,7 +762,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                &error_message_with_reason(
+                    "stringListArg",
+                    "[String]",
+                    "Expected `String`, found: 2",
+                ),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -732,7 +784,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("stringListArg", "[String]"),
+                &error_message_with_reason(
+                    "stringListArg",
+                    "[String]",
+                    "Expected `String`, found: 1",
+                ),
                 &[SourcePosition::new(97, 3, 50)],
             )],
         );
@@ -891,11 +947,11 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("req2", "Int!"),
+                    &error_message_with_reason("req2", "Int!", r#"Expected `Int`, found: "two""#),
                     &[SourcePosition::new(82, 3, 35)],
                 ),
                 RuleError::new(
-                    &error_message("req1", "Int!"),
+                    &error_message_with_reason("req1", "Int!", r#"Expected `Int`, found: "one""#),
                     &[SourcePosition::new(95, 3, 48)],
                 ),
             ],
@@ -914,7 +970,7 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("req1", "Int!"),
+                &error_message_with_reason("req1", "Int!", r#"Expected `Int`, found: "one""#),
                 &[SourcePosition::new(82, 3, 35)],
             )],
         );
@@ -1049,7 +1105,11 @@ mod tests {
             }
         "#,
             &[RuleError::new(
-                &error_message("complexArg", "ComplexInput"),
+                &error_message_with_reason(
+                    "complexArg",
+                    "ComplexInput",
+                    "Expected `String`, found: 2",
+                ),
                 &[SourcePosition::new(91, 3, 44)],
             )],
         );
@@ -1106,11 +1166,15 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message_with_reason(
+                        "if",
+                        "Boolean!",
+                        r#"Expected `Boolean`, found: "yes""#,
+                    ),
                     &[SourcePosition::new(38, 2, 27)],
                 ),
                 RuleError::new(
-                    &error_message("if", "Boolean!"),
+                    &error_message_with_reason("if", "Boolean!", "Expected `Boolean`, found: ENUM"),
                     &[SourcePosition::new(74, 3, 27)],
                 ),
             ],
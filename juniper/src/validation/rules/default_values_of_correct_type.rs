@@ -1,7 +1,7 @@
 use crate::{
     ast::VariableDefinition,
     parser::Spanning,
-    types::utilities::is_valid_literal_value,
+    types::utilities::validate_literal_value,
     validation::{ValidatorContext, Visitor},
     value::ScalarValue,
 };
@@ -35,11 +35,15 @@ where
             } else {
                 let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
-                if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
-                    ctx.report_error(
-                        &type_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
-                        &[*start],
-                    );
+                if let Err(reason) = validate_literal_value(ctx.schema, &meta_type, var_value) {
+                    let type_name = format!("{}", var_def.var_type.item);
+                    let message = match reason {
+                        Some(reason) => {
+                            type_error_message_with_reason(var_name.item, &type_name, &reason)
+                        }
+                        None => type_error_message(var_name.item, &type_name),
+                    };
+                    ctx.report_error(&message, &[*start]);
                 }
             }
         }
@@ -53,6 +57,10 @@ fn type_error_message(arg_name: &str, type_name: &str) -> String {
     )
 }
 
+fn type_error_message_with_reason(arg_name: &str, type_name: &str, reason: &str) -> String {
+    format!("{}: {}", type_error_message(arg_name, type_name), reason)
+}
+
 fn non_null_error_message(arg_name: &str, type_name: &str) -> String {
     format!(
         "Argument \"{}\" has type \"{}\" and is not nullable, so it can't have a default value",
@@ -62,7 +70,9 @@ fn non_null_error_message(arg_name: &str, type_name: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{factory, non_null_error_message, type_error_message};
+    use super::{
+        factory, non_null_error_message, type_error_message, type_error_message_with_reason,
+    };
 
     use crate::{
         parser::SourcePosition,
@@ -147,15 +157,19 @@ mod tests {
         "#,
             &[
                 RuleError::new(
-                    &type_error_message("a", "Int"),
+                    &type_error_message_with_reason("a", "Int", r#"Expected `Int`, found: "one""#),
                     &[SourcePosition::new(61, 2, 22)],
                 ),
                 RuleError::new(
-                    &type_error_message("b", "String"),
+                    &type_error_message_with_reason("b", "String", "Expected `String`, found: 4"),
                     &[SourcePosition::new(93, 3, 25)],
                 ),
                 RuleError::new(
-                    &type_error_message("c", "ComplexInput"),
+                    &type_error_message_with_reason(
+                        "c",
+                        "ComplexInput",
+                        r#"Expected input object, found: "notverycomplex""#,
+                    ),
                     &[SourcePosition::new(127, 4, 31)],
                 ),
             ],
@@ -188,7 +202,7 @@ mod tests {
           }
         "#,
             &[RuleError::new(
-                &type_error_message("a", "[String]"),
+                &type_error_message_with_reason("a", "[String]", "Expected `String`, found: 2"),
                 &[SourcePosition::new(44, 1, 43)],
             )],
         );
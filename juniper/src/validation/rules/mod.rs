@@ -61,5 +61,13 @@ where
         .with(self::variables_are_input_types::factory())
         .with(self::variables_in_allowed_position::factory());
 
-    visit(&mut mv, ctx, doc)
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("validate_document").entered();
+
+    visit(&mut mv, ctx, doc);
+
+    #[cfg(feature = "tracing")]
+    for e in ctx.errors() {
+        tracing::debug!(error = %e, "document validation failed");
+    }
 }
@@ -208,12 +208,14 @@ impl<S> FromInputValue<S> for DogCommand
 where
     S: ScalarValue,
 {
-    fn from_input_value<'a>(v: &InputValue<S>) -> Option<DogCommand> {
+    type Error = String;
+
+    fn from_input_value<'a>(v: &InputValue<S>) -> Result<DogCommand, String> {
         match v.as_enum_value() {
-            Some("SIT") => Some(DogCommand::Sit),
-            Some("HEEL") => Some(DogCommand::Heel),
-            Some("DOWN") => Some(DogCommand::Down),
-            _ => None,
+            Some("SIT") => Ok(DogCommand::Sit),
+            Some("HEEL") => Ok(DogCommand::Heel),
+            Some("DOWN") => Ok(DogCommand::Down),
+            _ => Err(format!("Unknown `DogCommand`: {}", v)),
         }
     }
 }
@@ -314,13 +316,15 @@ impl<S> FromInputValue<S> for FurColor
 where
     S: ScalarValue,
 {
-    fn from_input_value<'a>(v: &InputValue<S>) -> Option<FurColor> {
+    type Error = String;
+
+    fn from_input_value<'a>(v: &InputValue<S>) -> Result<FurColor, String> {
         match v.as_enum_value() {
-            Some("BROWN") => Some(FurColor::Brown),
-            Some("BLACK") => Some(FurColor::Black),
-            Some("TAN") => Some(FurColor::Tan),
-            Some("SPOTTED") => Some(FurColor::Spotted),
-            _ => None,
+            Some("BROWN") => Ok(FurColor::Brown),
+            Some("BLACK") => Ok(FurColor::Black),
+            Some("TAN") => Ok(FurColor::Tan),
+            Some("SPOTTED") => Ok(FurColor::Spotted),
+            _ => Err(format!("Unknown `FurColor`: {}", v)),
         }
     }
 }
@@ -612,21 +616,24 @@ impl<S> FromInputValue<S> for ComplexInput
 where
     S: ScalarValue,
 {
-    fn from_input_value<'a>(v: &InputValue<S>) -> Option<ComplexInput> {
+    type Error = String;
+
+    fn from_input_value<'a>(v: &InputValue<S>) -> Result<ComplexInput, String> {
         let obj = match v.to_object_value() {
             Some(o) => o,
-            None => return None,
+            None => return Err(format!("Expected input object, found: {}", v)),
         };
 
-        Some(ComplexInput {
-            required_field: match obj.get("requiredField").and_then(|v| v.convert()) {
-                Some(f) => f,
-                None => return None,
+        Ok(ComplexInput {
+            required_field: match obj.get("requiredField").map(|v| v.convert()) {
+                Some(Ok(f)) => f,
+                Some(Err(e)) => return Err(e),
+                None => return Err("Missing required field `requiredField`".into()),
             },
-            int_field: obj.get("intField").and_then(|v| v.convert()),
-            string_field: obj.get("stringField").and_then(|v| v.convert()),
-            boolean_field: obj.get("booleanField").and_then(|v| v.convert()),
-            string_list_field: obj.get("stringListField").and_then(|v| v.convert()),
+            int_field: obj.get("intField").and_then(|v| v.convert().ok()),
+            string_field: obj.get("stringField").and_then(|v| v.convert().ok()),
+            boolean_field: obj.get("booleanField").and_then(|v| v.convert().ok()),
+            string_list_field: obj.get("stringListField").and_then(|v| v.convert().ok()),
         })
     }
 }
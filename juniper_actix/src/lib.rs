@@ -40,49 +40,52 @@ Check the LICENSE file for details.
 #![deny(warnings)]
 #![doc(html_root_url = "https://docs.rs/juniper_actix/0.1.0")]
 
-use actix_web::{
-    error::JsonPayloadError, http::Method, web, Error, FromRequest, HttpMessage, HttpRequest,
-    HttpResponse,
-};
+use actix_web::{http::header, web, Error, FromRequest, HttpRequest, HttpResponse};
+use futures::StreamExt as _;
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::{graphiql_source, graphiql_source_with_config, GraphiQLConfig},
+        handler::{
+            self, HandlerConfig, HttpRequest as GraphQLHttpRequest,
+            HttpResponse as GraphQLHttpResponse,
+        },
+        playground::{playground_source, playground_source_with_config, PlaygroundConfig},
     },
     ScalarValue,
 };
-use serde::Deserialize;
-
-#[derive(Deserialize, Clone, PartialEq, Debug)]
-#[serde(deny_unknown_fields)]
-struct GetGraphQLRequest {
-    query: String,
-    #[serde(rename = "operationName")]
-    operation_name: Option<String>,
-    variables: Option<String>,
-}
 
-impl<S> From<GetGraphQLRequest> for GraphQLRequest<S>
+/// Actix Web GraphQL Handler for GET and POST requests
+///
+/// Requests are handled as described in [`juniper::http::handler`].
+pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: HttpRequest,
+    payload: actix_web::web::Payload,
+) -> Result<HttpResponse, Error>
 where
-    S: ScalarValue,
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
 {
-    fn from(get_req: GetGraphQLRequest) -> Self {
-        let GetGraphQLRequest {
-            query,
-            operation_name,
-            variables,
-        } = get_req;
-        let variables = variables.map(|s| serde_json::from_str(&s).unwrap());
-        Self::new(query, operation_name, variables)
-    }
+    graphql_handler_with_config(schema, context, req, payload, &HandlerConfig::default()).await
 }
 
-/// Actix Web GraphQL Handler for GET and POST requests
-pub async fn graphql_handler<Query, Mutation, Subscription, CtxT, S>(
+/// Actix Web GraphQL Handler for GET and POST requests, checking them against the given
+/// [`HandlerConfig`]
+///
+/// The body is read up to the maximum size of the `config`, if any.
+pub async fn graphql_handler_with_config<Query, Mutation, Subscription, CtxT, S>(
     schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context: &CtxT,
     req: HttpRequest,
     payload: actix_web::web::Payload,
+    config: &HandlerConfig,
 ) -> Result<HttpResponse, Error>
 where
     Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
@@ -94,11 +97,15 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    match *req.method() {
-        Method::POST => post_graphql_handler(schema, context, req, payload).await,
-        Method::GET => get_graphql_handler(schema, context, req).await,
-        _ => Err(actix_web::error::UrlGenerationError::ResourceNotFound.into()),
-    }
+    let body = if config.max_body_size().is_some() {
+        match read_body(&req, payload, config).await? {
+            Ok(body) => body,
+            Err(resp) => return Ok(into_response(resp)),
+        }
+    } else {
+        web::Bytes::from_request(&req, &mut payload.into_inner()).await?
+    };
+    Ok(handle(schema, context, &req, &body, config).await)
 }
 /// Actix GraphQL Handler for GET requests
 pub async fn get_graphql_handler<Query, Mutation, Subscription, CtxT, S>(
@@ -116,17 +123,7 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
-    let req = GraphQLRequest::from(get_req.into_inner());
-    let gql_response = req.execute(schema, context).await;
-    let body_response = serde_json::to_string(&gql_response)?;
-    let mut response = match gql_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
-    Ok(response
-        .content_type("application/json")
-        .body(body_response))
+    Ok(handle(schema, context, &req, &[], &HandlerConfig::default()).await)
 }
 
 /// Actix GraphQL Handler for POST requests
@@ -146,27 +143,77 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let req = match req.content_type() {
-        "application/json" => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            serde_json::from_str::<GraphQLBatchRequest<S>>(&body)
-                .map_err(JsonPayloadError::Deserialize)
+    graphql_handler(schema, context, req, payload).await
+}
+
+async fn handle<Query, Mutation, Subscription, CtxT, S>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: &HttpRequest,
+    body: &[u8],
+    config: &HandlerConfig,
+) -> HttpResponse
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let headers = req.headers();
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    let request = GraphQLHttpRequest {
+        method: req.method().as_str(),
+        query_string: Some(req.query_string()),
+        content_type: header(header::CONTENT_TYPE),
+        accept: header(header::ACCEPT),
+        headers: &headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+            .collect::<Vec<_>>(),
+        body,
+    };
+    into_response(handler::handle_with_config(request, schema, context, config).await)
+}
+
+/// Reads the whole payload of the request, failing as soon as it gets larger than the maximum size
+/// of the `config`.
+async fn read_body(
+    req: &HttpRequest,
+    mut payload: web::Payload,
+    config: &HandlerConfig,
+) -> Result<Result<web::Bytes, GraphQLHttpResponse>, Error> {
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        if let Err(resp) = config.check_body_size(content_length) {
+            return Ok(Err(resp));
         }
-        "application/graphql" => {
-            let body = String::from_request(&req, &mut payload.into_inner()).await?;
-            Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-                body, None, None,
-            )))
+    }
+
+    let mut body = web::BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        if let Err(resp) = config.check_body_size(body.len() + chunk.len()) {
+            return Ok(Err(resp));
         }
-        _ => Err(JsonPayloadError::ContentType),
-    }?;
-    let gql_batch_response = req.execute(schema, context).await;
-    let gql_response = serde_json::to_string(&gql_batch_response)?;
-    let mut response = match gql_batch_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
-    Ok(response.content_type("application/json").body(gql_response))
+        body.extend_from_slice(&chunk);
+    }
+    Ok(Ok(body.freeze()))
+}
+
+fn into_response(resp: GraphQLHttpResponse) -> HttpResponse {
+    let mut response =
+        HttpResponse::build(http::StatusCode::from_u16(resp.status).expect("valid status code"));
+    for (name, value) in resp.headers {
+        response.insert_header((name, value));
+    }
+    response.body(resp.body)
 }
 
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
@@ -191,6 +238,31 @@ pub async fn graphiql_handler(
         .body(html))
 }
 
+/// Create a handler that replies with an HTML page containing GraphiQL configured with the given
+/// [`GraphiQLConfig`]. This does not handle routing, so you can mount it on any endpoint
+///
+/// For example:
+///
+/// ```
+/// # use juniper::http::graphiql::GraphiQLConfig;
+/// # use juniper_actix::graphiql_handler_with_config;
+/// # use actix_web::{web, App};
+///
+/// let app = App::new().route(
+///     "/",
+///     web::get().to(|| {
+///         let config = GraphiQLConfig::new("/graphql").with_header("Authorization", "Bearer token");
+///         async move { graphiql_handler_with_config(&config).await }
+///     }),
+/// );
+/// ```
+pub async fn graphiql_handler_with_config(config: &GraphiQLConfig) -> Result<HttpResponse, Error> {
+    let html = graphiql_source_with_config(config);
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(html))
+}
+
 /// Create a handler that replies with an HTML page containing GraphQL Playground. This does not handle routing, so you cant mount it on any endpoint.
 pub async fn playground_handler(
     graphql_endpoint_url: &str,
@@ -202,6 +274,41 @@ pub async fn playground_handler(
         .body(html))
 }
 
+/// Create a handler that replies with an HTML page containing GraphQL Playground configured with
+/// the given [`PlaygroundConfig`]. This does not handle routing, so you can mount it on any
+/// endpoint.
+pub async fn playground_handler_with_config(
+    config: &PlaygroundConfig,
+) -> Result<HttpResponse, Error> {
+    let html = playground_source_with_config(config);
+    Ok(HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(html))
+}
+
+/// Create a handler that replies with the [bundled asset](juniper::http::assets) named by the rest
+/// of the request path, or with a `404 Not Found` if there is no such asset.
+///
+/// Mount it under the assets base URL given to the GraphiQL and GraphQL Playground pages:
+///
+/// ```
+/// # use juniper_actix::asset_handler;
+/// # use actix_web::{web, App};
+///
+/// let app = App::new()
+///          .route("/assets/{name:.*}", web::get().to(asset_handler));
+/// ```
+#[cfg(feature = "bundled-assets")]
+pub async fn asset_handler(name: web::Path<String>) -> HttpResponse {
+    match juniper::http::assets::find(&name) {
+        Some(asset) => HttpResponse::Ok()
+            .content_type(asset.content_type)
+            .insert_header((header::CACHE_CONTROL, juniper::http::assets::CACHE_CONTROL))
+            .body(asset.contents()),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
 /// `juniper_actix` subscriptions handler implementation.
 /// Cannot be merged to `juniper_actix` yet as GraphQL over WS[1]
 /// is not fully supported in current implementation.
@@ -539,12 +646,68 @@ mod tests {
             resp.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
             "text/html; charset=utf-8"
         );
-        assert!(body.contains("<script>var GRAPHQL_URL = '/dogs-api/graphql';</script>"));
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/dogs-api/graphql\";</script>"));
         assert!(body.contains(
-            "<script>var GRAPHQL_SUBSCRIPTIONS_URL = '/dogs-api/subscriptions';</script>"
+            "<script>var GRAPHQL_SUBSCRIPTIONS_URL = \"/dogs-api/subscriptions\";</script>"
         ))
     }
 
+    #[cfg(feature = "bundled-assets")]
+    #[actix_web::rt::test]
+    async fn asset_endpoint_returns_bundled_assets() {
+        use juniper::http::assets::{CACHE_CONTROL, GRAPHIQL_2_CSS};
+
+        let mut app =
+            test::init_service(App::new().route("/assets/{name:.*}", web::get().to(asset_handler)))
+                .await;
+        let req = test::TestRequest::get()
+            .uri(&format!("/assets/{}", GRAPHIQL_2_CSS.name))
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
+            GRAPHIQL_2_CSS.content_type,
+        );
+        assert_eq!(
+            resp.headers()
+                .get(header::CACHE_CONTROL)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            CACHE_CONTROL,
+        );
+        assert_eq!(test::read_body(resp).await, GRAPHIQL_2_CSS.contents());
+
+        let req = test::TestRequest::get()
+            .uri("/assets/graphiql.min.css")
+            .to_request();
+
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::rt::test]
+    async fn graphiql_endpoint_returns_configured_graphiql_source() {
+        async fn graphql_handler() -> Result<HttpResponse, Error> {
+            let config = GraphiQLConfig::new("/dogs-api/graphql").with_title("Dogs API");
+            graphiql_handler_with_config(&config).await
+        }
+        let mut app =
+            test::init_service(App::new().route("/", web::get().to(graphql_handler))).await;
+        let req = test::TestRequest::get()
+            .uri("/")
+            .append_header((ACCEPT, "text/html"))
+            .to_request();
+
+        let mut resp = test::call_service(&mut app, req).await;
+        let body = take_response_body_string(&mut resp).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/dogs-api/graphql\";</script>"));
+        assert!(body.contains("<title>Dogs API</title>"));
+    }
+
     #[actix_web::rt::test]
     async fn playground_endpoint_matches() {
         async fn graphql_handler() -> Result<HttpResponse, Error> {
@@ -580,7 +743,7 @@ mod tests {
             resp.headers().get(CONTENT_TYPE).unwrap().to_str().unwrap(),
             "text/html; charset=utf-8"
         );
-        assert!(body.contains("GraphQLPlayground.init(root, { endpoint: '/dogs-api/graphql', subscriptionEndpoint: '/dogs-api/subscriptions' })"));
+        assert!(body.contains("GraphQLPlayground.init(root, { endpoint: \"/dogs-api/graphql\", subscriptionEndpoint: \"/dogs-api/subscriptions\" })"));
     }
 
     #[actix_web::rt::test]
@@ -619,6 +782,55 @@ mod tests {
         );
     }
 
+    #[actix_web::rt::test]
+    async fn graphql_checks_requests_against_config() {
+        async fn limited_index(
+            req: HttpRequest,
+            payload: actix_web::web::Payload,
+            schema: web::Data<Schema>,
+        ) -> Result<HttpResponse, Error> {
+            let context = Database::new();
+            let config = HandlerConfig::new()
+                .with_csrf_prevention()
+                .with_max_body_size(32);
+            graphql_handler_with_config(&schema, &context, req, payload, &config).await
+        }
+
+        let schema: Schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(limited_index))
+                .route("/", web::post().to(limited_index)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/?query=%7B+hero+%7B+name+%7D+%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .append_header(("apollo-require-preflight", "true"))
+            .uri("/?query=%7B+hero+%7B+name+%7D+%7D")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+
+        let req = test::TestRequest::post()
+            .append_header(("content-type", "application/graphql"))
+            .set_payload("{ hero { name friends { name } } }")
+            .uri("/")
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[actix_web::rt::test]
     async fn graphql_get_works() {
         let schema: Schema = RootNode::new(
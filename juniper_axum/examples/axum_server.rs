@@ -0,0 +1,60 @@
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::WebSocketUpgrade,
+    http::Request,
+    routing::{get, on, MethodFilter},
+    Router, Server,
+};
+use juniper::{
+    tests::fixtures::starwars::schema::{Database, Query, Subscription},
+    EmptyMutation, RootNode,
+};
+use juniper_axum::subscriptions::subscriptions_handler;
+use juniper_graphql_ws::ConnectionConfig;
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+#[tokio::main]
+async fn main() {
+    let schema = Arc::new(Schema::new(Query, EmptyMutation::new(), Subscription));
+    let subscriptions_schema = schema.clone();
+
+    let app = Router::new()
+        .route(
+            "/",
+            get(|| juniper_axum::graphiql("/graphql", Some("/subscriptions"))),
+        )
+        .route(
+            "/playground",
+            get(|| juniper_axum::playground("/graphql", Some("/subscriptions"))),
+        )
+        .route(
+            "/graphql",
+            on(
+                MethodFilter::GET | MethodFilter::POST,
+                move |req: Request<Body>| {
+                    let schema = schema.clone();
+                    async move { juniper_axum::graphql(&schema, &Database::new(), req).await }
+                },
+            ),
+        )
+        .route(
+            "/subscriptions",
+            get(move |upgrade: WebSocketUpgrade| {
+                let schema = subscriptions_schema.clone();
+                async move {
+                    subscriptions_handler(upgrade, schema, ConnectionConfig::new(Database::new()))
+                }
+            }),
+        );
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    println!("Listening on http://{}", addr);
+
+    Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,88 @@
+//! Types and traits for extracting GraphQL requests from axum requests.
+
+use axum::{
+    async_trait, body::HttpBody, extract::FromRequest, http::Request, response::Response, BoxError,
+};
+use juniper::{
+    http::{handler::HandlerConfig, GraphQLBatchRequest},
+    DefaultScalarValue, ScalarValue,
+};
+
+use crate::{into_response, BufferedRequest};
+
+/// Extractor for a [`GraphQLBatchRequest`], from either a `GET` or a `POST` request, as described
+/// in [`juniper::http::handler`].
+///
+/// Requests which can't be extracted are rejected with the response to send back, like a
+/// `400 Bad Request` one carrying the GraphQL error.
+///
+/// Unlike [`graphql()`], this extractor has no access to the schema, so it doesn't reject
+/// mutations sent with `GET` requests: only use it on `GET` routes of schemas without mutations.
+///
+/// Requests are checked against the [`HandlerConfig`] found in the extensions of the request, if
+/// any, which can be provided with an [`Extension`] layer.
+///
+/// ```
+/// # use std::sync::Arc;
+/// #
+/// # use axum::{
+/// #     response::{IntoResponse as _, Response},
+/// #     routing::post,
+/// #     Extension, Router,
+/// # };
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_axum::{JuniperRequest, JuniperResponse};
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// async fn graphql(
+///     Extension(schema): Extension<Arc<Schema>>,
+///     request: JuniperRequest,
+/// ) -> Response {
+///     let context = Database::new();
+///     JuniperResponse(request.0.execute(&schema, &context).await).into_response()
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// let app: Router = Router::new()
+///     .route("/graphql", post(graphql))
+///     .layer(Extension(Arc::new(schema)));
+/// ```
+///
+/// [`Extension`]: axum::Extension
+/// [`graphql()`]: crate::graphql
+#[derive(Debug, PartialEq)]
+pub struct JuniperRequest<S = DefaultScalarValue>(pub GraphQLBatchRequest<S>)
+where
+    S: ScalarValue;
+
+#[async_trait]
+impl<S, State, B> FromRequest<State, B> for JuniperRequest<S>
+where
+    S: ScalarValue,
+    State: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, _: &State) -> Result<Self, Self::Rejection> {
+        let config = req
+            .extensions()
+            .get::<HandlerConfig>()
+            .cloned()
+            .unwrap_or_default();
+        let req = BufferedRequest::read(req, &config).await?;
+        let headers = req.headers();
+        let (request, _) = req
+            .as_http_request(&headers)
+            .parse_unchecked_with_config(&config)
+            .map_err(into_response)?;
+        Ok(Self(request))
+    }
+}
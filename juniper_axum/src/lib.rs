@@ -0,0 +1,574 @@
+/*!
+
+# juniper_axum
+
+This repository contains the [axum][axum] web server integration for
+[Juniper][Juniper], a [GraphQL][GraphQL] implementation for Rust.
+
+## Documentation
+
+For documentation, including guides and examples, check out [Juniper][Juniper].
+
+A basic usage example can also be found in the [API documentation][documentation].
+
+## Examples
+
+Check [examples/axum_server.rs][example] for example code of a working axum
+server with GraphQL handlers.
+
+## Links
+
+* [Juniper][Juniper]
+* [API Reference][documentation]
+* [axum][axum]
+
+## License
+
+This project is under the BSD-2 license.
+
+Check the LICENSE file for details.
+
+[axum]: https://github.com/tokio-rs/axum
+[Juniper]: https://github.com/graphql-rust/juniper
+[GraphQL]: http://graphql.org
+[documentation]: https://docs.rs/juniper_axum
+[example]: https://github.com/graphql-rust/juniper/blob/master/juniper_axum/examples/axum_server.rs
+*/
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+#![doc(html_root_url = "https://docs.rs/juniper_axum/0.1.0")]
+
+pub mod extract;
+pub mod response;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+use axum::{
+    body::{Bytes, HttpBody},
+    extract::FromRequest,
+    http::{
+        header::{self, HeaderName, HeaderValue},
+        request::Parts,
+        Request, StatusCode,
+    },
+    response::{Html, IntoResponse, Response},
+    BoxError,
+};
+use bytes::{Buf as _, BufMut as _, BytesMut};
+use juniper::{
+    http::{
+        graphiql::{graphiql_source, graphiql_source_with_config, GraphiQLConfig},
+        handler::{self, HandlerConfig, HttpRequest, HttpResponse},
+        playground::{playground_source, playground_source_with_config, PlaygroundConfig},
+    },
+    GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+};
+
+#[doc(inline)]
+pub use self::{extract::JuniperRequest, response::JuniperResponse};
+
+/// Executes the GraphQL request in the given axum request, and returns the response to send back.
+///
+/// Requests are handled as described in [`juniper::http::handler`]. This does not handle
+/// routing, so you can mount it on any endpoint:
+///
+/// ```
+/// # use std::sync::Arc;
+/// #
+/// # use axum::{body::Body, http::Request, routing::on, routing::MethodFilter, Router};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// let schema = Arc::new(Schema::new(Query, EmptyMutation::new(), EmptySubscription::new()));
+///
+/// let app: Router = Router::new().route(
+///     "/graphql",
+///     on(MethodFilter::GET | MethodFilter::POST, move |req: Request<Body>| {
+///         let schema = schema.clone();
+///         async move { juniper_axum::graphql(&schema, &Database::new(), req).await }
+///     }),
+/// );
+/// ```
+pub async fn graphql<Query, Mutation, Subscription, CtxT, S, B>(
+    root_node: &RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: Request<B>,
+) -> Response
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    graphql_with_config(root_node, context, req, &HandlerConfig::default()).await
+}
+
+/// Executes the GraphQL request in the given axum request, like [`graphql()`], after checking it
+/// against the given [`HandlerConfig`].
+///
+/// The body is read up to the maximum size of the `config`, if any.
+pub async fn graphql_with_config<Query, Mutation, Subscription, CtxT, S, B>(
+    root_node: &RootNode<'static, Query, Mutation, Subscription, S>,
+    context: &CtxT,
+    req: Request<B>,
+    config: &HandlerConfig,
+) -> Response
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    let req = match BufferedRequest::read(req, config).await {
+        Ok(req) => req,
+        Err(resp) => return resp,
+    };
+    let headers = req.headers();
+    into_response(
+        handler::handle_with_config(req.as_http_request(&headers), root_node, context, config)
+            .await,
+    )
+}
+
+/// Replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount
+/// it on any endpoint:
+///
+/// ```
+/// # use axum::{routing::get, Router};
+/// #
+/// let app: Router = Router::new().route(
+///     "/",
+///     get(|| juniper_axum::graphiql("/graphql", Some("/subscriptions"))),
+/// );
+/// ```
+pub async fn graphiql(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> Html<String> {
+    Html(graphiql_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+/// Replies with an HTML page containing GraphiQL configured with the given [`GraphiQLConfig`]. This
+/// does not handle routing, so you can mount it on any endpoint:
+///
+/// ```
+/// # use axum::{routing::get, Router};
+/// # use juniper::http::graphiql::GraphiQLConfig;
+/// #
+/// let config = GraphiQLConfig::new("/graphql").with_header("Authorization", "Bearer token");
+///
+/// let app: Router = Router::new().route(
+///     "/",
+///     get(|| async move { juniper_axum::graphiql_with_config(&config).await }),
+/// );
+/// ```
+pub async fn graphiql_with_config(config: &GraphiQLConfig) -> Html<String> {
+    Html(graphiql_source_with_config(config))
+}
+
+/// Replies with an HTML page containing GraphQL Playground. This does not handle routing, so you
+/// can mount it on any endpoint.
+pub async fn playground(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> Html<String> {
+    Html(playground_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+/// Replies with an HTML page containing GraphQL Playground configured with the given
+/// [`PlaygroundConfig`]. This does not handle routing, so you can mount it on any endpoint.
+pub async fn playground_with_config(config: &PlaygroundConfig) -> Html<String> {
+    Html(playground_source_with_config(config))
+}
+
+/// Replies with the [bundled asset](juniper::http::assets) named by the path parameter, or with a
+/// `404 Not Found` if there is no such asset.
+///
+/// Mount it under the assets base URL given to the GraphiQL and GraphQL Playground pages:
+///
+/// ```
+/// # use axum::{routing::get, Router};
+/// #
+/// let app: Router = Router::new().route("/assets/*name", get(juniper_axum::asset));
+/// ```
+#[cfg(feature = "bundled-assets")]
+pub async fn asset(axum::extract::Path(name): axum::extract::Path<String>) -> Response {
+    match juniper::http::assets::find(name.trim_start_matches('/')) {
+        Some(asset) => (
+            [
+                (header::CONTENT_TYPE, asset.content_type),
+                (header::CACHE_CONTROL, juniper::http::assets::CACHE_CONTROL),
+            ],
+            asset.contents(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Parts of an axum request needed to build a [`HttpRequest`], with its body read to the end.
+struct BufferedRequest {
+    parts: Parts,
+    body: Bytes,
+}
+
+impl BufferedRequest {
+    /// Reads the body of the given request, failing as soon as it gets larger than the maximum
+    /// size of the `config`, if any.
+    async fn read<B>(req: Request<B>, config: &HandlerConfig) -> Result<Self, Response>
+    where
+        B: HttpBody + Send + 'static,
+        B::Data: Send,
+        B::Error: Into<BoxError>,
+    {
+        let (parts, body) = req.into_parts();
+        if config.max_body_size().is_none() {
+            let body = Bytes::from_request(Request::new(body), &())
+                .await
+                .map_err(IntoResponse::into_response)?;
+            return Ok(Self { parts, body });
+        }
+
+        let content_length = parts
+            .headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok()?.parse().ok());
+        if let Some(content_length) = content_length {
+            config
+                .check_body_size(content_length)
+                .map_err(into_response)?;
+        }
+
+        futures::pin_mut!(body);
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read the request body: {}", e.into()),
+                )
+                    .into_response()
+            })?;
+            config
+                .check_body_size(buf.len() + chunk.remaining())
+                .map_err(into_response)?;
+            buf.put(chunk);
+        }
+        Ok(Self {
+            parts,
+            body: buf.freeze(),
+        })
+    }
+
+    fn headers(&self) -> Vec<(&str, &str)> {
+        self.parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+            .collect()
+    }
+
+    fn as_http_request<'a>(&'a self, headers: &'a [(&'a str, &'a str)]) -> HttpRequest<'a> {
+        let header = |name| self.parts.headers.get(name).and_then(|v| v.to_str().ok());
+        HttpRequest {
+            method: self.parts.method.as_str(),
+            query_string: self.parts.uri.query(),
+            content_type: header(header::CONTENT_TYPE),
+            accept: header(header::ACCEPT),
+            headers,
+            body: &self.body,
+        }
+    }
+}
+
+fn into_response(resp: HttpResponse) -> Response {
+    let mut r = resp.body.into_response();
+    *r.status_mut() = StatusCode::from_u16(resp.status).expect("valid status code");
+    for (name, value) in resp.headers {
+        r.headers_mut().insert(
+            HeaderName::from_bytes(name.as_bytes()).expect("valid header name"),
+            HeaderValue::from_static(value),
+        );
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{header::CONTENT_TYPE, Request},
+        routing::{get, on, post, MethodFilter},
+        Router,
+    };
+    use juniper::{
+        http::tests::{run_http_test_suite, HttpIntegration, TestResponse},
+        tests::fixtures::starwars::schema::{Database, Query},
+        DefaultScalarValue, EmptyMutation, EmptySubscription, RootNode,
+    };
+    use tower::ServiceExt as _;
+
+    use super::*;
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    fn app() -> Router {
+        let schema = Arc::new(Schema::new(
+            Query,
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        ));
+        let extract_schema = schema.clone();
+        Router::new()
+            .route(
+                "/",
+                on(
+                    MethodFilter::GET | MethodFilter::POST,
+                    move |req: Request<Body>| {
+                        let schema = schema.clone();
+                        async move { graphql(&schema, &Database::new(), req).await }
+                    },
+                ),
+            )
+            .route(
+                "/extract",
+                post(move |JuniperRequest(req): JuniperRequest| {
+                    let schema = extract_schema.clone();
+                    async move {
+                        let context = Database::new();
+                        JuniperResponse(req.execute(&schema, &context).await).into_response()
+                    }
+                }),
+            )
+            .route(
+                "/graphiql",
+                get(|| graphiql("/dogs-api/graphql", Some("/dogs-api/subscriptions"))),
+            )
+            .route(
+                "/playground",
+                get(|| playground("/dogs-api/graphql", Some("/dogs-api/subscriptions"))),
+            )
+    }
+
+    async fn send(req: Request<Body>) -> TestResponse {
+        let resp = app().oneshot(req).await.unwrap();
+        let status_code = resp.status().as_u16().into();
+        let content_type = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|v| v.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        TestResponse {
+            status_code,
+            body: Some(String::from_utf8(body.to_vec()).unwrap()),
+            content_type,
+        }
+    }
+
+    struct TestAxumIntegration;
+
+    impl TestAxumIntegration {
+        fn make_request(&self, req: Request<Body>) -> TestResponse {
+            tokio::runtime::Runtime::new().unwrap().block_on(send(req))
+        }
+
+        fn post(&self, url: &str, content_type: &str, body: &str) -> TestResponse {
+            self.make_request(
+                Request::post(url)
+                    .header(CONTENT_TYPE, content_type)
+                    .body(Body::from(body.to_owned()))
+                    .unwrap(),
+            )
+        }
+    }
+
+    impl HttpIntegration for TestAxumIntegration {
+        fn get(&self, url: &str) -> TestResponse {
+            self.make_request(Request::get(url).body(Body::empty()).unwrap())
+        }
+
+        fn post_json(&self, url: &str, body: &str) -> TestResponse {
+            self.post(url, "application/json", body)
+        }
+
+        fn post_graphql(&self, url: &str, body: &str) -> TestResponse {
+            self.post(url, "application/graphql", body)
+        }
+    }
+
+    #[test]
+    fn test_axum_integration() {
+        run_http_test_suite(&TestAxumIntegration);
+    }
+
+    #[tokio::test]
+    async fn graphiql_endpoint_returns_graphiql_source() {
+        let resp = send(Request::get("/graphiql").body(Body::empty()).unwrap()).await;
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(resp.content_type, "text/html; charset=utf-8");
+        let body = resp.body.unwrap();
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/dogs-api/graphql\";</script>"));
+        assert!(body.contains(
+            "<script>var GRAPHQL_SUBSCRIPTIONS_URL = \"/dogs-api/subscriptions\";</script>"
+        ));
+    }
+
+    #[tokio::test]
+    async fn playground_endpoint_returns_playground_source() {
+        let resp = send(Request::get("/playground").body(Body::empty()).unwrap()).await;
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(resp.content_type, "text/html; charset=utf-8");
+        assert!(resp.body.unwrap().contains(
+            "GraphQLPlayground.init(root, { endpoint: \"/dogs-api/graphql\", \
+             subscriptionEndpoint: \"/dogs-api/subscriptions\" })",
+        ));
+    }
+
+    #[tokio::test]
+    async fn extracts_requests_and_builds_responses() {
+        let resp = send(
+            Request::post("/extract")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"query": "{ hero { name } }"}"#))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(resp.content_type, "application/json");
+        assert_eq!(resp.body.unwrap(), r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+
+        let resp = send(
+            Request::post("/extract")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"query": "{ hero { unknown } }"}"#))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(resp.status_code, 400);
+
+        let resp = send(
+            Request::post("/extract")
+                .header(CONTENT_TYPE, "text/plain")
+                .body(Body::from("{ hero { name } }"))
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(resp.status_code, 415);
+    }
+
+    #[cfg(feature = "bundled-assets")]
+    #[tokio::test]
+    async fn serves_bundled_assets() {
+        use juniper::http::assets::{CACHE_CONTROL, PLAYGROUND_FAVICON};
+
+        let app = Router::new().route("/assets/*name", get(asset));
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::get(format!("/assets/{}", PLAYGROUND_FAVICON.name))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers()[CONTENT_TYPE],
+            PLAYGROUND_FAVICON.content_type
+        );
+        assert_eq!(resp.headers()[header::CACHE_CONTROL], CACHE_CONTROL);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(body, PLAYGROUND_FAVICON.contents());
+
+        let resp = app
+            .oneshot(
+                Request::get("/assets/favicon.png")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+    }
+
+    #[tokio::test]
+    async fn rejects_mutations_over_get() {
+        let resp = send(
+            Request::get("/?query=mutation%7B__typename%7D")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        assert_eq!(resp.status_code, 405);
+    }
+
+    #[tokio::test]
+    async fn checks_requests_against_config() {
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let config = HandlerConfig::new()
+            .with_csrf_prevention()
+            .with_max_body_size(32);
+        let post = |body: &'static str| {
+            Request::post("/")
+                .header(CONTENT_TYPE, "application/graphql")
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let req = Request::get("/?query=%7B+hero+%7B+name+%7D+%7D")
+            .body(Body::empty())
+            .unwrap();
+        let resp = graphql_with_config(&schema, &Database::new(), req, &config).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = post("{ hero { name } }");
+        let resp = graphql_with_config(&schema, &Database::new(), req, &config).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = post("{ hero { name friends { name } } }");
+        let resp = graphql_with_config(&schema, &Database::new(), req, &config).await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let mut req = post("{ hero { name friends { name } } }");
+        req.extensions_mut().insert(config.clone());
+        let rejection = JuniperRequest::<DefaultScalarValue>::from_request(req, &())
+            .await
+            .unwrap_err();
+        assert_eq!(rejection.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
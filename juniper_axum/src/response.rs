@@ -0,0 +1,30 @@
+//! Types for building axum responses from GraphQL responses.
+
+use axum::response::{IntoResponse, Response};
+use juniper::{
+    http::{
+        handler::{HttpResponse, MediaType},
+        GraphQLBatchResponse,
+    },
+    DefaultScalarValue, ScalarValue,
+};
+
+use crate::into_response;
+
+/// Wrapper around a [`GraphQLBatchResponse`], converting it into an `application/json` axum
+/// response.
+///
+/// Responses are `200 OK` if every operation has been executed, even if some of their fields
+/// failed, and `400 Bad Request` otherwise.
+pub struct JuniperResponse<'a, S = DefaultScalarValue>(pub GraphQLBatchResponse<'a, S>)
+where
+    S: ScalarValue;
+
+impl<'a, S> IntoResponse for JuniperResponse<'a, S>
+where
+    S: ScalarValue,
+{
+    fn into_response(self) -> Response {
+        into_response(HttpResponse::from_graphql(&self.0, MediaType::Json))
+    }
+}
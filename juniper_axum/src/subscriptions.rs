@@ -0,0 +1,276 @@
+//! Serving of GraphQL subscriptions over WebSocket connections with the [graphql-ws][1] protocol.
+//!
+//! [1]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+
+use std::{convert::Infallible, fmt, sync::Arc};
+
+use axum::{
+    extract::ws::{self, WebSocket, WebSocketUpgrade},
+    response::Response,
+};
+use juniper::{
+    futures::{
+        future::{self, Either},
+        sink::SinkExt,
+        stream::StreamExt,
+    },
+    GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+};
+use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+
+struct Message(ws::Message);
+
+impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
+    type Error = DeserializeError;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        match msg.0 {
+            ws::Message::Close(_) => Ok(ClientMessage::ConnectionTerminate),
+            msg => serde_json::from_slice(&msg.into_data()).map_err(DeserializeError),
+        }
+    }
+}
+
+/// Error that can happen while deserializing client messages.
+#[derive(Debug)]
+struct DeserializeError(serde_json::Error);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "serde error: {}", self.0)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+/// Errors that can happen while serving a connection.
+#[derive(Debug)]
+pub enum Error {
+    /// Errors that can happen in axum while serving a connection.
+    Axum(axum::Error),
+
+    /// Errors that can happen while serializing outgoing messages. Note that errors that occur
+    /// while deserializing incoming messages are handled internally by the protocol.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Axum(e) => write!(f, "axum error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<axum::Error> for Error {
+    fn from(err: axum::Error) -> Self {
+        Self::Axum(err)
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(_err: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+/// Upgrades the request to a WebSocket connection serving the graphql-ws protocol.
+///
+/// See [`serve_graphql_ws`] for the meaning of the `init` argument. Errors of the connection are
+/// ignored, use [`serve_graphql_ws`] directly to handle them.
+///
+/// ```
+/// # use std::sync::Arc;
+/// #
+/// # use axum::{extract::WebSocketUpgrade, routing::get, Router};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+/// #     EmptyMutation, RootNode,
+/// # };
+/// # use juniper_graphql_ws::ConnectionConfig;
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+///
+/// let schema = Arc::new(Schema::new(Query, EmptyMutation::new(), Subscription));
+///
+/// let app: Router = Router::new().route(
+///     "/subscriptions",
+///     get(move |upgrade: WebSocketUpgrade| async move {
+///         let config = ConnectionConfig::new(Database::new());
+///         juniper_axum::subscriptions::subscriptions_handler(upgrade, schema, config)
+///     }),
+/// );
+/// ```
+pub fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
+    upgrade: WebSocketUpgrade,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Response
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    upgrade
+        .protocols(["graphql-ws"])
+        .on_upgrade(move |socket| async move {
+            // TODO: trace
+            let _ = serve_graphql_ws(socket, root_node, init).await;
+        })
+}
+
+/// Serves the graphql-ws protocol over a WebSocket connection.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    socket: WebSocket,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = socket.split();
+    let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+
+    // Pings are answered by axum itself, so only data and closing frames reach the protocol.
+    let ws_rx = ws_rx
+        .filter(|r| {
+            future::ready(!matches!(
+                r,
+                Ok(ws::Message::Ping(_)) | Ok(ws::Message::Pong(_))
+            ))
+        })
+        .map(|r| r.map(Message).map_err(Error::Axum));
+    let s_rx = s_rx.map(|msg| {
+        serde_json::to_string(&msg)
+            .map(ws::Message::Text)
+            .map_err(Error::Serde)
+    });
+
+    match future::select(
+        ws_rx.forward(s_tx.sink_err_into()),
+        s_rx.forward(ws_tx.sink_err_into()),
+    )
+    .await
+    {
+        Either::Left((r, _)) | Either::Right((r, _)) => r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+    use axum::{extract::WebSocketUpgrade, routing::get, Router, Server};
+    use juniper::{
+        futures::{SinkExt, StreamExt},
+        http::tests::{run_ws_test_suite, WsIntegration, WsIntegrationMessage},
+        tests::fixtures::starwars::schema::{Database, Query, Subscription},
+        EmptyMutation, LocalBoxFuture, RootNode,
+    };
+    use juniper_graphql_ws::ConnectionConfig;
+    use tokio::time::timeout;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    use super::subscriptions_handler;
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    struct TestAxumWsIntegration;
+
+    impl TestAxumWsIntegration {
+        fn serve() -> SocketAddr {
+            let schema = Arc::new(Schema::new(Query, EmptyMutation::new(), Subscription));
+            let app = Router::new().route(
+                "/subscriptions",
+                get(move |upgrade: WebSocketUpgrade| async move {
+                    let config = ConnectionConfig::new(Database::new());
+                    subscriptions_handler(upgrade, schema, config)
+                }),
+            );
+            let server =
+                Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+            let addr = server.local_addr();
+            tokio::spawn(server);
+            addr
+        }
+
+        async fn run_async(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            let addr = Self::serve();
+            let (mut socket, _) = connect_async(format!("ws://{}/subscriptions", addr)).await?;
+
+            for message in &messages {
+                match message {
+                    WsIntegrationMessage::Send(body) => {
+                        socket.send(Message::Text(body.to_owned())).await?;
+                    }
+                    WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message =
+                            timeout(Duration::from_millis(*message_timeout), socket.next())
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                                .ok_or_else(|| anyhow::anyhow!("Empty message received"))??;
+
+                        match message {
+                            Message::Text(text) => {
+                                let expected_value =
+                                    serde_json::from_str::<serde_json::Value>(body)?;
+                                let value = serde_json::from_str::<serde_json::Value>(&text)?;
+
+                                if value != expected_value {
+                                    return Err(anyhow::anyhow!(
+                                        "Expected message: {}. Received message: {}",
+                                        expected_value,
+                                        value,
+                                    ));
+                                }
+                            }
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl WsIntegration for TestAxumWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_axum_ws_integration() {
+        run_ws_test_suite(&TestAxumWsIntegration).await;
+    }
+}
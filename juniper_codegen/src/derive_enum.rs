@@ -88,16 +88,38 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 );
             }
 
+            if let Some(timeout) = field_attrs.timeout {
+                error.unsupported_attribute_within(
+                    timeout.span_ident(),
+                    UnsupportedAttribute::Timeout,
+                );
+            }
+
+            if let Some(tracing) = field_attrs.tracing_skip {
+                error.unsupported_attribute_within(
+                    tracing.span_ident(),
+                    UnsupportedAttribute::Tracing,
+                );
+            }
+
             Some(util::GraphQLTypeDefinitionField {
                 name,
                 _type,
                 args: Vec::new(),
                 description: field_attrs.description.map(SpanContainer::into_inner),
                 deprecation: field_attrs.deprecation.map(SpanContainer::into_inner),
+                args_resolver_code: quote!(),
                 resolver_code,
                 is_type_inferred: true,
                 is_async: false,
                 default: None,
+                timeout: None,
+                skip_tracing: false,
+                directives: field_attrs
+                    .directives
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
                 span,
             })
         })
@@ -144,6 +166,11 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         // NOTICE: only unit variants allow -> no generics possible
         generics: syn::Generics::default(),
         interfaces: vec![],
+        directives: attrs
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
@@ -67,6 +67,21 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 )
             }
 
+            if let Some(timeout) = field_attrs.timeout {
+                error.unsupported_attribute_within(
+                    timeout.span_ident(),
+                    UnsupportedAttribute::Timeout,
+                )
+            }
+
+            if let Some(tracing) = field_attrs.tracing_skip {
+                error.unsupported_attribute_within(
+                    tracing.span_ident(),
+                    UnsupportedAttribute::Tracing,
+                )
+            }
+
+
             if name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = field_attrs.name {
                     name.span_ident()
@@ -90,10 +105,18 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 args: Vec::new(),
                 description: field_attrs.description.map(SpanContainer::into_inner),
                 deprecation: None,
+                args_resolver_code: quote!(),
                 resolver_code,
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                timeout: None,
+                skip_tracing: false,
+                directives: field_attrs
+                    .directives
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
                 span,
             })
         })
@@ -142,6 +165,11 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         fields,
         generics: ast.generics,
         interfaces: vec![],
+        directives: attrs
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
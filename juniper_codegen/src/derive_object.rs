@@ -82,8 +82,16 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 args: Vec::new(),
                 description: field_attrs.description.map(SpanContainer::into_inner),
                 deprecation: field_attrs.deprecation.map(SpanContainer::into_inner),
+                args_resolver_code: quote!(),
                 resolver_code,
                 default: None,
+                timeout: field_attrs.timeout.map(SpanContainer::into_inner),
+                skip_tracing: field_attrs.tracing_skip.is_some(),
+                directives: field_attrs
+                    .directives
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
                 is_type_inferred: true,
                 is_async: false,
                 span,
@@ -128,6 +136,11 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
             .into_iter()
             .map(SpanContainer::into_inner)
             .collect(),
+        directives: attrs
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
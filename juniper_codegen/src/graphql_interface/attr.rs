@@ -210,6 +210,11 @@ pub fn expand_on_trait(
 
         fields,
         implementers,
+        directives: meta
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
     };
 
     // Attach the `juniper::AsDynGraphQLValue` on top of the trait if dynamic dispatch is used.
@@ -501,6 +506,11 @@ impl TraitMethod {
             method: method_ident.clone(),
             arguments,
             is_async: method.sig.asyncness.is_some(),
+            directives: meta
+                .directives
+                .iter()
+                .map(|directive| directive.as_ref().clone())
+                .collect(),
         })
     }
 
@@ -24,7 +24,10 @@ use crate::{
         },
         ScalarValueType,
     },
-    util::{filter_attrs, get_deprecated, get_doc_comment, span_container::SpanContainer},
+    util::{
+        filter_attrs, get_deprecated, get_doc_comment, span_container::SpanContainer,
+        AppliedDirective,
+    },
 };
 
 /// Available metadata (arguments) behind `#[graphql_interface]` attribute placed on a trait
@@ -116,6 +119,11 @@ struct TraitMeta {
     /// [2]: https://spec.graphql.org/June2018/#sec-Objects
     external_downcasts: HashMap<syn::Type, SpanContainer<syn::ExprPath>>,
 
+    /// Explicitly specified directives applied to this [GraphQL interface][1] type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    directives: Vec<SpanContainer<AppliedDirective>>,
+
     /// Indicator whether the generated code is intended to be used only inside the [`juniper`]
     /// library.
     is_internal: bool,
@@ -214,6 +222,9 @@ impl Parse for TraitMeta {
                         .insert(ty, dwncst_spanned)
                         .none_or_else(|_| err::dup_arg(dwncst_span))?
                 }
+                "directive" => output
+                    .directives
+                    .extend(AppliedDirective::parse_list(input)?),
                 "internal" => {
                     output.is_internal = true;
                 }
@@ -243,6 +254,11 @@ impl TraitMeta {
             external_downcasts: try_merge_hashmap!(
                 external_downcasts: self, another => span_joined
             ),
+            directives: self
+                .directives
+                .into_iter()
+                .chain(another.directives)
+                .collect(),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -412,6 +428,11 @@ struct MethodMeta {
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
     /// [2]: https://spec.graphql.org/June2018/#sec-Objects
     downcast: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified directives applied to this [GraphQL field][1].
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    directives: Vec<SpanContainer<AppliedDirective>>,
 }
 
 impl Parse for MethodMeta {
@@ -460,6 +481,9 @@ impl Parse for MethodMeta {
                     .downcast
                     .replace(SpanContainer::new(ident.span(), None, ident.clone()))
                     .none_or_else(|_| err::dup_arg(&ident))?,
+                "directive" => output
+                    .directives
+                    .extend(AppliedDirective::parse_list(input)?),
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -480,6 +504,11 @@ impl MethodMeta {
             deprecated: try_merge_opt!(deprecated: self, another),
             ignore: try_merge_opt!(ignore: self, another),
             downcast: try_merge_opt!(downcast: self, another),
+            directives: self
+                .directives
+                .into_iter()
+                .chain(another.directives)
+                .collect(),
         })
     }
 
@@ -751,6 +780,11 @@ struct Definition {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
     implementers: Vec<Implementer>,
+
+    /// Directives applied to this [GraphQL interface][1] to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    directives: Vec<AppliedDirective>,
 }
 
 impl Definition {
@@ -798,6 +832,7 @@ impl Definition {
         });
 
         let fields_meta = self.fields.iter().map(Field::method_meta_tokens);
+        let directives = &self.directives;
 
         quote! {
             #[automatically_derived]
@@ -821,6 +856,7 @@ impl Definition {
                     ];
                     registry.build_interface_type::<#ty>(info, &fields)
                         #description
+                        #( .directive(#directives) )*
                         .into_meta()
                 }
             }
@@ -959,7 +995,7 @@ impl Definition {
         let fields_resolvers = self
             .fields
             .iter()
-            .map(|f| f.method_resolve_field_async_tokens(&trait_ty));
+            .map(|f| f.method_resolve_field_async_tokens(&trait_ty, scalar));
         let no_field_panic = self.panic_no_field_tokens();
 
         let custom_downcasts = self
@@ -1214,7 +1250,7 @@ impl MethodArgument {
                 );
 
                 quote! {
-                    args.get::<#ty>(#name).expect(#err_text)
+                    args.get::<#ty>(#name)?.expect(#err_text)
                 }
             }
 
@@ -1272,6 +1308,11 @@ struct Field {
     ///
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
     is_async: bool,
+
+    /// Directives applied to this [GraphQL field][2] to put into GraphQL schema.
+    ///
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    directives: Vec<AppliedDirective>,
 }
 
 impl Field {
@@ -1302,11 +1343,14 @@ impl Field {
             .iter()
             .filter_map(MethodArgument::method_meta_tokens);
 
+        let directives = &self.directives;
+
         quote! {
             registry.field_convert::<#ty, _, Self::Context>(#name, info)
                 #( #arguments )*
                 #description
                 #deprecated
+                #( .directive(#directives) )*
         }
     }
 
@@ -1344,7 +1388,11 @@ impl Field {
     ///
     /// [`GraphQLValueAsync::resolve_field_async`]: juniper::GraphQLValueAsync::resolve_field_async
     #[must_use]
-    fn method_resolve_field_async_tokens(&self, trait_ty: &syn::Type) -> TokenStream {
+    fn method_resolve_field_async_tokens(
+        &self,
+        trait_ty: &syn::Type,
+        scalar: &ScalarValueType,
+    ) -> TokenStream {
         let (name, ty, method) = (&self.name, &self.ty, &self.method);
 
         let arguments = self
@@ -1359,9 +1407,14 @@ impl Field {
 
         let resolving_code = gen::async_resolving_code(Some(ty));
 
+        // Arguments are converted eagerly, so their conversion errors are returned before any
+        // `Future` is created.
         quote! {
             #name => {
-                let fut = #fut;
+                let fut = match (move || -> ::juniper::FieldResult<_, #scalar> { Ok(#fut) })() {
+                    Ok(fut) => fut,
+                    Err(e) => return Box::pin(::juniper::futures::future::ready(Err(e))),
+                };
                 #resolving_code
             }
         }
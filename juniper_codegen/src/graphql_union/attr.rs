@@ -86,6 +86,11 @@ pub fn expand(attr_args: TokenStream, body: TokenStream) -> syn::Result<TokenStr
         ty: parse_quote! { #trait_ident },
         is_trait_object: true,
         description: meta.description.map(SpanContainer::into_inner),
+        directives: meta
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         context,
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics.clone(),
@@ -80,6 +80,11 @@ fn expand_enum(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         ty: parse_quote! { #enum_ident },
         is_trait_object: false,
         description: meta.description.map(SpanContainer::into_inner),
+        directives: meta
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         context: meta.context.map(SpanContainer::into_inner),
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
@@ -205,6 +210,11 @@ fn expand_struct(ast: syn::DeriveInput) -> syn::Result<UnionDefinition> {
         ty: parse_quote! { #struct_ident },
         is_trait_object: false,
         description: meta.description.map(SpanContainer::into_inner),
+        directives: meta
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         context: meta.context.map(SpanContainer::into_inner),
         scalar: meta.scalar.map(SpanContainer::into_inner),
         generics: ast.generics,
@@ -21,7 +21,7 @@ use crate::{
         attr::{err, OptionExt as _},
         ParseBufferExt as _,
     },
-    util::{filter_attrs, get_doc_comment, span_container::SpanContainer},
+    util::{filter_attrs, get_doc_comment, span_container::SpanContainer, AppliedDirective},
 };
 
 /// Helper alias for the type of [`UnionMeta::external_resolvers`] field.
@@ -77,6 +77,11 @@ struct UnionMeta {
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     pub external_resolvers: UnionMetaResolvers,
 
+    /// Explicitly specified directives applied to this [GraphQL union][1] type.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub directives: Vec<SpanContainer<AppliedDirective>>,
+
     /// Indicator whether the generated code is intended to be used only inside the `juniper`
     /// library.
     pub is_internal: bool,
@@ -140,6 +145,9 @@ impl Parse for UnionMeta {
                         .insert(ty, rslvr_spanned)
                         .none_or_else(|_| err::dup_arg(rslvr_span))?
                 }
+                "directive" => output
+                    .directives
+                    .extend(AppliedDirective::parse_list(input)?),
                 "internal" => {
                     output.is_internal = true;
                 }
@@ -165,6 +173,11 @@ impl UnionMeta {
             external_resolvers: try_merge_hashmap!(
                 external_resolvers: self, another => span_joined
             ),
+            directives: self
+                .directives
+                .into_iter()
+                .chain(another.directives)
+                .collect(),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -335,6 +348,11 @@ struct UnionDefinition {
     ///
     /// [1]: https://spec.graphql.org/June2018/#sec-Unions
     pub variants: Vec<UnionVariantDefinition>,
+
+    /// Directives applied to this [GraphQL union][1] to put into GraphQL schema.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Unions
+    pub directives: Vec<AppliedDirective>,
 }
 
 impl ToTokens for UnionDefinition {
@@ -359,6 +377,8 @@ impl ToTokens for UnionDefinition {
             .as_ref()
             .map(|desc| quote! { .description(#desc) });
 
+        let directives = &self.directives;
+
         let var_types: Vec<_> = self.variants.iter().map(|var| &var.ty).collect();
 
         let all_variants_unique = if var_types.len() > 1 {
@@ -475,6 +495,7 @@ impl ToTokens for UnionDefinition {
                     ];
                     registry.build_union_type::<#ty_full>(info, &types)
                     #description
+                    #( .directive(#directives) )*
                     .into_meta()
                 }
             }
@@ -111,7 +111,7 @@ fn create(
 
                     let resolver = quote!(
                         let #mut_modifier #arg_ident = args
-                            .get::<#ty>(#final_name)
+                            .get::<#ty>(#final_name)?
                             .unwrap_or_else(::juniper::FromInputValue::<#scalar>::from_implicit_null);
                     );
 
@@ -124,6 +124,15 @@ fn create(
                             .and_then(|arg| arg.default.clone()),
                         _type: ty.clone(),
                         name: final_name,
+                        directives: attrs
+                            .argument(&arg_name)
+                            .map(|arg| {
+                                arg.directives
+                                    .iter()
+                                    .map(|directive| directive.as_ref().clone())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
                     };
                     Ok((resolver, field_type))
                 });
@@ -136,9 +145,11 @@ fn create(
                 }
             };
 
+            let args_resolver_code = quote!(
+                #( #resolve_parts )*
+            );
             let body = &method.block;
             let resolver_code = quote!(
-                #( #resolve_parts )*
                 #body
             );
 
@@ -175,10 +186,18 @@ fn create(
                 args,
                 description: attrs.description.map(SpanContainer::into_inner),
                 deprecation: attrs.deprecation.map(SpanContainer::into_inner),
+                args_resolver_code,
                 resolver_code,
                 is_type_inferred: false,
                 is_async,
                 default: None,
+                timeout: attrs.timeout.map(SpanContainer::into_inner),
+                skip_tracing: attrs.tracing_skip.is_some(),
+                directives: attrs
+                    .directives
+                    .into_iter()
+                    .map(SpanContainer::into_inner)
+                    .collect(),
                 span,
             })
         })
@@ -222,6 +241,12 @@ fn create(
             .into_iter()
             .map(SpanContainer::into_inner)
             .collect(),
+        directives: _impl
+            .attrs
+            .directives
+            .into_iter()
+            .map(SpanContainer::into_inner)
+            .collect(),
         include_type_generics: false,
         generic_scalar: true,
         no_async: _impl.attrs.no_async.is_some(),
@@ -206,6 +206,10 @@ pub fn build_scalar(
         Some(val) => quote!(.description(#val)),
         None => quote!(),
     };
+    let accept_composite_values = attrs
+        .accept_composite_values
+        .map(|_| quote!(.accept_composite_values()));
+    let directives = attrs.directives.iter();
     let async_generic_type = match input.custom_data_type_is_struct {
         true => quote!(__S),
         _ => quote!(#custom_data_type),
@@ -273,6 +277,8 @@ pub fn build_scalar(
             {
                 registry.build_scalar_type::<Self>(info)
                     #description
+                    #accept_composite_values
+                    #( .directive(#directives) )*
                     .into_meta()
             }
         }
@@ -309,6 +315,8 @@ pub fn build_scalar(
         impl#generic_type_decl ::juniper::FromInputValue<#generic_type> for #impl_for_type
         #generic_type_bound
         {
+            type Error = <#from_input_value_result as ::juniper::ExtractError>::Error;
+
             fn from_input_value(#from_input_value_arg: &::juniper::InputValue<#generic_type>) -> #from_input_value_result {
                 #from_input_value_body
             }
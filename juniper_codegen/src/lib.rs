@@ -508,8 +508,13 @@ pub fn graphql_object(args: TokenStream, input: TokenStream) -> TokenStream {
 ///         juniper::Value::scalar(self.0.to_owned())
 ///     }
 ///
-///     fn from_input_value(value: &juniper::InputValue) -> Option<UserID> {
-///         value.as_string_value().map(|s| UserID(s.to_owned()))
+///     // The error is reported to clients when the input is rejected. It may be of any type
+///     // implementing `juniper::IntoFieldError`.
+///     fn from_input_value(value: &juniper::InputValue) -> Result<UserID, String> {
+///         value
+///             .as_string_value()
+///             .map(|s| UserID(s.to_owned()))
+///             .ok_or_else(|| format!("Expected `String`, found: {}", value))
 ///     }
 ///
 ///     fn from_str<'a>(value: juniper::ScalarToken<'a>) -> juniper::ParseScalarResult<'a, S> {
@@ -523,6 +528,10 @@ pub fn graphql_object(args: TokenStream, input: TokenStream) -> TokenStream {
 /// In addition to implementing `GraphQLType` for the type in question,
 /// `FromInputValue` and `ToInputValue` is also implemented. This makes the type
 /// usable as arguments and default values.
+///
+/// List and object literals are rejected for scalars before `from_input_value`
+/// is called. Scalars representing composite data, like a JSON scalar, may
+/// accept them with the `accept_composite_values` attribute.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn graphql_scalar(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -57,6 +57,8 @@ pub enum UnsupportedAttribute {
     Scalar,
     Deprecation,
     Default,
+    Timeout,
+    Tracing,
 }
 
 impl GraphQLScope {
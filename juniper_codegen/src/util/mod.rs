@@ -81,6 +81,137 @@ pub struct DeprecationAttr {
     pub reason: Option<String>,
 }
 
+/// Directive applied to a type system definition, like `@auth(role: ADMIN)` declared by
+/// `#[graphql(directive(auth(role = ADMIN)))]`, or `@key(fields: "id")` declared by
+/// `#[graphql(key = "id")]`.
+#[derive(Clone, Debug)]
+pub struct AppliedDirective {
+    pub name: String,
+    pub arguments: Vec<(String, AppliedDirectiveValue)>,
+}
+
+impl AppliedDirective {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments: vec![],
+        }
+    }
+
+    fn with_fields(name: &str, fields: &syn::LitStr) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments: vec![(
+                "fields".to_owned(),
+                AppliedDirectiveValue::String(fields.value()),
+            )],
+        }
+    }
+
+    /// Parses the contents of a `directive(...)` attribute, like `auth(role = ADMIN), internal`.
+    pub fn parse_list(input: ParseStream) -> syn::Result<Vec<SpanContainer<Self>>> {
+        let content;
+        syn::parenthesized!(content in input);
+        let directives = Punctuated::<SpanContainer<Self>, token::Comma>::parse_terminated_with(
+            &content,
+            Self::parse_one,
+        )?;
+        Ok(directives.into_iter().collect())
+    }
+
+    /// Parses a single directive, like `auth(role = ADMIN)`.
+    fn parse_one(input: ParseStream) -> syn::Result<SpanContainer<Self>> {
+        let ident = input.parse_any_ident()?;
+        let mut directive = AppliedDirective::new(&ident.unraw().to_string());
+
+        if input.peek(token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            while !content.is_empty() {
+                let arg = content.parse_any_ident()?;
+                content.parse::<token::Eq>()?;
+                let value = content.parse::<AppliedDirectiveValue>()?;
+                directive.arguments.push((arg.unraw().to_string(), value));
+                content.try_parse::<token::Comma>()?;
+            }
+        }
+
+        Ok(SpanContainer::new(ident.span(), None, directive))
+    }
+}
+
+/// Literal argument value of an [`AppliedDirective`].
+#[derive(Clone, Debug)]
+pub enum AppliedDirectiveValue {
+    String(String),
+    Int(i32),
+    Float(f64),
+    Boolean(bool),
+    Enum(String),
+    List(Vec<AppliedDirectiveValue>),
+}
+
+impl Parse for AppliedDirectiveValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            let items = Punctuated::<Self, token::Comma>::parse_terminated(&content)?;
+            return Ok(Self::List(items.into_iter().collect()));
+        }
+        if input.peek(syn::Ident) && !input.peek(syn::LitBool) {
+            let ident = input.parse::<syn::Ident>()?;
+            return Ok(Self::Enum(ident.unraw().to_string()));
+        }
+
+        let negative = input.parse::<Option<token::Sub>>()?.is_some();
+        let lit = input.parse::<syn::Lit>()?;
+        match lit {
+            syn::Lit::Str(s) if !negative => Ok(Self::String(s.value())),
+            syn::Lit::Bool(b) if !negative => Ok(Self::Boolean(b.value)),
+            syn::Lit::Int(i) => {
+                let val = i.base10_parse::<i32>()?;
+                Ok(Self::Int(if negative { -val } else { val }))
+            }
+            syn::Lit::Float(f) => {
+                let val = f.base10_parse::<f64>()?;
+                Ok(Self::Float(if negative { -val } else { val }))
+            }
+            _ => Err(syn::Error::new(
+                lit.span(),
+                "expected a string, number, boolean, enum value or list",
+            )),
+        }
+    }
+}
+
+impl quote::ToTokens for AppliedDirective {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = &self.name;
+        let args = self
+            .arguments
+            .iter()
+            .map(|(arg, val)| quote!( .argument(#arg, #val) ));
+        tokens.extend(quote! {
+            ::juniper::meta::AppliedDirective::new(#name)
+                #( #args )*
+        })
+    }
+}
+
+impl quote::ToTokens for AppliedDirectiveValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Self::String(val) => quote!( ::juniper::InputValue::scalar(#val.to_owned()) ),
+            Self::Int(val) => quote!( ::juniper::InputValue::scalar(#val) ),
+            Self::Float(val) => quote!( ::juniper::InputValue::scalar(#val) ),
+            Self::Boolean(val) => quote!( ::juniper::InputValue::scalar(#val) ),
+            Self::Enum(val) => quote!( ::juniper::InputValue::enum_value(#val) ),
+            Self::List(items) => quote!(::juniper::InputValue::list(vec![ #( #items ),* ])),
+        })
+    }
+}
+
 pub fn find_graphql_attr(attrs: &[Attribute]) -> Option<&Attribute> {
     attrs
         .iter()
@@ -338,6 +469,7 @@ pub struct ObjectAttributes {
     pub no_async: Option<SpanContainer<()>>,
     pub is_internal: bool,
     pub rename: Option<RenameRule>,
+    pub directives: Vec<SpanContainer<AppliedDirective>>,
 }
 
 impl Parse for ObjectAttributes {
@@ -399,6 +531,27 @@ impl Parse for ObjectAttributes {
                 "internal" => {
                     output.is_internal = true;
                 }
+                "key" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::LitStr>()?;
+                    output.directives.push(SpanContainer::new(
+                        ident.span(),
+                        Some(val.span()),
+                        AppliedDirective::with_fields("key", &val),
+                    ));
+                }
+                "shareable" => {
+                    output.directives.push(SpanContainer::new(
+                        ident.span(),
+                        None,
+                        AppliedDirective::new("shareable"),
+                    ));
+                }
+                "directive" => {
+                    output
+                        .directives
+                        .extend(AppliedDirective::parse_list(input)?);
+                }
                 "rename" => {
                     input.parse::<syn::Token![=]>()?;
                     let val = input.parse::<syn::LitStr>()?;
@@ -446,6 +599,7 @@ pub struct FieldAttributeArgument {
     pub rename: Option<SpanContainer<syn::LitStr>>,
     pub default: Option<syn::Expr>,
     pub description: Option<syn::LitStr>,
+    pub directives: Vec<SpanContainer<AppliedDirective>>,
 }
 
 impl Parse for FieldAttributeArgument {
@@ -457,12 +611,19 @@ impl Parse for FieldAttributeArgument {
             rename: None,
             default: None,
             description: None,
+            directives: vec![],
         };
 
         let content;
         syn::parenthesized!(content in input);
         while !content.is_empty() {
             let name = content.parse::<syn::Ident>()?;
+            if name == "directive" {
+                arg.directives
+                    .extend(AppliedDirective::parse_list(&content)?);
+                content.parse::<token::Comma>().ok();
+                continue;
+            }
             content.parse::<token::Eq>()?;
 
             match name.to_string().as_str() {
@@ -498,8 +659,12 @@ enum FieldAttribute {
     Description(SpanContainer<syn::LitStr>),
     Deprecation(SpanContainer<DeprecationAttr>),
     Skip(SpanContainer<syn::Ident>),
+    Directives(Vec<SpanContainer<AppliedDirective>>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(Box<SpanContainer<Option<syn::Expr>>>),
+    Timeout(SpanContainer<u64>),
+    TracingSkip(SpanContainer<syn::Ident>),
+    AcceptCompositeValues(SpanContainer<syn::Ident>),
 }
 
 impl Parse for FieldAttribute {
@@ -574,11 +739,104 @@ impl Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(Box::new(default_expr)))
             }
+            "timeout" => {
+                input.parse::<token::Eq>()?;
+                let lit = input.parse::<syn::Lit>()?;
+                let millis = parse_timeout_millis(&lit)?;
+                Ok(FieldAttribute::Timeout(SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    millis,
+                )))
+            }
+            "tracing" => {
+                let content;
+                syn::parenthesized!(content in input);
+                let behavior = content.parse::<syn::Ident>()?;
+                if behavior != "skip" {
+                    return Err(syn::Error::new(
+                        behavior.span(),
+                        "unknown tracing attribute, expected `skip`",
+                    ));
+                }
+                Ok(FieldAttribute::TracingSkip(SpanContainer::new(
+                    ident.span(),
+                    Some(behavior.span()),
+                    behavior,
+                )))
+            }
+            "accept_composite_values" => Ok(FieldAttribute::AcceptCompositeValues(
+                SpanContainer::new(ident.span(), None, ident),
+            )),
+            "external" | "shareable" => {
+                let name = if ident == "external" {
+                    "external"
+                } else {
+                    "shareable"
+                };
+                Ok(FieldAttribute::Directives(vec![SpanContainer::new(
+                    ident.span(),
+                    None,
+                    AppliedDirective::new(name),
+                )]))
+            }
+            "requires" | "provides" => {
+                input.parse::<token::Eq>()?;
+                let lit = input.parse::<syn::LitStr>()?;
+                let name = if ident == "requires" {
+                    "requires"
+                } else {
+                    "provides"
+                };
+                Ok(FieldAttribute::Directives(vec![SpanContainer::new(
+                    ident.span(),
+                    Some(lit.span()),
+                    AppliedDirective::with_fields(name, &lit),
+                )]))
+            }
+            "directive" => Ok(FieldAttribute::Directives(AppliedDirective::parse_list(
+                input,
+            )?)),
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
 }
 
+/// Parses a `timeout` attribute value into milliseconds.
+///
+/// Accepts either an integer literal of milliseconds, or a string literal with a `ms`, `s` or `m`
+/// unit suffix, like `"500ms"` or `"2s"`.
+fn parse_timeout_millis(lit: &syn::Lit) -> syn::Result<u64> {
+    let err = || {
+        syn::Error::new(
+            lit.span(),
+            "expected milliseconds or a duration string like \"500ms\", \"2s\" or \"1m\"",
+        )
+    };
+    match lit {
+        syn::Lit::Int(int) => int.base10_parse::<u64>(),
+        syn::Lit::Str(s) => {
+            let val = s.value();
+            let val = val.trim();
+            let (num, factor) = if let Some(num) = val.strip_suffix("ms") {
+                (num, 1)
+            } else if let Some(num) = val.strip_suffix('s') {
+                (num, 1_000)
+            } else if let Some(num) = val.strip_suffix('m') {
+                (num, 60_000)
+            } else {
+                return Err(err());
+            };
+            num.trim()
+                .parse::<u64>()
+                .ok()
+                .and_then(|n| n.checked_mul(factor))
+                .ok_or_else(err)
+        }
+        _ => Err(err()),
+    }
+}
+
 #[derive(Default)]
 pub struct FieldAttributes {
     pub name: Option<SpanContainer<String>>,
@@ -590,6 +848,13 @@ pub struct FieldAttributes {
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Only relevant for objects. Stored in milliseconds.
+    pub timeout: Option<SpanContainer<u64>>,
+    /// Only relevant for objects.
+    pub tracing_skip: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for scalars.
+    pub accept_composite_values: Option<SpanContainer<syn::Ident>>,
+    pub directives: Vec<SpanContainer<AppliedDirective>>,
 }
 
 impl Parse for FieldAttributes {
@@ -618,6 +883,18 @@ impl Parse for FieldAttributes {
                 FieldAttribute::Default(expr) => {
                     output.default = Some(*expr);
                 }
+                FieldAttribute::Timeout(millis) => {
+                    output.timeout = Some(millis);
+                }
+                FieldAttribute::TracingSkip(ident) => {
+                    output.tracing_skip = Some(ident);
+                }
+                FieldAttribute::AcceptCompositeValues(ident) => {
+                    output.accept_composite_values = Some(ident);
+                }
+                FieldAttribute::Directives(directives) => {
+                    output.directives.extend(directives);
+                }
             }
         }
 
@@ -644,6 +921,13 @@ impl FieldAttributes {
             None => Self::default(),
         };
 
+        if let Some(ident) = &output.accept_composite_values {
+            return Err(syn::Error::new(
+                ident.span_ident(),
+                "`accept_composite_values` is only allowed on scalars",
+            ));
+        }
+
         // Check for regular doc comment.
         if output.description.is_none() {
             output.description = doc_comment;
@@ -666,6 +950,7 @@ pub struct GraphQLTypeDefinitionFieldArg {
     pub description: Option<String>,
     pub default: Option<syn::Expr>,
     pub _type: Box<syn::Type>,
+    pub directives: Vec<AppliedDirective>,
 }
 
 #[derive(Debug)]
@@ -675,10 +960,17 @@ pub struct GraphQLTypeDefinitionField {
     pub description: Option<String>,
     pub deprecation: Option<DeprecationAttr>,
     pub args: Vec<GraphQLTypeDefinitionFieldArg>,
+    /// Code binding the arguments of the field before `resolver_code` runs. It may use `?` to
+    /// fail with a `FieldError` when an argument can't be converted.
+    pub args_resolver_code: TokenStream,
     pub resolver_code: TokenStream,
     pub is_type_inferred: bool,
     pub is_async: bool,
     pub default: Option<TokenStream>,
+    /// Resolution timeout in milliseconds.
+    pub timeout: Option<u64>,
+    pub skip_tracing: bool,
+    pub directives: Vec<AppliedDirective>,
     pub span: Span,
 }
 
@@ -707,6 +999,7 @@ pub struct GraphQLTypeDefiniton {
     pub fields: Vec<GraphQLTypeDefinitionField>,
     pub generics: syn::Generics,
     pub interfaces: Vec<syn::Type>,
+    pub directives: Vec<AppliedDirective>,
     // Due to syn parsing differences,
     // when parsing an impl the type generics are included in the type
     // directly, but in syn::DeriveInput, the type generics are
@@ -747,6 +1040,7 @@ impl GraphQLTypeDefiniton {
                     Some(value) => quote!( .description( #value ) ),
                     None => quote!(),
                 };
+                let directives = arg.directives.iter();
 
                 // Code.
                 match arg.default.as_ref() {
@@ -754,12 +1048,14 @@ impl GraphQLTypeDefiniton {
                         .argument(
                             registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
                                 #description
+                                #( .directive(#directives) )*
                         )
                     ),
                     None => quote!(
                         .argument(
                             registry.arg::<#arg_type>(#arg_name, info)
                                 #description
+                                #( .directive(#directives) )*
                         )
                     ),
                 }
@@ -781,6 +1077,18 @@ impl GraphQLTypeDefiniton {
                 None => quote!(),
             };
 
+            let timeout = field
+                .timeout
+                .map(|millis| quote!( .timeout(::std::time::Duration::from_millis(#millis)) ));
+
+            let skip_tracing = if field.skip_tracing {
+                Some(quote!( .skip_tracing() ))
+            } else {
+                None
+            };
+
+            let directives = field.directives.iter();
+
             let field_name = &field.name;
 
             let _type = &field._type;
@@ -790,6 +1098,9 @@ impl GraphQLTypeDefiniton {
                     #(#args)*
                     #description
                     #deprecation
+                    #timeout
+                    #skip_tracing
+                    #( .directive(#directives) )*
             }
         });
 
@@ -809,6 +1120,7 @@ impl GraphQLTypeDefiniton {
 
         let resolve_matches = self.fields.iter().map(|field| {
             let name = &field.name;
+            let args_code = &field.args_resolver_code;
             let code = &field.resolver_code;
 
             if field.is_async {
@@ -829,6 +1141,7 @@ impl GraphQLTypeDefiniton {
                 };
                 quote!(
                     #name => {
+                        #args_code
                         let res #_type = (|| { #code })();
                         ::juniper::IntoResolvable::into(
                             res,
@@ -850,6 +1163,8 @@ impl GraphQLTypeDefiniton {
             .as_ref()
             .map(|description| quote!( .description(#description) ));
 
+        let directives = self.directives.iter();
+
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
 
@@ -889,6 +1204,7 @@ impl GraphQLTypeDefiniton {
         let resolve_field_async = {
             let resolve_matches_async = self.fields.iter().map(|field| {
                 let name = &field.name;
+                let args_code = &field.args_resolver_code;
                 let code = &field.resolver_code;
                 let _type = if field.is_type_inferred {
                     quote!()
@@ -901,6 +1217,7 @@ impl GraphQLTypeDefiniton {
                     quote!(
                         #name => {
                             let f = async move {
+                                #args_code
                                 let res #_type = async move { #code }.await;
 
                                 let inner_res = ::juniper::IntoResolvable::into(
@@ -951,11 +1268,14 @@ impl GraphQLTypeDefiniton {
 
                     quote!(
                         #name => {
-                            let res #_type = (||{ #code })();
-                            let res2 = ::juniper::IntoResolvable::into(
-                                res,
-                                executor.context()
-                            );
+                            let res2 = (|| -> ::juniper::FieldResult<_, #scalar> {
+                                #args_code
+                                let res #_type = (||{ #code })();
+                                ::juniper::IntoResolvable::into(
+                                    res,
+                                    executor.context()
+                                )
+                            })();
                             #inner
                         },
                     )
@@ -1071,7 +1391,8 @@ impl GraphQLTypeDefiniton {
                     ];
                     let meta = registry.build_object_type::<#ty>(info, &fields)
                         #description
-                        #interfaces;
+                        #interfaces
+                        #( .directive(#directives) )*;
                     meta.into_meta()
                 }
         }
@@ -1150,18 +1471,21 @@ impl GraphQLTypeDefiniton {
                     Some(value) => quote!( .description( #value ) ),
                     None => quote!(),
                 };
+                let directives = arg.directives.iter();
 
                 match arg.default.as_ref() {
                     Some(value) => quote!(
                         .argument(
                             registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
                                 #description
+                                #( .directive(#directives) )*
                         )
                     ),
                     None => quote!(
                         .argument(
                             registry.arg::<#arg_type>(#arg_name, info)
                                 #description
+                                #( .directive(#directives) )*
                         )
                     ),
                 }
@@ -1195,12 +1519,15 @@ impl GraphQLTypeDefiniton {
                 panic!("Synchronous resolvers are not supported. Specify that this function is async: 'async fn foo()'")
             }
 
+            let directives = field.directives.iter();
+
             quote! {
                 registry
                     .field_convert::<#_type, _, Self::Context>(#field_name, info)
                     #(#args)*
                     #description
                     #deprecation
+                    #( .directive(#directives) )*
             }
         });
 
@@ -1209,6 +1536,8 @@ impl GraphQLTypeDefiniton {
             .as_ref()
             .map(|description| quote!( .description(#description) ));
 
+        let directives = self.directives.iter();
+
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
 
@@ -1259,6 +1588,7 @@ impl GraphQLTypeDefiniton {
         let resolve_matches_async = self.fields.iter().filter(|field| field.is_async).map(
             |field| {
                 let name = &field.name;
+                let args_code = &field.args_resolver_code;
                 let code = &field.resolver_code;
 
                 let _type;
@@ -1271,6 +1601,7 @@ impl GraphQLTypeDefiniton {
                 quote!(
                     #name => {
                         ::juniper::futures::FutureExt::boxed(async move {
+                            #args_code
                             let res #_type = async { #code }.await;
                             let res = ::juniper::IntoFieldResult::<_, #scalar>::into_result(res)?;
                             let executor= executor.as_owned_executor();
@@ -1352,7 +1683,8 @@ impl GraphQLTypeDefiniton {
                         ];
                         let meta = registry.build_object_type::<#ty>(info, &fields)
                             #description
-                            #interfaces;
+                            #interfaces
+                            #( .directive(#directives) )*;
                         meta.into_meta()
                     }
             }
@@ -1461,6 +1793,8 @@ impl GraphQLTypeDefiniton {
             .as_ref()
             .map(|description| quote!( .description(#description) ));
 
+        let directives = self.directives.iter();
+
         let values = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
 
@@ -1479,11 +1813,14 @@ impl GraphQLTypeDefiniton {
                 })
                 .unwrap_or_else(|| quote!(::juniper::meta::DeprecationStatus::Current));
 
+            let directives = variant.directives.iter();
+
             quote!(
                 ::juniper::meta::EnumValue {
                     name: #variant_name.to_string(),
                     description: #descr,
                     deprecation_status: #depr,
+                    directives: vec![ #( #directives ),* ],
                 },
             )
         });
@@ -1502,7 +1839,7 @@ impl GraphQLTypeDefiniton {
             let resolver_code = &variant.resolver_code;
 
             quote!(
-                Some(#variant_name) => Some(#resolver_code),
+                Some(#variant_name) => Ok(#resolver_code),
             )
         });
 
@@ -1580,6 +1917,7 @@ impl GraphQLTypeDefiniton {
                         #( #values )*
                     ])
                     #description
+                    #( .directive(#directives) )*
                     .into_meta()
                 }
             }
@@ -1610,13 +1948,15 @@ impl GraphQLTypeDefiniton {
             impl#impl_generics ::juniper::FromInputValue<#scalar> for #ty
                 #where_clause
             {
-                fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Option<#ty>
+                type Error = ::std::string::String;
+
+                fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Result<#ty, Self::Error>
                 {
                     match v.as_enum_value().or_else(|| {
                         v.as_string_value()
                     }) {
                         #( #from_inputs )*
-                        _ => None,
+                        _ => Err(::std::format!("Unknown value for enum `{}`: {}", #name, v)),
                     }
                 }
             }
@@ -1686,6 +2026,8 @@ impl GraphQLTypeDefiniton {
                     None => quote!(),
                 };
 
+                let directives = field.directives.iter();
+
                 let create_meta_field = match field.default {
                     Some(ref def) => {
                         quote! {
@@ -1704,6 +2046,7 @@ impl GraphQLTypeDefiniton {
                         #create_meta_field
                         #description
                         #deprecation
+                        #( .directive(#directives) )*
                     },
                 )
             })
@@ -1731,7 +2074,8 @@ impl GraphQLTypeDefiniton {
                         // TODO: investigate the unwraps here, they seem dangerous!
                         match obj.get(#field_name) {
                             #from_input_default
-                            Some(ref v) => ::juniper::FromInputValue::from_input_value(v)?,
+                            Some(ref v) => ::juniper::FromInputValue::<#scalar>::from_input_value(v)
+                                .map_err(::juniper::IntoFieldError::<#scalar>::into_field_error)?,
                             None => ::juniper::FromInputValue::<#scalar>::from_implicit_null(),
                         }
                     },
@@ -1757,6 +2101,8 @@ impl GraphQLTypeDefiniton {
             .as_ref()
             .map(|description| quote!( .description(#description) ));
 
+        let directives = self.directives.iter();
+
         // Preserve the original type_generics before modification,
         // since alteration makes them invalid if self.generic_scalar
         // is specified.
@@ -1829,6 +2175,7 @@ impl GraphQLTypeDefiniton {
                     ];
                     registry.build_input_object_type::<#ty>(&(), fields)
                     #description
+                    #( .directive(#directives) )*
                     .into_meta()
                 }
             }
@@ -1847,10 +2194,17 @@ impl GraphQLTypeDefiniton {
             impl#impl_generics ::juniper::FromInputValue<#scalar> for #ty #type_generics_tokens
                 #where_clause
             {
-                fn from_input_value(value: &::juniper::InputValue<#scalar>) -> Option<Self>
+                type Error = ::juniper::FieldError<#scalar>;
+
+                fn from_input_value(value: &::juniper::InputValue<#scalar>) -> Result<Self, Self::Error>
                 {
-                    let obj = value.to_object_value()?;
-                    Some(#ty {
+                    let obj = value.to_object_value().ok_or_else(|| {
+                        ::juniper::FieldError::new(
+                            ::std::format!("Expected input object `{}`, found: {}", #name, value),
+                            ::juniper::Value::null(),
+                        )
+                    })?;
+                    Ok(#ty {
                         #( #from_inputs )*
                     })
                 }
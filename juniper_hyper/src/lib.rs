@@ -1,17 +1,23 @@
 #![doc(html_root_url = "https://docs.rs/juniper_hyper/0.2.0")]
 
-use std::{error::Error, fmt, string::FromUtf8Error, sync::Arc};
+pub mod service;
+
+use std::{error::Error as StdError, fmt, sync::Arc};
 
 use hyper::{
-    header::{self, HeaderValue},
-    Body, Method, Request, Response, StatusCode,
+    body::{Buf as _, HttpBody},
+    header::{self, HeaderName, HeaderValue},
+    http::request::Parts,
+    Body, Request, Response, StatusCode,
 };
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
-    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
+    http::{
+        graphiql::GraphiQLConfig,
+        handler::{self, HandlerConfig, HttpRequest, HttpResponse, MediaType},
+        playground::PlaygroundConfig,
+    },
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode, ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use url::form_urlencoded;
 
 pub async fn graphql_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
@@ -28,10 +34,42 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    match parse_req(req).await {
-        Ok(req) => execute_request_sync(root_node, context, req).await,
-        Err(resp) => resp,
-    }
+    graphql_sync_with_config(root_node, context, req, &HandlerConfig::default()).await
+}
+
+/// Executes the GraphQL request synchronously, like [`graphql_sync()`], after checking it against
+/// the given [`HandlerConfig`].
+///
+/// The body is read up to the maximum size of the `config`, if any.
+pub async fn graphql_sync_with_config<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
+    req: Request<Body>,
+    config: &HandlerConfig,
+) -> Response<Body>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let (parts, body) = req.into_parts();
+    let body = match read_body(&parts, body, config).await {
+        Ok(body) => body,
+        Err(resp) => return resp,
+    };
+    let headers = headers(&parts);
+    let resp = handler::handle_sync_with_config(
+        http_request(&parts, &headers, &body),
+        &*root_node,
+        &*context,
+        config,
+    );
+    into_response(resp)
 }
 
 pub async fn graphql<CtxT, QueryT, MutationT, SubscriptionT, S>(
@@ -49,73 +87,43 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    match parse_req(req).await {
-        Ok(req) => execute_request(root_node, context, req).await,
-        Err(resp) => resp,
-    }
+    graphql_with_config(root_node, context, req, &HandlerConfig::default()).await
 }
 
-async fn parse_req<S: ScalarValue>(
-    req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
-    match *req.method() {
-        Method::GET => parse_get_req(req),
-        Method::POST => {
-            let content_type = req
-                .headers()
-                .get(header::CONTENT_TYPE)
-                .map(HeaderValue::to_str);
-            match content_type {
-                Some(Ok("application/json")) => parse_post_json_req(req.into_body()).await,
-                Some(Ok("application/graphql")) => parse_post_graphql_req(req.into_body()).await,
-                _ => return Err(new_response(StatusCode::BAD_REQUEST)),
-            }
-        }
-        _ => return Err(new_response(StatusCode::METHOD_NOT_ALLOWED)),
-    }
-    .map_err(render_error)
-}
-
-fn parse_get_req<S: ScalarValue>(
+/// Executes the GraphQL request, like [`graphql()`], after checking it against the given
+/// [`HandlerConfig`].
+///
+/// The body is read up to the maximum size of the `config`, if any.
+pub async fn graphql_with_config<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context: Arc<CtxT>,
     req: Request<Body>,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    req.uri()
-        .query()
-        .map(|q| gql_request_from_get(q).map(GraphQLBatchRequest::Single))
-        .unwrap_or_else(|| {
-            Err(GraphQLRequestError::Invalid(
-                "'query' parameter is missing".to_string(),
-            ))
-        })
-}
-
-async fn parse_post_json_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let input = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
-
-    serde_json::from_str::<GraphQLBatchRequest<S>>(&input)
-        .map_err(GraphQLRequestError::BodyJSONError)
-}
-
-async fn parse_post_graphql_req<S: ScalarValue>(
-    body: Body,
-) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
-    let chunk = hyper::body::to_bytes(body)
-        .await
-        .map_err(GraphQLRequestError::BodyHyper)?;
-
-    let query = String::from_utf8(chunk.iter().cloned().collect())
-        .map_err(GraphQLRequestError::BodyUtf8)?;
-
-    Ok(GraphQLBatchRequest::Single(GraphQLRequest::new(
-        query, None, None,
-    )))
+    config: &HandlerConfig,
+) -> Response<Body>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    let (parts, body) = req.into_parts();
+    let body = match read_body(&parts, body, config).await {
+        Ok(body) => body,
+        Err(resp) => return resp,
+    };
+    let headers = headers(&parts);
+    let resp = handler::handle_with_config(
+        http_request(&parts, &headers, &body),
+        &*root_node,
+        &*context,
+        config,
+    )
+    .await;
+    into_response(resp)
 }
 
 pub async fn graphiql(
@@ -131,6 +139,12 @@ pub async fn graphiql(
     resp
 }
 
+pub async fn graphiql_with_config(config: &GraphiQLConfig) -> Response<Body> {
+    let mut resp = new_html_response(StatusCode::OK);
+    *resp.body_mut() = Body::from(juniper::http::graphiql::graphiql_source_with_config(config));
+    resp
+}
+
 pub async fn playground(
     graphql_endpoint: &str,
     subscriptions_endpoint: Option<&str>,
@@ -143,121 +157,117 @@ pub async fn playground(
     resp
 }
 
-fn render_error(err: GraphQLRequestError) -> Response<Body> {
-    let message = format!("{}", err);
-    let mut resp = new_response(StatusCode::BAD_REQUEST);
-    *resp.body_mut() = Body::from(message);
+pub async fn playground_with_config(config: &PlaygroundConfig) -> Response<Body> {
+    let mut resp = new_html_response(StatusCode::OK);
+    *resp.body_mut() = Body::from(juniper::http::playground::playground_source_with_config(
+        config,
+    ));
     resp
 }
 
-async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
-    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
-    context: Arc<CtxT>,
-    request: GraphQLBatchRequest<S>,
-) -> Response<Body>
-where
-    QueryT: GraphQLType<S, Context = CtxT>,
-    QueryT::TypeInfo: Sync,
-    MutationT: GraphQLType<S, Context = CtxT>,
-    MutationT::TypeInfo: Sync,
-    SubscriptionT: GraphQLType<S, Context = CtxT>,
-    SubscriptionT::TypeInfo: Sync,
-    CtxT: Sync,
-    S: ScalarValue + Send + Sync,
-{
-    let res = request.execute_sync(&*root_node, &context);
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
+/// Replies with the [bundled asset](juniper::http::assets) of the given name, or with a
+/// `404 Not Found` if there is no such asset.
+///
+/// Mount it under the assets base URL given to the GraphiQL and GraphQL Playground pages, passing
+/// the rest of the request path as the asset name.
+#[cfg(feature = "bundled-assets")]
+pub async fn asset(name: &str) -> Response<Body> {
+    let asset = match juniper::http::assets::find(name) {
+        Some(asset) => asset,
+        None => return new_response(StatusCode::NOT_FOUND),
     };
-    let mut resp = new_response(code);
+    let mut resp = new_response(StatusCode::OK);
     resp.headers_mut().insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        HeaderValue::from_static(asset.content_type),
     );
-    *resp.body_mut() = body;
-    resp
-}
-
-async fn execute_request<CtxT, QueryT, MutationT, SubscriptionT, S>(
-    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
-    context: Arc<CtxT>,
-    request: GraphQLBatchRequest<S>,
-) -> Response<Body>
-where
-    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
-    QueryT::TypeInfo: Sync,
-    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
-    MutationT::TypeInfo: Sync,
-    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
-    SubscriptionT::TypeInfo: Sync,
-    CtxT: Sync,
-    S: ScalarValue + Send + Sync,
-{
-    let res = request.execute(&*root_node, &context).await;
-    let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
-    let mut resp = new_response(code);
     resp.headers_mut().insert(
-        header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(juniper::http::assets::CACHE_CONTROL),
     );
-    *resp.body_mut() = body;
+    *resp.body_mut() = Body::from(asset.contents());
     resp
 }
 
-fn gql_request_from_get<S>(input: &str) -> Result<JuniperGraphQLRequest<S>, GraphQLRequestError>
+fn http_request<'a>(
+    parts: &'a Parts,
+    headers: &'a [(&'a str, &'a str)],
+    body: &'a [u8],
+) -> HttpRequest<'a> {
+    let header = |name| parts.headers.get(name).and_then(|v| v.to_str().ok());
+    HttpRequest {
+        method: parts.method.as_str(),
+        query_string: parts.uri.query(),
+        content_type: header(header::CONTENT_TYPE),
+        accept: header(header::ACCEPT),
+        headers,
+        body,
+    }
+}
+
+fn headers(parts: &Parts) -> Vec<(&str, &str)> {
+    parts
+        .headers
+        .iter()
+        .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+        .collect()
+}
+
+/// Reads the whole body of a request, failing as soon as it gets larger than the maximum size of
+/// the `config`, if any.
+async fn read_body<B>(
+    parts: &Parts,
+    body: B,
+    config: &HandlerConfig,
+) -> Result<Vec<u8>, Response<Body>>
 where
-    S: ScalarValue,
+    B: HttpBody,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
 {
-    let mut query = None;
-    let operation_name = None;
-    let mut variables = None;
-    for (key, value) in form_urlencoded::parse(input.as_bytes()).into_owned() {
-        match key.as_ref() {
-            "query" => {
-                if query.is_some() {
-                    return Err(invalid_err("query"));
-                }
-                query = Some(value)
-            }
-            "operationName" => {
-                if operation_name.is_some() {
-                    return Err(invalid_err("operationName"));
-                }
-            }
-            "variables" => {
-                if variables.is_some() {
-                    return Err(invalid_err("variables"));
-                }
-                match serde_json::from_str::<InputValue<S>>(&value)
-                    .map_err(GraphQLRequestError::Variables)
-                {
-                    Ok(parsed_variables) => variables = Some(parsed_variables),
-                    Err(e) => return Err(e),
-                }
-            }
-            _ => continue,
+    let content_length = parts
+        .headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        config
+            .check_body_size(content_length)
+            .map_err(into_response)?;
+    }
+
+    futures::pin_mut!(body);
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let mut chunk = chunk.map_err(|e| render_body_error(e.into()))?;
+        config
+            .check_body_size(buf.len() + chunk.remaining())
+            .map_err(into_response)?;
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            buf.extend_from_slice(bytes);
+            chunk.advance(len);
         }
     }
-    match query {
-        Some(query) => Ok(JuniperGraphQLRequest::new(query, operation_name, variables)),
-        None => Err(GraphQLRequestError::Invalid(
-            "'query' parameter is missing".to_string(),
-        )),
+    Ok(buf)
+}
+
+fn into_response(resp: HttpResponse) -> Response<Body> {
+    let mut r = new_response(StatusCode::from_u16(resp.status).unwrap());
+    for (name, value) in resp.headers {
+        r.headers_mut().insert(
+            HeaderName::from_bytes(name.as_bytes()).unwrap(),
+            HeaderValue::from_static(value),
+        );
     }
+    *r.body_mut() = Body::from(resp.body);
+    r
 }
 
-fn invalid_err(parameter_name: &str) -> GraphQLRequestError {
-    GraphQLRequestError::Invalid(format!(
-        "'{}' parameter is specified multiple times",
-        parameter_name
+fn render_body_error(err: impl fmt::Display) -> Response<Body> {
+    into_response(HttpResponse::error(
+        400,
+        MediaType::Json,
+        &format!("Failed to read the request body: {}", err),
     ))
 }
 
@@ -276,39 +286,6 @@ fn new_html_response(code: StatusCode) -> Response<Body> {
     resp
 }
 
-#[derive(Debug)]
-enum GraphQLRequestError {
-    BodyHyper(hyper::Error),
-    BodyUtf8(FromUtf8Error),
-    BodyJSONError(SerdeError),
-    Variables(SerdeError),
-    Invalid(String),
-}
-
-impl fmt::Display for GraphQLRequestError {
-    fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            GraphQLRequestError::BodyHyper(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyUtf8(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::BodyJSONError(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Variables(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLRequestError::Invalid(ref err) => fmt::Display::fmt(err, &mut f),
-        }
-    }
-}
-
-impl Error for GraphQLRequestError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match *self {
-            GraphQLRequestError::BodyHyper(ref err) => Some(err),
-            GraphQLRequestError::BodyUtf8(ref err) => Some(err),
-            GraphQLRequestError::BodyJSONError(ref err) => Some(err),
-            GraphQLRequestError::Variables(ref err) => Some(err),
-            GraphQLRequestError::Invalid(_) => None,
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use hyper::{
@@ -452,4 +429,70 @@ mod tests {
     async fn test_sync_hyper_integration() {
         run_hyper_integration(true).await
     }
+
+    #[tokio::test]
+    async fn checks_requests_against_config() {
+        use hyper::Request;
+        use juniper::http::handler::HandlerConfig;
+
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let ctx = Arc::new(Database::new());
+        let config = HandlerConfig::new()
+            .with_csrf_prevention()
+            .with_max_body_size(32);
+        let send = |req| super::graphql_with_config(root_node.clone(), ctx.clone(), req, &config);
+
+        let req = Request::get("/graphql?query=%7B+hero+%7B+name+%7D+%7D")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(req).await.status(), StatusCode::BAD_REQUEST);
+
+        let req = Request::get("/graphql?query=%7B+hero+%7B+name+%7D+%7D")
+            .header("apollo-require-preflight", "true")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(req).await.status(), StatusCode::OK);
+
+        let req = Request::post("/graphql")
+            .header(hyper::header::CONTENT_TYPE, "application/graphql")
+            .body(Body::from("{ hero { name } }"))
+            .unwrap();
+        assert_eq!(send(req).await.status(), StatusCode::OK);
+
+        let req = Request::post("/graphql")
+            .header(hyper::header::CONTENT_TYPE, "application/graphql")
+            .body(Body::from("{ hero { name friends { name } } }"))
+            .unwrap();
+        assert_eq!(send(req).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let req = Request::post("/graphql")
+            .header(hyper::header::CONTENT_TYPE, "application/graphql")
+            .header(hyper::header::CONTENT_LENGTH, "1024")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(req).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[cfg(feature = "bundled-assets")]
+    #[tokio::test]
+    async fn serves_bundled_assets() {
+        use juniper::http::assets::{CACHE_CONTROL, GRAPHIQL_2_JS};
+
+        let resp = super::asset(GRAPHIQL_2_JS.name).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()[hyper::header::CONTENT_TYPE],
+            GRAPHIQL_2_JS.content_type,
+        );
+        assert_eq!(resp.headers()[hyper::header::CACHE_CONTROL], CACHE_CONTROL);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(body, GRAPHIQL_2_JS.contents());
+
+        let resp = super::asset("../Cargo.toml").await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,497 @@
+//! [`tower`] services executing GraphQL requests, composable with any `tower` middleware and
+//! servable by any `tower`-compatible server.
+//!
+//! [`tower`]: https://docs.rs/tower
+
+use std::{
+    convert::Infallible,
+    error::Error as StdError,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use hyper::{body::HttpBody, http::request::Parts, Body, Request, Response};
+use juniper::{
+    http::handler::{self, HandlerConfig},
+    GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode, ScalarValue,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{headers, http_request, into_response, read_body};
+
+/// Boxed future returned by the services of this module.
+pub type ResponseFuture<E> = Pin<Box<dyn Future<Output = Result<Response<Body>, E>> + Send>>;
+
+/// [`Service`] executing the GraphQL requests it's called with, as described in
+/// [`juniper::http::handler`], regardless of their path.
+///
+/// A context is built for each request by calling the context factory with the parts of the
+/// request, which allows authenticating it from its headers, for example.
+///
+/// ```
+/// # use std::{sync::Arc, time::Duration};
+/// #
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_hyper::service::GraphQLService;
+/// # use tower::ServiceBuilder;
+/// #
+/// let root_node = Arc::new(RootNode::new(
+///     Query,
+///     EmptyMutation::<Database>::new(),
+///     EmptySubscription::<Database>::new(),
+/// ));
+///
+/// let service = ServiceBuilder::new()
+///     .concurrency_limit(64)
+///     .timeout(Duration::from_secs(10))
+///     .service(GraphQLService::new(root_node, |_: &_| Database::new()));
+/// ```
+pub struct GraphQLService<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context_factory: Arc<F>,
+    config: Arc<HandlerConfig>,
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> GraphQLService<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+    F: Fn(&Parts) -> QueryT::Context,
+{
+    /// Creates a service executing requests against the given schema, with the contexts returned
+    /// by `context_factory`.
+    pub fn new(
+        root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+        context_factory: F,
+    ) -> Self {
+        Self {
+            root_node,
+            context_factory: Arc::new(context_factory),
+            config: Arc::default(),
+        }
+    }
+
+    /// Checks the requests against the given [`HandlerConfig`] before executing them.
+    pub fn with_config(mut self, config: HandlerConfig) -> Self {
+        self.config = Arc::new(config);
+        self
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> Clone
+    for GraphQLService<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root_node: self.root_node.clone(),
+            context_factory: self.context_factory.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> fmt::Debug
+    for GraphQLService<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLService").finish()
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F, B> Service<Request<B>>
+    for GraphQLService<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLTypeAsync<S> + Send + Sync + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    QueryT::Context: Send + Sync + 'static,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context> + Send + Sync + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context> + Send + Sync + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    S: ScalarValue + Send + Sync + 'static,
+    F: Fn(&Parts) -> QueryT::Context + Send + Sync + 'static,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = ResponseFuture<Infallible>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let root_node = self.root_node.clone();
+        let context_factory = self.context_factory.clone();
+        let config = self.config.clone();
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let context = context_factory(&parts);
+            let body = match read_body(&parts, body, &config).await {
+                Ok(body) => body,
+                Err(resp) => return Ok(resp),
+            };
+            let headers = headers(&parts);
+            let resp = handler::handle_with_config(
+                http_request(&parts, &headers, &body),
+                &*root_node,
+                &context,
+                &config,
+            )
+            .await;
+            Ok(into_response(resp))
+        })
+    }
+}
+
+/// [`Layer`] serving GraphQL requests on a single path with a [`GraphQLService`], and forwarding
+/// requests on any other path to the inner service.
+///
+/// ```
+/// # use std::{convert::Infallible, sync::Arc};
+/// #
+/// # use hyper::{Body, Request, Response, StatusCode};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_hyper::service::{GraphQLLayer, GraphQLService};
+/// # use tower::{service_fn, ServiceBuilder};
+/// #
+/// let root_node = Arc::new(RootNode::new(
+///     Query,
+///     EmptyMutation::<Database>::new(),
+///     EmptySubscription::<Database>::new(),
+/// ));
+/// let graphql = GraphQLService::new(root_node, |_: &_| Database::new());
+///
+/// let service = ServiceBuilder::new()
+///     .layer(GraphQLLayer::new("/graphql", graphql))
+///     .service(service_fn(|_: Request<Body>| async {
+///         let mut resp = Response::new(Body::empty());
+///         *resp.status_mut() = StatusCode::NOT_FOUND;
+///         Ok::<_, Infallible>(resp)
+///     }));
+/// ```
+pub struct GraphQLLayer<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    path: Arc<str>,
+    service: GraphQLService<QueryT, MutationT, SubscriptionT, S, F>,
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> GraphQLLayer<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    /// Creates a layer serving GraphQL requests on `path` with the given service.
+    pub fn new(
+        path: impl Into<String>,
+        service: GraphQLService<QueryT, MutationT, SubscriptionT, S, F>,
+    ) -> Self {
+        Self {
+            path: path.into().into(),
+            service,
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> Clone
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, S, F> fmt::Debug
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLLayer")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<Inner, QueryT, MutationT, SubscriptionT, S, F> Layer<Inner>
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    type Service = GraphQLEndpoint<Inner, QueryT, MutationT, SubscriptionT, S, F>;
+
+    fn layer(&self, inner: Inner) -> Self::Service {
+        GraphQLEndpoint {
+            inner,
+            path: self.path.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+/// [`Service`] built by a [`GraphQLLayer`].
+pub struct GraphQLEndpoint<Inner, QueryT, MutationT, SubscriptionT, S, F>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    inner: Inner,
+    path: Arc<str>,
+    service: GraphQLService<QueryT, MutationT, SubscriptionT, S, F>,
+}
+
+impl<Inner, QueryT, MutationT, SubscriptionT, S, F> Clone
+    for GraphQLEndpoint<Inner, QueryT, MutationT, SubscriptionT, S, F>
+where
+    Inner: Clone,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            path: self.path.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<Inner, QueryT, MutationT, SubscriptionT, S, F> fmt::Debug
+    for GraphQLEndpoint<Inner, QueryT, MutationT, SubscriptionT, S, F>
+where
+    Inner: fmt::Debug,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLEndpoint")
+            .field("inner", &self.inner)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<Inner, QueryT, MutationT, SubscriptionT, S, F, B> Service<Request<B>>
+    for GraphQLEndpoint<Inner, QueryT, MutationT, SubscriptionT, S, F>
+where
+    Inner: Service<Request<B>, Response = Response<Body>>,
+    Inner::Future: Send + 'static,
+    GraphQLService<QueryT, MutationT, SubscriptionT, S, F>:
+        Service<Request<B>, Response = Response<Body>, Error = Infallible>,
+    <GraphQLService<QueryT, MutationT, SubscriptionT, S, F> as Service<Request<B>>>::Future:
+        Send + 'static,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue,
+{
+    type Response = Response<Body>;
+    type Error = Inner::Error;
+    type Future = ResponseFuture<Inner::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        if req.uri().path() == &*self.path {
+            let resp = self.service.call(req);
+            Box::pin(async move {
+                match resp.await {
+                    Ok(resp) => Ok(resp),
+                    Err(e) => match e {},
+                }
+            })
+        } else {
+            Box::pin(self.inner.call(req))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    use hyper::{header, http::request::Parts, Body, Request, Response, StatusCode};
+    use juniper::{
+        http::tests as http_tests,
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+    use tower::{service_fn, ServiceBuilder, ServiceExt as _};
+
+    use super::{GraphQLLayer, GraphQLService};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    fn root_node() -> Arc<Schema> {
+        Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ))
+    }
+
+    async fn into_test_response(resp: Response<Body>) -> http_tests::TestResponse {
+        let status_code = resp.status().as_u16().into();
+        let content_type = resp
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map(|ct| ct.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        http_tests::TestResponse {
+            status_code,
+            body: Some(String::from_utf8(body.to_vec()).unwrap()),
+            content_type,
+        }
+    }
+
+    struct TestServiceIntegration;
+
+    impl TestServiceIntegration {
+        fn make_request(&self, req: Request<Body>) -> http_tests::TestResponse {
+            tokio::runtime::Runtime::new().unwrap().block_on(async {
+                let service = GraphQLService::new(root_node(), |_: &_| Database::new());
+                into_test_response(service.oneshot(req).await.unwrap()).await
+            })
+        }
+
+        fn post(&self, url: &str, content_type: &str, body: &str) -> http_tests::TestResponse {
+            self.make_request(
+                Request::post(url)
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body.to_owned()))
+                    .unwrap(),
+            )
+        }
+    }
+
+    impl http_tests::HttpIntegration for TestServiceIntegration {
+        fn get(&self, url: &str) -> http_tests::TestResponse {
+            self.make_request(Request::get(url).body(Body::empty()).unwrap())
+        }
+
+        fn post_json(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.post(url, "application/json", body)
+        }
+
+        fn post_graphql(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.post(url, "application/graphql", body)
+        }
+    }
+
+    #[test]
+    fn test_service_integration() {
+        http_tests::run_http_test_suite(&TestServiceIntegration);
+    }
+
+    #[tokio::test]
+    async fn builds_context_per_request() {
+        let contexts = Arc::new(AtomicUsize::new(0));
+        let counter = contexts.clone();
+        let service = GraphQLService::new(root_node(), move |parts: &Parts| {
+            assert_eq!(parts.headers[header::AUTHORIZATION], "Bearer token");
+            counter.fetch_add(1, Ordering::SeqCst);
+            Database::new()
+        });
+
+        for _ in 0..2 {
+            let req = Request::get("/?query=%7Bhero%7Bname%7D%7D")
+                .header(header::AUTHORIZATION, "Bearer token")
+                .body(Body::empty())
+                .unwrap();
+            let resp = service.clone().oneshot(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+
+        assert_eq!(contexts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn layer_forwards_other_paths() {
+        let graphql = GraphQLService::new(root_node(), |_: &_| Database::new());
+        let service = ServiceBuilder::new()
+            .layer(GraphQLLayer::new("/graphql", graphql))
+            .service(service_fn(|_: Request<Body>| async {
+                let mut resp = Response::new(Body::from("fallback"));
+                *resp.status_mut() = StatusCode::NOT_FOUND;
+                Ok::<_, Infallible>(resp)
+            }));
+
+        let req = Request::get("/graphql?query=%7Bhero%7Bname%7D%7D")
+            .body(Body::empty())
+            .unwrap();
+        let resp = into_test_response(service.clone().oneshot(req).await.unwrap()).await;
+        assert_eq!(resp.status_code, 200);
+        assert_eq!(resp.body.unwrap(), r#"{"data":{"hero":{"name":"R2-D2"}}}"#,);
+
+        let req = Request::get("/other").body(Body::empty()).unwrap();
+        let resp = into_test_response(service.oneshot(req).await.unwrap()).await;
+        assert_eq!(resp.status_code, 404);
+        assert_eq!(resp.body.unwrap(), "fallback");
+    }
+}
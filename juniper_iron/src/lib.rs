@@ -96,7 +96,11 @@ fn main() {
 See the the [`GraphQLHandler`][3] documentation for more information on what request methods are
 supported.
 
+Schemas with async resolvers are served with an [`AsyncGraphQLHandler`][4] instead, which takes the
+same arguments.
+
 [3]: ./struct.GraphQLHandler.html
+[4]: ./struct.AsyncGraphQLHandler.html
 [Iron]: https://github.com/iron/iron
 [Juniper]: https://github.com/graphql-rust/juniper
 [GraphQL]: http://graphql.org
@@ -106,22 +110,19 @@ supported.
 
 #![doc(html_root_url = "https://docs.rs/juniper_iron/0.3.0")]
 
-use std::{error::Error, fmt, io::Read, ops::Deref as _};
+use std::{error::Error, fmt, io::Read};
 
-use iron::{
-    headers::ContentType,
-    itry, method,
-    middleware::Handler,
-    mime::{Mime, TopLevel},
-    prelude::*,
-    status,
-};
+use futures::future::BoxFuture;
+use iron::{middleware::Handler, mime::Mime, prelude::*, status, typemap::Key, Headers};
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
+    http::{
+        graphiql::GraphiQLConfig,
+        handler::{self, HandlerConfig, HttpRequest, HttpResponse, MediaType},
+        playground::PlaygroundConfig,
+    },
+    DefaultScalarValue, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     ScalarValue,
 };
-use serde_json::error::Error as SerdeError;
-use urlencoded::{UrlDecodingError, UrlEncodedQuery};
 
 /// Handler that executes `GraphQL` queries in the given schema
 ///
@@ -133,6 +134,8 @@ use urlencoded::{UrlDecodingError, UrlEncodedQuery};
 /// this endpoint containing the field `"query"` and optionally `"variables"`.
 /// The variables should be a JSON object containing the variable to value
 /// mapping.
+///
+/// Requests are handled as described in [`juniper::http::handler`].
 pub struct GraphQLHandler<
     'a,
     CtxFactory,
@@ -151,50 +154,60 @@ pub struct GraphQLHandler<
 {
     context_factory: CtxFactory,
     root_node: RootNode<'a, Query, Mutation, Subscription, S>,
+    config: HandlerConfig,
+}
+
+/// Handler that executes `GraphQL` queries in the given schema, with async resolvers
+///
+/// Requests are handled like with [`GraphQLHandler`], except that they are executed with
+/// [`juniper::execute`](juniper::execute), so the schema may have async resolvers: as Iron
+/// handlers are synchronous, the request thread is blocked until the execution completes. The
+/// execution runs on a lightweight executor by default, which doesn't provide any reactor or
+/// timer, so resolvers depending on a specific runtime need it to be provided with
+/// [`AsyncGraphQLHandler::with_runtime`].
+pub struct AsyncGraphQLHandler<
+    'a,
+    CtxFactory,
+    Query,
+    Mutation,
+    Subscription,
+    CtxT,
+    S = DefaultScalarValue,
+> where
+    S: ScalarValue,
+    CtxFactory: Fn(&mut Request) -> IronResult<CtxT> + Send + Sync + 'static,
+    CtxT: 'static,
+    Query: GraphQLType<S, Context = CtxT> + Send + Sync + 'static,
+    Mutation: GraphQLType<S, Context = CtxT> + Send + Sync + 'static,
+    Subscription: GraphQLType<S, Context = CtxT> + Send + Sync + 'static,
+{
+    context_factory: CtxFactory,
+    root_node: RootNode<'a, Query, Mutation, Subscription, S>,
+    config: HandlerConfig,
+    block_on: Box<BlockOn>,
 }
 
+/// Function running a future to completion on a runtime, blocking the current thread.
+type BlockOn = dyn for<'f> Fn(BoxFuture<'f, HttpResponse>) -> HttpResponse + Send + Sync;
+
 /// Handler that renders `GraphiQL` - a graphical query editor interface
 pub struct GraphiQLHandler {
-    graphql_url: String,
-    subscription_url: Option<String>,
+    config: GraphiQLConfig,
 }
 
 /// Handler that renders `GraphQL Playground` - a graphical query editor interface
 pub struct PlaygroundHandler {
-    graphql_url: String,
-    subscription_url: Option<String>,
-}
-
-fn get_single_value<T>(mut values: Vec<T>) -> IronResult<T> {
-    if values.len() == 1 {
-        Ok(values.remove(0))
-    } else {
-        Err(GraphQLIronError::InvalidData("Duplicate URL query parameter").into())
-    }
+    config: PlaygroundConfig,
 }
 
-fn parse_url_param(params: Option<Vec<String>>) -> IronResult<Option<String>> {
-    if let Some(values) = params {
-        get_single_value(values).map(Some)
-    } else {
-        Ok(None)
-    }
-}
-
-fn parse_variable_param<S>(params: Option<Vec<String>>) -> IronResult<Option<InputValue<S>>>
-where
-    S: ScalarValue,
-{
-    if let Some(values) = params {
-        Ok(
-            serde_json::from_str::<InputValue<S>>(get_single_value(values)?.as_ref())
-                .map(Some)
-                .map_err(GraphQLIronError::Serde)?,
-        )
-    } else {
-        Ok(None)
-    }
-}
+/// Handler that serves the [bundled assets](juniper::http::assets) of `GraphiQL` and
+/// `GraphQL Playground`
+///
+/// The asset is named by the request path, so this handler should be mounted under the assets base
+/// URL given to the pages, e.g. with `mount.mount("/assets/", AssetsHandler)`.
+#[cfg(feature = "bundled-assets")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AssetsHandler;
 
 impl<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
     GraphQLHandler<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
@@ -221,59 +234,70 @@ where
         GraphQLHandler {
             context_factory,
             root_node: RootNode::new_with_scalar_value(query, mutation, subscription),
+            config: HandlerConfig::default(),
         }
     }
 
-    fn handle_get(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let url_query = req
-            .get_mut::<UrlEncodedQuery>()
-            .map_err(GraphQLIronError::Url)?;
-
-        let query = parse_url_param(url_query.remove("query"))?
-            .ok_or(GraphQLIronError::InvalidData("No query provided"))?;
-        let operation_name = parse_url_param(url_query.remove("operationName"))?;
-        let variables = parse_variable_param(url_query.remove("variables"))?;
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            query,
-            operation_name,
-            variables,
-        )))
+    /// Check the requests against the given configuration before executing them, for example
+    /// to limit the size of their bodies.
+    pub fn with_config(mut self, config: HandlerConfig) -> Self {
+        self.config = config;
+        self
     }
+}
 
-    fn handle_post_json(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
-
-        Ok(
-            serde_json::from_str::<GraphQLBatchRequest<S>>(payload.as_str())
-                .map_err(GraphQLIronError::Serde)?,
-        )
+impl<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
+    AsyncGraphQLHandler<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
+where
+    S: ScalarValue + Send + Sync + 'static,
+    CtxFactory: Fn(&mut Request) -> IronResult<CtxT> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    Query: GraphQLTypeAsync<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+{
+    /// Build a new GraphQL handler executing requests with async resolvers
+    ///
+    /// The context factory is the same as the one of [`GraphQLHandler::new`].
+    pub fn new(
+        context_factory: CtxFactory,
+        query: Query,
+        mutation: Mutation,
+        subscription: Subscription,
+    ) -> Self {
+        AsyncGraphQLHandler {
+            context_factory,
+            root_node: RootNode::new_with_scalar_value(query, mutation, subscription),
+            config: HandlerConfig::default(),
+            block_on: Box::new(|future| futures::executor::block_on(future)),
+        }
     }
 
-    fn handle_post_graphql(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
-        let mut payload = String::new();
-        itry!(req.body.read_to_string(&mut payload));
-
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            payload, None, None,
-        )))
+    /// Check the requests against the given configuration before executing them, for example
+    /// to limit the size of their bodies.
+    pub fn with_config(mut self, config: HandlerConfig) -> Self {
+        self.config = config;
+        self
     }
 
-    fn execute_sync(
-        &self,
-        context: &CtxT,
-        request: GraphQLBatchRequest<S>,
-    ) -> IronResult<Response> {
-        let response = request.execute_sync(&self.root_node, context);
-        let content_type = "application/json".parse::<Mime>().unwrap();
-        let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() {
-            status::Ok
-        } else {
-            status::BadRequest
-        };
-        Ok(Response::with((content_type, status, json)))
+    /// Execute the requests on a runtime, with the given function running a future to
+    /// completion on it, instead of the default executor.
+    ///
+    /// For example, with a [Tokio](https://tokio.rs) runtime, resolvers can use Tokio's I/O and
+    /// timers:
+    ///
+    /// ```ignore
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let handle = runtime.handle().clone();
+    /// let graphql_endpoint = AsyncGraphQLHandler::new(context_factory, Query, Mutation, Subscription)
+    ///     .with_runtime(move |future| handle.block_on(future));
+    /// ```
+    pub fn with_runtime<F>(mut self, block_on: F) -> Self
+    where
+        F: for<'f> Fn(BoxFuture<'f, HttpResponse>) -> HttpResponse + Send + Sync + 'static,
+    {
+        self.block_on = Box::new(block_on);
+        self
     }
 }
 
@@ -283,10 +307,17 @@ impl GraphiQLHandler {
     /// The provided URL should point to the URL of the attached `GraphQLHandler`. It can be
     /// relative, so a common value could be `"/graphql"`.
     pub fn new(graphql_url: &str, subscription_url: Option<&str>) -> GraphiQLHandler {
-        GraphiQLHandler {
-            graphql_url: graphql_url.to_owned(),
-            subscription_url: subscription_url.map(|s| s.to_owned()),
+        let mut config = GraphiQLConfig::new(graphql_url);
+        if let Some(url) = subscription_url {
+            config = config.with_subscriptions_endpoint_url(url);
         }
+        GraphiQLHandler::with_config(config)
+    }
+
+    /// Build a new GraphiQL handler rendering GraphiQL with the given configuration, for
+    /// example with default headers or an initial query.
+    pub fn with_config(config: GraphiQLConfig) -> GraphiQLHandler {
+        GraphiQLHandler { config }
     }
 }
 
@@ -296,10 +327,17 @@ impl PlaygroundHandler {
     /// The provided URL should point to the URL of the attached `GraphQLHandler`. It can be
     /// relative, so a common value could be `"/graphql"`.
     pub fn new(graphql_url: &str, subscription_url: Option<&str>) -> PlaygroundHandler {
-        PlaygroundHandler {
-            graphql_url: graphql_url.to_owned(),
-            subscription_url: subscription_url.map(|s| s.to_owned()),
+        let mut config = PlaygroundConfig::new(graphql_url);
+        if let Some(url) = subscription_url {
+            config = config.with_subscriptions_endpoint_url(url);
         }
+        PlaygroundHandler::with_config(config)
+    }
+
+    /// Build a new GraphQL Playground handler rendering GraphQL Playground with the given
+    /// configuration, for example with its settings.
+    pub fn with_config(config: PlaygroundConfig) -> PlaygroundHandler {
+        PlaygroundHandler { config }
     }
 }
 
@@ -314,26 +352,176 @@ where
     Subscription: GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
     'a: 'static,
 {
-    fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
         let context = (self.context_factory)(req)?;
 
-        let graphql_request = match req.method {
-            method::Get => self.handle_get(&mut req)?,
-            method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
-                Some(Mime(TopLevel::Application, sub_lvl, _)) => match sub_lvl.as_str() {
-                    "json" => self.handle_post_json(&mut req)?,
-                    "graphql" => self.handle_post_graphql(&mut req)?,
-                    _ => return Ok(Response::with(status::BadRequest)),
-                },
-                _ => return Ok(Response::with(status::BadRequest)),
-            },
-            _ => return Ok(Response::with(status::MethodNotAllowed)),
-        };
+        Ok(handle_request(req, &self.config, |request| {
+            handler::handle_sync_with_config(request, &self.root_node, &context, &self.config)
+        }))
+    }
+}
+
+impl<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S> Handler
+    for AsyncGraphQLHandler<'a, CtxFactory, Query, Mutation, Subscription, CtxT, S>
+where
+    S: ScalarValue + Sync + Send + 'static,
+    CtxFactory: Fn(&mut Request) -> IronResult<CtxT> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    Query: GraphQLTypeAsync<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    'a: 'static,
+{
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
+        let context = (self.context_factory)(req)?;
 
-        self.execute_sync(&context, graphql_request)
+        Ok(handle_request(req, &self.config, |request| {
+            (self.block_on)(Box::pin(handler::handle_with_config(
+                request,
+                &self.root_node,
+                &context,
+                &self.config,
+            )))
+        }))
     }
 }
 
+/// Returns the value of the `K` extension of the request, inserted by a previous middleware, for
+/// context factories to build the context from
+///
+/// For example, with an authentication middleware inserting the authenticated user:
+///
+/// ```
+/// # use std::sync::Arc;
+/// #
+/// # use iron::{prelude::*, typemap::Key};
+/// #
+/// # struct User;
+/// # struct Database;
+/// #
+/// struct Context {
+///     user: Arc<User>,
+///     db: Arc<Database>,
+/// }
+///
+/// struct CurrentUser;
+///
+/// impl Key for CurrentUser {
+///     type Value = Arc<User>;
+/// }
+///
+/// # let db = Arc::new(Database);
+/// let context_factory = move |req: &mut Request| -> IronResult<Context> {
+///     Ok(Context {
+///         user: juniper_iron::extension::<CurrentUser>(req)?,
+///         db: db.clone(),
+///     })
+/// };
+/// # let _ = &context_factory;
+/// ```
+///
+/// # Errors
+///
+/// An `IronError` with `500 Internal Server Error` if the request has no such extension, which
+/// means the middleware inserting it isn't linked before the GraphQL handler.
+pub fn extension<K>(req: &Request) -> IronResult<K::Value>
+where
+    K: Key,
+    K::Value: Clone,
+{
+    req.extensions.get::<K>().cloned().ok_or_else(|| {
+        IronError::new(
+            MissingExtension(std::any::type_name::<K>()),
+            status::InternalServerError,
+        )
+    })
+}
+
+/// Error of [`extension()`] when a request has no extension of the given key.
+#[derive(Debug)]
+pub struct MissingExtension(&'static str);
+
+impl fmt::Display for MissingExtension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The request has no `{}` extension", self.0)
+    }
+}
+
+impl Error for MissingExtension {}
+
+/// Reads the request, and handles it with the given function unless it can't be read.
+fn handle_request(
+    req: &mut Request,
+    config: &HandlerConfig,
+    handle: impl FnOnce(HttpRequest<'_>) -> HttpResponse,
+) -> Response {
+    let body = match read_body(req, config) {
+        Ok(body) => body,
+        Err(resp) => return into_response(resp),
+    };
+    let method = req.method.to_string();
+    let headers = req
+        .headers
+        .iter()
+        .filter_map(|h| Some((h.name(), raw_header(&req.headers, h.name())?)))
+        .collect::<Vec<_>>();
+    let request = HttpRequest {
+        method: &method,
+        query_string: req.url.query(),
+        content_type: raw_header(&req.headers, "Content-Type"),
+        accept: raw_header(&req.headers, "Accept"),
+        headers: &headers,
+        body: &body,
+    };
+
+    into_response(handle(request))
+}
+
+/// Reads the whole body of the request, failing as soon as it gets larger than the maximum size of
+/// the `config`, if any.
+fn read_body(req: &mut Request, config: &HandlerConfig) -> Result<Vec<u8>, HttpResponse> {
+    let content_length = raw_header(&req.headers, "Content-Length").and_then(|v| v.parse().ok());
+    if let Some(content_length) = content_length {
+        config.check_body_size(content_length)?;
+    }
+
+    let mut body = vec![];
+    let read = match config.max_body_size() {
+        Some(max_body_size) => req
+            .body
+            .by_ref()
+            .take(max_body_size as u64 + 1)
+            .read_to_end(&mut body),
+        None => req.body.read_to_end(&mut body),
+    };
+    if let Err(e) = read {
+        return Err(HttpResponse::error(
+            400,
+            MediaType::Json,
+            &format!("Failed to read the request body: {}", e),
+        ));
+    }
+    config.check_body_size(body.len())?;
+    Ok(body)
+}
+
+fn raw_header<'h>(headers: &'h Headers, name: &str) -> Option<&'h str> {
+    headers
+        .get_raw(name)
+        .and_then(|values| values.first())
+        .and_then(|value| std::str::from_utf8(value).ok())
+}
+
+fn into_response(resp: HttpResponse) -> Response {
+    let mut response = Response::with((status::Status::from_u16(resp.status), resp.body));
+    for (name, value) in resp.headers {
+        response
+            .headers
+            .set_raw(name, vec![value.as_bytes().to_vec()]);
+    }
+    response
+}
+
 impl Handler for GraphiQLHandler {
     fn handle(&self, _: &mut Request) -> IronResult<Response> {
         let content_type = "text/html; charset=utf-8".parse::<Mime>().unwrap();
@@ -341,10 +529,7 @@ impl Handler for GraphiQLHandler {
         Ok(Response::with((
             content_type,
             status::Ok,
-            juniper::http::graphiql::graphiql_source(
-                &self.graphql_url,
-                self.subscription_url.as_deref(),
-            ),
+            juniper::http::graphiql::graphiql_source_with_config(&self.config),
         )))
     }
 }
@@ -356,45 +541,28 @@ impl Handler for PlaygroundHandler {
         Ok(Response::with((
             content_type,
             status::Ok,
-            juniper::http::playground::playground_source(
-                &self.graphql_url,
-                self.subscription_url.as_deref(),
-            ),
+            juniper::http::playground::playground_source_with_config(&self.config),
         )))
     }
 }
 
-#[derive(Debug)]
-enum GraphQLIronError {
-    Serde(SerdeError),
-    Url(UrlDecodingError),
-    InvalidData(&'static str),
-}
-
-impl fmt::Display for GraphQLIronError {
-    fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::InvalidData(err) => fmt::Display::fmt(err, &mut f),
-        }
-    }
-}
-
-impl Error for GraphQLIronError {
-    fn cause(&self) -> Option<&dyn Error> {
-        match *self {
-            GraphQLIronError::Serde(ref err) => Some(err),
-            GraphQLIronError::Url(ref err) => Some(err),
-            GraphQLIronError::InvalidData(_) => None,
-        }
-    }
-}
+#[cfg(feature = "bundled-assets")]
+impl Handler for AssetsHandler {
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
+        let asset = match juniper::http::assets::find(&req.url.path().join("/")) {
+            Some(asset) => asset,
+            None => return Ok(Response::with(status::NotFound)),
+        };
 
-impl From<GraphQLIronError> for IronError {
-    fn from(err: GraphQLIronError) -> IronError {
-        let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
+        let mut response = Response::with((status::Ok, asset.contents().to_vec()));
+        response
+            .headers
+            .set_raw("Content-Type", vec![asset.content_type.as_bytes().to_vec()]);
+        response.headers.set_raw(
+            "Cache-Control",
+            vec![juniper::http::assets::CACHE_CONTROL.as_bytes().to_vec()],
+        );
+        Ok(response)
     }
 }
 
@@ -437,11 +605,13 @@ mod tests {
         )
     }
 
-    struct TestIronIntegration;
+    struct TestIronIntegration {
+        make_handler: fn() -> Box<dyn Handler>,
+    }
 
     impl http_tests::HttpIntegration for TestIronIntegration {
         fn get(&self, url: &str) -> http_tests::TestResponse {
-            request::get(&fixup_url(url), Headers::new(), &make_handler())
+            request::get(&fixup_url(url), Headers::new(), &(self.make_handler)())
                 .map(make_test_response)
                 .unwrap_or_else(make_test_error_response)
         }
@@ -449,7 +619,7 @@ mod tests {
         fn post_json(&self, url: &str, body: &str) -> http_tests::TestResponse {
             let mut headers = Headers::new();
             headers.set(ContentType::json());
-            request::post(&fixup_url(url), headers, body, &make_handler())
+            request::post(&fixup_url(url), headers, body, &(self.make_handler)())
                 .map(make_test_response)
                 .unwrap_or_else(make_test_error_response)
         }
@@ -461,7 +631,7 @@ mod tests {
                 SubLevel::Ext("graphql".into()),
                 vec![],
             )));
-            request::post(&fixup_url(url), headers, body, &make_handler())
+            request::post(&fixup_url(url), headers, body, &(self.make_handler)())
                 .map(make_test_response)
                 .unwrap_or_else(make_test_error_response)
         }
@@ -469,7 +639,16 @@ mod tests {
 
     #[test]
     fn test_iron_integration() {
-        let integration = TestIronIntegration;
+        let integration = TestIronIntegration { make_handler };
+
+        http_tests::run_http_test_suite(&integration);
+    }
+
+    #[test]
+    fn test_async_iron_integration() {
+        let integration = TestIronIntegration {
+            make_handler: make_async_handler,
+        };
 
         http_tests::run_http_test_suite(&integration);
     }
@@ -518,4 +697,164 @@ mod tests {
             EmptySubscription::<Database>::new(),
         ))
     }
+
+    #[test]
+    fn checks_requests_against_config() {
+        let handler = <GraphQLHandler<_, _, _, _, _, DefaultScalarValue>>::new(
+            context_factory,
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        )
+        .with_config(
+            HandlerConfig::new()
+                .with_csrf_prevention()
+                .with_max_body_size(32),
+        );
+        let url = fixup_url("/?query={hero{name}}");
+
+        let response = request::get(&url, Headers::new(), &handler).unwrap();
+        assert_eq!(response.status, Some(status::BadRequest));
+
+        let mut headers = Headers::new();
+        headers.set_raw("Apollo-Require-Preflight", vec![b"true".to_vec()]);
+        let response = request::get(&url, headers, &handler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+
+        let mut headers = Headers::new();
+        headers.set(ContentType::json());
+        let body = r#"{"query": "{ hero { name } }"}"#;
+        let response = request::post("http://localhost:3000/", headers, body, &handler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+
+        let mut headers = Headers::new();
+        headers.set(ContentType::json());
+        let body = r#"{"query": "{ hero { name friends { name } } }"}"#;
+        let response = request::post("http://localhost:3000/", headers, body, &handler).unwrap();
+        assert_eq!(response.status, Some(status::PayloadTooLarge));
+    }
+
+    fn make_async_handler() -> Box<dyn Handler> {
+        Box::new(
+            <AsyncGraphQLHandler<_, _, _, _, _, DefaultScalarValue>>::new(
+                context_factory,
+                Query,
+                EmptyMutation::<Database>::new(),
+                EmptySubscription::<Database>::new(),
+            ),
+        )
+    }
+
+    #[test]
+    fn executes_requests_on_the_given_runtime() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let runtime_calls = calls.clone();
+        let handler = <AsyncGraphQLHandler<_, _, _, _, _, DefaultScalarValue>>::new(
+            context_factory,
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        )
+        .with_runtime(move |future| {
+            runtime_calls.fetch_add(1, Ordering::SeqCst);
+            futures::executor::block_on(future)
+        });
+
+        let url = fixup_url("/?query={hero{name}}");
+        let response = request::get(&url, Headers::new(), &handler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(
+            response::extract_body_to_string(response),
+            r#"{"data":{"hero":{"name":"R2-D2"}}}"#,
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn builds_contexts_from_request_extensions() {
+        use iron::{typemap::Key, BeforeMiddleware, Chain};
+
+        struct Episode;
+
+        impl Key for Episode {
+            type Value = String;
+        }
+
+        struct InsertEpisode;
+
+        impl BeforeMiddleware for InsertEpisode {
+            fn before(&self, req: &mut Request) -> IronResult<()> {
+                req.extensions.insert::<Episode>("EMPIRE".into());
+                Ok(())
+            }
+        }
+
+        let make_handler = || {
+            <GraphQLHandler<_, _, _, _, _, DefaultScalarValue>>::new(
+                |req: &mut Request| {
+                    assert_eq!(extension::<Episode>(req)?, "EMPIRE");
+                    Ok(Database::new())
+                },
+                Query,
+                EmptyMutation::<Database>::new(),
+                EmptySubscription::<Database>::new(),
+            )
+        };
+        let url = fixup_url("/?query={hero{name}}");
+
+        let mut chain = Chain::new(make_handler());
+        chain.link_before(InsertEpisode);
+        let response = request::get(&url, Headers::new(), &chain).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+
+        let err = request::get(&url, Headers::new(), &make_handler()).unwrap_err();
+        assert_eq!(err.response.status, Some(status::InternalServerError));
+        assert!(err.to_string().contains("Episode"));
+    }
+
+    #[test]
+    fn renders_configured_graphiql() {
+        let handler =
+            GraphiQLHandler::with_config(GraphiQLConfig::new("/graphql").with_title("Star Wars"));
+        let response = request::get("http://localhost:3000/", Headers::new(), &handler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+        let body = response::extract_body_to_string(response);
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/graphql\";</script>"));
+        assert!(body.contains("<title>Star Wars</title>"));
+    }
+
+    #[cfg(feature = "bundled-assets")]
+    #[test]
+    fn serves_bundled_assets() {
+        use juniper::http::assets::{CACHE_CONTROL, PLAYGROUND_JS};
+
+        let url = format!("http://localhost:3000/{}", PLAYGROUND_JS.name);
+        let response = request::get(&url, Headers::new(), &AssetsHandler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(
+            response.headers.get_raw("content-type").unwrap()[0],
+            PLAYGROUND_JS.content_type.as_bytes(),
+        );
+        assert_eq!(
+            response.headers.get_raw("cache-control").unwrap()[0],
+            CACHE_CONTROL.as_bytes(),
+        );
+        assert_eq!(
+            response::extract_body_to_bytes(response),
+            PLAYGROUND_JS.contents(),
+        );
+
+        let response = request::get(
+            "http://localhost:3000/middleware.js",
+            Headers::new(),
+            &AssetsHandler,
+        )
+        .unwrap();
+        assert_eq!(response.status, Some(status::NotFound));
+    }
 }
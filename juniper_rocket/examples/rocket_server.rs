@@ -7,17 +7,17 @@ use rocket::{response::content, Rocket, State};
 type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
 
 #[rocket::get("/")]
-fn graphiql() -> content::Html<String> {
+fn graphiql() -> content::RawHtml<String> {
     juniper_rocket::graphiql_source("/graphql", None)
 }
 
-#[rocket::get("/graphql?<request>")]
+#[rocket::get("/graphql?<request..>")]
 fn get_graphql_handler(
     context: &State<Database>,
     request: juniper_rocket::GraphQLRequest,
     schema: &State<Schema>,
 ) -> juniper_rocket::GraphQLResponse {
-    request.execute_sync(&*schema, &*context)
+    request.execute_sync(schema, context)
 }
 
 #[rocket::post("/graphql", data = "<request>")]
@@ -26,7 +26,7 @@ fn post_graphql_handler(
     request: juniper_rocket::GraphQLRequest,
     schema: &State<Schema>,
 ) -> juniper_rocket::GraphQLResponse {
-    request.execute_sync(&*schema, &*context)
+    request.execute_sync(schema, context)
 }
 
 #[rocket::main]
@@ -38,42 +38,76 @@ Check the LICENSE file for details.
 
 #![doc(html_root_url = "https://docs.rs/juniper_rocket/0.7.1")]
 
-use std::{borrow::Cow, io::Cursor};
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+use std::{borrow::Cow, io::Cursor, marker::PhantomData};
 
 use rocket::{
     data::{self, FromData, ToByteUnit},
     form::{error::ErrorKind, DataField, Error, Errors, FromForm, Options, ValueField},
     http::{ContentType, Status},
-    outcome::Outcome::{Failure, Forward, Success},
+    outcome::Outcome,
+    request::{self, FromRequest},
     response::{self, content, Responder, Response},
     Data, Request,
 };
 
 use juniper::{
-    http::{self, GraphQLBatchRequest},
+    http::{
+        self,
+        handler::{self, HandlerConfig, HttpRequest, HttpResponse, MediaType},
+        GraphQLBatchRequest,
+    },
     DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync,
-    InputValue, RootNode, ScalarValue,
+    RootNode, ScalarValue, SchemaType,
 };
 
+#[cfg(feature = "subscriptions")]
+use std::sync::Arc;
+
+#[cfg(feature = "subscriptions")]
+use futures::{Stream, StreamExt as _};
+#[cfg(feature = "subscriptions")]
+use juniper::Value;
+#[cfg(feature = "subscriptions")]
+use juniper_subscriptions::Connection;
+#[cfg(feature = "subscriptions")]
+use rocket::response::stream::{Event, EventStream};
+#[cfg(feature = "subscriptions")]
+use serde::Serialize;
+
 /// Simple wrapper around an incoming GraphQL request
 ///
 /// See the `http` module for more information. This type can be constructed
 /// automatically from both GET and POST routes by implementing the `FromForm`
 /// and `FromData` traits.
+///
+/// Both are extracted as described in [`juniper::http::handler`], and mutations
+/// extracted from a query string are rejected with `405 Method Not Allowed` on
+/// execution. Responses are always `application/json`.
+///
+/// POST request bodies are checked against the [`HandlerConfig`] managed by the
+/// Rocket instance, if any, and are limited to 100 KiB otherwise. GET routes
+/// need a [`CsrfPrevention`] guard to apply its CSRF prevention.
 #[derive(Debug, PartialEq)]
-pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>)
+pub struct GraphQLRequest<S = DefaultScalarValue>
 where
-    S: ScalarValue;
+    S: ScalarValue,
+{
+    batch: GraphQLBatchRequest<S>,
+    is_get: bool,
+}
 
 impl<S: ScalarValue> AsRef<GraphQLBatchRequest<S>> for GraphQLRequest<S> {
     fn as_ref(&self) -> &GraphQLBatchRequest<S> {
-        &self.0
+        &self.batch
     }
 }
 
 impl<S: ScalarValue> AsMut<GraphQLBatchRequest<S>> for GraphQLRequest<S> {
     fn as_mut(&mut self) -> &mut GraphQLBatchRequest<S> {
-        &mut self.0
+        &mut self.batch
     }
 }
 
@@ -84,24 +118,69 @@ pub struct GraphQLResponse(pub Status, pub String);
 pub fn graphiql_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
-) -> content::Html<String> {
-    content::Html(juniper::http::graphiql::graphiql_source(
+) -> content::RawHtml<String> {
+    content::RawHtml(juniper::http::graphiql::graphiql_source(
         graphql_endpoint_url,
         subscriptions_endpoint_url,
     ))
 }
 
+/// Generate an HTML page containing GraphiQL configured with the given [`GraphiQLConfig`]
+///
+/// [`GraphiQLConfig`]: juniper::http::graphiql::GraphiQLConfig
+pub fn graphiql_source_with_config(
+    config: &http::graphiql::GraphiQLConfig,
+) -> content::RawHtml<String> {
+    content::RawHtml(http::graphiql::graphiql_source_with_config(config))
+}
+
 /// Generate an HTML page containing GraphQL Playground
 pub fn playground_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
-) -> content::Html<String> {
-    content::Html(juniper::http::playground::playground_source(
+) -> content::RawHtml<String> {
+    content::RawHtml(juniper::http::playground::playground_source(
         graphql_endpoint_url,
         subscriptions_endpoint_url,
     ))
 }
 
+/// Generate an HTML page containing GraphQL Playground configured with the given
+/// [`PlaygroundConfig`]
+///
+/// [`PlaygroundConfig`]: juniper::http::playground::PlaygroundConfig
+pub fn playground_source_with_config(
+    config: &http::playground::PlaygroundConfig,
+) -> content::RawHtml<String> {
+    content::RawHtml(http::playground::playground_source_with_config(config))
+}
+
+/// A [bundled asset](juniper::http::assets) of GraphiQL or GraphQL Playground
+///
+/// Use [`bundled_asset`] to find one from a route mounted under the assets base URL given to the
+/// pages:
+///
+/// ```ignore
+/// #[rocket::get("/assets/<name..>")]
+/// fn assets(name: PathBuf) -> Option<juniper_rocket::BundledAsset> {
+///     juniper_rocket::bundled_asset(name)
+/// }
+/// ```
+#[cfg(feature = "bundled-assets")]
+#[derive(Clone, Copy, Debug)]
+pub struct BundledAsset(pub &'static http::assets::Asset);
+
+/// Find the bundled asset of the given name, if there is one
+#[cfg(feature = "bundled-assets")]
+pub fn bundled_asset(name: std::path::PathBuf) -> Option<BundledAsset> {
+    let name = name
+        .iter()
+        .map(|segment| segment.to_str())
+        .collect::<Option<Vec<_>>>()?
+        .join("/");
+    http::assets::find(&name).map(BundledAsset)
+}
+
 impl<S> GraphQLRequest<S>
 where
     S: ScalarValue,
@@ -117,15 +196,12 @@ where
         MutationT: GraphQLType<S, Context = CtxT>,
         SubscriptionT: GraphQLType<S, Context = CtxT>,
     {
-        let response = self.0.execute_sync(root_node, context);
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
-        let json = serde_json::to_string(&response).unwrap();
+        if let Err(response) = self.check_get(&root_node.schema) {
+            return response;
+        }
 
-        GraphQLResponse(status, json)
+        let response = self.batch.execute_sync(root_node, context);
+        GraphQLResponse::from_http(HttpResponse::from_graphql(&response, MediaType::Json))
     }
 
     /// Asynchronously execute an incoming GraphQL query.
@@ -144,25 +220,112 @@ where
         CtxT: Sync,
         S: Send + Sync,
     {
-        let response = self.0.execute(root_node, context).await;
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
-        let json = serde_json::to_string(&response).unwrap();
+        if let Err(response) = self.check_get(&root_node.schema) {
+            return response;
+        }
 
-        GraphQLResponse(status, json)
+        let response = self.batch.execute(root_node, context).await;
+        GraphQLResponse::from_http(HttpResponse::from_graphql(&response, MediaType::Json))
+    }
+
+    /// Rejects mutations extracted from a query string.
+    fn check_get(&self, schema: &SchemaType<S>) -> Result<(), GraphQLResponse> {
+        if self.is_get {
+            handler::check_get_request(&self.batch, schema).map_err(GraphQLResponse::from_http)?;
+        }
+        Ok(())
     }
 
     /// Returns the operation names associated with this request.
     ///
     /// For batch requests there will be multiple names.
     pub fn operation_names(&self) -> Vec<Option<&str>> {
-        self.0.operation_names()
+        self.batch.operation_names()
+    }
+
+    /// Execute an incoming GraphQL subscription, streaming its results as Server-Sent Events.
+    ///
+    /// Only available with the `subscriptions` feature.
+    ///
+    /// Each result is sent as a `next` event carrying its JSON, followed by a `complete` event
+    /// once the subscription ends, as in the "distinct connections" mode of the
+    /// [GraphQL over SSE protocol](https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md).
+    /// Requests which can't be subscribed to, like batches, get a single `next` event with their
+    /// errors.
+    ///
+    /// As the stream outlives the request, the schema is shared with an [`Arc`], and the context is
+    /// owned by the stream:
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// #
+    /// # use juniper::{
+    /// #     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+    /// #     EmptyMutation, RootNode,
+    /// # };
+    /// # use rocket::{
+    /// #     get,
+    /// #     response::stream::{Event, EventStream},
+    /// #     State,
+    /// # };
+    /// #
+    /// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+    ///
+    /// #[get("/subscriptions?<request..>")]
+    /// fn subscriptions(
+    ///     request: juniper_rocket::GraphQLRequest,
+    ///     schema: &State<Arc<Schema>>,
+    /// ) -> EventStream![] {
+    ///     request.subscribe(schema.inner().clone(), Database::new())
+    /// }
+    /// ```
+    #[cfg(feature = "subscriptions")]
+    pub fn subscribe<CtxT, QueryT, MutationT, SubscriptionT>(
+        self,
+        root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+        context: CtxT,
+    ) -> EventStream<impl Stream<Item = Event>>
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        QueryT::TypeInfo: Send + Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        MutationT::TypeInfo: Send + Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+        SubscriptionT::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: Send + Sync + 'static,
+    {
+        EventStream! {
+            let request = match self.batch {
+                GraphQLBatchRequest::Single(request) => request,
+                GraphQLBatchRequest::Batch(_) => {
+                    let error = FieldError::<S>::new("Subscriptions can't be batched", Value::null());
+                    yield next_event(&http::GraphQLResponse::error(error));
+                    yield Event::empty().event("complete");
+                    return;
+                }
+            };
+
+            match http::resolve_into_stream(&request, &root_node, &context).await {
+                Ok((stream, errors)) => {
+                    let mut connection = Connection::from_stream(stream, errors);
+                    while let Some(output) = connection.next().await {
+                        yield next_event(&output);
+                    }
+                }
+                Err(e) => yield next_event(&http::GraphQLResponse::<S>::from_result(Err(e))),
+            }
+            yield Event::empty().event("complete");
+        }
     }
 }
 
+/// Builds the `next` event of a subscription carrying the given result.
+#[cfg(feature = "subscriptions")]
+fn next_event(result: &impl Serialize) -> Event {
+    Event::data(serde_json::to_string(result).unwrap()).event("next")
+}
+
 impl GraphQLResponse {
     /// Constructs an error response outside of the normal execution flow
     ///
@@ -209,52 +372,45 @@ impl GraphQLResponse {
         let json = serde_json::to_string(&response).unwrap();
         GraphQLResponse(status, json)
     }
+
+    fn from_http(response: HttpResponse) -> Self {
+        let status = Status::from_code(response.status).unwrap_or(Status::InternalServerError);
+        GraphQLResponse(status, response.body)
+    }
+}
+
+/// Extracts the error message from an error response of [`juniper::http::handler`].
+fn error_message(response: &HttpResponse) -> String {
+    serde_json::from_str::<serde_json::Value>(&response.body)
+        .ok()
+        .and_then(|body| Some(body.pointer("/errors/0/message")?.as_str()?.to_owned()))
+        .unwrap_or_else(|| response.body.clone())
 }
 
+/// Query string a [`GraphQLRequest`] is extracted from, as a form.
 pub struct GraphQLContext<'f, S: ScalarValue> {
     opts: Options,
-    query: Option<String>,
-    operation_name: Option<String>,
-    variables: Option<InputValue<S>>,
+    query_string: String,
     errors: Errors<'f>,
+    _scalar: PhantomData<S>,
 }
 
 impl<'f, S: ScalarValue> GraphQLContext<'f, S> {
-    fn query(&mut self, value: String) {
-        if self.query.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("query");
-
-            self.errors.push(error)
-        } else {
-            self.query = Some(value);
+    /// Appends the decoded field to the query string, encoded again.
+    fn push(&mut self, key: &str, value: &str) {
+        if !self.query_string.is_empty() {
+            self.query_string.push('&');
         }
-    }
-
-    fn operation_name(&mut self, value: String) {
-        if self.operation_name.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("operation_name");
-
-            self.errors.push(error)
-        } else {
-            self.operation_name = Some(value);
-        }
-    }
-
-    fn variables(&mut self, value: String) {
-        if self.variables.is_some() {
-            let error = Error::from(ErrorKind::Duplicate).with_name("variables");
-
-            self.errors.push(error)
-        } else {
-            let parse_result = serde_json::from_str::<InputValue<S>>(&value);
-
-            match parse_result {
-                Ok(variables) => self.variables = Some(variables),
-                Err(e) => {
-                    let error = Error::from(ErrorKind::Validation(Cow::Owned(e.to_string())))
-                        .with_name("variables");
-
-                    self.errors.push(error);
+        for (i, component) in [key, value].iter().enumerate() {
+            if i > 0 {
+                self.query_string.push('=');
+            }
+            for b in component.bytes() {
+                match b {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                        self.query_string.push(b as char)
+                    }
+                    _ => self.query_string.push_str(&format!("%{:02X}", b)),
                 }
             }
         }
@@ -271,18 +427,20 @@ where
     fn init(opts: Options) -> Self::Context {
         GraphQLContext {
             opts,
-            query: None,
-            operation_name: None,
-            variables: None,
+            query_string: String::new(),
             errors: Errors::new(),
+            _scalar: PhantomData,
         }
     }
 
     fn push_value(ctx: &mut Self::Context, field: ValueField<'f>) {
         match field.name.key().map(|key| key.as_str()) {
-            Some("query") => ctx.query(field.value.to_owned()),
-            Some("operation_name") => ctx.operation_name(field.value.to_owned()),
-            Some("variables") => ctx.variables(field.value.to_owned()),
+            Some(key @ "query") | Some(key @ "operationName") | Some(key @ "variables") => {
+                ctx.push(key, field.value)
+            }
+            // Name of the `operationName` parameter before requests were extracted as described in
+            // `juniper::http::handler`.
+            Some("operation_name") => ctx.push("operationName", field.value),
             Some(key) => {
                 if ctx.opts.strict {
                     let error = Error::from(ErrorKind::Unknown).with_name(key);
@@ -309,22 +467,33 @@ where
     }
 
     fn finalize(mut ctx: Self::Context) -> rocket::form::Result<'f, Self> {
-        if ctx.query.is_none() {
-            let error = Error::from(ErrorKind::Missing).with_name("query");
-
-            ctx.errors.push(error)
-        }
-
-        match ctx.errors.is_empty() {
-            true => Ok(GraphQLRequest(GraphQLBatchRequest::Single(
-                http::GraphQLRequest::new(ctx.query.unwrap(), ctx.operation_name, ctx.variables),
-            ))),
-            false => Err(ctx.errors),
+        let request = HttpRequest {
+            method: "GET",
+            query_string: Some(&ctx.query_string),
+            content_type: None,
+            accept: None,
+            body: &[],
+            headers: &[],
+        };
+        match request.parse_unchecked() {
+            Ok((batch, _)) if ctx.errors.is_empty() => Ok(GraphQLRequest {
+                batch,
+                is_get: true,
+            }),
+            Ok(_) => Err(ctx.errors),
+            Err(response) => {
+                let message = error_message(&response);
+                ctx.errors
+                    .push(Error::from(ErrorKind::Validation(Cow::Owned(message))));
+                Err(ctx.errors)
+            }
         }
     }
 }
 
-const BODY_LIMIT: u64 = 1024 * 100;
+/// Maximum size of request bodies when no [`HandlerConfig`] is managed, lower than the
+/// [`juniper::http::handler::DEFAULT_MAX_BODY_SIZE`] for backward compatibility.
+const BODY_LIMIT: usize = 1024 * 100;
 
 #[rocket::async_trait]
 impl<'r, S> FromData<'r> for GraphQLRequest<S>
@@ -337,34 +506,109 @@ where
         req: &'r Request<'_>,
         data: Data<'r>,
     ) -> data::Outcome<'r, Self, Self::Error> {
-        use rocket::tokio::io::AsyncReadExt as _;
+        let default_config = HandlerConfig::default().with_max_body_size(BODY_LIMIT);
+        let config = req
+            .rocket()
+            .state::<HandlerConfig>()
+            .unwrap_or(&default_config);
+        let limit = config.max_body_size().map_or(u64::MAX, |size| size as u64);
+
+        let body = match data.open(limit.bytes()).into_bytes().await {
+            Ok(body) if body.is_complete() => body.into_inner(),
+            Ok(_) => {
+                return Outcome::Error((
+                    Status::PayloadTooLarge,
+                    format!("The request body is larger than {} bytes", limit),
+                ))
+            }
+            Err(e) => return Outcome::Error((Status::InternalServerError, format!("{:?}", e))),
+        };
 
-        let content_type = req
-            .content_type()
-            .map(|ct| (ct.top().as_str(), ct.sub().as_str()));
-        let is_json = match content_type {
-            Some(("application", "json")) => true,
-            Some(("application", "graphql")) => false,
-            _ => return Box::pin(async move { Forward(data) }).await,
+        let headers = req.headers().iter().collect::<Vec<_>>();
+        let headers = headers
+            .iter()
+            .map(|h| (h.name().as_str(), h.value()))
+            .collect::<Vec<_>>();
+        let request = HttpRequest {
+            method: req.method().as_str(),
+            query_string: req.uri().query().map(|q| q.as_str()),
+            content_type: req.headers().get_one("Content-Type"),
+            accept: None,
+            headers: &headers,
+            body: &body,
         };
+        match request.parse_unchecked_with_config(config) {
+            Ok((batch, _)) => Outcome::Success(GraphQLRequest {
+                batch,
+                is_get: request.method.eq_ignore_ascii_case("GET"),
+            }),
+            Err(response) => Outcome::Error((
+                Status::from_code(response.status).unwrap_or(Status::BadRequest),
+                error_message(&response),
+            )),
+        }
+    }
+}
 
-        Box::pin(async move {
-            let mut body = String::new();
-            let mut reader = data.open(BODY_LIMIT.bytes());
-            if let Err(e) = reader.read_to_string(&mut body).await {
-                return Failure((Status::InternalServerError, format!("{:?}", e)));
-            }
+/// Request guard applying the CSRF prevention of the [`HandlerConfig`] managed by the Rocket
+/// instance, if any, to `GET` requests.
+///
+/// A [`GraphQLRequest`] is extracted from the query string of a `GET` request without access to
+/// its headers, so `GET` routes have to use this guard to reject requests browsers may send
+/// without a CORS preflight. `POST` requests are always accepted, as [`GraphQLRequest`] is only
+/// extracted from their bodies with a non-simple `Content-Type`.
+///
+/// ```
+/// # use rocket::{get, State};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// #[get("/graphql?<request..>")]
+/// fn get_graphql_handler(
+///     _csrf: juniper_rocket::CsrfPrevention,
+///     context: &State<Database>,
+///     request: juniper_rocket::GraphQLRequest,
+///     schema: &State<Schema>,
+/// ) -> juniper_rocket::GraphQLResponse {
+///     request.execute_sync(&*schema, &*context)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CsrfPrevention;
 
-            Success(GraphQLRequest(if is_json {
-                match serde_json::from_str(&body) {
-                    Ok(req) => req,
-                    Err(e) => return Failure((Status::BadRequest, format!("{}", e))),
-                }
-            } else {
-                GraphQLBatchRequest::Single(http::GraphQLRequest::new(body, None, None))
-            }))
-        })
-        .await
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CsrfPrevention {
+    type Error = String;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let config = match req.rocket().state::<HandlerConfig>() {
+            Some(config) => config,
+            None => return Outcome::Success(CsrfPrevention),
+        };
+
+        // The `Header`s are owned by their iterator, so they're collected first for their names
+        // and values to be borrowed.
+        let headers = req.headers().iter().collect::<Vec<_>>();
+        let headers = headers
+            .iter()
+            .map(|h| (h.name().as_str(), h.value()))
+            .collect::<Vec<_>>();
+        let request = HttpRequest {
+            method: req.method().as_str(),
+            query_string: req.uri().query().map(|q| q.as_str()),
+            content_type: req.headers().get_one("Content-Type"),
+            accept: req.headers().get_one("Accept"),
+            headers: &headers,
+            body: &[],
+        };
+        match config.check_csrf(&request) {
+            Ok(()) => Outcome::Success(CsrfPrevention),
+            Err(resp) => Outcome::Error((Status::BadRequest, resp.body)),
+        }
     }
 }
 
@@ -380,6 +624,21 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
     }
 }
 
+#[cfg(feature = "bundled-assets")]
+impl<'r, 'o: 'r> Responder<'r, 'o> for BundledAsset {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+        let BundledAsset(asset) = self;
+        let content_type =
+            ContentType::parse_flexible(asset.content_type).ok_or(Status::InternalServerError)?;
+
+        Response::build()
+            .header(content_type)
+            .raw_header("Cache-Control", http::assets::CACHE_CONTROL)
+            .sized_body(asset.contents().len(), Cursor::new(asset.contents()))
+            .ok()
+    }
+}
+
 #[cfg(test)]
 mod fromform_tests {
     use super::*;
@@ -412,11 +671,30 @@ mod fromform_tests {
         }
     }
 
+    fn validation_error(message: &str) -> Error<'static> {
+        Error::from(ErrorKind::Validation(Cow::Owned(message.to_owned())))
+    }
+
+    fn get_request(
+        query: &str,
+        operation_name: Option<&str>,
+        variables: Option<InputValue>,
+    ) -> GraphQLRequest {
+        GraphQLRequest {
+            batch: GraphQLBatchRequest::Single(http::GraphQLRequest::new(
+                query.to_owned(),
+                operation_name.map(str::to_owned),
+                variables,
+            )),
+            is_get: true,
+        }
+    }
+
     #[test]
     fn test_empty_form() {
         check_error(
             "",
-            vec![Error::from(ErrorKind::Missing).with_name("query")],
+            vec![validation_error("`query` parameter is missing")],
             false,
         );
     }
@@ -425,7 +703,7 @@ mod fromform_tests {
     fn test_no_query() {
         check_error(
             "operation_name=foo&variables={}",
-            vec![Error::from(ErrorKind::Missing).with_name("query")],
+            vec![validation_error("`query` parameter is missing")],
             false,
         );
     }
@@ -443,7 +721,9 @@ mod fromform_tests {
     fn test_duplicate_query() {
         check_error(
             "query=foo&query=bar",
-            vec![Error::from(ErrorKind::Duplicate).with_name("query")],
+            vec![validation_error(
+                "`query` parameter is specified multiple times",
+            )],
             false,
         );
     }
@@ -451,8 +731,10 @@ mod fromform_tests {
     #[test]
     fn test_duplicate_operation_name() {
         check_error(
-            "query=test&operation_name=op1&operation_name=op2",
-            vec![Error::from(ErrorKind::Duplicate).with_name("operation_name")],
+            "query=test&operation_name=op1&operationName=op2",
+            vec![validation_error(
+                "`operationName` parameter is specified multiple times",
+            )],
             false,
         );
     }
@@ -461,7 +743,9 @@ mod fromform_tests {
     fn test_duplicate_variables() {
         check_error(
             "query=test&variables={}&variables={}",
-            vec![Error::from(ErrorKind::Duplicate).with_name("variables")],
+            vec![validation_error(
+                "`variables` parameter is specified multiple times",
+            )],
             false,
         );
     }
@@ -470,10 +754,9 @@ mod fromform_tests {
     fn test_variables_invalid_json() {
         check_error(
             "query=test&variables=NOT_JSON",
-            vec![Error::from(ErrorKind::Validation(Cow::Owned(
-                "expected value at line 1 column 1".to_owned(),
-            )))
-            .with_name("variables")],
+            vec![validation_error(
+                "Invalid `variables` parameter: expected value at line 1 column 1",
+            )],
             false,
         );
     }
@@ -484,12 +767,7 @@ mod fromform_tests {
             Form::parse_encoded(RawStr::new(r#"query=test&variables={"foo":"bar"}"#));
         assert!(result.is_ok());
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"bar"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
-        assert_eq!(result.unwrap(), expected);
+        assert_eq!(result.unwrap(), get_request("test", None, Some(variables)));
     }
 
     #[test]
@@ -498,46 +776,51 @@ mod fromform_tests {
             r#"query=test&variables={"foo":"x%20y%26%3F+z"}"#,
         ));
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"x y&? z"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
-        assert_eq!(result.unwrap(), expected);
+        assert_eq!(result.unwrap(), get_request("test", None, Some(variables)));
     }
 
     #[test]
     fn test_url_decode() {
         let result: Result<GraphQLRequest, Errors> = Form::parse_encoded(RawStr::new(
-            "query=%25foo%20bar+baz%26%3F&operation_name=test",
+            "query=%25foo%20bar+baz%26%3F&operationName=test",
         ));
         assert!(result.is_ok());
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "%foo bar baz&?".to_string(),
-            Some("test".to_string()),
-            None,
-        )));
-        assert_eq!(result.unwrap(), expected);
+        assert_eq!(
+            result.unwrap(),
+            get_request("%foo bar baz&?", Some("test"), None),
+        );
+    }
+
+    #[test]
+    fn test_legacy_operation_name() {
+        let result: Result<GraphQLRequest, Errors> =
+            Form::parse_encoded(RawStr::new("query=test&operation_name=test"));
+        assert_eq!(result.unwrap(), get_request("test", Some("test"), None));
     }
 }
 
 #[cfg(test)]
 mod tests {
-
-    use futures;
-
     use juniper::{
-        http::tests as http_tests,
+        http::{handler::HandlerConfig, tests as http_tests},
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
     use rocket::{
         self, get,
-        http::ContentType,
+        http::{ContentType, Header, Status},
         local::asynchronous::{Client, LocalResponse},
         post, routes, Build, Rocket, State,
     };
 
+    #[cfg(feature = "subscriptions")]
+    use std::sync::Arc;
+
+    #[cfg(feature = "subscriptions")]
+    use juniper::tests::fixtures::starwars::schema::Subscription;
+    #[cfg(feature = "subscriptions")]
+    use rocket::response::stream::EventStream;
+
     type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
 
     #[get("/?<request..>")]
@@ -546,7 +829,7 @@ mod tests {
         request: super::GraphQLRequest,
         schema: &State<Schema>,
     ) -> super::GraphQLResponse {
-        request.execute_sync(&*schema, &*context)
+        request.execute_sync(schema, context)
     }
 
     #[post("/", data = "<request>")]
@@ -555,7 +838,39 @@ mod tests {
         request: super::GraphQLRequest,
         schema: &State<Schema>,
     ) -> super::GraphQLResponse {
-        request.execute_sync(&*schema, &*context)
+        request.execute_sync(schema, context)
+    }
+
+    #[post("/", data = "<request>")]
+    fn post_graphql_assert_operation_name_handler(
+        context: &State<Database>,
+        request: super::GraphQLRequest,
+        schema: &State<Schema>,
+    ) -> super::GraphQLResponse {
+        assert_eq!(request.operation_names(), vec![Some("TestQuery")]);
+        request.execute_sync(schema, context)
+    }
+
+    #[get("/?<request..>")]
+    fn get_csrf_prevented_graphql_handler(
+        _csrf: super::CsrfPrevention,
+        context: &State<Database>,
+        request: super::GraphQLRequest,
+        schema: &State<Schema>,
+    ) -> super::GraphQLResponse {
+        request.execute_sync(schema, context)
+    }
+
+    #[cfg(feature = "subscriptions")]
+    type SubscriptionSchema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    #[cfg(feature = "subscriptions")]
+    #[get("/subscriptions?<request..>")]
+    fn subscriptions(
+        request: super::GraphQLRequest,
+        schema: &State<Arc<SubscriptionSchema>>,
+    ) -> EventStream![] {
+        request.subscribe(schema.inner().clone(), Database::new())
     }
 
     struct TestRocketIntegration {
@@ -596,17 +911,25 @@ mod tests {
     }
 
     #[rocket::async_test]
-    async fn test_operation_names() {
-        #[post("/", data = "<request>")]
-        fn post_graphql_assert_operation_name_handler(
-            context: &State<Database>,
-            request: super::GraphQLRequest,
-            schema: &State<Schema>,
-        ) -> super::GraphQLResponse {
-            assert_eq!(request.operation_names(), vec![Some("TestQuery")]);
-            request.execute_sync(&*schema, &*context)
-        }
+    async fn test_rejected_requests() {
+        let client = Client::untracked(make_rocket())
+            .await
+            .expect("valid rocket");
+
+        let resp = client.get("/?query=mutation%7Bforget%7D").dispatch().await;
+        assert_eq!(resp.status(), Status::MethodNotAllowed);
+
+        let resp = client
+            .post("/")
+            .header(ContentType::Plain)
+            .body("{hero{name}}")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::UnsupportedMediaType);
+    }
 
+    #[rocket::async_test]
+    async fn test_operation_names() {
         let rocket = make_rocket_without_routes()
             .mount("/", routes![post_graphql_assert_operation_name_handler]);
         let client = Client::untracked(rocket).await.expect("valid rocket");
@@ -622,6 +945,76 @@ mod tests {
         assert_eq!(resp.await.status_code, 200);
     }
 
+    #[rocket::async_test]
+    async fn test_handler_config() {
+        let rocket = make_rocket_without_routes()
+            .manage(
+                HandlerConfig::new()
+                    .with_csrf_prevention()
+                    .with_max_body_size(32),
+            )
+            .mount(
+                "/",
+                routes![post_graphql_handler, get_csrf_prevented_graphql_handler],
+            );
+        let client = Client::untracked(rocket).await.expect("valid rocket");
+
+        let resp = client.get("/?query=%7Bhero%7Bname%7D%7D").dispatch().await;
+        assert_eq!(resp.status(), Status::BadRequest);
+
+        let resp = client
+            .get("/?query=%7Bhero%7Bname%7D%7D")
+            .header(Header::new("Apollo-Require-Preflight", "true"))
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::Ok);
+
+        let resp = client
+            .post("/")
+            .header(ContentType::new("application", "graphql"))
+            .body("{ hero { name friends { name } } }")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::PayloadTooLarge);
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[rocket::async_test]
+    async fn test_subscriptions_over_sse() {
+        let rocket = Rocket::build()
+            .manage(Arc::new(SubscriptionSchema::new(
+                Query,
+                EmptyMutation::new(),
+                Subscription,
+            )))
+            .mount("/", routes![subscriptions]);
+        let client = Client::untracked(rocket).await.expect("valid rocket");
+
+        let resp = client
+            .get("/subscriptions?query=subscription%7BasyncHuman%7Bname%7D%7D")
+            .dispatch()
+            .await;
+        assert_eq!(resp.status(), Status::Ok);
+        assert_eq!(resp.content_type(), Some(ContentType::EventStream));
+        // Heartbeat comments may be sent at any time, so they're left out.
+        let body = resp.into_string().await.unwrap();
+        let lines = body
+            .lines()
+            .filter(|l| !l.starts_with(':'))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "event:next",
+                r#"data:{"data":{"asyncHuman":{"name":"Luke Skywalker"}},"errors":[]}"#,
+                "",
+                "event:complete",
+                "data:",
+                "",
+            ],
+        );
+    }
+
     fn make_rocket() -> Rocket<Build> {
         make_rocket_without_routes().mount("/", routes![post_graphql_handler, get_graphql_handler])
     }
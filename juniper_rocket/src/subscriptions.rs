@@ -0,0 +1,380 @@
+//! Serving of GraphQL subscriptions over WebSocket connections with the [graphql-ws][1] protocol.
+//!
+//! [1]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+
+use std::{convert::Infallible, fmt, io, pin::Pin, sync::Arc};
+
+use juniper::{
+    futures::{
+        future::{self, BoxFuture, Either},
+        sink::SinkExt,
+        stream::StreamExt,
+    },
+    GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue,
+};
+use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+use rocket::{
+    data::{IoHandler, IoStream},
+    http::{Header, Status},
+    outcome::Outcome,
+    request::{self, FromRequest},
+    response::{self, Responder, Response},
+    Request,
+};
+use tokio_tungstenite::{
+    tungstenite::{self, handshake::derive_accept_key, protocol::Role},
+    WebSocketStream,
+};
+
+struct Message(tungstenite::Message);
+
+impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
+    type Error = DeserializeError;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        match msg.0 {
+            tungstenite::Message::Close(_) => Ok(ClientMessage::ConnectionTerminate),
+            msg => serde_json::from_slice(&msg.into_data()).map_err(DeserializeError),
+        }
+    }
+}
+
+/// Error that can happen while deserializing client messages.
+#[derive(Debug)]
+struct DeserializeError(serde_json::Error);
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "serde error: {}", self.0)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+/// Errors that can happen while serving a connection.
+#[derive(Debug)]
+pub enum Error {
+    /// Errors that can happen in the WebSocket transport while serving a connection.
+    WebSocket(Box<tungstenite::Error>),
+
+    /// Errors that can happen while serializing outgoing messages. Note that errors that occur
+    /// while deserializing incoming messages are handled internally by the protocol.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WebSocket(e) => write!(f, "websocket error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<tungstenite::Error> for Error {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(_err: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+/// Request guard accepting requests that ask for an upgrade to a WebSocket connection.
+///
+/// Requests without the `Upgrade: websocket` and `Sec-WebSocket-Key` headers fail with a
+/// `400 Bad Request`.
+#[derive(Clone, Debug)]
+pub struct WebSocketUpgrade {
+    accept_key: String,
+    protocol: Option<&'static str>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebSocketUpgrade {
+    type Error = String;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = req.headers();
+        let is_websocket = headers
+            .get("Upgrade")
+            .any(|v| v.eq_ignore_ascii_case("websocket"));
+        let key = match headers.get_one("Sec-WebSocket-Key") {
+            Some(key) if is_websocket => key,
+            _ => {
+                return Outcome::Error((
+                    Status::BadRequest,
+                    "Expected a WebSocket upgrade request".to_owned(),
+                ))
+            }
+        };
+        let protocol = headers
+            .get("Sec-WebSocket-Protocol")
+            .flat_map(|v| v.split(','))
+            .find(|p| p.trim() == "graphql-ws")
+            .map(|_| "graphql-ws");
+
+        Outcome::Success(WebSocketUpgrade {
+            accept_key: derive_accept_key(key.as_bytes()),
+            protocol,
+        })
+    }
+}
+
+/// Response upgrading the connection to a WebSocket one serving the graphql-ws protocol.
+///
+/// Returned by [`subscriptions_handler`].
+pub struct SubscriptionsResponse {
+    upgrade: WebSocketUpgrade,
+    handler: GraphQLWs,
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for SubscriptionsResponse {
+    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+        let mut response = Response::build();
+        response.header(Header::new("Sec-WebSocket-Accept", self.upgrade.accept_key));
+        if let Some(protocol) = self.upgrade.protocol {
+            response.header(Header::new("Sec-WebSocket-Protocol", protocol));
+        }
+        response.upgrade("websocket", self.handler).ok()
+    }
+}
+
+/// [`IoHandler`] running the graphql-ws protocol over the upgraded connection.
+struct GraphQLWs(Box<dyn FnOnce(IoStream) -> BoxFuture<'static, ()> + Send>);
+
+#[rocket::async_trait]
+impl IoHandler for GraphQLWs {
+    async fn io(self: Pin<Box<Self>>, io: IoStream) -> io::Result<()> {
+        (Pin::into_inner(self).0)(io).await;
+        Ok(())
+    }
+}
+
+/// Upgrades the request to a WebSocket connection serving the graphql-ws protocol.
+///
+/// See [`serve_graphql_ws`] for the meaning of the `init` argument. Errors of the connection are
+/// logged, use [`serve_graphql_ws`] directly to handle them otherwise.
+///
+/// ```
+/// # use std::sync::Arc;
+/// #
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+/// #     EmptyMutation, RootNode,
+/// # };
+/// # use juniper_graphql_ws::ConnectionConfig;
+/// # use juniper_rocket::subscriptions::{
+/// #     subscriptions_handler, SubscriptionsResponse, WebSocketUpgrade,
+/// # };
+/// # use rocket::{get, State};
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+///
+/// #[get("/subscriptions")]
+/// fn subscriptions(
+///     upgrade: WebSocketUpgrade,
+///     schema: &State<Arc<Schema>>,
+/// ) -> SubscriptionsResponse {
+///     let config = ConnectionConfig::new(Database::new());
+///     subscriptions_handler(upgrade, schema.inner().clone(), config)
+/// }
+/// ```
+pub fn subscriptions_handler<Query, Mutation, Subscription, CtxT, S, I>(
+    upgrade: WebSocketUpgrade,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> SubscriptionsResponse
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let handler = GraphQLWs(Box::new(move |io| {
+        Box::pin(async move {
+            let socket = WebSocketStream::from_raw_socket(io, Role::Server, None).await;
+            if let Err(e) = serve_graphql_ws(socket, root_node, init).await {
+                rocket::error_!("GraphQL subscriptions connection failed: {}", e);
+            }
+        })
+    }));
+    SubscriptionsResponse { upgrade, handler }
+}
+
+/// Serves the graphql-ws protocol over a WebSocket connection.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    socket: WebSocketStream<IoStream>,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = socket.split();
+    let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+
+    // Pings are answered by tungstenite itself, so only data and closing frames reach the
+    // protocol.
+    let ws_rx = ws_rx
+        .filter(|r| {
+            future::ready(!matches!(
+                r,
+                Ok(tungstenite::Message::Ping(_))
+                    | Ok(tungstenite::Message::Pong(_))
+                    | Ok(tungstenite::Message::Frame(_))
+            ))
+        })
+        .map(|r| r.map(Message).map_err(Error::from));
+    let s_rx = s_rx.map(|msg| {
+        serde_json::to_string(&msg)
+            .map(tungstenite::Message::Text)
+            .map_err(Error::Serde)
+    });
+
+    match future::select(
+        ws_rx.forward(s_tx.sink_err_into()),
+        s_rx.forward(ws_tx.sink_err_into()),
+    )
+    .await
+    {
+        Either::Left((r, _)) | Either::Right((r, _)) => r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use juniper::{
+        futures::{channel::oneshot, SinkExt, StreamExt},
+        http::tests::{run_ws_test_suite, WsIntegration, WsIntegrationMessage},
+        tests::fixtures::starwars::schema::{Database, Query, Subscription},
+        EmptyMutation, LocalBoxFuture, RootNode,
+    };
+    use juniper_graphql_ws::ConnectionConfig;
+    use rocket::{config::LogLevel, fairing::AdHoc, get, routes, Config, State};
+    use tokio::time::timeout;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    use super::{subscriptions_handler, SubscriptionsResponse, WebSocketUpgrade};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+    #[get("/subscriptions")]
+    fn subscriptions(
+        upgrade: WebSocketUpgrade,
+        schema: &State<Arc<Schema>>,
+    ) -> SubscriptionsResponse {
+        let config = ConnectionConfig::new(Database::new());
+        subscriptions_handler(upgrade, schema.inner().clone(), config)
+    }
+
+    struct TestRocketWsIntegration;
+
+    impl TestRocketWsIntegration {
+        async fn serve() -> u16 {
+            let config = Config {
+                port: 0,
+                log_level: LogLevel::Off,
+                ..Config::debug_default()
+            };
+            let (tx, rx) = oneshot::channel();
+            let rocket = rocket::custom(config)
+                .manage(Arc::new(Schema::new(
+                    Query,
+                    EmptyMutation::new(),
+                    Subscription,
+                )))
+                .mount("/", routes![subscriptions])
+                .attach(AdHoc::on_liftoff("Port", move |rocket| {
+                    Box::pin(async move {
+                        let _ = tx.send(rocket.config().port);
+                    })
+                }));
+            tokio::spawn(rocket.launch());
+            rx.await.unwrap()
+        }
+
+        async fn run_async(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            let port = Self::serve().await;
+            let (mut socket, _) =
+                connect_async(format!("ws://127.0.0.1:{}/subscriptions", port)).await?;
+
+            for message in &messages {
+                match message {
+                    WsIntegrationMessage::Send(body) => {
+                        socket.send(Message::Text(body.to_owned())).await?;
+                    }
+                    WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message =
+                            timeout(Duration::from_millis(*message_timeout), socket.next())
+                                .await
+                                .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                                .ok_or_else(|| anyhow::anyhow!("Empty message received"))??;
+
+                        match message {
+                            Message::Text(text) => {
+                                let expected_value =
+                                    serde_json::from_str::<serde_json::Value>(body)?;
+                                let value = serde_json::from_str::<serde_json::Value>(&text)?;
+
+                                if value != expected_value {
+                                    return Err(anyhow::anyhow!(
+                                        "Expected message: {}. Received message: {}",
+                                        expected_value,
+                                        value,
+                                    ));
+                                }
+                            }
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    impl WsIntegration for TestRocketWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<WsIntegrationMessage>,
+        ) -> LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rocket_ws_integration() {
+        run_ws_test_suite(&TestRocketWsIntegration).await;
+    }
+}
@@ -0,0 +1,1246 @@
+//! Generation of the Rust code of a parsed schema.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::TryFrom,
+};
+
+use graphql_parser::schema::{
+    parse_schema, Definition, Directive, Document, EnumType, Field, InputObjectType, InputValue,
+    InterfaceType, ObjectType, SchemaDefinition, Type, TypeDefinition, UnionType, Value,
+};
+
+use crate::{names, Error, Generator};
+
+type Def<'a> = TypeDefinition<'a, String>;
+
+/// Built-in scalars and the Rust types they're generated as.
+const BUILTIN_SCALARS: &[(&str, &str)] = &[
+    ("Boolean", "bool"),
+    ("Float", "f64"),
+    ("ID", "::juniper::ID"),
+    ("Int", "i32"),
+    ("String", "String"),
+];
+
+/// Generates the Rust code of the schema `sdl`.
+pub(crate) fn generate(generator: &Generator, sdl: &str) -> Result<String, Error> {
+    let doc = parse_schema::<String>(sdl)?;
+    let schema = Schema::new(generator, &doc)?;
+    schema.validate()?;
+
+    let mut out = Code::default();
+    out.line(
+        0,
+        "// This file is generated by `juniper_sdl_codegen`, do not edit it by hand.",
+    );
+    schema.write_schema_alias(&mut out);
+    for def in &schema.definitions {
+        match def {
+            TypeDefinition::Scalar(_) => {}
+            TypeDefinition::Object(o) if schema.is_resolved(o) => {
+                schema.write_resolved(&mut out, o)?
+            }
+            TypeDefinition::Object(o) => schema.write_struct(&mut out, o)?,
+            TypeDefinition::Interface(i) => schema.write_interface(&mut out, i)?,
+            TypeDefinition::Union(u) => schema.write_union(&mut out, u),
+            TypeDefinition::Enum(e) => schema.write_enum(&mut out, e),
+            TypeDefinition::InputObject(i) => schema.write_input_object(&mut out, i)?,
+        }
+    }
+    schema.write_test(&mut out)?;
+    Ok(out.0)
+}
+
+/// Rust code being generated.
+#[derive(Default)]
+struct Code(String);
+
+impl Code {
+    fn line(&mut self, depth: usize, line: impl AsRef<str>) {
+        let line = line.as_ref();
+        if !line.is_empty() {
+            self.0.push_str(&"    ".repeat(depth));
+        }
+        self.0.push_str(line);
+        self.0.push('\n');
+    }
+
+    /// Writes a `#[graphql(...)]` attribute, unless there's nothing to put in it.
+    fn attr(&mut self, depth: usize, attrs: impl IntoIterator<Item = String>) {
+        let attrs = attrs.into_iter().collect::<Vec<_>>();
+        if !attrs.is_empty() {
+            self.line(depth, graphql_attr(attrs));
+        }
+    }
+}
+
+/// Schema being generated, with its types indexed by name.
+struct Schema<'a> {
+    generator: &'a Generator,
+    definitions: Vec<&'a Def<'a>>,
+    types: HashMap<&'a str, &'a Def<'a>>,
+    query: &'a str,
+    mutation: Option<&'a str>,
+    subscription: Option<&'a str>,
+    implementers: HashMap<&'a str, Vec<&'a ObjectType<'a, String>>>,
+}
+
+impl<'a> Schema<'a> {
+    fn new(generator: &'a Generator, doc: &'a Document<'a, String>) -> Result<Self, Error> {
+        let mut definitions = Vec::new();
+        let mut types = HashMap::new();
+        let mut roots = None;
+        for def in &doc.definitions {
+            match def {
+                Definition::SchemaDefinition(s) => {
+                    if !s.directives.is_empty() {
+                        return Err(unsupported("directives applied to the schema"));
+                    }
+                    roots = Some(s);
+                }
+                Definition::TypeDefinition(def) => {
+                    if types.insert(type_name(def), def).is_some() {
+                        return Err(unsupported(format!(
+                            "type `{}` is defined twice",
+                            type_name(def),
+                        )));
+                    }
+                    definitions.push(def);
+                }
+                Definition::TypeExtension(_) => return Err(unsupported("type extensions")),
+                Definition::DirectiveDefinition(d) => {
+                    return Err(unsupported(format!("directive definition `@{}`", d.name)))
+                }
+            }
+        }
+
+        // Without a schema definition, the root operation types go by their default names.
+        let (query, mutation, subscription) = match roots {
+            Some(s) => (
+                s.query
+                    .as_deref()
+                    .ok_or_else(|| unsupported("schema definition without a query type"))?,
+                s.mutation.as_deref(),
+                s.subscription.as_deref(),
+            ),
+            None => (
+                "Query",
+                Some("Mutation").filter(|n| types.contains_key(n)),
+                Some("Subscription").filter(|n| types.contains_key(n)),
+            ),
+        };
+
+        let mut implementers = HashMap::<_, Vec<_>>::new();
+        for def in &definitions {
+            if let TypeDefinition::Object(o) = def {
+                for i in &o.implements_interfaces {
+                    implementers.entry(i.as_str()).or_default().push(o);
+                }
+            }
+        }
+
+        Ok(Self {
+            generator,
+            definitions,
+            types,
+            query,
+            mutation,
+            subscription,
+            implementers,
+        })
+    }
+
+    /// Checks that the schema only uses what can be generated, before any code is.
+    fn validate(&self) -> Result<(), Error> {
+        for root in self.roots() {
+            match self.types.get(root) {
+                Some(TypeDefinition::Object(_)) => {}
+                Some(_) => {
+                    return Err(unsupported(format!(
+                        "root operation type `{}` isn't an object",
+                        root,
+                    )))
+                }
+                None => return Err(Error::UnknownType(root.into())),
+            }
+        }
+
+        for def in &self.definitions {
+            match def {
+                TypeDefinition::Scalar(s) => {
+                    no_directives(&s.directives, &s.name)?;
+                    if !self.generator.scalars.contains_key(&s.name) && !is_builtin(&s.name) {
+                        return Err(Error::UnknownScalar(s.name.clone()));
+                    }
+                }
+                TypeDefinition::Object(o) => {
+                    no_directives(&o.directives, &o.name)?;
+                    self.validate_fields(&o.fields)?;
+                    for i in &o.implements_interfaces {
+                        match self.types.get(i.as_str()) {
+                            Some(TypeDefinition::Interface(i)) => {
+                                self.validate_implementer(o, i)?
+                            }
+                            Some(_) => {
+                                return Err(unsupported(format!(
+                                    "`{}` implements `{}`, which isn't an interface",
+                                    o.name, i,
+                                )))
+                            }
+                            None => return Err(Error::UnknownType(i.clone())),
+                        }
+                    }
+                }
+                TypeDefinition::Interface(i) => {
+                    no_directives(&i.directives, &i.name)?;
+                    self.validate_fields(&i.fields)?;
+                    if !self.implementers.contains_key(i.name.as_str()) {
+                        return Err(unsupported(format!(
+                            "interface `{}` isn't implemented by any object",
+                            i.name,
+                        )));
+                    }
+                }
+                TypeDefinition::Union(u) => {
+                    no_directives(&u.directives, &u.name)?;
+                    for member in &u.types {
+                        match self.types.get(member.as_str()) {
+                            Some(TypeDefinition::Object(_)) => {}
+                            Some(_) => {
+                                return Err(unsupported(format!(
+                                    "member `{}` of union `{}` isn't an object",
+                                    member, u.name,
+                                )))
+                            }
+                            None => return Err(Error::UnknownType(member.clone())),
+                        }
+                    }
+                }
+                TypeDefinition::Enum(e) => {
+                    no_directives(&e.directives, &e.name)?;
+                    for v in &e.values {
+                        deprecation(&v.directives, &v.name)?;
+                    }
+                }
+                TypeDefinition::InputObject(i) => {
+                    no_directives(&i.directives, &i.name)?;
+                    for f in &i.fields {
+                        self.validate_input_value(f)?;
+                        if self.kind(base_type(&f.value_type)) == Kind::Output {
+                            return Err(unsupported(format!(
+                                "input field `{}` has an output type",
+                                f.name,
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_fields(&self, fields: &[Field<'a, String>]) -> Result<(), Error> {
+        for f in fields {
+            deprecation(&f.directives, &f.name)?;
+            if self.kind(base_type(&f.field_type)) == Kind::Input {
+                return Err(unsupported(
+                    format!("field `{}` has an input type", f.name,),
+                ));
+            }
+            for arg in &f.arguments {
+                self.validate_input_value(arg)?;
+                if self.kind(base_type(&arg.value_type)) == Kind::Output {
+                    return Err(unsupported(format!(
+                        "argument `{}` of field `{}` has an output type",
+                        arg.name, f.name,
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_input_value(&self, value: &InputValue<'a, String>) -> Result<(), Error> {
+        no_directives(&value.directives, &value.name)?;
+        self.rust_type(&value.value_type, false)?;
+        if let Some(default) = &value.default_value {
+            self.default_expr(&value.value_type, default, &value.name)?;
+        }
+        Ok(())
+    }
+
+    fn validate_implementer(
+        &self,
+        object: &ObjectType<'a, String>,
+        interface: &InterfaceType<'a, String>,
+    ) -> Result<(), Error> {
+        for field in &interface.fields {
+            let same = object
+                .fields
+                .iter()
+                .find(|f| f.name == field.name)
+                .map(|f| {
+                    f.field_type == field.field_type
+                        && f.arguments.len() == field.arguments.len()
+                        && f.arguments.iter().zip(&field.arguments).all(|(a, b)| {
+                            a.name == b.name
+                                && a.value_type == b.value_type
+                                && a.default_value == b.default_value
+                        })
+                });
+            match same {
+                Some(true) => {}
+                Some(false) => {
+                    return Err(unsupported(format!(
+                        "field `{}` of `{}` differs from the one of interface `{}`",
+                        field.name, object.name, interface.name,
+                    )))
+                }
+                None => {
+                    return Err(unsupported(format!(
+                        "`{}` lacks the field `{}` of interface `{}`",
+                        object.name, field.name, interface.name,
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn roots(&self) -> impl Iterator<Item = &'a str> {
+        std::iter::once(self.query)
+            .chain(self.mutation)
+            .chain(self.subscription)
+    }
+
+    /// Whether `object` is resolved by a trait rather than derived on a struct.
+    fn is_resolved(&self, object: &ObjectType<'a, String>) -> bool {
+        self.roots().any(|r| r == object.name)
+            || !object.implements_interfaces.is_empty()
+            || object.fields.iter().any(|f| !f.arguments.is_empty())
+    }
+
+    fn kind(&self, name: &str) -> Kind {
+        match self.types.get(name) {
+            None | Some(TypeDefinition::Scalar(_)) | Some(TypeDefinition::Enum(_)) => Kind::Leaf,
+            Some(TypeDefinition::InputObject(_)) => Kind::Input,
+            Some(_) => Kind::Output,
+        }
+    }
+
+    /// Whether a value of the type `name` is held inline by the generated types, which recursive
+    /// types can't do without boxing.
+    fn is_inline(&self, name: &str) -> bool {
+        match self.types.get(name) {
+            Some(TypeDefinition::Object(o)) => !self.is_resolved(o),
+            Some(TypeDefinition::Union(_)) | Some(TypeDefinition::InputObject(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Types held inline by the generated type `name`.
+    fn inline_types(&self, name: &str) -> Vec<&'a str> {
+        let fields = match self.types.get(name) {
+            Some(TypeDefinition::Object(o)) if !self.is_resolved(o) => {
+                o.fields.iter().map(|f| &f.field_type).collect()
+            }
+            Some(TypeDefinition::InputObject(i)) => {
+                i.fields.iter().map(|f| &f.value_type).collect()
+            }
+            Some(TypeDefinition::Union(u)) => {
+                return u.types.iter().map(String::as_str).collect();
+            }
+            _ => Vec::new(),
+        };
+        fields
+            .into_iter()
+            .filter_map(|ty| match strip_non_null(ty) {
+                Type::NamedType(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .filter(|name| self.is_inline(name))
+            .collect()
+    }
+
+    /// Whether a field of type `field_type` of the generated type `owner` has to be boxed.
+    fn is_recursive(&self, owner: &str, field_type: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from(vec![field_type]);
+        while let Some(name) = queue.pop_front() {
+            if name == owner {
+                return true;
+            }
+            if seen.insert(name) {
+                queue.extend(self.inline_types(name));
+            }
+        }
+        false
+    }
+
+    /// Rust type of the named GraphQL type.
+    fn rust_named_type(&self, name: &str) -> Result<String, Error> {
+        if let Some((_, ty)) = BUILTIN_SCALARS.iter().find(|(n, _)| *n == name) {
+            return Ok((*ty).into());
+        }
+        match self.types.get(name) {
+            Some(TypeDefinition::Scalar(_)) => self
+                .generator
+                .scalars
+                .get(name)
+                .cloned()
+                .ok_or_else(|| Error::UnknownScalar(name.into())),
+            Some(TypeDefinition::Interface(_)) => Ok(format!("{}Value", name)),
+            Some(_) => Ok(names::ident(name.into())),
+            None => Err(Error::UnknownType(name.into())),
+        }
+    }
+
+    /// Rust type of a GraphQL type, with the named type boxed if `boxed`.
+    fn rust_type(&self, ty: &Type<'a, String>, boxed: bool) -> Result<String, Error> {
+        Ok(match ty {
+            Type::NonNullType(inner) => self.rust_non_null_type(inner, boxed)?,
+            ty => format!("Option<{}>", self.rust_non_null_type(ty, boxed)?),
+        })
+    }
+
+    fn rust_non_null_type(&self, ty: &Type<'a, String>, boxed: bool) -> Result<String, Error> {
+        Ok(match ty {
+            Type::NamedType(name) if boxed => format!("Box<{}>", self.rust_named_type(name)?),
+            Type::NamedType(name) => self.rust_named_type(name)?,
+            Type::ListType(inner) => format!("Vec<{}>", self.rust_type(inner, false)?),
+            Type::NonNullType(inner) => self.rust_non_null_type(inner, boxed)?,
+        })
+    }
+
+    /// Rust type of a field of the generated type `owner`.
+    fn rust_field_type(&self, owner: &str, ty: &Type<'a, String>) -> Result<String, Error> {
+        let boxed = match strip_non_null(ty) {
+            Type::NamedType(name) => self.is_inline(name) && self.is_recursive(owner, name),
+            _ => false,
+        };
+        self.rust_type(ty, boxed)
+    }
+
+    /// Rust type of an argument or input field, whose default value makes it non-null.
+    fn rust_input_type(
+        &self,
+        owner: &str,
+        value: &InputValue<'a, String>,
+    ) -> Result<String, Error> {
+        match &value.default_value {
+            Some(_) => self.rust_non_null_type(&value.value_type, {
+                matches!(&value.value_type, Type::NamedType(name) if self.is_inline(name) && self.is_recursive(owner, name))
+            }),
+            None => self.rust_field_type(owner, &value.value_type),
+        }
+    }
+
+    /// Rust expression of the default value of the input value `name` of type `ty`.
+    fn default_expr(
+        &self,
+        ty: &Type<'a, String>,
+        value: &Value<'a, String>,
+        name: &str,
+    ) -> Result<String, Error> {
+        match (ty, value) {
+            (Type::NonNullType(_), _) => Err(unsupported(format!(
+                "default value of the non-null `{}`",
+                name,
+            ))),
+            (_, Value::Null) => Err(unsupported(format!("null default value of `{}`", name))),
+            (ty, value) => self.value_expr(ty, value, name),
+        }
+    }
+
+    fn value_expr(
+        &self,
+        ty: &Type<'a, String>,
+        value: &Value<'a, String>,
+        name: &str,
+    ) -> Result<String, Error> {
+        let unsupported_value =
+            || unsupported(format!("default value of `{}` of type `{}`", name, ty));
+        Ok(match (ty, value) {
+            (Type::NonNullType(inner), value) => self.value_expr(inner, value, name)?,
+            (Type::ListType(inner), Value::List(items)) => {
+                let items = items
+                    .iter()
+                    .map(|item| self.list_item_expr(inner, item, name))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("vec![{}]", items.join(", "))
+            }
+            (Type::ListType(inner), item) => {
+                format!("vec![{}]", self.list_item_expr(inner, item, name)?)
+            }
+            (Type::NamedType(ty), value) => match (ty.as_str(), value) {
+                ("Int", Value::Int(n)) => n
+                    .as_i64()
+                    .filter(|n| i32::try_from(*n).is_ok())
+                    .ok_or_else(unsupported_value)?
+                    .to_string(),
+                ("Float", Value::Float(f)) => format!("{:?}", f),
+                ("String", Value::String(s)) => format!("String::from({:?})", s),
+                ("ID", Value::String(s)) => format!("::juniper::ID::from(String::from({:?}))", s),
+                ("Boolean", Value::Boolean(b)) => b.to_string(),
+                _ => return Err(unsupported_value()),
+            },
+        })
+    }
+
+    fn list_item_expr(
+        &self,
+        ty: &Type<'a, String>,
+        value: &Value<'a, String>,
+        name: &str,
+    ) -> Result<String, Error> {
+        Ok(match (ty, value) {
+            (Type::NonNullType(_), _) => self.value_expr(ty, value, name)?,
+            (_, Value::Null) => "None".into(),
+            (ty, value) => format!("Some({})", self.value_expr(ty, value, name)?),
+        })
+    }
+
+    fn context(&self) -> &str {
+        &self.generator.context_type
+    }
+
+    fn write_schema_alias(&self, out: &mut Code) {
+        let ctx = self.context();
+        let mutation = match self.mutation {
+            Some(m) => names::ident(m.into()),
+            None => format!("::juniper::EmptyMutation<{}>", ctx),
+        };
+        let subscription = match self.subscription {
+            Some(s) => names::ident(s.into()),
+            None => format!("::juniper::EmptySubscription<{}>", ctx),
+        };
+        out.line(0, "");
+        out.line(0, "/// Schema of the root operation types.");
+        out.line(
+            0,
+            format!(
+                "pub type Schema = ::juniper::RootNode<'static, {}, {}, {}>;",
+                names::ident(self.query.into()),
+                mutation,
+                subscription,
+            ),
+        );
+    }
+
+    fn write_struct(&self, out: &mut Code, object: &ObjectType<'a, String>) -> Result<(), Error> {
+        out.line(0, "");
+        out.line(0, "#[derive(::juniper::GraphQLObject)]");
+        out.attr(
+            0,
+            Some(format!("context = {}", self.context()))
+                .into_iter()
+                .chain(description(&object.description)),
+        );
+        out.line(
+            0,
+            format!("pub struct {} {{", names::ident(object.name.clone())),
+        );
+        for f in &object.fields {
+            let ident = field_ident(&f.name);
+            let attrs = rename(&ident, &f.name)
+                .into_iter()
+                .chain(description(&f.description))
+                .chain(deprecation(&f.directives, &f.name)?);
+            out.attr(1, attrs);
+            out.line(
+                1,
+                format!(
+                    "pub {}: {},",
+                    ident,
+                    self.rust_field_type(&object.name, &f.field_type)?,
+                ),
+            );
+        }
+        out.line(0, "}");
+        Ok(())
+    }
+
+    /// Parameters of the resolver of `field`, with the names given by `param`.
+    fn params(
+        &self,
+        field: &Field<'a, String>,
+        mut param: impl FnMut(&InputValue<'a, String>, String) -> Result<String, Error>,
+    ) -> Result<String, Error> {
+        let mut params = vec!["&self".to_string(), format!("context: &{}", self.context())];
+        for arg in &field.arguments {
+            params.push(param(arg, arg_ident(&arg.name))?);
+        }
+        Ok(params.join(", "))
+    }
+
+    fn return_type(&self, field: &Field<'a, String>, stream: bool) -> Result<String, Error> {
+        let ty = self.rust_type(&field.field_type, false)?;
+        Ok(if stream {
+            format!(
+                "::juniper::FieldResult<::juniper::futures::stream::BoxStream<'static, {}>>",
+                ty,
+            )
+        } else {
+            format!("::juniper::FieldResult<{}>", ty)
+        })
+    }
+
+    fn write_resolved(&self, out: &mut Code, object: &ObjectType<'a, String>) -> Result<(), Error> {
+        let name = names::ident(object.name.clone());
+        let resolvers = format!("{}Resolvers", object.name);
+        let is_subscription = self.subscription == Some(object.name.as_str());
+        let asyncness = if is_subscription { "async " } else { "" };
+
+        out.line(0, "");
+        out.line(0, format!("/// Resolvers of the fields of [`{}`].", name));
+        if is_subscription {
+            out.line(0, "#[::juniper::async_trait]");
+        }
+        out.line(0, format!("pub trait {}: Send + Sync {{", resolvers));
+        for f in &object.fields {
+            let params = self.params(f, |arg, ident| {
+                Ok(format!(
+                    "{}: {}",
+                    ident,
+                    self.rust_input_type(&object.name, arg)?
+                ))
+            })?;
+            out.line(
+                1,
+                format!(
+                    "{}fn {}({}) -> {};",
+                    asyncness,
+                    field_ident(&f.name),
+                    params,
+                    self.return_type(f, is_subscription)?,
+                ),
+            );
+        }
+        out.line(0, "}");
+
+        out.line(0, "");
+        if let Some(desc) = &object.description {
+            out.line(0, format!("#[doc = {:?}]", desc));
+        }
+        out.line(
+            0,
+            format!("pub struct {}(pub Box<dyn {}>);", name, resolvers),
+        );
+
+        out.line(0, "");
+        let mut attrs = vec![format!("context = {}", self.context())];
+        attrs.extend(description(&object.description));
+        if !object.implements_interfaces.is_empty() {
+            let values = object
+                .implements_interfaces
+                .iter()
+                .map(|i| format!("{}Value", i))
+                .collect::<Vec<_>>();
+            attrs.push(format!("impl = [{}]", values.join(", ")));
+        }
+        let macro_name = if is_subscription {
+            "graphql_subscription"
+        } else {
+            "graphql_object"
+        };
+        out.line(
+            0,
+            format!("#[::juniper::{}({})]", macro_name, attrs.join(", ")),
+        );
+        out.line(0, format!("impl {} {{", name));
+        for f in &object.fields {
+            let ident = field_ident(&f.name);
+            let mut attrs = rename(&ident, &f.name)
+                .into_iter()
+                .chain(description(&f.description))
+                .chain(deprecation(&f.directives, &f.name)?)
+                .collect::<Vec<_>>();
+            let mut args = Vec::new();
+            for arg in &f.arguments {
+                let arg_attrs = self.arg_attrs(arg)?;
+                if !arg_attrs.is_empty() {
+                    args.push(format!(
+                        "{}({})",
+                        arg_ident(&arg.name),
+                        arg_attrs.join(", ")
+                    ));
+                }
+            }
+            if !args.is_empty() {
+                attrs.push(format!("arguments({})", args.join(", ")));
+            }
+            out.attr(1, attrs);
+
+            let params = self.params(f, |arg, ident| {
+                Ok(format!(
+                    "{}: {}",
+                    ident,
+                    self.rust_input_type(&object.name, arg)?
+                ))
+            })?;
+            let call_args = std::iter::once("&*self.0".to_string())
+                .chain(std::iter::once("context".to_string()))
+                .chain(f.arguments.iter().map(|arg| arg_ident(&arg.name)))
+                .collect::<Vec<_>>();
+            out.line(
+                1,
+                format!(
+                    "{}fn {}({}) -> {} {{",
+                    asyncness,
+                    ident,
+                    params,
+                    self.return_type(f, is_subscription)?,
+                ),
+            );
+            out.line(
+                2,
+                format!(
+                    "{}::{}({}){}",
+                    resolvers,
+                    ident,
+                    call_args.join(", "),
+                    if is_subscription { ".await" } else { "" },
+                ),
+            );
+            out.line(1, "}");
+        }
+        out.line(0, "}");
+
+        for i in &object.implements_interfaces {
+            let interface = match self.types.get(i.as_str()) {
+                Some(TypeDefinition::Interface(i)) => i,
+                _ => continue,
+            };
+            out.line(0, "");
+            out.line(0, "#[::juniper::graphql_interface]");
+            out.line(
+                0,
+                format!("impl {} for {} {{", names::ident(i.clone()), name),
+            );
+            for f in &interface.fields {
+                let ident = field_ident(&f.name);
+                let params = self.params(f, |arg, ident| {
+                    Ok(format!(
+                        "{}: {}",
+                        ident,
+                        self.rust_input_type(&object.name, arg)?
+                    ))
+                })?;
+                let call_args = std::iter::once("&*self.0".to_string())
+                    .chain(std::iter::once("context".to_string()))
+                    .chain(f.arguments.iter().map(|arg| arg_ident(&arg.name)))
+                    .collect::<Vec<_>>();
+                out.line(
+                    1,
+                    format!(
+                        "fn {}({}) -> {} {{",
+                        ident,
+                        params,
+                        self.return_type(f, false)?,
+                    ),
+                );
+                out.line(
+                    2,
+                    format!("{}::{}({})", resolvers, ident, call_args.join(", ")),
+                );
+                out.line(1, "}");
+            }
+            out.line(0, "}");
+        }
+        Ok(())
+    }
+
+    /// Attributes of an argument which `juniper_codegen` can't guess from its Rust parameter.
+    fn arg_attrs(&self, arg: &InputValue<'a, String>) -> Result<Vec<String>, Error> {
+        let mut attrs = rename(&arg_ident(&arg.name), &arg.name)
+            .into_iter()
+            .chain(description(&arg.description))
+            .collect::<Vec<_>>();
+        if let Some(default) = &arg.default_value {
+            attrs.push(format!(
+                "default = {}",
+                self.default_expr(&arg.value_type, default, &arg.name)?,
+            ));
+        }
+        Ok(attrs)
+    }
+
+    fn write_interface(
+        &self,
+        out: &mut Code,
+        interface: &InterfaceType<'a, String>,
+    ) -> Result<(), Error> {
+        let implementers = self.implementers[interface.name.as_str()]
+            .iter()
+            .map(|o| names::ident(o.name.clone()))
+            .collect::<Vec<_>>();
+        let mut attrs = vec![
+            format!("for = [{}]", implementers.join(", ")),
+            format!("context = {}", self.context()),
+        ];
+        attrs.extend(description(&interface.description));
+
+        out.line(0, "");
+        out.line(
+            0,
+            format!("#[::juniper::graphql_interface({})]", attrs.join(", ")),
+        );
+        out.line(
+            0,
+            format!("pub trait {} {{", names::ident(interface.name.clone())),
+        );
+        for f in &interface.fields {
+            let ident = field_ident(&f.name);
+            let attrs = rename(&ident, &f.name)
+                .into_iter()
+                .chain(description(&f.description))
+                .chain(deprecation(&f.directives, &f.name)?)
+                .collect::<Vec<_>>();
+            if !attrs.is_empty() {
+                out.attr(1, attrs);
+            }
+            let params = self.params(f, |arg, ident| {
+                let attrs = self.arg_attrs(arg)?;
+                let ty = self.rust_input_type(&interface.name, arg)?;
+                Ok(if attrs.is_empty() {
+                    format!("{}: {}", ident, ty)
+                } else {
+                    format!("{} {}: {}", graphql_attr(attrs), ident, ty)
+                })
+            })?;
+            out.line(
+                1,
+                format!(
+                    "fn {}({}) -> {};",
+                    ident,
+                    params,
+                    self.return_type(f, false)?
+                ),
+            );
+        }
+        out.line(0, "}");
+        Ok(())
+    }
+
+    fn write_union(&self, out: &mut Code, union: &UnionType<'a, String>) {
+        out.line(0, "");
+        out.line(0, "#[derive(::juniper::GraphQLUnion)]");
+        out.attr(
+            0,
+            Some(format!("context = {}", self.context()))
+                .into_iter()
+                .chain(description(&union.description)),
+        );
+        out.line(
+            0,
+            format!("pub enum {} {{", names::ident(union.name.clone())),
+        );
+        for member in &union.types {
+            let ty = names::ident(member.clone());
+            if self.is_inline(member) && self.is_recursive(&union.name, member) {
+                out.line(1, format!("{}(Box<{}>),", ty, ty));
+            } else {
+                out.line(1, format!("{}({}),", ty, ty));
+            }
+        }
+        out.line(0, "}");
+    }
+
+    fn write_enum(&self, out: &mut Code, enum_type: &EnumType<'a, String>) {
+        out.line(0, "");
+        out.line(
+            0,
+            "#[derive(::juniper::GraphQLEnum, Clone, Copy, Debug, Eq, PartialEq)]",
+        );
+        if let Some(attr) = description(&enum_type.description) {
+            out.attr(0, Some(attr));
+        }
+        out.line(
+            0,
+            format!("pub enum {} {{", names::ident(enum_type.name.clone())),
+        );
+        for v in &enum_type.values {
+            let ident = variant_ident(&v.name);
+            let attrs = Some(format!("name = {:?}", v.name))
+                .filter(|_| names::to_upper_snake_case(names::unraw(&ident)) != v.name)
+                .into_iter()
+                .chain(description(&v.description))
+                // Validated beforehand.
+                .chain(deprecation(&v.directives, &v.name).unwrap_or_default())
+                .collect::<Vec<_>>();
+            if !attrs.is_empty() {
+                out.attr(1, attrs);
+            }
+            out.line(1, format!("{},", ident));
+        }
+        out.line(0, "}");
+    }
+
+    fn write_input_object(
+        &self,
+        out: &mut Code,
+        input: &InputObjectType<'a, String>,
+    ) -> Result<(), Error> {
+        out.line(0, "");
+        out.line(0, "#[derive(::juniper::GraphQLInputObject)]");
+        if let Some(attr) = description(&input.description) {
+            out.attr(0, Some(attr));
+        }
+        out.line(
+            0,
+            format!("pub struct {} {{", names::ident(input.name.clone())),
+        );
+        for f in &input.fields {
+            let ident = field_ident(&f.name);
+            let mut attrs = rename(&ident, &f.name)
+                .into_iter()
+                .chain(description(&f.description))
+                .collect::<Vec<_>>();
+            if let Some(default) = &f.default_value {
+                attrs.push(format!(
+                    "default = {:?}",
+                    self.default_expr(&f.value_type, default, &f.name)?,
+                ));
+            }
+            if !attrs.is_empty() {
+                out.attr(1, attrs);
+            }
+            out.line(
+                1,
+                format!("pub {}: {},", ident, self.rust_input_type(&input.name, f)?),
+            );
+        }
+        out.line(0, "}");
+        Ok(())
+    }
+
+    /// Writes the test asserting that the generated schema prints back as the input one.
+    fn write_test(&self, out: &mut Code) -> Result<(), Error> {
+        let sdl = self.canonical_sdl();
+        let mut hashes = "#".to_string();
+        while sdl.contains(&format!("\"{}", hashes)) {
+            hashes.push('#');
+        }
+
+        out.line(0, "");
+        out.line(0, "#[cfg(test)]");
+        out.line(0, "mod schema_language_tests {");
+        out.line(1, "use super::*;");
+        out.line(0, "");
+        out.line(1, "struct Unimplemented;");
+
+        for root in self.roots() {
+            let object = match self.types.get(root) {
+                Some(TypeDefinition::Object(o)) => o,
+                _ => continue,
+            };
+            let is_subscription = self.subscription == Some(root);
+            out.line(0, "");
+            if is_subscription {
+                out.line(1, "#[::juniper::async_trait]");
+            }
+            out.line(1, format!("impl {}Resolvers for Unimplemented {{", root));
+            for f in &object.fields {
+                let params = self
+                    .params(f, |arg, _| {
+                        Ok(format!("_: {}", self.rust_input_type(root, arg)?))
+                    })?
+                    .replacen("context:", "_:", 1);
+                out.line(
+                    2,
+                    format!(
+                        "{}fn {}({}) -> {} {{",
+                        if is_subscription { "async " } else { "" },
+                        field_ident(&f.name),
+                        params,
+                        self.return_type(f, is_subscription)?,
+                    ),
+                );
+                out.line(3, "unimplemented!()");
+                out.line(2, "}");
+            }
+            out.line(1, "}");
+        }
+
+        let root_value = |root: Option<&str>, empty: &str| match root {
+            Some(r) => format!("{}(Box::new(Unimplemented))", names::ident(r.into())),
+            None => format!("::juniper::{}::new()", empty),
+        };
+        out.line(0, "");
+        out.line(1, "#[test]");
+        out.line(1, "fn prints_the_schema_back() {");
+        out.line(2, "let schema = Schema::new(");
+        out.line(3, format!("{},", root_value(Some(self.query), "")));
+        out.line(
+            3,
+            format!("{},", root_value(self.mutation, "EmptyMutation")),
+        );
+        out.line(
+            3,
+            format!("{},", root_value(self.subscription, "EmptySubscription")),
+        );
+        out.line(2, ");");
+        out.line(0, "");
+        out.line(2, "assert_eq!(");
+        out.line(3, "schema.as_schema_language(),");
+        out.0
+            .push_str(&format!("            r{}\"{}\"{},\n", hashes, sdl, hashes));
+        out.line(2, ");");
+        out.line(1, "}");
+        out.line(0, "}");
+        Ok(())
+    }
+
+    /// Schema language of the schema as printed by `RootNode::as_schema_language`, which only
+    /// knows of the types reachable from the root operation types and sorts them by name.
+    fn canonical_sdl(&self) -> String {
+        let mut reachable = HashSet::new();
+        let mut queue = self.roots().collect::<VecDeque<_>>();
+        while let Some(name) = queue.pop_front() {
+            let def = match self.types.get(name) {
+                Some(def) if !is_builtin(name) && reachable.insert(name) => def,
+                _ => continue,
+            };
+            let fields_types = |fields: &'a [Field<'a, String>]| {
+                fields.iter().flat_map(|f| {
+                    std::iter::once(base_type(&f.field_type))
+                        .chain(f.arguments.iter().map(|a| base_type(&a.value_type)))
+                })
+            };
+            match def {
+                TypeDefinition::Object(o) => {
+                    queue.extend(fields_types(&o.fields));
+                    queue.extend(o.implements_interfaces.iter().map(String::as_str));
+                }
+                TypeDefinition::Interface(i) => {
+                    queue.extend(fields_types(&i.fields));
+                    queue.extend(
+                        self.implementers[i.name.as_str()]
+                            .iter()
+                            .map(|o| o.name.as_str()),
+                    );
+                }
+                TypeDefinition::Union(u) => queue.extend(u.types.iter().map(String::as_str)),
+                TypeDefinition::InputObject(i) => {
+                    queue.extend(i.fields.iter().map(|f| base_type(&f.value_type)))
+                }
+                TypeDefinition::Scalar(_) | TypeDefinition::Enum(_) => {}
+            }
+        }
+
+        let mut definitions = self
+            .definitions
+            .iter()
+            .filter(|def| reachable.contains(type_name(def)))
+            .map(|def| Definition::TypeDefinition((*def).clone()))
+            .collect::<Vec<_>>();
+        definitions.sort_by(|a, b| match (a, b) {
+            (Definition::TypeDefinition(a), Definition::TypeDefinition(b)) => {
+                type_name(a).cmp(type_name(b))
+            }
+            _ => std::cmp::Ordering::Equal,
+        });
+        definitions.push(Definition::SchemaDefinition(SchemaDefinition {
+            position: Default::default(),
+            directives: Vec::new(),
+            query: Some(self.query.into()),
+            mutation: self.mutation.map(Into::into),
+            subscription: self.subscription.map(Into::into),
+        }));
+        Document { definitions }.to_string()
+    }
+}
+
+/// Kind of values of a GraphQL type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Kind {
+    Leaf,
+    Input,
+    Output,
+}
+
+fn unsupported(msg: impl Into<String>) -> Error {
+    Error::Unsupported(msg.into())
+}
+
+fn is_builtin(name: &str) -> bool {
+    BUILTIN_SCALARS.iter().any(|(n, _)| *n == name)
+}
+
+fn type_name<'a>(def: &'a Def<'_>) -> &'a str {
+    match def {
+        TypeDefinition::Scalar(t) => &t.name,
+        TypeDefinition::Object(t) => &t.name,
+        TypeDefinition::Interface(t) => &t.name,
+        TypeDefinition::Union(t) => &t.name,
+        TypeDefinition::Enum(t) => &t.name,
+        TypeDefinition::InputObject(t) => &t.name,
+    }
+}
+
+fn strip_non_null<'t, 'a>(ty: &'t Type<'a, String>) -> &'t Type<'a, String> {
+    match ty {
+        Type::NonNullType(inner) => inner,
+        ty => ty,
+    }
+}
+
+/// Name of the named type wrapped by `ty`.
+fn base_type<'t>(ty: &'t Type<'_, String>) -> &'t str {
+    match ty {
+        Type::NamedType(name) => name,
+        Type::ListType(inner) | Type::NonNullType(inner) => base_type(inner),
+    }
+}
+
+fn field_ident(name: &str) -> String {
+    names::ident(names::to_snake_case(name))
+}
+
+/// Identifier of an argument, which can't clash with the context parameter of the resolvers or
+/// the names `juniper_codegen` reserves.
+fn arg_ident(name: &str) -> String {
+    let ident = field_ident(name);
+    match names::unraw(&ident) {
+        "context" | "ctx" | "executor" => format!("{}_", ident),
+        _ => ident,
+    }
+}
+
+fn variant_ident(name: &str) -> String {
+    names::ident(names::to_pascal_case(name))
+}
+
+/// `name` attribute of the Rust item `ident`, if `juniper_codegen` wouldn't name it `name`.
+fn rename(ident: &str, name: &str) -> Option<String> {
+    Some(format!("name = {:?}", name)).filter(|_| names::to_camel_case(names::unraw(ident)) != name)
+}
+
+fn description(description: &Option<String>) -> Option<String> {
+    description
+        .as_ref()
+        .map(|d| format!("description = {:?}", d))
+}
+
+fn graphql_attr(attrs: impl IntoIterator<Item = String>) -> String {
+    format!(
+        "#[graphql({})]",
+        attrs.into_iter().collect::<Vec<_>>().join(", ")
+    )
+}
+
+fn no_directives(directives: &[Directive<'_, String>], name: &str) -> Result<(), Error> {
+    match directives.first() {
+        Some(d) => Err(unsupported(format!(
+            "directive `@{}` applied to `{}`",
+            d.name, name
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// `deprecated` attribute of the `@deprecated` field or enum value `name`.
+fn deprecation(directives: &[Directive<'_, String>], name: &str) -> Result<Option<String>, Error> {
+    let mut attr = None;
+    for d in directives {
+        if d.name != "deprecated" {
+            return Err(unsupported(format!(
+                "directive `@{}` applied to `{}`",
+                d.name, name
+            )));
+        }
+        attr = Some(match d.arguments.as_slice() {
+            [] => "deprecated".to_string(),
+            [(arg, Value::String(reason))] if arg == "reason" => {
+                format!("deprecated = {:?}", reason)
+            }
+            _ => {
+                return Err(unsupported(format!(
+                    "arguments of `@deprecated` on `{}`",
+                    name
+                )))
+            }
+        });
+    }
+    Ok(attr)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Generator};
+
+    fn generate(sdl: &str) -> Result<String, Error> {
+        Generator::new().generate(sdl)
+    }
+
+    #[test]
+    fn boxes_recursive_fields_only() {
+        let code = generate(
+            "type Query { root: Node }
+             type Node { parent: Node, children: [Node!]!, leaf: Leaf! }
+             type Leaf { name: String! }",
+        )
+        .unwrap();
+
+        assert!(code.contains("pub parent: Option<Box<Node>>,"));
+        assert!(code.contains("pub children: Vec<Node>,"));
+        assert!(code.contains("pub leaf: Leaf,"));
+    }
+
+    #[test]
+    fn renames_what_juniper_codegen_would_not_guess() {
+        let code = generate(
+            "type Query { URL(type: String, context: Int): String }
+             enum Color { RED, lightBlue }",
+        )
+        .unwrap();
+
+        assert!(code.contains(r#"#[graphql(name = "URL")]"#));
+        assert!(code.contains(
+            "fn url(&self, context: &(), r#type: Option<String>, context_: Option<i32>)",
+        ));
+        assert!(code.contains("    Red,\n"));
+        assert!(code.contains("    #[graphql(name = \"lightBlue\")]\n    LightBlue,\n"));
+    }
+
+    #[test]
+    fn maps_custom_scalars() {
+        let sdl = "scalar DateTime type Query { now: DateTime! }";
+
+        assert!(matches!(
+            generate(sdl),
+            Err(Error::UnknownScalar(name)) if name == "DateTime",
+        ));
+        let code = Generator::new()
+            .with_scalar("DateTime", "chrono::DateTime<chrono::Utc>")
+            .generate(sdl)
+            .unwrap();
+        assert!(code.contains("::juniper::FieldResult<chrono::DateTime<chrono::Utc>>"));
+    }
+
+    #[test]
+    fn rejects_unsupported_schemas() {
+        for sdl in &[
+            "type Query { a: Int } extend type Query { b: Int }",
+            "directive @key on OBJECT type Query { a: Int }",
+            "type Query @key { a: Int }",
+            "type Query { a(b: Int! = 1): Int }",
+            "type Query { a(b: Color = RED): Int } enum Color { RED }",
+            "type Query { a: Node } interface Node { id: ID! }",
+            "type Query implements Node { id: Int } interface Node { id: ID! }",
+            "schema { query: Root } union Root = Query type Query { a: Int }",
+        ] {
+            assert!(
+                matches!(generate(sdl), Err(Error::Unsupported(_))),
+                "generated `{}`",
+                sdl,
+            );
+        }
+        assert!(matches!(
+            generate("type Query { a: Missing }"),
+            Err(Error::UnknownType(name)) if name == "Missing",
+        ));
+        assert!(matches!(generate("type Query {"), Err(Error::Parse(_))));
+    }
+}
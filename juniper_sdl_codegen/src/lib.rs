@@ -0,0 +1,212 @@
+/*!
+
+# juniper_sdl_codegen
+
+Schema-first development for [Juniper][Juniper]: this crate reads a schema written in the
+[GraphQL Schema Language][SDL] and generates the Rust skeleton of its types.
+
+* Objects whose fields take no arguments become `#[derive(GraphQLObject)]` structs.
+* The root operation types, objects with field arguments and the implementers of interfaces are
+  resolved by traits: a `QueryResolvers` trait for a `Query` type, implemented by the application
+  and wrapped by a `#[graphql_object]` `Query(pub Box<dyn QueryResolvers>)` struct.
+* Interfaces become `#[graphql_interface]` traits, unions `GraphQLUnion` enums, enums
+  `GraphQLEnum`s and input objects `GraphQLInputObject`s.
+* A `Schema` alias names the `RootNode` of the root operation types.
+
+The names, descriptions, nullability, default values and deprecations of the schema are kept, and
+the generated code comes with a test asserting that `RootNode::as_schema_language` prints the
+schema back.
+
+## Build scripts
+
+The generator is meant to run in a `build.rs`, with the generated code included in the crate:
+
+```no_run
+// In the `main` of `build.rs`.
+juniper_sdl_codegen::Generator::new()
+    .with_context_type("crate::Context")
+    .with_scalar("DateTime", "chrono::DateTime<chrono::Utc>")
+    .compile("schema.graphql")
+    .unwrap();
+```
+
+```ignore
+// src/schema.rs
+include!(concat!(env!("OUT_DIR"), "/schema.rs"));
+```
+
+## Limitations
+
+Custom scalars must be mapped to Rust types implementing them with [`Generator::with_scalar`].
+Directive definitions, applied directives other than `@deprecated`, type extensions, non-null
+arguments or input fields with default values, enum, input object and custom scalar default
+values, and interface fields resolved with other arguments or types by their implementers aren't
+supported, and types which can't be reached from the root operation types aren't part of the
+resulting schema.
+
+## Links
+
+* [Juniper][Juniper]
+* [Api Reference][documentation]
+
+## License
+
+This project is under the BSD-2 license.
+
+Check the LICENSE file for details.
+
+[Juniper]: https://github.com/graphql-rust/juniper
+[SDL]: https://graphql.org/learn/schema/#type-language
+[documentation]: https://docs.rs/juniper_sdl_codegen
+
+*/
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+
+mod codegen;
+mod names;
+
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use graphql_parser::schema::ParseError;
+
+/// Generator of the Rust code of schemas written in the GraphQL Schema Language.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    context_type: String,
+    scalars: BTreeMap<String, String>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator {
+    /// Creates a generator resolving fields without context, which can be set with
+    /// [`Generator::with_context_type`].
+    pub fn new() -> Self {
+        Self {
+            context_type: "()".into(),
+            scalars: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the path of the context type passed to the resolvers, like `crate::Context`.
+    ///
+    /// The path is used as is by the generated code, so it should be absolute.
+    pub fn with_context_type(mut self, context_type: impl Into<String>) -> Self {
+        self.context_type = context_type.into();
+        self
+    }
+
+    /// Maps the custom scalar `name` of the schema to the Rust type at `rust_type`.
+    ///
+    /// The Rust type must implement the scalar under the same GraphQL name, like with
+    /// `#[graphql_scalar]`.
+    pub fn with_scalar(mut self, name: impl Into<String>, rust_type: impl Into<String>) -> Self {
+        self.scalars.insert(name.into(), rust_type.into());
+        self
+    }
+
+    /// Generates the Rust code of the given schema.
+    pub fn generate(&self, sdl: &str) -> Result<String, Error> {
+        codegen::generate(self, sdl)
+    }
+
+    /// Generates the Rust code of the schema in the `input` file to the `output` file.
+    pub fn generate_file(
+        &self,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> Result<(), Error> {
+        let sdl = fs::read_to_string(input)?;
+        fs::write(output, self.generate(&sdl)?)?;
+        Ok(())
+    }
+
+    /// Generates the Rust code of the schema in the `input` file from a build script.
+    ///
+    /// The code is written to the `OUT_DIR` of the build, in a file named after the input one
+    /// with an `.rs` extension, whose path is returned. The build script is rerun when the input
+    /// file changes.
+    pub fn compile(&self, input: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        let input = input.as_ref();
+        let out_dir = env::var_os("OUT_DIR").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "`OUT_DIR` isn't set, `compile` should be called from a build script",
+            )
+        })?;
+        let output = Path::new(&out_dir)
+            .join(input.file_name().unwrap_or_default())
+            .with_extension("rs");
+
+        println!("cargo:rerun-if-changed={}", input.display());
+        self.generate_file(input, &output)?;
+        Ok(output)
+    }
+}
+
+/// Error of generating the code of a schema.
+#[derive(Debug)]
+pub enum Error {
+    /// The schema couldn't be read or the code couldn't be written.
+    Io(io::Error),
+
+    /// The schema isn't valid GraphQL Schema Language.
+    Parse(ParseError),
+
+    /// The schema refers to a type it doesn't define.
+    UnknownType(String),
+
+    /// The schema defines a custom scalar not mapped with [`Generator::with_scalar`].
+    UnknownScalar(String),
+
+    /// The schema uses a feature which can't be generated.
+    Unsupported(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "io error: {}", e),
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::UnknownType(name) => write!(f, "unknown type `{}`", name),
+            Self::UnknownScalar(name) => write!(
+                f,
+                "custom scalar `{}` isn't mapped to a Rust type, use `Generator::with_scalar`",
+                name,
+            ),
+            Self::Unsupported(msg) => write!(f, "unsupported schema: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
@@ -0,0 +1,156 @@
+//! Conversions between GraphQL names and Rust identifiers.
+//!
+//! The conversions back from Rust identifiers mirror the default renaming of `juniper_codegen`,
+//! so the generated code only spells out the names it doesn't guess right.
+
+/// Rust keywords, which can only be used as raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Keywords which can't even be used as raw identifiers.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Makes a valid Rust identifier of `name`, escaping keywords.
+pub(crate) fn ident(name: String) -> String {
+    if RESERVED.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// Strips the `r#` prefix of a raw identifier.
+pub(crate) fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
+
+/// Converts a `camelCase` GraphQL field or argument name to a `snake_case` Rust identifier.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            snake.push(c);
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    snake
+}
+
+/// Converts a `SCREAMING_SNAKE_CASE` GraphQL enum value to a `PascalCase` Rust identifier.
+pub(crate) fn to_pascal_case(name: &str) -> String {
+    let mut pascal = String::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            pascal.extend(first.to_uppercase());
+            if part.chars().all(|c| !c.is_lowercase()) {
+                pascal.extend(chars.flat_map(char::to_lowercase));
+            } else {
+                pascal.extend(chars);
+            }
+        }
+    }
+    if pascal.is_empty() || pascal.starts_with(|c: char| c.is_ascii_digit()) {
+        pascal.insert(0, '_');
+    }
+    pascal
+}
+
+/// Default GraphQL name given by `juniper_codegen` to fields and arguments.
+pub(crate) fn to_camel_case(ident: &str) -> String {
+    let mut dest = String::new();
+    let parts = if let Some(s) = ident.strip_prefix("__") {
+        dest.push_str("__");
+        s
+    } else {
+        ident.strip_prefix('_').unwrap_or(ident)
+    }
+    .split('_')
+    .enumerate();
+
+    for (i, part) in parts {
+        let mut chars = part.chars();
+        match chars.next() {
+            Some(first) if i > 0 => {
+                dest.extend(first.to_uppercase());
+                dest.push_str(chars.as_str());
+            }
+            _ if i == 0 => dest.push_str(part),
+            _ => {}
+        }
+    }
+    dest
+}
+
+/// Default GraphQL name given by `juniper_codegen` to enum values.
+pub(crate) fn to_upper_snake_case(ident: &str) -> String {
+    let mut last_lower = false;
+    let mut upper = String::new();
+    for c in ident.chars() {
+        if c == '_' {
+            last_lower = false;
+        } else if c.is_lowercase() {
+            last_lower = true;
+        } else if c.is_uppercase() {
+            if last_lower {
+                upper.push('_');
+            }
+            last_lower = false;
+        }
+        upper.extend(c.to_uppercase());
+    }
+    upper
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ident, to_camel_case, to_pascal_case, to_snake_case, to_upper_snake_case};
+
+    #[test]
+    fn converts_field_names() {
+        for (name, snake) in &[
+            ("id", "id"),
+            ("appearsIn", "appears_in"),
+            ("homePlanet2", "home_planet2"),
+            ("URL", "url"),
+        ] {
+            assert_eq!(&to_snake_case(name), snake);
+        }
+        assert_eq!(to_camel_case("appears_in"), "appearsIn");
+        assert_ne!(to_camel_case("url"), "URL");
+    }
+
+    #[test]
+    fn converts_enum_values() {
+        for (name, pascal) in &[
+            ("NEW_HOPE", "NewHope"),
+            ("EMPIRE", "Empire"),
+            ("newHope", "NewHope"),
+            ("_1", "_1"),
+        ] {
+            assert_eq!(&to_pascal_case(name), pascal);
+        }
+        assert_eq!(to_upper_snake_case("NewHope"), "NEW_HOPE");
+    }
+
+    #[test]
+    fn escapes_keywords() {
+        assert_eq!(ident("type".into()), "r#type");
+        assert_eq!(ident("self".into()), "self_");
+        assert_eq!(ident("name".into()), "name");
+    }
+}
@@ -40,15 +40,18 @@ Check the LICENSE file for details.
 #![deny(warnings)]
 #![doc(html_root_url = "https://docs.rs/juniper_warp/0.2.0")]
 
-use anyhow::anyhow;
-use futures::{FutureExt as _, TryFutureExt};
+use futures::{Stream, StreamExt as _, TryFutureExt};
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
+    http::{
+        graphiql::GraphiQLConfig,
+        handler::{self, HandlerConfig, HttpRequest, HttpResponse, MediaType},
+        playground::PlaygroundConfig,
+    },
     ScalarValue,
 };
-use std::{collections::HashMap, str, sync::Arc};
+use std::sync::Arc;
 use tokio::task;
-use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
+use warp::{body, filters::BoxedFilter, http, hyper::body::Buf, query, Filter};
 
 /// Make a filter for graphql queries/mutations.
 ///
@@ -56,7 +59,8 @@ use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
 ///
 /// The `context_extractor` argument should be a filter that provides the GraphQL context required by the schema.
 ///
-/// In order to avoid blocking, this helper will use the `tokio_threadpool` threadpool created by hyper to resolve GraphQL requests.
+/// Requests are handled as described in [`juniper::http::handler`], with the default
+/// [`HandlerConfig`].
 ///
 /// Example:
 ///
@@ -118,80 +122,103 @@ where
     CtxT: Send + Sync + 'static,
     S: ScalarValue + Send + Sync + 'static,
 {
-    let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
-
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let resp = req.execute(&schema, &context).await;
-
-            Ok::<_, warp::Rejection>(build_response(
-                serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
-                    .map_err(Into::into),
-            ))
-        }
-    };
-    let post_json_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
-
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let query = str::from_utf8(body.as_ref())
-                .map_err(|e| anyhow!("Request body query is not a valid UTF-8 string: {}", e))?;
-            let req = GraphQLRequest::new(query.into(), None, None);
+    make_graphql_filter_with_config(schema, context_extractor, HandlerConfig::default())
+}
 
-            let resp = req.execute(&schema, &context).await;
+/// Make a synchronous filter for graphql endpoint.
+///
+/// Requests are handled as described in [`juniper::http::handler`], with the default
+/// [`HandlerConfig`], and executed on the blocking thread pool of tokio.
+pub fn make_graphql_filter_sync<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    make_graphql_filter_sync_with_config(schema, context_extractor, HandlerConfig::default())
+}
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-        }
-        .then(|res| async { Ok::<_, warp::Rejection>(build_response(res)) })
-    };
-    let post_graphql_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::bytes())
-        .and_then(handle_post_graphql_request);
+/// Make a filter for graphql queries/mutations, checking them against the given [`HandlerConfig`].
+///
+/// Requests are handled as described in [`juniper::http::handler`], and their bodies are read up
+/// to the maximum size of the `config`, if any.
+///
+/// Example:
+///
+/// ```
+/// # use juniper::{
+/// #     http::handler::HandlerConfig,
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_warp::make_graphql_filter_with_config;
+/// # use warp::Filter;
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+/// let context_extractor = warp::any().map(Database::new).boxed();
+/// let config = HandlerConfig::new()
+///     .with_csrf_prevention()
+///     .with_max_body_size(64 * 1024);
+///
+/// let graphql_endpoint = warp::path("graphql")
+///     .and(make_graphql_filter_with_config(schema, context_extractor, config));
+/// ```
+pub fn make_graphql_filter_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: HandlerConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let schema = Arc::new(schema);
+    let config = Arc::new(config);
+    let request_filter = buffered_request(config.clone());
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+    let handle_request = move |context: CtxT, req: Result<BufferedRequest, HttpResponse>| {
         let schema = schema.clone();
+        let config = config.clone();
         async move {
-            let req = GraphQLRequest::new(
-                qry.remove("query")
-                    .ok_or_else(|| anyhow!("Missing GraphQL query string in query parameters"))?,
-                qry.remove("operation_name"),
-                qry.remove("variables")
-                    .map(|vs| serde_json::from_str(&vs))
-                    .transpose()?,
-            );
-
-            let resp = req.execute(&schema, &context).await;
-
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            let req = match req {
+                Ok(req) => req,
+                Err(resp) => return into_response(resp),
+            };
+            let headers = req.headers();
+            let resp = handler::handle_with_config(
+                req.as_http_request(&headers),
+                &schema,
+                &context,
+                &config,
+            )
+            .await;
+            into_response(resp)
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
     };
-    let get_filter = warp::get()
-        .and(context_extractor)
-        .and(query::query())
-        .and_then(handle_get_request);
-
-    get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
-        .unify()
+
+    context_extractor
+        .and(request_filter)
+        .then(handle_request)
         .boxed()
 }
 
-/// Make a synchronous filter for graphql endpoint.
-pub fn make_graphql_filter_sync<Query, Mutation, Subscription, CtxT, S>(
+/// Make a synchronous filter for graphql endpoint, checking the requests against the given
+/// [`HandlerConfig`], like [`make_graphql_filter_with_config()`].
+pub fn make_graphql_filter_sync_with_config<Query, Mutation, Subscription, CtxT, S>(
     schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context_extractor: BoxedFilter<(CtxT,)>,
+    config: HandlerConfig,
 ) -> BoxedFilter<(http::Response<Vec<u8>>,)>
 where
     Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
@@ -201,83 +228,134 @@ where
     S: ScalarValue + Send + Sync + 'static,
 {
     let schema = Arc::new(schema);
-    let post_json_schema = schema.clone();
-    let post_graphql_schema = schema.clone();
+    let config = Arc::new(config);
+    let request_filter = buffered_request(config.clone());
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
+    let handle_request = move |context: CtxT, req: Result<BufferedRequest, HttpResponse>| {
+        let schema = schema.clone();
+        let config = config.clone();
         async move {
-            let res = task::spawn_blocking(move || {
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            let req = match req {
+                Ok(req) => req,
+                Err(resp) => return Ok(into_response(resp)),
+            };
+            let resp = task::spawn_blocking(move || {
+                let headers = req.headers();
+                handler::handle_sync_with_config(
+                    req.as_http_request(&headers),
+                    &schema,
+                    &context,
+                    &config,
+                )
             })
             .await?;
-
-            Ok(build_response(res))
+            Ok(into_response(resp))
         }
         .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
     };
-    let post_json_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::json())
-        .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let query = str::from_utf8(body.as_ref())
-                    .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
-                let req = GraphQLRequest::new(query.into(), None, None);
+    context_extractor
+        .and(request_filter)
+        .and_then(handle_request)
+        .boxed()
+}
 
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+/// Parts of a warp request needed to build a [`HttpRequest`], with its body read to the end.
+struct BufferedRequest {
+    method: http::Method,
+    query: Option<String>,
+    headers: http::HeaderMap,
+    body: Vec<u8>,
+}
+
+impl BufferedRequest {
+    fn headers(&self) -> Vec<(&str, &str)> {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+            .collect()
+    }
 
-            Ok(build_response(res))
+    fn as_http_request<'a>(&'a self, headers: &'a [(&'a str, &'a str)]) -> HttpRequest<'a> {
+        let header = |name| self.headers.get(name).and_then(|v| v.to_str().ok());
+        HttpRequest {
+            method: self.method.as_str(),
+            query_string: self.query.as_deref(),
+            content_type: header(http::header::CONTENT_TYPE),
+            accept: header(http::header::ACCEPT),
+            headers,
+            body: &self.body,
         }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
-    let post_graphql_filter = warp::post()
-        .and(context_extractor.clone())
-        .and(body::bytes())
-        .and_then(handle_post_graphql_request);
+    }
+}
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
-        let schema = schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let req = GraphQLRequest::new(
-                    qry.remove("query").ok_or_else(|| {
-                        anyhow!("Missing GraphQL query string in query parameters")
-                    })?,
-                    qry.remove("operation_name"),
-                    qry.remove("variables")
-                        .map(|vs| serde_json::from_str(&vs))
-                        .transpose()?,
-                );
-
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+/// Extracts a [`BufferedRequest`], reading its body up to the maximum size of the `config`, if
+/// any, or the response to reject it with otherwise.
+fn buffered_request(
+    config: Arc<HandlerConfig>,
+) -> impl Filter<Extract = (Result<BufferedRequest, HttpResponse>,), Error = warp::Rejection> + Clone
+{
+    warp::method()
+        .and(query::raw().map(Some).or(warp::any().map(|| None)).unify())
+        .and(warp::header::headers_cloned())
+        .and(body::stream())
+        .then(move |method, query, headers: http::HeaderMap, body| {
+            let config = config.clone();
+            async move {
+                let body = read_body(&headers, body, &config).await?;
+                Ok(BufferedRequest {
+                    method,
+                    query,
+                    headers,
+                    body,
+                })
+            }
+        })
+}
+
+/// Reads the whole body of a request, failing as soon as it gets larger than the maximum size of
+/// the `config`, if any.
+async fn read_body(
+    headers: &http::HeaderMap,
+    body: impl Stream<Item = Result<impl Buf, warp::Error>>,
+    config: &HandlerConfig,
+) -> Result<Vec<u8>, HttpResponse> {
+    let content_length = headers
+        .get(http::header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok()?.parse().ok());
+    if let Some(content_length) = content_length {
+        config.check_body_size(content_length)?;
+    }
 
-            Ok(build_response(res))
+    futures::pin_mut!(body);
+    let mut buf = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let mut chunk = chunk.map_err(|e| {
+            HttpResponse::error(
+                400,
+                MediaType::Json,
+                &format!("Failed to read the request body: {}", e),
+            )
+        })?;
+        config.check_body_size(buf.len() + chunk.remaining())?;
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            buf.extend_from_slice(bytes);
+            chunk.advance(len);
         }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
-    let get_filter = warp::get()
-        .and(context_extractor)
-        .and(query::query())
-        .and_then(handle_get_request);
-
-    get_filter
-        .or(post_json_filter)
-        .unify()
-        .or(post_graphql_filter)
-        .unify()
-        .boxed()
+    }
+    Ok(buf)
+}
+
+fn into_response(resp: HttpResponse) -> http::Response<Vec<u8>> {
+    let mut builder = http::Response::builder().status(resp.status);
+    for (name, value) in resp.headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(resp.body.into_bytes())
+        .expect("response is valid")
 }
 
 /// Error raised by `tokio_threadpool` if the thread pool has been shutdown.
@@ -288,20 +366,6 @@ pub struct JoinError(task::JoinError);
 
 impl warp::reject::Reject for JoinError {}
 
-fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Response<Vec<u8>> {
-    match response {
-        Ok((body, is_ok)) => http::Response::builder()
-            .status(if is_ok { 200 } else { 400 })
-            .header("content-type", "application/json")
-            .body(body)
-            .expect("response is valid"),
-        Err(_) => http::Response::builder()
-            .status(http::StatusCode::INTERNAL_SERVER_ERROR)
-            .body(Vec::new())
-            .expect("status code is valid"),
-    }
-}
-
 /// Create a filter that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint.
 ///
 /// For example:
@@ -336,13 +400,33 @@ fn graphiql_response(
     graphql_endpoint_url: &'static str,
     subscriptions_endpoint: Option<&'static str>,
 ) -> http::Response<Vec<u8>> {
-    http::Response::builder()
-        .header("content-type", "text/html;charset=utf-8")
-        .body(
-            juniper::http::graphiql::graphiql_source(graphql_endpoint_url, subscriptions_endpoint)
-                .into_bytes(),
-        )
-        .expect("response is valid")
+    html_response(juniper::http::graphiql::graphiql_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint,
+    ))
+}
+
+/// Create a filter that replies with an HTML page containing GraphiQL configured with the given
+/// [`GraphiQLConfig`]. This does not handle routing, so you can mount it on any endpoint.
+///
+/// For example:
+///
+/// ```
+/// # use warp::Filter;
+/// # use juniper::http::graphiql::GraphiQLConfig;
+/// # use juniper_warp::graphiql_filter_with_config;
+/// #
+/// let graphiql_route = warp::path("graphiql").and(graphiql_filter_with_config(
+///     GraphiQLConfig::new("/graphql").with_header("Authorization", "Bearer token"),
+/// ));
+/// ```
+pub fn graphiql_filter_with_config(
+    config: GraphiQLConfig,
+) -> warp::filters::BoxedFilter<(http::Response<Vec<u8>>,)> {
+    let source = juniper::http::graphiql::graphiql_source_with_config(&config);
+    warp::any()
+        .map(move || html_response(source.clone()))
+        .boxed()
 }
 
 /// Create a filter that replies with an HTML page containing GraphQL Playground. This does not handle routing, so you can mount it on any endpoint.
@@ -359,18 +443,59 @@ fn playground_response(
     graphql_endpoint_url: &'static str,
     subscriptions_endpoint_url: Option<&'static str>,
 ) -> http::Response<Vec<u8>> {
+    html_response(juniper::http::playground::playground_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+/// Create a filter that replies with an HTML page containing GraphQL Playground configured with
+/// the given [`PlaygroundConfig`]. This does not handle routing, so you can mount it on any
+/// endpoint.
+pub fn playground_filter_with_config(
+    config: PlaygroundConfig,
+) -> warp::filters::BoxedFilter<(http::Response<Vec<u8>>,)> {
+    let source = juniper::http::playground::playground_source_with_config(&config);
+    warp::any()
+        .map(move || html_response(source.clone()))
+        .boxed()
+}
+
+fn html_response(source: String) -> http::Response<Vec<u8>> {
     http::Response::builder()
         .header("content-type", "text/html;charset=utf-8")
-        .body(
-            juniper::http::playground::playground_source(
-                graphql_endpoint_url,
-                subscriptions_endpoint_url,
-            )
-            .into_bytes(),
-        )
+        .body(source.into_bytes())
         .expect("response is valid")
 }
 
+/// Create a filter that replies with the [bundled asset](juniper::http::assets) named by the rest
+/// of the request path, or rejects the request with a `404 Not Found` if there is no such asset.
+/// Mount it under the assets base URL given to the GraphiQL and GraphQL Playground pages:
+///
+/// ```
+/// # use warp::Filter;
+/// # use juniper_warp::assets_filter;
+/// #
+/// let assets_route = warp::path("assets").and(assets_filter());
+/// ```
+#[cfg(feature = "bundled-assets")]
+pub fn assets_filter() -> BoxedFilter<(http::Response<&'static [u8]>,)> {
+    warp::get()
+        .and(warp::path::tail())
+        .and_then(|tail: warp::path::Tail| async move {
+            juniper::http::assets::find(tail.as_str())
+                .map(|asset| {
+                    http::Response::builder()
+                        .header("content-type", asset.content_type)
+                        .header("cache-control", juniper::http::assets::CACHE_CONTROL)
+                        .body(asset.contents())
+                        .expect("response is valid")
+                })
+                .ok_or_else(warp::reject::not_found)
+        })
+        .boxed()
+}
+
 /// `juniper_warp` subscriptions handler implementation.
 /// Cannot be merged to `juniper_warp` yet as GraphQL over WS[1]
 /// is not fully supported in current implementation.
@@ -483,6 +608,7 @@ pub mod subscriptions {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use juniper::http::GraphQLBatchRequest;
     use warp::{http, test::request};
 
     #[test]
@@ -525,7 +651,64 @@ mod tests {
         );
         let body = String::from_utf8(response.body().to_vec()).unwrap();
 
-        assert!(body.contains("<script>var GRAPHQL_URL = '/dogs-api/graphql';</script>"));
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/dogs-api/graphql\";</script>"));
+    }
+
+    #[cfg(feature = "bundled-assets")]
+    #[tokio::test]
+    async fn assets_endpoint_returns_bundled_assets() {
+        use juniper::http::assets::{CACHE_CONTROL, GRAPHIQL_0_JS};
+
+        let filter = warp::path("assets").and(assets_filter());
+        let response = request()
+            .method("GET")
+            .path(&format!("/assets/{}", GRAPHIQL_0_JS.name))
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            GRAPHIQL_0_JS.content_type,
+        );
+        assert_eq!(
+            response.headers().get("cache-control").unwrap(),
+            CACHE_CONTROL,
+        );
+        assert_eq!(response.body().as_ref(), GRAPHIQL_0_JS.contents());
+
+        let response = request()
+            .method("GET")
+            .path("/assets/graphiql.min.js")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn graphiql_endpoint_returns_configured_graphiql_source() {
+        let filter = warp::get()
+            .and(warp::path("graphiql"))
+            .and(graphiql_filter_with_config(
+                GraphiQLConfig::new("/dogs-api/graphql").with_query("{ dogs { name } }"),
+            ));
+        let response = request()
+            .method("GET")
+            .path("/graphiql")
+            .header("accept", "text/html")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            "text/html;charset=utf-8"
+        );
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+
+        assert!(body.contains("<script>var GRAPHQL_URL = \"/dogs-api/graphql\";</script>"));
+        assert!(body.contains(r#"var INITIAL_QUERY = "{ dogs { name } }";"#));
     }
 
     #[tokio::test]
@@ -583,7 +766,7 @@ mod tests {
         );
         let body = String::from_utf8(response.body().to_vec()).unwrap();
 
-        assert!(body.contains("GraphQLPlayground.init(root, { endpoint: '/dogs-api/graphql', subscriptionEndpoint: '/dogs-api/subscriptions' })"));
+        assert!(body.contains("GraphQLPlayground.init(root, { endpoint: \"/dogs-api/graphql\", subscriptionEndpoint: \"/dogs-api/subscriptions\" })"));
     }
 
     #[tokio::test]
@@ -625,6 +808,54 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn graphql_handler_checks_requests_against_config() {
+        use juniper::{
+            http::handler::HandlerConfig,
+            tests::fixtures::starwars::schema::{Database, Query},
+            EmptyMutation, EmptySubscription, RootNode,
+        };
+
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let config = HandlerConfig::new()
+            .with_csrf_prevention()
+            .with_max_body_size(32);
+        let state = warp::any().map(Database::new);
+        let filter = warp::path("graphql2").and(make_graphql_filter_with_config(
+            schema,
+            state.boxed(),
+            config,
+        ));
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7B+hero+%7B+name+%7D+%7D")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+
+        let response = request()
+            .method("GET")
+            .path("/graphql2?query=%7B+hero+%7B+name+%7D+%7D")
+            .header("apollo-require-preflight", "true")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let response = request()
+            .method("POST")
+            .path("/graphql2")
+            .header("content-type", "application/graphql")
+            .body("{ hero { name friends { name } } }")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     #[tokio::test]
     async fn batch_requests_work() {
         use juniper::{
@@ -715,6 +946,24 @@ mod tests_http_harness {
             }
         }
 
+        fn with_config(is_sync: bool, config: HandlerConfig) -> Self {
+            let schema = RootNode::new(
+                Query,
+                EmptyMutation::<Database>::new(),
+                EmptySubscription::<Database>::new(),
+            );
+            let state = warp::any().map(move || Database::new());
+
+            let filter = path::end().and(if is_sync {
+                make_graphql_filter_sync_with_config(schema, state.boxed(), config)
+            } else {
+                make_graphql_filter_with_config(schema, state.boxed(), config)
+            });
+            Self {
+                filter: filter.boxed(),
+            }
+        }
+
         fn make_request(&self, req: warp::test::RequestBuilder) -> TestResponse {
             let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio::Runtime");
             make_test_response(rt.block_on(async move {
@@ -803,4 +1052,20 @@ mod tests_http_harness {
     fn test_sync_warp_integration() {
         run_http_test_suite(&TestWarpIntegration::new(true));
     }
+
+    #[test]
+    fn test_warp_integration_with_config() {
+        run_http_test_suite(&TestWarpIntegration::with_config(
+            false,
+            HandlerConfig::default(),
+        ));
+    }
+
+    #[test]
+    fn test_sync_warp_integration_with_config() {
+        run_http_test_suite(&TestWarpIntegration::with_config(
+            true,
+            HandlerConfig::default(),
+        ));
+    }
 }